@@ -278,6 +278,9 @@ pub(crate) mod test {
             pagination: Setting::NotSet,
             embedders: Setting::NotSet,
             search_cutoff_ms: Setting::NotSet,
+            localized_attributes: Setting::NotSet,
+            infix_searchable_attributes: Setting::NotSet,
+            pinned_results: Setting::NotSet,
             _kind: std::marker::PhantomData,
         };
         settings.check()
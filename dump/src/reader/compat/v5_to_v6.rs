@@ -380,6 +380,9 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
             },
             embedders: v6::Setting::NotSet,
             search_cutoff_ms: v6::Setting::NotSet,
+            localized_attributes: v6::Setting::NotSet,
+            infix_searchable_attributes: v6::Setting::NotSet,
+            pinned_results: v6::Setting::NotSet,
             _kind: std::marker::PhantomData,
         }
     }
@@ -107,6 +107,9 @@ pub struct IndexStats {
     pub used_database_size: u64,
     /// Association of every field name with the number of times it occurs in the documents.
     pub field_distribution: FieldDistribution,
+    /// Size taken by the n-gram database of the infix searchable attributes, in bytes.
+    #[serde(default)]
+    pub infix_database_size: u64,
     /// Creation date of the index.
     pub created_at: OffsetDateTime,
     /// Date of the last update of the index.
@@ -125,6 +128,7 @@ impl IndexStats {
             database_size: index.on_disk_size()?,
             used_database_size: index.used_size()?,
             field_distribution: index.field_distribution(rtxn)?,
+            infix_database_size: index.ngram_word_docids_size(rtxn)?,
             created_at: index.created_at(rtxn)?,
             updated_at: index.updated_at(rtxn)?,
         })
@@ -210,6 +210,8 @@ IndexPrimaryKeyAlreadyExists          , InvalidRequest       , BAD_REQUEST ;
 IndexPrimaryKeyMultipleCandidatesFound, InvalidRequest       , BAD_REQUEST;
 IndexPrimaryKeyNoCandidateFound       , InvalidRequest       , BAD_REQUEST ;
 Internal                              , Internal             , INTERNAL_SERVER_ERROR ;
+InvalidAnalyzeAttribute               , InvalidRequest       , BAD_REQUEST ;
+InvalidAnalyzeText                    , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKey                         , Auth                 , FORBIDDEN ;
 InvalidApiKeyActions                  , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyDescription              , InvalidRequest       , BAD_REQUEST ;
@@ -237,18 +239,29 @@ InvalidIndexLimit                     , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexOffset                    , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexPrimaryKey                , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexUid                       , InvalidRequest       , BAD_REQUEST ;
+InvalidMultiSearchFederationOptions   , InvalidRequest       , BAD_REQUEST ;
+InvalidMultiSearchQueryCollapse       , InvalidRequest       , BAD_REQUEST ;
+InvalidMultiSearchQueryFacets         , InvalidRequest       , BAD_REQUEST ;
+InvalidMultiSearchQueryPagination     , InvalidRequest       , BAD_REQUEST ;
+InvalidMultiSearchQuerySuggestions    , InvalidRequest       , BAD_REQUEST ;
+InvalidMultiSearchWeight              , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchAttributesToSearchOn     , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchAttributesToCrop         , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchAttributesToHighlight    , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarAttributesToRetrieve    , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarRetrieveVectors         , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchAttributesToRetrieve     , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchRankingRules             , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchRankingScoreThreshold    , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarRankingScoreThreshold   , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchRetrieveVectors          , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchCropLength               , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchCropMarker               , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchFacets                   , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchFacetRanges              , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchDisjunctiveFacets        , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchFacetStatsAggregations   , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchGeoAggregation           , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchSemanticRatio            , InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchFacetName           , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarId                      , InvalidRequest       , BAD_REQUEST ;
@@ -259,10 +272,16 @@ InvalidSearchHighlightPreTag          , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchHitsPerPage              , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarLimit                   , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchLimit                    , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchLocales                  , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchTypoTolerantNegation     , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchShowSuggestions          , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchSuggestionsThreshold     , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchMatchingStrategy         , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarOffset                  , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchOffset                   , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchPage                     , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchSearchAfter              , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchShowCursor               , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchQ                        , InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchQuery               , InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchName                , InvalidRequest       , BAD_REQUEST ;
@@ -274,13 +293,18 @@ InvalidSearchShowRankingScoreDetails  , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarShowRankingScoreDetails , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchSort                     , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchDistinct                 , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchExplainDocumentIds       , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchCollapse                 , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsDisplayedAttributes    , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsDistinctAttribute      , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsProximityPrecision     , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsFaceting               , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsFilterableAttributes   , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsPagination             , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsPinnedResults          , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsSearchCutoffMs         , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsLocalizedAttributes    , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsInfixSearchableAttributes, InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsEmbedders              , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsRankingRules           , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsSearchableAttributes   , InvalidRequest       , BAD_REQUEST ;
@@ -311,6 +335,7 @@ IoError                               , System               , UNPROCESSABLE_ENT
 FeatureNotEnabled                     , InvalidRequest       , BAD_REQUEST ;
 MalformedPayload                      , InvalidRequest       , BAD_REQUEST ;
 MaxFieldsLimitExceeded                , InvalidRequest       , BAD_REQUEST ;
+MissingAnalyzeText                    , InvalidRequest       , BAD_REQUEST ;
 MissingApiKeyActions                  , InvalidRequest       , BAD_REQUEST ;
 MissingApiKeyExpiresAt                , InvalidRequest       , BAD_REQUEST ;
 MissingApiKeyIndexes                  , InvalidRequest       , BAD_REQUEST ;
@@ -388,15 +413,27 @@ impl ErrorCode for milli::Error {
                     UserError::InvalidDistinctAttribute { .. } => Code::InvalidSearchDistinct,
                     UserError::SortRankingRuleMissing => Code::InvalidSearchSort,
                     UserError::InvalidFacetsDistribution { .. } => Code::InvalidSearchFacets,
+                    UserError::InvalidFacetHistogramInterval { .. } => {
+                        Code::InvalidSearchFacetRanges
+                    }
                     UserError::InvalidSortableAttribute { .. } => Code::InvalidSearchSort,
+                    UserError::InvalidRankingRuleAttribute { .. } => {
+                        Code::InvalidSearchRankingRules
+                    }
                     UserError::InvalidSearchableAttribute { .. } => {
                         Code::InvalidSearchAttributesToSearchOn
                     }
+                    UserError::InvalidFieldScopedSearch { .. } => Code::InvalidSearchQ,
+                    UserError::InvalidPhraseSlop { .. } => Code::InvalidSearchQ,
+                    UserError::StaleSearchCursor => Code::InvalidSearchSearchAfter,
+                    UserError::SearchCursorMismatch => Code::InvalidSearchSearchAfter,
+                    UserError::InvalidSearchCursor => Code::InvalidSearchSearchAfter,
                     UserError::InvalidFacetSearchFacetName { .. } => {
                         Code::InvalidFacetSearchFacetName
                     }
                     UserError::CriterionError(_) => Code::InvalidSettingsRankingRules,
                     UserError::InvalidGeoField { .. } => Code::InvalidDocumentGeoField,
+                    UserError::InvalidGeoAggregation => Code::InvalidSearchGeoAggregation,
                     UserError::InvalidVectorDimensions { .. } => Code::InvalidVectorDimensions,
                     UserError::InvalidVectorsMapType { .. }
                     | UserError::InvalidVectorsEmbedderConf { .. } => Code::InvalidVectorsType,
@@ -513,6 +550,16 @@ impl fmt::Display for deserr_codes::InvalidSimilarId {
     }
 }
 
+impl fmt::Display for deserr_codes::InvalidSearchLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the value of `limit` is invalid, expected a positive integer lower than or equal to `{}`.",
+            milli::MAX_AUTOCOMPLETE_LIMIT
+        )
+    }
+}
+
 impl fmt::Display for deserr_codes::InvalidSearchRankingScoreThreshold {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -522,12 +569,36 @@ impl fmt::Display for deserr_codes::InvalidSearchRankingScoreThreshold {
     }
 }
 
+impl fmt::Display for deserr_codes::InvalidSearchFacetStatsAggregations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the value of `facetStatsAggregations` is invalid, expected one of `sum`, `avg`, `count` or a percentile between `p0` and `p100`."
+        )
+    }
+}
+
+impl fmt::Display for deserr_codes::InvalidSearchSearchAfter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the value of `searchAfter` is invalid, expected the `cursor` returned by a previous search."
+        )
+    }
+}
+
 impl fmt::Display for deserr_codes::InvalidSimilarRankingScoreThreshold {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         deserr_codes::InvalidSearchRankingScoreThreshold.fmt(f)
     }
 }
 
+impl fmt::Display for deserr_codes::InvalidMultiSearchWeight {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the value of `weight` is invalid, expected a positive float (>= 0.0).")
+    }
+}
+
 #[macro_export]
 macro_rules! internal_error {
     ($target:ty : $($other:path), *) => {
@@ -284,6 +284,9 @@ pub enum Action {
     #[serde(rename = "experimental.update")]
     #[deserr(rename = "experimental.update")]
     ExperimentalFeaturesUpdate,
+    #[serde(rename = "search.debug")]
+    #[deserr(rename = "search.debug")]
+    SearchDebug,
 }
 
 impl Action {
@@ -323,6 +326,7 @@ impl Action {
             KEYS_DELETE => Some(Self::KeysDelete),
             EXPERIMENTAL_FEATURES_GET => Some(Self::ExperimentalFeaturesGet),
             EXPERIMENTAL_FEATURES_UPDATE => Some(Self::ExperimentalFeaturesUpdate),
+            SEARCH_DEBUG => Some(Self::SearchDebug),
             _otherwise => None,
         }
     }
@@ -368,4 +372,5 @@ pub mod actions {
     pub const KEYS_DELETE: u8 = KeysDelete.repr();
     pub const EXPERIMENTAL_FEATURES_GET: u8 = ExperimentalFeaturesGet.repr();
     pub const EXPERIMENTAL_FEATURES_UPDATE: u8 = ExperimentalFeaturesUpdate.repr();
+    pub const SEARCH_DEBUG: u8 = SearchDebug.repr();
 }
@@ -0,0 +1,175 @@
+use std::fmt;
+use std::str::FromStr;
+
+use deserr::Deserr;
+use enum_iterator::Sequence;
+use milli::tokenizer::Language;
+use milli::LocalizedAttributesRule;
+use serde::{Deserialize, Serialize};
+
+use crate::deserr::DeserrJsonError;
+use crate::error::deserr_codes::*;
+
+/// A rule constraining the languages detected in the attributes matching its patterns.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct LocalizedAttributesRuleView {
+    #[deserr(error = DeserrJsonError<InvalidSettingsLocalizedAttributes>)]
+    pub attribute_patterns: Vec<String>,
+    #[deserr(error = DeserrJsonError<InvalidSettingsLocalizedAttributes>)]
+    pub locales: Vec<Locale>,
+}
+
+impl From<LocalizedAttributesRule> for LocalizedAttributesRuleView {
+    fn from(rule: LocalizedAttributesRule) -> Self {
+        Self {
+            attribute_patterns: rule.attribute_patterns,
+            locales: rule.locales.into_iter().map(|l| l.into()).collect(),
+        }
+    }
+}
+
+impl From<LocalizedAttributesRuleView> for LocalizedAttributesRule {
+    fn from(view: LocalizedAttributesRuleView) -> Self {
+        Self {
+            attribute_patterns: view.attribute_patterns,
+            locales: view.locales.into_iter().map(|l| l.into()).collect(),
+        }
+    }
+}
+
+macro_rules! make_locale {
+    ($($language:ident),+ $(,)?) => {
+        /// A language supported by the tokenizer, identified by its ISO 639-3 code.
+        #[derive(
+            Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Deserr, Serialize, Deserialize, Sequence,
+        )]
+        #[deserr(rename_all = camelCase)]
+        #[serde(rename_all = "camelCase")]
+        pub enum Locale {
+            $($language),+,
+        }
+
+        impl From<Language> for Locale {
+            fn from(other: Language) -> Locale {
+                match other {
+                    $(Language::$language => Locale::$language),+
+                }
+            }
+        }
+
+        impl From<Locale> for Language {
+            fn from(other: Locale) -> Language {
+                match other {
+                    $(Locale::$language => Language::$language),+
+                }
+            }
+        }
+    };
+}
+
+make_locale! {
+    Epo,
+    Eng,
+    Rus,
+    Cmn,
+    Spa,
+    Por,
+    Ita,
+    Ben,
+    Fra,
+    Deu,
+    Ukr,
+    Kat,
+    Ara,
+    Hin,
+    Jpn,
+    Heb,
+    Yid,
+    Pol,
+    Amh,
+    Jav,
+    Kor,
+    Nob,
+    Dan,
+    Swe,
+    Fin,
+    Tur,
+    Nld,
+    Hun,
+    Ces,
+    Ell,
+    Bul,
+    Bel,
+    Mar,
+    Kan,
+    Ron,
+    Slv,
+    Hrv,
+    Srp,
+    Mkd,
+    Lit,
+    Lav,
+    Est,
+    Tam,
+    Vie,
+    Urd,
+    Tha,
+    Guj,
+    Uzb,
+    Pan,
+    Aze,
+    Ind,
+    Tel,
+    Pes,
+    Mal,
+    Ori,
+    Mya,
+    Nep,
+    Sin,
+    Khm,
+    Tuk,
+    Aka,
+    Zul,
+    Sna,
+    Afr,
+    Lat,
+    Slk,
+    Cat,
+    Tgl,
+    Hye,
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(Language::from(*self).name())
+    }
+}
+
+impl FromStr for Locale {
+    type Err = LocaleFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        enum_iterator::all::<Locale>()
+            .find(|locale| locale.to_string() == s)
+            .ok_or_else(|| LocaleFormatError { invalid_locale: s.to_string() })
+    }
+}
+
+#[derive(Debug)]
+pub struct LocaleFormatError {
+    pub invalid_locale: String,
+}
+
+impl fmt::Display for LocaleFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let valid_locales = enum_iterator::all::<Locale>()
+            .map(|locale| format!("`{locale}`"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(f, "Unsupported locale `{}`, expected one of {}", self.invalid_locale, valid_locales)
+    }
+}
+
+impl std::error::Error for LocaleFormatError {}
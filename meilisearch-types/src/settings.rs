@@ -9,6 +9,7 @@ use std::str::FromStr;
 use deserr::{DeserializeError, Deserr, ErrorKind, MergeWithError, ValuePointerRef};
 use fst::IntoStreamer;
 use milli::index::IndexEmbeddingConfig;
+use milli::pinned_results::{PinnedDocument, PinnedResultsRule, QueryMatching};
 use milli::proximity::ProximityPrecision;
 use milli::update::Setting;
 use milli::{Criterion, CriterionError, Index, DEFAULT_VALUES_PER_FACET};
@@ -17,6 +18,7 @@ use serde::{Deserialize, Serialize, Serializer};
 use crate::deserr::DeserrJsonError;
 use crate::error::deserr_codes::*;
 use crate::facet_values_sort::FacetValuesSort;
+use crate::locales::LocalizedAttributesRuleView;
 
 /// The maximum number of results that the engine
 /// will be able to return in one search call.
@@ -133,6 +135,20 @@ impl MergeWithError<milli::CriterionError> for DeserrJsonError<InvalidSettingsRa
     }
 }
 
+impl MergeWithError<milli::CriterionError> for DeserrJsonError<InvalidSearchRankingRules> {
+    fn merge(
+        _self_: Option<Self>,
+        other: milli::CriterionError,
+        merge_location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        Self::error::<Infallible>(
+            None,
+            ErrorKind::Unexpected { msg: other.to_string() },
+            merge_location,
+        )
+    }
+}
+
 /// Holds all the settings for an index. `T` can either be `Checked` if they represents settings
 /// whose validity is guaranteed, or `Unchecked` if they need to be validated. In the later case, a
 /// call to `check` will return a `Settings<Checked>` from a `Settings<Unchecked>`.
@@ -198,6 +214,15 @@ pub struct Settings<T> {
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsSearchCutoffMs>)]
     pub search_cutoff_ms: Setting<u64>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsLocalizedAttributes>)]
+    pub localized_attributes: Setting<Vec<LocalizedAttributesRuleView>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsInfixSearchableAttributes>)]
+    pub infix_searchable_attributes: Setting<BTreeSet<String>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsPinnedResults>)]
+    pub pinned_results: Setting<Vec<PinnedResultsRuleView>>,
 
     #[serde(skip)]
     #[deserr(skip)]
@@ -261,6 +286,9 @@ impl Settings<Checked> {
             pagination: Setting::Reset,
             embedders: Setting::Reset,
             search_cutoff_ms: Setting::Reset,
+            localized_attributes: Setting::Reset,
+            infix_searchable_attributes: Setting::Reset,
+            pinned_results: Setting::Reset,
             _kind: PhantomData,
         }
     }
@@ -284,6 +312,9 @@ impl Settings<Checked> {
             pagination,
             embedders,
             search_cutoff_ms,
+            localized_attributes,
+            infix_searchable_attributes,
+            pinned_results,
             ..
         } = self;
 
@@ -305,6 +336,9 @@ impl Settings<Checked> {
             pagination,
             embedders,
             search_cutoff_ms,
+            localized_attributes,
+            infix_searchable_attributes,
+            pinned_results,
             _kind: PhantomData,
         }
     }
@@ -352,6 +386,9 @@ impl Settings<Unchecked> {
             pagination: self.pagination,
             embedders: self.embedders,
             search_cutoff_ms: self.search_cutoff_ms,
+            localized_attributes: self.localized_attributes,
+            infix_searchable_attributes: self.infix_searchable_attributes,
+            pinned_results: self.pinned_results,
             _kind: PhantomData,
         }
     }
@@ -402,6 +439,9 @@ pub fn apply_settings_to_builder(
         pagination,
         embedders,
         search_cutoff_ms,
+        localized_attributes,
+        infix_searchable_attributes,
+        pinned_results,
         _kind,
     } = settings;
 
@@ -583,6 +623,29 @@ pub fn apply_settings_to_builder(
         Setting::Reset => builder.reset_search_cutoff(),
         Setting::NotSet => (),
     }
+
+    match localized_attributes {
+        Setting::Set(rules) => builder
+            .set_localized_attributes_rules(rules.iter().cloned().map(|r| r.into()).collect()),
+        Setting::Reset => builder.reset_localized_attributes_rules(),
+        Setting::NotSet => (),
+    }
+
+    match infix_searchable_attributes {
+        Setting::Set(attrs) => {
+            builder.set_infix_searchable_attributes(attrs.iter().cloned().collect())
+        }
+        Setting::Reset => builder.reset_infix_searchable_attributes(),
+        Setting::NotSet => (),
+    }
+
+    match pinned_results {
+        Setting::Set(rules) => {
+            builder.set_pinned_results(rules.iter().cloned().map(|r| r.into()).collect())
+        }
+        Setting::Reset => builder.reset_pinned_results(),
+        Setting::NotSet => (),
+    }
 }
 
 pub enum SecretPolicy {
@@ -679,6 +742,13 @@ pub fn settings(
 
     let search_cutoff_ms = index.search_cutoff(rtxn)?;
 
+    let localized_attributes_rules = index.localized_attributes_rules(rtxn)?;
+
+    let infix_searchable_attributes =
+        index.infix_searchable_attributes(rtxn)?.into_iter().map(String::from).collect();
+
+    let pinned_results = index.pinned_results(rtxn)?.into_iter().map(|r| r.into()).collect();
+
     let mut settings = Settings {
         displayed_attributes: match displayed_attributes {
             Some(attrs) => Setting::Set(attrs),
@@ -711,6 +781,12 @@ pub fn settings(
             Some(cutoff) => Setting::Set(cutoff),
             None => Setting::Reset,
         },
+        localized_attributes: match localized_attributes_rules {
+            Some(rules) => Setting::Set(rules.into_iter().map(|r| r.into()).collect()),
+            None => Setting::Reset,
+        },
+        infix_searchable_attributes: Setting::Set(infix_searchable_attributes),
+        pinned_results: Setting::Set(pinned_results),
         _kind: PhantomData,
     };
 
@@ -743,6 +819,8 @@ pub enum RankingRuleView {
     Asc(String),
     /// Sorted by the decreasing value of the field specified.
     Desc(String),
+    /// Sorted by the decreasing score of a decay function around an origin, e.g. `release_date:gauss(now, 7d)`.
+    Decay(milli::Decay),
 }
 impl Serialize for RankingRuleView {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -799,6 +877,7 @@ impl From<Criterion> for RankingRuleView {
             Criterion::Exactness => RankingRuleView::Exactness,
             Criterion::Asc(x) => RankingRuleView::Asc(x),
             Criterion::Desc(x) => RankingRuleView::Desc(x),
+            Criterion::Decay(x) => RankingRuleView::Decay(x),
         }
     }
 }
@@ -813,6 +892,7 @@ impl From<RankingRuleView> for Criterion {
             RankingRuleView::Exactness => Criterion::Exactness,
             RankingRuleView::Asc(x) => Criterion::Asc(x),
             RankingRuleView::Desc(x) => Criterion::Desc(x),
+            RankingRuleView::Decay(x) => Criterion::Decay(x),
         }
     }
 }
@@ -843,6 +923,86 @@ impl From<ProximityPrecisionView> for ProximityPrecision {
     }
 }
 
+/// A curation rule placing documents at fixed positions of the results of the queries it matches.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(error = DeserrJsonError<InvalidSettingsPinnedResults>, rename_all = camelCase, deny_unknown_fields)]
+pub struct PinnedResultsRuleView {
+    pub query: String,
+    #[serde(default)]
+    #[deserr(default)]
+    pub matching: QueryMatchingView,
+    pub pinned: Vec<PinnedDocumentView>,
+    #[serde(default)]
+    #[deserr(default)]
+    pub hidden: Vec<String>,
+}
+
+impl From<PinnedResultsRule> for PinnedResultsRuleView {
+    fn from(rule: PinnedResultsRule) -> Self {
+        let PinnedResultsRule { query, matching, pinned, hidden } = rule;
+        let pinned = pinned.into_iter().map(Into::into).collect();
+        Self { query, matching: matching.into(), pinned, hidden }
+    }
+}
+
+impl From<PinnedResultsRuleView> for PinnedResultsRule {
+    fn from(view: PinnedResultsRuleView) -> Self {
+        let PinnedResultsRuleView { query, matching, pinned, hidden } = view;
+        let pinned = pinned.into_iter().map(Into::into).collect();
+        Self { query, matching: matching.into(), pinned, hidden }
+    }
+}
+
+/// A document pinned at a position of the results, starting at `0`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(error = DeserrJsonError<InvalidSettingsPinnedResults>, rename_all = camelCase, deny_unknown_fields)]
+pub struct PinnedDocumentView {
+    pub id: String,
+    pub position: usize,
+}
+
+impl From<PinnedDocument> for PinnedDocumentView {
+    fn from(document: PinnedDocument) -> Self {
+        let PinnedDocument { id, position } = document;
+        Self { id, position }
+    }
+}
+
+impl From<PinnedDocumentView> for PinnedDocument {
+    fn from(view: PinnedDocumentView) -> Self {
+        let PinnedDocumentView { id, position } = view;
+        Self { id, position }
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Deserr, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(error = DeserrJsonError<InvalidSettingsPinnedResults>, rename_all = camelCase, deny_unknown_fields)]
+pub enum QueryMatchingView {
+    Exact,
+    #[default]
+    Normalized,
+}
+
+impl From<QueryMatching> for QueryMatchingView {
+    fn from(value: QueryMatching) -> Self {
+        match value {
+            QueryMatching::Exact => QueryMatchingView::Exact,
+            QueryMatching::Normalized => QueryMatchingView::Normalized,
+        }
+    }
+}
+impl From<QueryMatchingView> for QueryMatching {
+    fn from(value: QueryMatchingView) -> Self {
+        match value {
+            QueryMatchingView::Exact => QueryMatching::Exact,
+            QueryMatchingView::Normalized => QueryMatching::Normalized,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
 pub struct WildcardSetting(Setting<Vec<String>>);
 
@@ -903,6 +1063,9 @@ pub(crate) mod test {
             pagination: Setting::NotSet,
             embedders: Setting::NotSet,
             search_cutoff_ms: Setting::NotSet,
+            localized_attributes: Setting::NotSet,
+            infix_searchable_attributes: Setting::NotSet,
+            pinned_results: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
 
@@ -931,6 +1094,9 @@ pub(crate) mod test {
             pagination: Setting::NotSet,
             embedders: Setting::NotSet,
             search_cutoff_ms: Setting::NotSet,
+            localized_attributes: Setting::NotSet,
+            infix_searchable_attributes: Setting::NotSet,
+            pinned_results: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
 
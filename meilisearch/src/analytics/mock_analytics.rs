@@ -42,7 +42,7 @@ pub struct MultiSearchAggregator;
 
 #[allow(dead_code)]
 impl MultiSearchAggregator {
-    pub fn from_queries(_: &dyn Any, _: &dyn Any) -> Self {
+    pub fn from_federated_search(_: &dyn Any, _: &dyn Any) -> Self {
         Self
     }
 
@@ -61,6 +61,18 @@ impl FacetSearchAggregator {
     pub fn succeed(&mut self, _: &dyn Any) {}
 }
 
+#[derive(Default)]
+pub struct AutocompleteAggregator;
+
+#[allow(dead_code)]
+impl AutocompleteAggregator {
+    pub fn from_query(_: &dyn Any, _: &dyn Any) -> Self {
+        Self
+    }
+
+    pub fn succeed(&mut self, _: &dyn Any) {}
+}
+
 impl MockAnalytics {
     #[allow(clippy::new_ret_no_self)]
     pub fn new(opt: &Opt) -> Arc<dyn Analytics> {
@@ -82,6 +94,7 @@ impl Analytics for MockAnalytics {
     fn post_similar(&self, _aggregate: super::SimilarAggregator) {}
     fn post_multi_search(&self, _aggregate: super::MultiSearchAggregator) {}
     fn post_facet_search(&self, _aggregate: super::FacetSearchAggregator) {}
+    fn post_autocomplete(&self, _aggregate: super::AutocompleteAggregator) {}
     fn add_documents(
         &self,
         _documents_query: &UpdateDocumentsQuery,
@@ -27,6 +27,8 @@ pub type SimilarAggregator = mock_analytics::SimilarAggregator;
 pub type MultiSearchAggregator = mock_analytics::MultiSearchAggregator;
 #[cfg(not(feature = "analytics"))]
 pub type FacetSearchAggregator = mock_analytics::FacetSearchAggregator;
+#[cfg(not(feature = "analytics"))]
+pub type AutocompleteAggregator = mock_analytics::AutocompleteAggregator;
 
 // if the feature analytics is enabled we use the real analytics
 #[cfg(feature = "analytics")]
@@ -39,6 +41,8 @@ pub type SimilarAggregator = segment_analytics::SimilarAggregator;
 pub type MultiSearchAggregator = segment_analytics::MultiSearchAggregator;
 #[cfg(feature = "analytics")]
 pub type FacetSearchAggregator = segment_analytics::FacetSearchAggregator;
+#[cfg(feature = "analytics")]
+pub type AutocompleteAggregator = segment_analytics::AutocompleteAggregator;
 
 /// The Meilisearch config dir:
 /// `~/.config/Meilisearch` on *NIX or *BSD.
@@ -102,6 +106,9 @@ pub trait Analytics: Sync + Send {
     /// This method should be called to aggregate post facet values searches
     fn post_facet_search(&self, aggregate: FacetSearchAggregator);
 
+    /// This method should be called to aggregate post autocomplete requests
+    fn post_autocomplete(&self, aggregate: AutocompleteAggregator);
+
     // this method should be called to aggregate a add documents request
     fn add_documents(
         &self,
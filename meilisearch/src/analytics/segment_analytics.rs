@@ -1,4 +1,4 @@
-use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::collections::{BTreeSet, BinaryHeap, HashMap, HashSet};
 use std::fs;
 use std::mem::take;
 use std::path::{Path, PathBuf};
@@ -11,6 +11,7 @@ use byte_unit::Byte;
 use http::header::CONTENT_TYPE;
 use index_scheduler::IndexScheduler;
 use meilisearch_auth::{AuthController, AuthFilter};
+use meilisearch_types::locales::Locale;
 use meilisearch_types::InstanceUid;
 use once_cell::sync::Lazy;
 use regex::Regex;
@@ -31,14 +32,15 @@ use crate::analytics::Analytics;
 use crate::option::{
     default_http_addr, IndexerOpts, LogMode, MaxMemory, MaxThreads, ScheduleSnapshot,
 };
+use crate::routes::indexes::autocomplete::AutocompleteQuery;
 use crate::routes::indexes::documents::UpdateDocumentsQuery;
 use crate::routes::indexes::facet_search::FacetSearchQuery;
 use crate::routes::{create_all_stats, Stats};
 use crate::search::{
-    FacetSearchResult, MatchingStrategy, SearchQuery, SearchQueryWithIndex, SearchResult,
-    SimilarQuery, SimilarResult, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER,
-    DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT,
-    DEFAULT_SEMANTIC_RATIO,
+    AutocompleteResult, FacetSearchResult, FederatedSearch, MatchingStrategy, SearchQuery,
+    SearchQueryWithIndex, SearchResult, SimilarQuery, SimilarResult, DEFAULT_CROP_LENGTH,
+    DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG,
+    DEFAULT_SEARCH_LIMIT, DEFAULT_SEMANTIC_RATIO,
 };
 use crate::Opt;
 
@@ -78,6 +80,7 @@ pub enum AnalyticsMsg {
     AggregatePostSimilar(SimilarAggregator),
     AggregatePostMultiSearch(MultiSearchAggregator),
     AggregatePostFacetSearch(FacetSearchAggregator),
+    AggregatePostAutocomplete(AutocompleteAggregator),
     AggregateAddDocuments(DocumentsAggregator),
     AggregateDeleteDocuments(DocumentsDeletionAggregator),
     AggregateUpdateDocuments(DocumentsAggregator),
@@ -146,6 +149,7 @@ impl SegmentAnalytics {
             post_search_aggregator: SearchAggregator::default(),
             post_multi_search_aggregator: MultiSearchAggregator::default(),
             post_facet_search_aggregator: FacetSearchAggregator::default(),
+            post_autocomplete_aggregator: AutocompleteAggregator::default(),
             get_search_aggregator: SearchAggregator::default(),
             add_documents_aggregator: DocumentsAggregator::default(),
             delete_documents_aggregator: DocumentsDeletionAggregator::default(),
@@ -201,6 +205,10 @@ impl super::Analytics for SegmentAnalytics {
         let _ = self.sender.try_send(AnalyticsMsg::AggregatePostFacetSearch(aggregate));
     }
 
+    fn post_autocomplete(&self, aggregate: AutocompleteAggregator) {
+        let _ = self.sender.try_send(AnalyticsMsg::AggregatePostAutocomplete(aggregate));
+    }
+
     fn post_multi_search(&self, aggregate: MultiSearchAggregator) {
         let _ = self.sender.try_send(AnalyticsMsg::AggregatePostMultiSearch(aggregate));
     }
@@ -387,6 +395,7 @@ pub struct Segment {
     post_search_aggregator: SearchAggregator,
     post_multi_search_aggregator: MultiSearchAggregator,
     post_facet_search_aggregator: FacetSearchAggregator,
+    post_autocomplete_aggregator: AutocompleteAggregator,
     add_documents_aggregator: DocumentsAggregator,
     delete_documents_aggregator: DocumentsDeletionAggregator,
     update_documents_aggregator: DocumentsAggregator,
@@ -451,6 +460,7 @@ impl Segment {
                         Some(AnalyticsMsg::AggregatePostSearch(agreg)) => self.post_search_aggregator.aggregate(agreg),
                         Some(AnalyticsMsg::AggregatePostMultiSearch(agreg)) => self.post_multi_search_aggregator.aggregate(agreg),
                         Some(AnalyticsMsg::AggregatePostFacetSearch(agreg)) => self.post_facet_search_aggregator.aggregate(agreg),
+                        Some(AnalyticsMsg::AggregatePostAutocomplete(agreg)) => self.post_autocomplete_aggregator.aggregate(agreg),
                         Some(AnalyticsMsg::AggregateAddDocuments(agreg)) => self.add_documents_aggregator.aggregate(agreg),
                         Some(AnalyticsMsg::AggregateDeleteDocuments(agreg)) => self.delete_documents_aggregator.aggregate(agreg),
                         Some(AnalyticsMsg::AggregateUpdateDocuments(agreg)) => self.update_documents_aggregator.aggregate(agreg),
@@ -506,6 +516,7 @@ impl Segment {
             post_search_aggregator,
             post_multi_search_aggregator,
             post_facet_search_aggregator,
+            post_autocomplete_aggregator,
             add_documents_aggregator,
             delete_documents_aggregator,
             update_documents_aggregator,
@@ -535,6 +546,11 @@ impl Segment {
         {
             let _ = self.batcher.push(post_facet_search).await;
         }
+        if let Some(post_autocomplete) =
+            take(post_autocomplete_aggregator).into_event(user, "Autocomplete POST")
+        {
+            let _ = self.batcher.push(post_autocomplete).await;
+        }
         if let Some(add_documents) =
             take(add_documents_aggregator).into_event(user, "Documents Added")
         {
@@ -588,6 +604,7 @@ pub struct SearchAggregator {
     total_succeeded: usize,
     total_degraded: usize,
     total_used_negative_operator: usize,
+    total_suggested: usize,
     time_spent: BinaryHeap<usize>,
 
     // sort
@@ -600,9 +617,35 @@ pub struct SearchAggregator {
     // distinct
     distinct: bool,
 
+    // collapse
+    // Whether the collapsed documents were requested, and the maximum number of them per hit
+    collapse: bool,
+    max_collapse_limit: usize,
+
+    // facet_ranges
+    // Whether the counts of documents per range of facet values were requested
+    facet_ranges: bool,
+
+    // disjunctive_facets
+    // The maximum number of disjunctive facets in a request
+    max_disjunctive_facets: usize,
+
+    // facet_stats_aggregations
+    // Whether aggregations other than the min and max of the facets were requested
+    facet_stats_aggregations: bool,
+
+    // geo_aggregation
+    // Whether the counts of documents per geohash cell or map tile were requested
+    geo_aggregation: bool,
+
+    // ranking_rules
+    // Whether the ranking rules of the index were overridden for the query
+    ranking_rules: bool,
+
     // filter
     filter_with_geo_radius: bool,
     filter_with_geo_bounding_box: bool,
+    filter_with_geo_polygon: bool,
     // every time a request has a filter, this field must be incremented by the number of terms it contains
     filter_sum_of_criteria_terms: usize,
     // every time a request has a filter, this field must be incremented by one
@@ -616,6 +659,10 @@ pub struct SearchAggregator {
     // q
     // The maximum number of terms in a q request
     max_terms_number: usize,
+    // Whether the negated words of a q request were allowed to match with typos
+    typo_tolerant_negation: bool,
+    // Whether a spelling suggestion was requested
+    show_suggestions: bool,
 
     // vector
     // The maximum number of floats in a vector request
@@ -634,6 +681,8 @@ pub struct SearchAggregator {
     max_limit: usize,
     max_offset: usize,
     finite_pagination: usize,
+    // Whether a search resumed after a cursor, or returned one
+    search_after: bool,
 
     // formatting
     max_attributes_to_retrieve: usize,
@@ -653,6 +702,9 @@ pub struct SearchAggregator {
     show_ranking_score: bool,
     show_ranking_score_details: bool,
     ranking_score_threshold: bool,
+
+    // locales
+    locales: BTreeSet<Locale>,
 }
 
 impl SearchAggregator {
@@ -665,6 +717,8 @@ impl SearchAggregator {
             limit,
             page,
             hits_per_page,
+            search_after,
+            show_cursor,
             attributes_to_retrieve: _,
             retrieve_vectors,
             attributes_to_crop: _,
@@ -675,8 +729,14 @@ impl SearchAggregator {
             show_ranking_score_details,
             filter,
             sort,
+            ranking_rules,
             distinct,
+            collapse,
             facets: _,
+            facet_ranges,
+            disjunctive_facets,
+            facet_stats_aggregations,
+            geo_aggregation,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
@@ -684,6 +744,10 @@ impl SearchAggregator {
             attributes_to_search_on,
             hybrid,
             ranking_score_threshold,
+            locales,
+            typo_tolerant_negation,
+            show_suggestions,
+            suggestions_threshold: _,
         } = query;
 
         let mut ret = Self::default();
@@ -699,6 +763,14 @@ impl SearchAggregator {
         }
 
         ret.distinct = distinct.is_some();
+        ret.collapse = collapse.is_some();
+        ret.max_collapse_limit = collapse.as_ref().map_or(0, |collapse| collapse.limit);
+        ret.facet_ranges = facet_ranges.is_some();
+        ret.max_disjunctive_facets = disjunctive_facets.as_ref().map_or(0, Vec::len);
+        ret.facet_stats_aggregations =
+            facet_stats_aggregations.as_ref().is_some_and(|aggregations| !aggregations.is_empty());
+        ret.geo_aggregation = geo_aggregation.is_some();
+        ret.ranking_rules = ranking_rules.is_some();
 
         if let Some(ref filter) = filter {
             static RE: Lazy<Regex> = Lazy::new(|| Regex::new("AND | OR").unwrap());
@@ -721,6 +793,7 @@ impl SearchAggregator {
             let stringified_filters = filter.to_string();
             ret.filter_with_geo_radius = stringified_filters.contains("_geoRadius(");
             ret.filter_with_geo_bounding_box = stringified_filters.contains("_geoBoundingBox(");
+            ret.filter_with_geo_polygon = stringified_filters.contains("_geoPolygon(");
             ret.filter_sum_of_criteria_terms = RE.split(&stringified_filters).count();
         }
 
@@ -732,6 +805,8 @@ impl SearchAggregator {
         if let Some(ref q) = q {
             ret.max_terms_number = q.split_whitespace().count();
         }
+        ret.typo_tolerant_negation = *typo_tolerant_negation;
+        ret.show_suggestions = *show_suggestions;
 
         if let Some(ref vector) = vector {
             ret.max_vector_size = vector.len();
@@ -748,6 +823,7 @@ impl SearchAggregator {
             ret.max_offset = *offset;
             ret.finite_pagination = 0;
         }
+        ret.search_after = search_after.is_some() || *show_cursor;
 
         ret.matching_strategy.insert(format!("{:?}", matching_strategy), 1);
 
@@ -761,6 +837,10 @@ impl SearchAggregator {
         ret.show_ranking_score_details = *show_ranking_score_details;
         ret.ranking_score_threshold = ranking_score_threshold.is_some();
 
+        if let Some(locales) = locales {
+            ret.locales = locales.iter().copied().collect();
+        }
+
         if let Some(hybrid) = hybrid {
             ret.semantic_ratio = hybrid.semantic_ratio != DEFAULT_SEMANTIC_RATIO();
             ret.embedder = hybrid.embedder.is_some();
@@ -779,8 +859,12 @@ impl SearchAggregator {
             semantic_hit_count: _,
             facet_distribution: _,
             facet_stats: _,
+            facet_ranges: _,
+            geo_aggregation: _,
             degraded,
             used_negative_operator,
+            suggestion,
+            cursor: _,
         } = result;
 
         self.total_succeeded = self.total_succeeded.saturating_add(1);
@@ -790,6 +874,9 @@ impl SearchAggregator {
         if *used_negative_operator {
             self.total_used_negative_operator = self.total_used_negative_operator.saturating_add(1);
         }
+        if suggestion.is_some() {
+            self.total_suggested = self.total_suggested.saturating_add(1);
+        }
         self.time_spent.push(*processing_time_ms as usize);
     }
 
@@ -805,19 +892,30 @@ impl SearchAggregator {
             sort_sum_of_criteria_terms,
             sort_total_number_of_criteria,
             distinct,
+            collapse,
+            max_collapse_limit,
+            facet_ranges,
+            max_disjunctive_facets,
+            facet_stats_aggregations,
+            geo_aggregation,
+            ranking_rules,
             filter_with_geo_radius,
             filter_with_geo_bounding_box,
+            filter_with_geo_polygon,
             filter_sum_of_criteria_terms,
             filter_total_number_of_criteria,
             used_syntax,
             attributes_to_search_on_total_number_of_uses,
             max_terms_number,
+            typo_tolerant_negation,
+            show_suggestions,
             max_vector_size,
             retrieve_vectors,
             matching_strategy,
             max_limit,
             max_offset,
             finite_pagination,
+            search_after,
             max_attributes_to_retrieve,
             max_attributes_to_highlight,
             highlight_pre_tag,
@@ -835,7 +933,9 @@ impl SearchAggregator {
             hybrid,
             total_degraded,
             total_used_negative_operator,
+            total_suggested,
             ranking_score_threshold,
+            locales,
         } = other;
 
         if self.timestamp.is_none() {
@@ -853,6 +953,7 @@ impl SearchAggregator {
         self.total_degraded = self.total_degraded.saturating_add(total_degraded);
         self.total_used_negative_operator =
             self.total_used_negative_operator.saturating_add(total_used_negative_operator);
+        self.total_suggested = self.total_suggested.saturating_add(total_suggested);
         self.time_spent.append(time_spent);
 
         // sort
@@ -865,9 +966,29 @@ impl SearchAggregator {
         // distinct
         self.distinct |= distinct;
 
+        // collapse
+        self.collapse |= collapse;
+        self.max_collapse_limit = self.max_collapse_limit.max(max_collapse_limit);
+
+        // facet_ranges
+        self.facet_ranges |= facet_ranges;
+
+        // disjunctive_facets
+        self.max_disjunctive_facets = self.max_disjunctive_facets.max(max_disjunctive_facets);
+
+        // facet_stats_aggregations
+        self.facet_stats_aggregations |= facet_stats_aggregations;
+
+        // geo_aggregation
+        self.geo_aggregation |= geo_aggregation;
+
+        // ranking_rules
+        self.ranking_rules |= ranking_rules;
+
         // filter
         self.filter_with_geo_radius |= filter_with_geo_radius;
         self.filter_with_geo_bounding_box |= filter_with_geo_bounding_box;
+        self.filter_with_geo_polygon |= filter_with_geo_polygon;
         self.filter_sum_of_criteria_terms =
             self.filter_sum_of_criteria_terms.saturating_add(filter_sum_of_criteria_terms);
         self.filter_total_number_of_criteria =
@@ -884,6 +1005,8 @@ impl SearchAggregator {
 
         // q
         self.max_terms_number = self.max_terms_number.max(max_terms_number);
+        self.typo_tolerant_negation |= typo_tolerant_negation;
+        self.show_suggestions |= show_suggestions;
 
         // vector
         self.max_vector_size = self.max_vector_size.max(max_vector_size);
@@ -896,6 +1019,7 @@ impl SearchAggregator {
         self.max_limit = self.max_limit.max(max_limit);
         self.max_offset = self.max_offset.max(max_offset);
         self.finite_pagination += finite_pagination;
+        self.search_after |= search_after;
 
         // formatting
         self.max_attributes_to_retrieve =
@@ -924,6 +1048,9 @@ impl SearchAggregator {
         self.show_ranking_score |= show_ranking_score;
         self.show_ranking_score_details |= show_ranking_score_details;
         self.ranking_score_threshold |= ranking_score_threshold;
+
+        // locales
+        self.locales.extend(locales);
     }
 
     pub fn into_event(self, user: &User, event_name: &str) -> Option<Track> {
@@ -937,19 +1064,30 @@ impl SearchAggregator {
             sort_sum_of_criteria_terms,
             sort_total_number_of_criteria,
             distinct,
+            collapse,
+            max_collapse_limit,
+            facet_ranges,
+            max_disjunctive_facets,
+            facet_stats_aggregations,
+            geo_aggregation,
+            ranking_rules,
             filter_with_geo_radius,
             filter_with_geo_bounding_box,
+            filter_with_geo_polygon,
             filter_sum_of_criteria_terms,
             filter_total_number_of_criteria,
             used_syntax,
             attributes_to_search_on_total_number_of_uses,
             max_terms_number,
+            typo_tolerant_negation,
+            show_suggestions,
             max_vector_size,
             retrieve_vectors,
             matching_strategy,
             max_limit,
             max_offset,
             finite_pagination,
+            search_after,
             max_attributes_to_retrieve,
             max_attributes_to_highlight,
             highlight_pre_tag,
@@ -967,7 +1105,9 @@ impl SearchAggregator {
             hybrid,
             total_degraded,
             total_used_negative_operator,
+            total_suggested,
             ranking_score_threshold,
+            locales,
         } = self;
 
         if total_received == 0 {
@@ -989,15 +1129,28 @@ impl SearchAggregator {
                     "total_received": total_received,
                     "total_degraded": total_degraded,
                     "total_used_negative_operator": total_used_negative_operator,
+                    "total_suggested": total_suggested,
                 },
                 "sort": {
                     "with_geoPoint": sort_with_geo_point,
                     "avg_criteria_number": format!("{:.2}", sort_sum_of_criteria_terms as f64 / sort_total_number_of_criteria as f64),
                 },
                 "distinct": distinct,
+                "collapse": {
+                    "used": collapse,
+                    "max_limit": max_collapse_limit,
+                },
+                "facet_ranges": facet_ranges,
+                "disjunctive_facets": {
+                    "max_disjunctive_facets": max_disjunctive_facets,
+                },
+                "facet_stats_aggregations": facet_stats_aggregations,
+                "geo_aggregation": geo_aggregation,
+                "ranking_rules": ranking_rules,
                 "filter": {
                    "with_geoRadius": filter_with_geo_radius,
                    "with_geoBoundingBox": filter_with_geo_bounding_box,
+                   "with_geoPolygon": filter_with_geo_polygon,
                    "avg_criteria_number": format!("{:.2}", filter_sum_of_criteria_terms as f64 / filter_total_number_of_criteria as f64),
                    "most_used_syntax": used_syntax.iter().max_by_key(|(_, v)| *v).map(|(k, _)| json!(k)).unwrap_or_else(|| json!(null)),
                 },
@@ -1006,6 +1159,8 @@ impl SearchAggregator {
                 },
                 "q": {
                    "max_terms_number": max_terms_number,
+                   "typo_tolerant_negation": typo_tolerant_negation,
+                   "show_suggestions": show_suggestions,
                 },
                 "vector": {
                     "max_vector_size": max_vector_size,
@@ -1020,6 +1175,7 @@ impl SearchAggregator {
                    "max_limit": max_limit,
                    "max_offset": max_offset,
                    "most_used_navigation": if finite_pagination > (total_received / 2) { "exhaustive" } else { "estimated" },
+                   "search_after": search_after,
                 },
                 "formatting": {
                     "max_attributes_to_retrieve": max_attributes_to_retrieve,
@@ -1042,6 +1198,7 @@ impl SearchAggregator {
                     "show_ranking_score_details": show_ranking_score_details,
                     "ranking_score_threshold": ranking_score_threshold,
                 },
+                "locales": locales,
             });
 
             Some(Track {
@@ -1075,17 +1232,26 @@ pub struct MultiSearchAggregator {
     show_ranking_score: bool,
     show_ranking_score_details: bool,
 
+    // federation
+    use_federation: bool,
+
     // context
     user_agents: HashSet<String>,
 }
 
 impl MultiSearchAggregator {
-    pub fn from_queries(query: &[SearchQueryWithIndex], request: &HttpRequest) -> Self {
+    pub fn from_federated_search(
+        federated_search: &FederatedSearch,
+        request: &HttpRequest,
+    ) -> Self {
         let timestamp = Some(OffsetDateTime::now_utc());
 
         let user_agents = extract_user_agents(request).into_iter().collect();
 
-        let distinct_indexes: HashSet<_> = query
+        let FederatedSearch { queries, federation } = federated_search;
+        let use_federation = federation.is_some();
+
+        let distinct_indexes: HashSet<_> = queries
             .iter()
             .map(|query| {
                 // make sure we get a compilation error if a field gets added to / removed from SearchQueryWithIndex
@@ -1097,6 +1263,8 @@ impl MultiSearchAggregator {
                     limit: _,
                     page: _,
                     hits_per_page: _,
+                    search_after: _,
+                    show_cursor: _,
                     attributes_to_retrieve: _,
                     retrieve_vectors: _,
                     attributes_to_crop: _,
@@ -1107,8 +1275,14 @@ impl MultiSearchAggregator {
                     show_matches_position: _,
                     filter: _,
                     sort: _,
+                    ranking_rules: _,
                     distinct: _,
+                    collapse: _,
                     facets: _,
+                    facet_ranges: _,
+                    disjunctive_facets: _,
+                    facet_stats_aggregations: _,
+                    geo_aggregation: _,
                     highlight_pre_tag: _,
                     highlight_post_tag: _,
                     crop_marker: _,
@@ -1116,14 +1290,20 @@ impl MultiSearchAggregator {
                     attributes_to_search_on: _,
                     hybrid: _,
                     ranking_score_threshold: _,
+                    locales: _,
+                    typo_tolerant_negation: _,
+                    show_suggestions: _,
+                    suggestions_threshold: _,
+                    federation_options: _,
                 } = query;
 
                 index_uid.as_str()
             })
             .collect();
 
-        let show_ranking_score = query.iter().any(|query| query.show_ranking_score);
-        let show_ranking_score_details = query.iter().any(|query| query.show_ranking_score_details);
+        let show_ranking_score = queries.iter().any(|query| query.show_ranking_score);
+        let show_ranking_score_details =
+            queries.iter().any(|query| query.show_ranking_score_details);
 
         Self {
             timestamp,
@@ -1131,9 +1311,10 @@ impl MultiSearchAggregator {
             total_succeeded: 0,
             total_distinct_index_count: distinct_indexes.len(),
             total_single_index: if distinct_indexes.len() == 1 { 1 } else { 0 },
-            total_search_count: query.len(),
+            total_search_count: queries.len(),
             show_ranking_score,
             show_ranking_score_details,
+            use_federation,
             user_agents,
         }
     }
@@ -1159,6 +1340,7 @@ impl MultiSearchAggregator {
         let show_ranking_score = this.show_ranking_score || other.show_ranking_score;
         let show_ranking_score_details =
             this.show_ranking_score_details || other.show_ranking_score_details;
+        let use_federation = this.use_federation || other.use_federation;
         let mut user_agents = this.user_agents;
 
         for user_agent in other.user_agents.into_iter() {
@@ -1176,6 +1358,7 @@ impl MultiSearchAggregator {
             user_agents,
             show_ranking_score,
             show_ranking_score_details,
+            use_federation,
             // do not add _ or ..Default::default() here
         };
 
@@ -1194,6 +1377,7 @@ impl MultiSearchAggregator {
             user_agents,
             show_ranking_score,
             show_ranking_score_details,
+            use_federation,
         } = self;
 
         if total_received == 0 {
@@ -1218,6 +1402,9 @@ impl MultiSearchAggregator {
                 "scoring": {
                     "show_ranking_score": show_ranking_score,
                     "show_ranking_score_details": show_ranking_score_details,
+                },
+                "federation": {
+                    "use_federation": use_federation,
                 }
             });
 
@@ -1371,6 +1558,169 @@ impl FacetSearchAggregator {
     }
 }
 
+#[derive(Default)]
+pub struct AutocompleteAggregator {
+    timestamp: Option<OffsetDateTime>,
+
+    // context
+    user_agents: HashSet<String>,
+
+    // requests
+    total_received: usize,
+    total_succeeded: usize,
+    time_spent: BinaryHeap<usize>,
+
+    // q
+    // The maximum number of terms in a q request
+    max_terms_number: usize,
+
+    // filter
+    // every time a request has a filter, this field must be incremented
+    filter_total_number_of_uses: usize,
+
+    // limit
+    max_limit: usize,
+
+    // completions
+    // every time a request returns no completion, this field must be incremented
+    total_without_completion: usize,
+}
+
+impl AutocompleteAggregator {
+    #[allow(clippy::field_reassign_with_default)]
+    pub fn from_query(query: &AutocompleteQuery, request: &HttpRequest) -> Self {
+        let AutocompleteQuery { q, filter, limit } = query;
+
+        let mut ret = Self::default();
+        ret.timestamp = Some(OffsetDateTime::now_utc());
+
+        ret.total_received = 1;
+        ret.user_agents = extract_user_agents(request).into_iter().collect();
+
+        if let Some(ref q) = q {
+            ret.max_terms_number = q.split_whitespace().count();
+        }
+        if filter.is_some() {
+            ret.filter_total_number_of_uses = 1;
+        }
+        ret.max_limit = *limit;
+
+        ret
+    }
+
+    pub fn succeed(&mut self, result: &AutocompleteResult) {
+        let AutocompleteResult {
+            completions,
+            query: _,
+            exhaustive_completions: _,
+            processing_time_ms,
+        } = result;
+        self.total_succeeded = self.total_succeeded.saturating_add(1);
+        if completions.is_empty() {
+            self.total_without_completion = self.total_without_completion.saturating_add(1);
+        }
+        self.time_spent.push(*processing_time_ms as usize);
+    }
+
+    /// Aggregate one [AutocompleteAggregator] into another.
+    pub fn aggregate(&mut self, mut other: Self) {
+        let Self {
+            timestamp,
+            user_agents,
+            total_received,
+            total_succeeded,
+            ref mut time_spent,
+            max_terms_number,
+            filter_total_number_of_uses,
+            max_limit,
+            total_without_completion,
+        } = other;
+
+        if self.timestamp.is_none() {
+            self.timestamp = timestamp;
+        }
+
+        // context
+        for user_agent in user_agents.into_iter() {
+            self.user_agents.insert(user_agent);
+        }
+
+        // request
+        self.total_received = self.total_received.saturating_add(total_received);
+        self.total_succeeded = self.total_succeeded.saturating_add(total_succeeded);
+        self.time_spent.append(time_spent);
+
+        // q
+        self.max_terms_number = self.max_terms_number.max(max_terms_number);
+
+        // filter
+        self.filter_total_number_of_uses =
+            self.filter_total_number_of_uses.saturating_add(filter_total_number_of_uses);
+
+        // limit
+        self.max_limit = self.max_limit.max(max_limit);
+
+        // completions
+        self.total_without_completion =
+            self.total_without_completion.saturating_add(total_without_completion);
+    }
+
+    pub fn into_event(self, user: &User, event_name: &str) -> Option<Track> {
+        let Self {
+            timestamp,
+            user_agents,
+            total_received,
+            total_succeeded,
+            time_spent,
+            max_terms_number,
+            filter_total_number_of_uses,
+            max_limit,
+            total_without_completion,
+        } = self;
+
+        if total_received == 0 {
+            None
+        } else {
+            // the index of the 99th percentage of value
+            let percentile_99th = 0.99 * (total_succeeded as f64 - 1.) + 1.;
+            // we get all the values in a sorted manner
+            let time_spent = time_spent.into_sorted_vec();
+            // We are only interested by the slowest value of the 99th fastest results
+            let time_spent = time_spent.get(percentile_99th as usize);
+
+            let properties = json!({
+                "user-agent": user_agents,
+                "requests": {
+                    "99th_response_time":  time_spent.map(|t| format!("{:.2}", t)),
+                    "total_succeeded": total_succeeded,
+                    "total_failed": total_received.saturating_sub(total_succeeded), // just to be sure we never panics
+                    "total_received": total_received,
+                },
+                "q": {
+                    "max_terms_number": max_terms_number,
+                },
+                "filter": {
+                    "total_number_of_uses": filter_total_number_of_uses,
+                },
+                "pagination": {
+                    "max_limit": max_limit,
+                },
+                "completions": {
+                    "total_without_completion": total_without_completion,
+                },
+            });
+
+            Some(Track {
+                timestamp,
+                user: user.clone(),
+                event: event_name.to_string(),
+                properties,
+                ..Default::default()
+            })
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct DocumentsAggregator {
     timestamp: Option<OffsetDateTime>,
@@ -1645,6 +1995,7 @@ pub struct SimilarAggregator {
     // filter
     filter_with_geo_radius: bool,
     filter_with_geo_bounding_box: bool,
+    filter_with_geo_polygon: bool,
     // every time a request has a filter, this field must be incremented by the number of terms it contains
     filter_sum_of_criteria_terms: usize,
     // every time a request has a filter, this field must be incremented by one
@@ -1711,6 +2062,7 @@ impl SimilarAggregator {
             let stringified_filters = filter.to_string();
             ret.filter_with_geo_radius = stringified_filters.contains("_geoRadius(");
             ret.filter_with_geo_bounding_box = stringified_filters.contains("_geoBoundingBox(");
+            ret.filter_with_geo_polygon = stringified_filters.contains("_geoPolygon(");
             ret.filter_sum_of_criteria_terms = RE.split(&stringified_filters).count();
         }
 
@@ -1745,6 +2097,7 @@ impl SimilarAggregator {
             ref mut time_spent,
             filter_with_geo_radius,
             filter_with_geo_bounding_box,
+            filter_with_geo_polygon,
             filter_sum_of_criteria_terms,
             filter_total_number_of_criteria,
             used_syntax,
@@ -1775,6 +2128,7 @@ impl SimilarAggregator {
         // filter
         self.filter_with_geo_radius |= filter_with_geo_radius;
         self.filter_with_geo_bounding_box |= filter_with_geo_bounding_box;
+        self.filter_with_geo_polygon |= filter_with_geo_polygon;
         self.filter_sum_of_criteria_terms =
             self.filter_sum_of_criteria_terms.saturating_add(filter_sum_of_criteria_terms);
         self.filter_total_number_of_criteria =
@@ -1810,6 +2164,7 @@ impl SimilarAggregator {
             time_spent,
             filter_with_geo_radius,
             filter_with_geo_bounding_box,
+            filter_with_geo_polygon,
             filter_sum_of_criteria_terms,
             filter_total_number_of_criteria,
             used_syntax,
@@ -1844,6 +2199,7 @@ impl SimilarAggregator {
                 "filter": {
                    "with_geoRadius": filter_with_geo_radius,
                    "with_geoBoundingBox": filter_with_geo_bounding_box,
+                   "with_geoPolygon": filter_with_geo_polygon,
                    "avg_criteria_number": format!("{:.2}", filter_sum_of_criteria_terms as f64 / filter_total_number_of_criteria as f64),
                    "most_used_syntax": used_syntax.iter().max_by_key(|(_, v)| *v).map(|(k, _)| json!(k)).unwrap_or_else(|| json!(null)),
                 },
@@ -59,6 +59,22 @@ pub enum MeilisearchHttpError {
     Join(#[from] JoinError),
     #[error("Invalid request: missing `hybrid` parameter when both `q` and `vector` are present.")]
     MissingSearchHybrid,
+    #[error("Using `federationOptions` is not allowed in a non-federated search.\n Hint: remove `federationOptions` from query #{0} or add `federation` to the request.")]
+    FederationOptionsInNonFederatedRequest(usize),
+    #[error("Using pagination options is not allowed in federated queries.\n Hint: remove `{1}` from query #{0} or remove `federation` from the request.")]
+    PaginationInFederatedQuery(usize, &'static str),
+    #[error("Using facet options is not allowed in federated queries.\n Hint: remove `facets` from query #{0} or remove `federation` from the request.")]
+    FacetsInFederatedQuery(usize),
+    #[error("Using `collapse` is not allowed in federated queries.\n Hint: remove `collapse` from query #{0} or remove `federation` from the request.")]
+    CollapseInFederatedQuery(usize),
+    #[error("Using suggestion options is not allowed in federated queries.\n Hint: remove `{1}` from query #{0} or remove `federation` from the request.")]
+    SuggestionsInFederatedQuery(usize, &'static str),
+    #[error("Using `collapse` requires a distinct attribute.\n Hint: add `distinct` to the request or set the `distinctAttribute` of the index.")]
+    CollapseWithoutDistinct,
+    #[error("Using `searchAfter` is not allowed in a hybrid search.\n Hint: remove `searchAfter` or `hybrid` from the request.")]
+    SearchAfterInHybridSearch,
+    #[error("Only keyword searches can be explained.\n Hint: remove `{0}` from the request.")]
+    SemanticSearchExplain(&'static str),
 }
 
 impl ErrorCode for MeilisearchHttpError {
@@ -86,6 +102,23 @@ impl ErrorCode for MeilisearchHttpError {
             MeilisearchHttpError::DocumentFormat(e) => e.error_code(),
             MeilisearchHttpError::Join(_) => Code::Internal,
             MeilisearchHttpError::MissingSearchHybrid => Code::MissingSearchHybrid,
+            MeilisearchHttpError::FederationOptionsInNonFederatedRequest(_) => {
+                Code::InvalidMultiSearchFederationOptions
+            }
+            MeilisearchHttpError::PaginationInFederatedQuery(_, _) => {
+                Code::InvalidMultiSearchQueryPagination
+            }
+            MeilisearchHttpError::FacetsInFederatedQuery(_) => Code::InvalidMultiSearchQueryFacets,
+            MeilisearchHttpError::CollapseInFederatedQuery(_) => {
+                Code::InvalidMultiSearchQueryCollapse
+            }
+            MeilisearchHttpError::SuggestionsInFederatedQuery(_, _) => {
+                Code::InvalidMultiSearchQuerySuggestions
+            }
+            MeilisearchHttpError::CollapseWithoutDistinct => Code::InvalidSearchCollapse,
+            MeilisearchHttpError::SearchAfterInHybridSearch => Code::InvalidSearchSearchAfter,
+            MeilisearchHttpError::SemanticSearchExplain("vector") => Code::InvalidSearchVector,
+            MeilisearchHttpError::SemanticSearchExplain(_) => Code::InvalidHybridQuery,
         }
     }
 }
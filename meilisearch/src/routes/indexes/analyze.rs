@@ -0,0 +1,50 @@
+use actix_web::web::Data;
+use actix_web::{web, HttpResponse};
+use deserr::actix_web::AwebJson;
+use index_scheduler::IndexScheduler;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid::IndexUid;
+use tracing::debug;
+
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::search::perform_analyze;
+use crate::search_queue::SearchQueue;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::post().to(SeqHandler(analyze))));
+}
+
+#[derive(Debug, Clone, Default, PartialEq, deserr::Deserr)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct AnalyzeQuery {
+    #[deserr(error = DeserrJsonError<InvalidAnalyzeText>, missing_field_error = DeserrJsonError::missing_analyze_text)]
+    pub text: String,
+    #[deserr(default, error = DeserrJsonError<InvalidAnalyzeAttribute>)]
+    pub attribute: Option<String>,
+}
+
+pub async fn analyze(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH_DEBUG }>, Data<IndexScheduler>>,
+    search_queue: Data<SearchQueue>,
+    index_uid: web::Path<String>,
+    params: AwebJson<AnalyzeQuery, DeserrJsonError>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    let query = params.into_inner();
+    debug!(parameters = ?query, "Analyze");
+
+    let AnalyzeQuery { text, attribute } = query;
+
+    let index = index_scheduler.index(&index_uid)?;
+    let _permit = search_queue.try_get_search_permit().await?;
+    let analyze_result =
+        tokio::task::spawn_blocking(move || perform_analyze(&index, text, attribute)).await??;
+
+    debug!(returns = ?analyze_result, "Analyze");
+    Ok(HttpResponse::Ok().json(analyze_result))
+}
@@ -0,0 +1,80 @@
+use actix_web::web::Data;
+use actix_web::{web, HttpRequest, HttpResponse};
+use deserr::actix_web::AwebJson;
+use index_scheduler::IndexScheduler;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::milli;
+use serde_json::Value;
+use tracing::debug;
+
+use crate::analytics::{Analytics, AutocompleteAggregator};
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::search::{add_search_rules, perform_autocomplete, DEFAULT_AUTOCOMPLETE_LIMIT};
+use crate::search_queue::SearchQueue;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::post().to(autocomplete)));
+}
+
+#[derive(Debug, Clone, Default, PartialEq, deserr::Deserr)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct AutocompleteQuery {
+    #[deserr(default, error = DeserrJsonError<InvalidSearchQ>)]
+    pub q: Option<String>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFilter>)]
+    pub filter: Option<Value>,
+    #[deserr(default = DEFAULT_AUTOCOMPLETE_LIMIT(), error = DeserrJsonError<InvalidSearchLimit>, try_from(usize) = validate_autocomplete_limit -> InvalidSearchLimit)]
+    pub limit: usize,
+}
+
+fn validate_autocomplete_limit(limit: usize) -> Result<usize, InvalidSearchLimit> {
+    if limit > milli::MAX_AUTOCOMPLETE_LIMIT {
+        Err(InvalidSearchLimit)
+    } else {
+        Ok(limit)
+    }
+}
+
+pub async fn autocomplete(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<IndexScheduler>>,
+    search_queue: Data<SearchQueue>,
+    index_uid: web::Path<String>,
+    params: AwebJson<AutocompleteQuery, DeserrJsonError>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    let query = params.into_inner();
+    debug!(parameters = ?query, "Autocomplete");
+
+    let mut aggregate = AutocompleteAggregator::from_query(&query, &req);
+
+    let AutocompleteQuery { q, mut filter, limit } = query;
+
+    // Tenant token search_rules.
+    if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
+        add_search_rules(&mut filter, search_rules);
+    }
+
+    let index = index_scheduler.index(&index_uid)?;
+    let _permit = search_queue.try_get_search_permit().await?;
+    let autocomplete_result = tokio::task::spawn_blocking(move || {
+        perform_autocomplete(&index, q.unwrap_or_default(), filter, limit)
+    })
+    .await?;
+
+    if let Ok(ref autocomplete_result) = autocomplete_result {
+        aggregate.succeed(autocomplete_result);
+    }
+    analytics.post_autocomplete(aggregate);
+
+    let autocomplete_result = autocomplete_result?;
+
+    debug!(returns = ?autocomplete_result, "Autocomplete");
+    Ok(HttpResponse::Ok().json(autocomplete_result))
+}
@@ -17,6 +17,7 @@ use crate::search::{
     add_search_rules, perform_facet_search, HybridQuery, MatchingStrategy, RankingScoreThreshold,
     SearchQuery, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG,
     DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET,
+    DEFAULT_SUGGESTIONS_THRESHOLD,
 };
 use crate::search_queue::SearchQueue;
 
@@ -114,6 +115,8 @@ impl From<FacetSearchQuery> for SearchQuery {
             limit: DEFAULT_SEARCH_LIMIT(),
             page: None,
             hits_per_page: None,
+            search_after: None,
+            show_cursor: false,
             attributes_to_retrieve: None,
             retrieve_vectors: false,
             attributes_to_crop: None,
@@ -124,16 +127,26 @@ impl From<FacetSearchQuery> for SearchQuery {
             show_ranking_score_details: false,
             filter,
             sort: None,
+            ranking_rules: None,
             distinct: None,
+            collapse: None,
             facets: None,
+            facet_ranges: None,
+            disjunctive_facets: None,
+            facet_stats_aggregations: None,
+            geo_aggregation: None,
             highlight_pre_tag: DEFAULT_HIGHLIGHT_PRE_TAG(),
             highlight_post_tag: DEFAULT_HIGHLIGHT_POST_TAG(),
             crop_marker: DEFAULT_CROP_MARKER(),
             matching_strategy,
             vector,
-            attributes_to_search_on,
+            attributes_to_search_on: attributes_to_search_on.map(|o| o.into_iter().collect()),
             hybrid,
             ranking_score_threshold,
+            locales: None,
+            typo_tolerant_negation: false,
+            show_suggestions: false,
+            suggestions_threshold: DEFAULT_SUGGESTIONS_THRESHOLD(),
         }
     }
 }
@@ -25,9 +25,12 @@ use crate::extractors::sequential_extractor::SeqHandler;
 use crate::routes::is_dry_run;
 use crate::Opt;
 
+pub mod analyze;
+pub mod autocomplete;
 pub mod documents;
 pub mod facet_search;
 pub mod search;
+pub mod search_explain;
 pub mod settings;
 pub mod similar;
 
@@ -47,8 +50,12 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
             )
             .service(web::resource("/stats").route(web::get().to(SeqHandler(get_index_stats))))
             .service(web::scope("/documents").configure(documents::configure))
+            // registered before `/search` which would otherwise match its path
+            .service(web::scope("/search/explain").configure(search_explain::configure))
             .service(web::scope("/search").configure(search::configure))
             .service(web::scope("/facet-search").configure(facet_search::configure))
+            .service(web::scope("/autocomplete").configure(autocomplete::configure))
+            .service(web::scope("/analyze").configure(analyze::configure))
             .service(web::scope("/similar").configure(similar::configure))
             .service(web::scope("/settings").configure(settings::configure)),
     );
@@ -256,6 +263,8 @@ pub struct IndexStats {
     pub is_indexing: bool,
     /// Association of every field name with the number of times it occurs in the documents.
     pub field_distribution: FieldDistribution,
+    /// Size taken by the n-gram database of the infix searchable attributes, in bytes.
+    pub infix_database_size: u64,
 }
 
 impl From<index_scheduler::IndexStats> for IndexStats {
@@ -264,6 +273,7 @@ impl From<index_scheduler::IndexStats> for IndexStats {
             number_of_documents: stats.inner_stats.number_of_documents,
             is_indexing: stats.is_indexing,
             field_distribution: stats.inner_stats.field_distribution,
+            infix_database_size: stats.inner_stats.infix_database_size,
         }
     }
 }
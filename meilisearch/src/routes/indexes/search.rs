@@ -7,6 +7,7 @@ use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
 use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::locales::Locale;
 use meilisearch_types::milli;
 use meilisearch_types::serde_cs::vec::CS;
 use serde_json::Value;
@@ -19,10 +20,11 @@ use crate::extractors::authentication::GuardedData;
 use crate::extractors::sequential_extractor::SeqHandler;
 use crate::metrics::MEILISEARCH_DEGRADED_SEARCH_REQUESTS;
 use crate::search::{
-    add_search_rules, perform_search, HybridQuery, MatchingStrategy, RankingScoreThreshold,
-    RetrieveVectors, SearchKind, SearchQuery, SemanticRatio, DEFAULT_CROP_LENGTH,
-    DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG,
-    DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET, DEFAULT_SEMANTIC_RATIO,
+    add_search_rules, perform_search, FacetStatsAggregation, HybridQuery, MatchingStrategy,
+    RankingScoreThreshold, RetrieveVectors, SearchAfter, SearchKind, SearchQuery, SemanticRatio,
+    DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG,
+    DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET, DEFAULT_SEMANTIC_RATIO,
+    DEFAULT_SUGGESTIONS_THRESHOLD,
 };
 use crate::search_queue::SearchQueue;
 
@@ -49,6 +51,10 @@ pub struct SearchQueryGet {
     page: Option<Param<usize>>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchHitsPerPage>)]
     hits_per_page: Option<Param<usize>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchSearchAfter>)]
+    search_after: Option<SearchAfter>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchShowCursor>)]
+    show_cursor: Param<bool>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchAttributesToRetrieve>)]
     attributes_to_retrieve: Option<CS<String>>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchRetrieveVectors>)]
@@ -73,6 +79,10 @@ pub struct SearchQueryGet {
     show_ranking_score_details: Param<bool>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchFacets>)]
     facets: Option<CS<String>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchDisjunctiveFacets>)]
+    disjunctive_facets: Option<CS<String>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchFacetStatsAggregations>)]
+    facet_stats_aggregations: Option<CS<FacetStatsAggregation>>,
     #[deserr( default = DEFAULT_HIGHLIGHT_PRE_TAG(), error = DeserrQueryParamError<InvalidSearchHighlightPreTag>)]
     highlight_pre_tag: String,
     #[deserr( default = DEFAULT_HIGHLIGHT_POST_TAG(), error = DeserrQueryParamError<InvalidSearchHighlightPostTag>)]
@@ -89,6 +99,14 @@ pub struct SearchQueryGet {
     pub hybrid_semantic_ratio: Option<SemanticRatioGet>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchRankingScoreThreshold>)]
     pub ranking_score_threshold: Option<RankingScoreThresholdGet>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchLocales>)]
+    pub locales: Option<CS<Locale>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchTypoTolerantNegation>)]
+    pub typo_tolerant_negation: Param<bool>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchShowSuggestions>)]
+    pub show_suggestions: Param<bool>,
+    #[deserr(default = Param(DEFAULT_SUGGESTIONS_THRESHOLD()), error = DeserrQueryParamError<InvalidSearchSuggestionsThreshold>)]
+    pub suggestions_threshold: Param<usize>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, deserr::Deserr)]
@@ -156,6 +174,8 @@ impl From<SearchQueryGet> for SearchQuery {
             limit: other.limit.0,
             page: other.page.as_deref().copied(),
             hits_per_page: other.hits_per_page.as_deref().copied(),
+            search_after: other.search_after,
+            show_cursor: other.show_cursor.0,
             attributes_to_retrieve: other.attributes_to_retrieve.map(|o| o.into_iter().collect()),
             retrieve_vectors: other.retrieve_vectors.0,
             attributes_to_crop: other.attributes_to_crop.map(|o| o.into_iter().collect()),
@@ -163,11 +183,19 @@ impl From<SearchQueryGet> for SearchQuery {
             attributes_to_highlight: other.attributes_to_highlight.map(|o| o.into_iter().collect()),
             filter,
             sort: other.sort.map(|attr| fix_sort_query_parameters(&attr)),
+            ranking_rules: None,
             distinct: other.distinct,
+            collapse: None,
             show_matches_position: other.show_matches_position.0,
             show_ranking_score: other.show_ranking_score.0,
             show_ranking_score_details: other.show_ranking_score_details.0,
             facets: other.facets.map(|o| o.into_iter().collect()),
+            facet_ranges: None,
+            disjunctive_facets: other.disjunctive_facets.map(|o| o.into_iter().collect()),
+            facet_stats_aggregations: other
+                .facet_stats_aggregations
+                .map(|o| o.into_iter().collect()),
+            geo_aggregation: None,
             highlight_pre_tag: other.highlight_pre_tag,
             highlight_post_tag: other.highlight_post_tag,
             crop_marker: other.crop_marker,
@@ -175,6 +203,10 @@ impl From<SearchQueryGet> for SearchQuery {
             attributes_to_search_on: other.attributes_to_search_on.map(|o| o.into_iter().collect()),
             hybrid,
             ranking_score_threshold: other.ranking_score_threshold.map(|o| o.0),
+            locales: other.locales.map(|o| o.into_iter().collect()),
+            typo_tolerant_negation: other.typo_tolerant_negation.0,
+            show_suggestions: other.show_suggestions.0,
+            suggestions_threshold: other.suggestions_threshold.0,
         }
     }
 }
@@ -0,0 +1,164 @@
+use actix_web::web::Data;
+use actix_web::{web, HttpResponse};
+use deserr::actix_web::AwebJson;
+use index_scheduler::IndexScheduler;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::locales::Locale;
+use meilisearch_types::settings::RankingRuleView;
+use serde_json::Value;
+use tracing::debug;
+
+use crate::error::MeilisearchHttpError;
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::search::{
+    perform_search_explain, AttributesToSearchOn, MatchingStrategy, RankingScoreThreshold,
+    SearchQuery, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG,
+    DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET,
+    DEFAULT_SUGGESTIONS_THRESHOLD,
+};
+use crate::search_queue::SearchQueue;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::post().to(SeqHandler(search_explain))));
+}
+
+/// # Important
+///
+/// Intentionally don't use `deny_unknown_fields` to ignore search parameters sent by user,
+/// the body of a search request can then be explained as is.
+///
+/// `hybrid` and `vector` are still parsed so that semantic searches are rejected instead of
+/// being silently explained as keyword searches.
+#[derive(Debug, Clone, Default, PartialEq, deserr::Deserr)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase)]
+pub struct ExplainSearchQuery {
+    #[deserr(default, error = DeserrJsonError<InvalidSearchExplainDocumentIds>)]
+    pub document_ids: Vec<String>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchQ>)]
+    pub q: Option<String>,
+    #[deserr(default = DEFAULT_SEARCH_OFFSET(), error = DeserrJsonError<InvalidSearchOffset>)]
+    pub offset: usize,
+    #[deserr(default = DEFAULT_SEARCH_LIMIT(), error = DeserrJsonError<InvalidSearchLimit>)]
+    pub limit: usize,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFilter>)]
+    pub filter: Option<Value>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchSort>)]
+    pub sort: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchRankingRules>)]
+    pub ranking_rules: Option<Vec<RankingRuleView>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDistinct>)]
+    pub distinct: Option<String>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchMatchingStrategy>, default)]
+    pub matching_strategy: MatchingStrategy,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToSearchOn>, default)]
+    pub attributes_to_search_on: Option<AttributesToSearchOn>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchRankingScoreThreshold>, default)]
+    pub ranking_score_threshold: Option<RankingScoreThreshold>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchLocales>, default)]
+    pub locales: Option<Vec<Locale>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchTypoTolerantNegation>, default)]
+    pub typo_tolerant_negation: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchVector>)]
+    pub vector: Option<Value>,
+    #[deserr(default, error = DeserrJsonError<InvalidHybridQuery>)]
+    pub hybrid: Option<Value>,
+}
+
+pub async fn search_explain(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH_DEBUG }>, Data<IndexScheduler>>,
+    search_queue: Data<SearchQueue>,
+    index_uid: web::Path<String>,
+    params: AwebJson<ExplainSearchQuery, DeserrJsonError>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    let query = params.into_inner();
+    debug!(parameters = ?query, "Search explain");
+
+    if query.hybrid.is_some() {
+        return Err(MeilisearchHttpError::SemanticSearchExplain("hybrid").into());
+    }
+    if query.vector.is_some() {
+        return Err(MeilisearchHttpError::SemanticSearchExplain("vector").into());
+    }
+
+    let document_ids = query.document_ids.clone();
+    let search_query = SearchQuery::from(query);
+
+    let index = index_scheduler.index(&index_uid)?;
+    let _permit = search_queue.try_get_search_permit().await?;
+    let explanation = tokio::task::spawn_blocking(move || {
+        perform_search_explain(&index, search_query, document_ids)
+    })
+    .await??;
+
+    debug!(returns = ?explanation, "Search explain");
+    Ok(HttpResponse::Ok().json(explanation))
+}
+
+impl From<ExplainSearchQuery> for SearchQuery {
+    fn from(value: ExplainSearchQuery) -> Self {
+        let ExplainSearchQuery {
+            document_ids: _,
+            q,
+            offset,
+            limit,
+            filter,
+            sort,
+            ranking_rules,
+            distinct,
+            matching_strategy,
+            attributes_to_search_on,
+            ranking_score_threshold,
+            locales,
+            typo_tolerant_negation,
+            vector: _,
+            hybrid: _,
+        } = value;
+
+        SearchQuery {
+            q,
+            offset,
+            limit,
+            page: None,
+            hits_per_page: None,
+            search_after: None,
+            show_cursor: false,
+            attributes_to_retrieve: None,
+            retrieve_vectors: false,
+            attributes_to_crop: None,
+            crop_length: DEFAULT_CROP_LENGTH(),
+            attributes_to_highlight: None,
+            show_matches_position: false,
+            show_ranking_score: false,
+            show_ranking_score_details: false,
+            filter,
+            sort,
+            ranking_rules,
+            distinct,
+            collapse: None,
+            facets: None,
+            facet_ranges: None,
+            disjunctive_facets: None,
+            facet_stats_aggregations: None,
+            geo_aggregation: None,
+            highlight_pre_tag: DEFAULT_HIGHLIGHT_PRE_TAG(),
+            highlight_post_tag: DEFAULT_HIGHLIGHT_POST_TAG(),
+            crop_marker: DEFAULT_CROP_MARKER(),
+            matching_strategy,
+            vector: None,
+            attributes_to_search_on,
+            hybrid: None,
+            ranking_score_threshold,
+            locales,
+            typo_tolerant_negation,
+            show_suggestions: false,
+            suggestions_threshold: DEFAULT_SUGGESTIONS_THRESHOLD(),
+        }
+    }
+}
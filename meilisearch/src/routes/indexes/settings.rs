@@ -497,6 +497,7 @@ make_setting_route!(
                     "attribute_position": setting.as_ref().map(|rr| rr.iter().position(|s| matches!(s, meilisearch_types::settings::RankingRuleView::Attribute))),
                     "sort_position": setting.as_ref().map(|rr| rr.iter().position(|s| matches!(s, meilisearch_types::settings::RankingRuleView::Sort))),
                     "exactness_position": setting.as_ref().map(|rr| rr.iter().position(|s| matches!(s, meilisearch_types::settings::RankingRuleView::Exactness))),
+                    "decay_count": setting.as_ref().map(|rr| rr.iter().filter(|s| matches!(s, meilisearch_types::settings::RankingRuleView::Decay(_))).count()),
                     "values": setting.as_ref().map(|rr| rr.iter().filter(|s| matches!(s, meilisearch_types::settings::RankingRuleView::Asc(_) | meilisearch_types::settings::RankingRuleView::Desc(_)) ).map(|x| x.to_string()).collect::<Vec<_>>().join(", ")),
                 }
             }),
@@ -642,6 +643,78 @@ make_setting_route!(
     }
 );
 
+make_setting_route!(
+    "/localized-attributes",
+    put,
+    Vec<meilisearch_types::locales::LocalizedAttributesRuleView>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsLocalizedAttributes,
+    >,
+    localized_attributes,
+    "localizedAttributes",
+    analytics,
+    |rules: &Option<Vec<meilisearch_types::locales::LocalizedAttributesRuleView>>, req: &HttpRequest| {
+        use serde_json::json;
+        analytics.publish(
+            "LocalizedAttributesRules Updated".to_string(),
+            json!({
+                "locales": rules.as_ref().map(|rules| rules.iter().flat_map(|rule| rule.locales.iter().cloned()).collect::<std::collections::BTreeSet<_>>())
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/infix-searchable-attributes",
+    put,
+    std::collections::BTreeSet<String>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsInfixSearchableAttributes,
+    >,
+    infix_searchable_attributes,
+    "infixSearchableAttributes",
+    analytics,
+    |setting: &Option<std::collections::BTreeSet<String>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "InfixSearchableAttributes Updated".to_string(),
+            json!({
+                "infix_searchable_attributes": {
+                    "total": setting.as_ref().map(|attrs| attrs.len()).unwrap_or(0),
+                }
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/pinned-results",
+    put,
+    Vec<meilisearch_types::settings::PinnedResultsRuleView>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsPinnedResults,
+    >,
+    pinned_results,
+    "pinnedResults",
+    analytics,
+    |setting: &Option<Vec<meilisearch_types::settings::PinnedResultsRuleView>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "PinnedResults Updated".to_string(),
+            json!({
+                "pinned_results": {
+                    "total": setting.as_ref().map(|rules| rules.len()).unwrap_or(0),
+                }
+            }),
+            Some(req),
+        );
+    }
+);
+
 macro_rules! generate_configure {
     ($($mod:ident),*) => {
         pub fn configure(cfg: &mut web::ServiceConfig) {
@@ -673,7 +746,10 @@ generate_configure!(
     pagination,
     faceting,
     embedders,
-    search_cutoff_ms
+    search_cutoff_ms,
+    localized_attributes,
+    infix_searchable_attributes,
+    pinned_results
 );
 
 pub async fn update_all(
@@ -700,6 +776,7 @@ pub async fn update_all(
                 "attribute_position": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().position(|s| matches!(s, RankingRuleView::Attribute))),
                 "sort_position": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().position(|s| matches!(s, RankingRuleView::Sort))),
                 "exactness_position": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().position(|s| matches!(s, RankingRuleView::Exactness))),
+                "decay_count": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().filter(|s| matches!(s, RankingRuleView::Decay(_))).count()),
                 "values": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().filter(|s| !matches!(s, RankingRuleView::Asc(_) | RankingRuleView::Desc(_)) ).map(|x| x.to_string()).collect::<Vec<_>>().join(", ")),
             },
             "searchable_attributes": {
@@ -786,6 +863,13 @@ pub async fn update_all(
             },
             "embedders": crate::routes::indexes::settings::embedder_analytics(new_settings.embedders.as_ref().set()),
             "search_cutoff_ms": new_settings.search_cutoff_ms.as_ref().set(),
+            "locales": new_settings.localized_attributes.as_ref().set().map(|rules| rules.iter().flat_map(|rule| rule.locales.iter().cloned()).collect::<std::collections::BTreeSet<_>>()),
+            "infix_searchable_attributes": {
+                "total": new_settings.infix_searchable_attributes.as_ref().set().map(|attrs| attrs.len()),
+            },
+            "pinned_results": {
+                "total": new_settings.pinned_results.as_ref().set().map(|rules| rules.len()),
+            },
         }),
         Some(&req),
     );
@@ -10,12 +10,14 @@ use serde::Serialize;
 use tracing::debug;
 
 use crate::analytics::{Analytics, MultiSearchAggregator};
+use crate::error::MeilisearchHttpError;
 use crate::extractors::authentication::policies::ActionPolicy;
 use crate::extractors::authentication::{AuthenticationError, GuardedData};
 use crate::extractors::sequential_extractor::SeqHandler;
 use crate::routes::indexes::search::search_kind;
 use crate::search::{
-    add_search_rules, perform_search, RetrieveVectors, SearchQueryWithIndex, SearchResultWithIndex,
+    add_search_rules, perform_federated_search, perform_search, FederatedSearch,
+    PreparedFederatedQuery, RetrieveVectors, SearchResultWithIndex, WithIndex,
 };
 use crate::search_queue::SearchQueue;
 
@@ -28,36 +30,126 @@ struct SearchResults {
     results: Vec<SearchResultWithIndex>,
 }
 
-#[derive(Debug, deserr::Deserr)]
-#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
-pub struct SearchQueries {
-    queries: Vec<SearchQueryWithIndex>,
-}
-
 pub async fn multi_search_with_post(
     index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<IndexScheduler>>,
     search_queue: Data<SearchQueue>,
-    params: AwebJson<SearchQueries, DeserrJsonError>,
+    params: AwebJson<FederatedSearch, DeserrJsonError>,
     req: HttpRequest,
     analytics: web::Data<dyn Analytics>,
 ) -> Result<HttpResponse, ResponseError> {
-    let queries = params.into_inner().queries;
+    let federated_search = params.into_inner();
 
-    let mut multi_aggregate = MultiSearchAggregator::from_queries(&queries, &req);
+    let mut multi_aggregate = MultiSearchAggregator::from_federated_search(&federated_search, &req);
+    let FederatedSearch { queries, federation } = federated_search;
     let features = index_scheduler.features();
 
     // Since we don't want to process half of the search requests and then get a permit refused
     // we're going to get one permit for the whole duration of the multi-search request.
     let _permit = search_queue.try_get_search_permit().await?;
 
+    if let Some(federation) = federation {
+        let search_result: Result<_, (ResponseError, usize)> = async {
+            let mut prepared_queries = Vec::with_capacity(queries.len());
+            for (query_index, federated_query) in queries.into_iter().enumerate() {
+                if let Some(pagination_field) = federated_query.has_pagination() {
+                    return Err(MeilisearchHttpError::PaginationInFederatedQuery(
+                        query_index,
+                        pagination_field,
+                    ))
+                    .with_index(query_index);
+                }
+                if federated_query.has_facets() {
+                    return Err(MeilisearchHttpError::FacetsInFederatedQuery(query_index))
+                        .with_index(query_index);
+                }
+                if federated_query.has_collapse() {
+                    return Err(MeilisearchHttpError::CollapseInFederatedQuery(query_index))
+                        .with_index(query_index);
+                }
+                if let Some(suggestions_field) = federated_query.has_suggestions() {
+                    return Err(MeilisearchHttpError::SuggestionsInFederatedQuery(
+                        query_index,
+                        suggestions_field,
+                    ))
+                    .with_index(query_index);
+                }
+
+                let (index_uid, mut query, federation_options) = federated_query.into_index_query();
+                debug!(on_index = query_index, parameters = ?query, "Federated search");
+
+                // Check index from API key
+                if !index_scheduler.filters().is_index_authorized(&index_uid) {
+                    return Err(AuthenticationError::InvalidToken).with_index(query_index);
+                }
+                // Apply search rules from tenant token
+                if let Some(search_rules) =
+                    index_scheduler.filters().get_index_search_rules(&index_uid)
+                {
+                    add_search_rules(&mut query.filter, search_rules);
+                }
+
+                let index = index_scheduler
+                    .index(&index_uid)
+                    .map_err(|err| {
+                        let mut err = ResponseError::from(err);
+                        // Patch the HTTP status code to 400 as it defaults to 404 for `index_not_found`, but
+                        // here the resource not found is not part of the URL.
+                        err.code = StatusCode::BAD_REQUEST;
+                        err
+                    })
+                    .with_index(query_index)?;
+
+                let search_kind = search_kind(&query, index_scheduler.get_ref(), &index, features)
+                    .with_index(query_index)?;
+                let retrieve_vectors = RetrieveVectors::new(query.retrieve_vectors, features)
+                    .with_index(query_index)?;
+
+                prepared_queries.push(PreparedFederatedQuery {
+                    query_index,
+                    index_uid,
+                    index,
+                    query,
+                    federation_options: federation_options.unwrap_or_default(),
+                    search_kind,
+                    retrieve_vectors,
+                });
+            }
+
+            // a failing join is not specific to any query, report it on the first one
+            tokio::task::spawn_blocking(move || {
+                perform_federated_search(prepared_queries, federation)
+            })
+            .await
+            .with_index(0)?
+        }
+        .await;
+
+        if search_result.is_ok() {
+            multi_aggregate.succeed();
+        }
+        analytics.post_multi_search(multi_aggregate);
+
+        let search_result = search_result.map_err(with_query_context)?;
+
+        debug!(returns = ?search_result, "Federated search");
+
+        return Ok(HttpResponse::Ok().json(search_result));
+    }
+
     // Explicitly expect a `(ResponseError, usize)` for the error type rather than `ResponseError` only,
     // so that `?` doesn't work if it doesn't use `with_index`, ensuring that it is not forgotten in case of code
     // changes.
     let search_results: Result<_, (ResponseError, usize)> = async {
         let mut search_results = Vec::with_capacity(queries.len());
-        for (query_index, (index_uid, mut query)) in
-            queries.into_iter().map(SearchQueryWithIndex::into_index_query).enumerate()
-        {
+        for (query_index, query) in queries.into_iter().enumerate() {
+            if query.has_federation_options() {
+                return Err(MeilisearchHttpError::FederationOptionsInNonFederatedRequest(
+                    query_index,
+                ))
+                .with_index(query_index);
+            }
+
+            let (index_uid, mut query, _federation_options) = query.into_index_query();
             debug!(on_index = query_index, parameters = ?query, "Multi-search");
 
             // Check index from API key
@@ -106,29 +198,18 @@ pub async fn multi_search_with_post(
     }
     analytics.post_multi_search(multi_aggregate);
 
-    let search_results = search_results.map_err(|(mut err, query_index)| {
-        // Add the query index that failed as context for the error message.
-        // We're doing it only here and not directly in the `WithIndex` trait so that the `with_index` function returns a different type
-        // of result and we can benefit from static typing.
-        err.message = format!("Inside `.queries[{query_index}]`: {}", err.message);
-        err
-    })?;
+    let search_results = search_results.map_err(with_query_context)?;
 
     debug!(returns = ?search_results, "Multi-search");
 
     Ok(HttpResponse::Ok().json(SearchResults { results: search_results }))
 }
 
-/// Local `Result` extension trait to avoid `map_err` boilerplate.
-trait WithIndex {
-    type T;
-    /// convert the error type inside of the `Result` to a `ResponseError`, and return a couple of it + the usize.
-    fn with_index(self, index: usize) -> Result<Self::T, (ResponseError, usize)>;
-}
-
-impl<T, E: Into<ResponseError>> WithIndex for Result<T, E> {
-    type T = T;
-    fn with_index(self, index: usize) -> Result<T, (ResponseError, usize)> {
-        self.map_err(|err| (err.into(), index))
-    }
+/// Add the query index that failed as context for the error message.
+///
+/// We're doing it only once the request is processed and not directly in the `WithIndex` trait so that
+/// the `with_index` function returns a different type of result and we can benefit from static typing.
+fn with_query_context((mut err, query_index): (ResponseError, usize)) -> ResponseError {
+    err.message = format!("Inside `.queries[{query_index}]`: {}", err.message);
+    err
 }
@@ -0,0 +1,376 @@
+use std::cmp::{min, Ordering};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use deserr::Deserr;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::milli::score_details::{ScoreDetails, ScoringStrategy};
+use meilisearch_types::milli::{self, DocumentId, Index, TimeBudget};
+use roaring::RoaringBitmap;
+use serde::Serialize;
+
+use super::{
+    make_hits, prepare_search, search_from_kind, AttributesFormat, HitsInfo, RetrieveVectors,
+    SearchHit, SearchKind, SearchQuery, SearchQueryWithIndex, DEFAULT_SEARCH_LIMIT,
+    DEFAULT_SEARCH_OFFSET,
+};
+
+#[derive(Debug, Deserr)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct FederatedSearch {
+    pub queries: Vec<SearchQueryWithIndex>,
+    #[deserr(default)]
+    pub federation: Option<Federation>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserr)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct Federation {
+    #[deserr(default = DEFAULT_SEARCH_LIMIT(), error = DeserrJsonError<InvalidSearchLimit>)]
+    pub limit: usize,
+    #[deserr(default = DEFAULT_SEARCH_OFFSET(), error = DeserrJsonError<InvalidSearchOffset>)]
+    pub offset: usize,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserr)]
+#[deserr(error = DeserrJsonError<InvalidMultiSearchFederationOptions>, rename_all = camelCase, deny_unknown_fields)]
+pub struct FederationOptions {
+    #[deserr(default, error = DeserrJsonError<InvalidMultiSearchWeight>)]
+    pub weight: Weight,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserr)]
+#[deserr(try_from(f64) = TryFrom::try_from -> InvalidMultiSearchWeight)]
+pub struct Weight(f64);
+
+impl Default for Weight {
+    fn default() -> Self {
+        Weight(1.0)
+    }
+}
+
+impl std::convert::TryFrom<f64> for Weight {
+    type Error = InvalidMultiSearchWeight;
+
+    fn try_from(f: f64) -> Result<Self, Self::Error> {
+        if f < 0.0 {
+            Err(InvalidMultiSearchWeight)
+        } else {
+            Ok(Weight(f))
+        }
+    }
+}
+
+impl std::ops::Deref for Weight {
+    type Target = f64;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Where a federated hit comes from and how it was ranked against the hits of the other queries.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HitFederation {
+    pub index_uid: String,
+    pub queries_position: usize,
+    pub weighted_ranking_score: f64,
+}
+
+/// A query of a federated search, checked against the API key and ready to be executed.
+pub struct PreparedFederatedQuery {
+    pub query_index: usize,
+    pub index_uid: IndexUid,
+    pub index: Index,
+    pub query: SearchQuery,
+    pub federation_options: FederationOptions,
+    pub search_kind: SearchKind,
+    pub retrieve_vectors: RetrieveVectors,
+}
+
+#[derive(Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FederatedSearchResult {
+    pub hits: Vec<SearchHit>,
+    pub processing_time_ms: u128,
+    #[serde(flatten)]
+    pub hits_info: HitsInfo,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub semantic_hit_count: Option<u32>,
+
+    // These fields are only used for analytics purposes
+    #[serde(skip)]
+    pub degraded: bool,
+    #[serde(skip)]
+    pub used_negative_operator: bool,
+}
+
+impl fmt::Debug for FederatedSearchResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let FederatedSearchResult {
+            hits,
+            processing_time_ms,
+            hits_info,
+            semantic_hit_count,
+            degraded,
+            used_negative_operator,
+        } = self;
+
+        let mut debug = f.debug_struct("SearchResult");
+        // The most important thing when looking at a search result is the time it took to process
+        debug.field("processing_time_ms", &processing_time_ms);
+        debug.field("hits", &format!("[{} hits returned]", hits.len()));
+        debug.field("hits_info", &hits_info);
+        if *used_negative_operator {
+            debug.field("used_negative_operator", used_negative_operator);
+        }
+        if *degraded {
+            debug.field("degraded", degraded);
+        }
+        if let Some(semantic_hit_count) = semantic_hit_count {
+            debug.field("semantic_hit_count", &semantic_hit_count);
+        }
+
+        debug.finish()
+    }
+}
+
+/// Local `Result` extension trait to avoid `map_err` boilerplate.
+pub(crate) trait WithIndex {
+    type T;
+    /// convert the error type inside of the `Result` to a `ResponseError`, and return a couple of it + the usize.
+    fn with_index(self, index: usize) -> Result<Self::T, (ResponseError, usize)>;
+}
+
+impl<T, E: Into<ResponseError>> WithIndex for Result<T, E> {
+    type T = T;
+    fn with_index(self, index: usize) -> Result<T, (ResponseError, usize)> {
+        self.map_err(|err| (err.into(), index))
+    }
+}
+
+/// A hit of one of the queries, along with what is needed to rank it against the other hits.
+struct WeightedHit {
+    hit: SearchHit,
+    query_index: usize,
+    weighted_ranking_score: f64,
+}
+
+/// The raw results of a query, kept around until the hits of its index are deduplicated.
+struct QueryResults {
+    query_index: usize,
+    format: AttributesFormat,
+    matching_words: milli::MatchingWords,
+    documents_ids: Vec<DocumentId>,
+    document_scores: Vec<Vec<ScoreDetails>>,
+    weighted_scores: Vec<f64>,
+}
+
+/// Executes all the queries of a federated search and merges their hits into a single list.
+///
+/// The hits are sorted by their weighted ranking score, so every query is executed
+/// with a detailed scoring strategy regardless of the `showRankingScore` parameter.
+/// A document matched by several queries of the same index is only returned once,
+/// for the query that gives it its best weighted ranking score.
+pub fn perform_federated_search(
+    queries: Vec<PreparedFederatedQuery>,
+    federation: Federation,
+) -> Result<FederatedSearchResult, (ResponseError, usize)> {
+    let before_search = Instant::now();
+    let Federation { limit, offset } = federation;
+    // every query must return enough hits to fill the requested page on its own
+    let required_hit_count = offset.saturating_add(limit);
+
+    // group the queries by index so that each index is only read through a single transaction
+    let mut queries_by_index: BTreeMap<String, Vec<PreparedFederatedQuery>> = BTreeMap::new();
+    for query in queries {
+        queries_by_index.entry(query.index_uid.to_string()).or_default().push(query);
+    }
+
+    let mut weighted_hits = Vec::new();
+    let mut estimated_total_hits = 0;
+    let mut semantic_hit_count = None;
+    let mut degraded = false;
+    let mut used_negative_operator = false;
+
+    for (index_uid, queries) in queries_by_index {
+        // the map only contains non-empty lists of queries
+        let first_query_index = queries[0].query_index;
+        let index = queries[0].index.clone();
+
+        let rtxn = index.read_txn().with_index(first_query_index)?;
+        let time_budget = match index.search_cutoff(&rtxn).with_index(first_query_index)? {
+            Some(cutoff) => TimeBudget::new(Duration::from_millis(cutoff)),
+            None => TimeBudget::default(),
+        };
+
+        let mut index_candidates = RoaringBitmap::new();
+        // the position in `query_results` of the query giving its best weighted score to a document
+        let mut best_query_for_document: HashMap<DocumentId, (f64, usize)> = HashMap::new();
+        let mut query_results = Vec::with_capacity(queries.len());
+
+        for PreparedFederatedQuery {
+            query_index,
+            index_uid: _,
+            index: _,
+            query,
+            federation_options,
+            search_kind,
+            retrieve_vectors,
+        } in queries
+        {
+            let (mut search, _is_finite_pagination, max_total_hits, _offset) =
+                prepare_search(&index, &rtxn, &query, &search_kind, time_budget.clone())
+                    .with_index(query_index)?;
+
+            // the pagination is applied once the hits of all the queries are merged
+            search.offset(0);
+            search.limit(min(required_hit_count, max_total_hits));
+            search.scoring_strategy(ScoringStrategy::Detailed);
+
+            let (
+                milli::SearchResult {
+                    matching_words,
+                    candidates,
+                    documents_ids,
+                    document_scores,
+                    cursor: _,
+                    degraded: query_degraded,
+                    used_negative_operator: query_used_negative_operator,
+                },
+                query_semantic_hit_count,
+            ) = search_from_kind(&search_kind, &search).with_index(query_index)?;
+
+            index_candidates |= candidates;
+            degraded |= query_degraded;
+            used_negative_operator |= query_used_negative_operator;
+            if let Some(query_semantic_hit_count) = query_semantic_hit_count {
+                semantic_hit_count =
+                    Some(semantic_hit_count.unwrap_or(0) + query_semantic_hit_count);
+            }
+
+            let weight = *federation_options.weight;
+            let position = query_results.len();
+            let weighted_scores: Vec<f64> = document_scores
+                .iter()
+                .map(|scores| ScoreDetails::global_score(scores.iter()) * weight)
+                .collect();
+            for (&docid, &weighted_score) in documents_ids.iter().zip(&weighted_scores) {
+                match best_query_for_document.get(&docid) {
+                    // on equality, keep the query that was given first
+                    Some(&(best_score, _)) if best_score >= weighted_score => {}
+                    _ => {
+                        best_query_for_document.insert(docid, (weighted_score, position));
+                    }
+                }
+            }
+
+            let SearchQuery {
+                attributes_to_retrieve,
+                attributes_to_crop,
+                crop_length,
+                attributes_to_highlight,
+                show_matches_position,
+                show_ranking_score,
+                show_ranking_score_details,
+                sort,
+                highlight_pre_tag,
+                highlight_post_tag,
+                crop_marker,
+                locales,
+                ..
+            } = query;
+
+            let format = AttributesFormat {
+                attributes_to_retrieve,
+                retrieve_vectors,
+                attributes_to_highlight,
+                attributes_to_crop,
+                crop_length,
+                crop_marker,
+                highlight_pre_tag,
+                highlight_post_tag,
+                show_matches_position,
+                sort,
+                show_ranking_score,
+                show_ranking_score_details,
+                locales: locales.map(|locales| locales.into_iter().map(Into::into).collect()),
+            };
+
+            query_results.push(QueryResults {
+                query_index,
+                format,
+                matching_words,
+                documents_ids,
+                document_scores,
+                weighted_scores,
+            });
+        }
+
+        estimated_total_hits += index_candidates.len() as usize;
+
+        for (
+            position,
+            QueryResults {
+                query_index,
+                format,
+                matching_words,
+                documents_ids,
+                document_scores,
+                weighted_scores,
+            },
+        ) in query_results.into_iter().enumerate()
+        {
+            // only keep the documents for which this query is the best one
+            let mut kept_ids = Vec::new();
+            let mut kept_scores = Vec::new();
+            let mut kept_weighted_scores = Vec::new();
+            for ((docid, scores), weighted_score) in
+                documents_ids.into_iter().zip(document_scores).zip(weighted_scores)
+            {
+                if best_query_for_document.get(&docid).map(|&(_, best)| best) == Some(position) {
+                    kept_ids.push(docid);
+                    kept_scores.push(scores);
+                    kept_weighted_scores.push(weighted_score);
+                }
+            }
+
+            let hits = make_hits(&index, &rtxn, format, matching_words, kept_ids, kept_scores)
+                .with_index(query_index)?;
+
+            for (mut hit, weighted_ranking_score) in hits.into_iter().zip(kept_weighted_scores) {
+                hit.federation = Some(HitFederation {
+                    index_uid: index_uid.clone(),
+                    queries_position: query_index,
+                    weighted_ranking_score,
+                });
+                weighted_hits.push(WeightedHit { hit, query_index, weighted_ranking_score });
+            }
+        }
+    }
+
+    // the sort is stable, so the hits of a query keep their relative order on equal scores
+    weighted_hits.sort_by(|left, right| {
+        match right.weighted_ranking_score.total_cmp(&left.weighted_ranking_score) {
+            Ordering::Equal => left.query_index.cmp(&right.query_index),
+            ordering => ordering,
+        }
+    });
+
+    let hits = weighted_hits.into_iter().skip(offset).take(limit).map(|hit| hit.hit).collect();
+
+    Ok(FederatedSearchResult {
+        hits,
+        processing_time_ms: before_search.elapsed().as_millis(),
+        hits_info: HitsInfo::OffsetLimit { limit, offset, estimated_total_hits },
+        semantic_hit_count,
+        degraded,
+        used_negative_operator,
+    })
+}
@@ -1,11 +1,12 @@
 use core::fmt;
 use std::cmp::min;
 use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::convert::Infallible;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use deserr::Deserr;
+use deserr::{DeserializeError, Deserr, ErrorKind, ValuePointerRef};
 use either::Either;
 use indexmap::IndexMap;
 use meilisearch_auth::IndexSearchRules;
@@ -14,23 +15,36 @@ use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::heed::RoTxn;
 use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::locales::Locale;
+use meilisearch_types::milli::localized_attributes_rules::script_language_allow_list;
 use meilisearch_types::milli::score_details::{ScoreDetails, ScoringStrategy};
 use meilisearch_types::milli::vector::parsed_vectors::ExplicitVectors;
 use meilisearch_types::milli::vector::Embedder;
-use meilisearch_types::milli::{FacetValueHit, OrderBy, SearchForFacetValues, TimeBudget};
-use meilisearch_types::settings::DEFAULT_PAGINATION_MAX_TOTAL_HITS;
+use meilisearch_types::milli::{
+    Analysis, Analyze, Autocomplete, BucketStep, Completion, Completions, ExplainedBucket,
+    ExplainedQueryGraph, FacetRangeCount, FacetValueHit, OrderBy, SearchForFacetValues, TimeBudget,
+};
+use meilisearch_types::settings::{RankingRuleView, DEFAULT_PAGINATION_MAX_TOTAL_HITS};
 use meilisearch_types::{milli, Document};
-use milli::tokenizer::TokenizerBuilder;
+use milli::tokenizer::{Language, TokenizerBuilder};
 use milli::{
-    AscDesc, FieldId, FieldsIdsMap, Filter, FormatOptions, Index, MatchBounds, MatcherBuilder,
-    SortError, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
+    AscDesc, Criterion, FieldId, FieldsIdsMap, Filter, FormatOptions, Index, MatchBounds,
+    MatcherBuilder, SortError, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
 };
 use regex::Regex;
+use roaring::RoaringBitmap;
 use serde::Serialize;
 use serde_json::{json, Value};
 
 use crate::error::MeilisearchHttpError;
 
+mod federated;
+pub(crate) use federated::WithIndex;
+pub use federated::{
+    perform_federated_search, FederatedSearch, FederatedSearchResult, Federation,
+    FederationOptions, HitFederation, PreparedFederatedQuery,
+};
+
 type MatchesPosition = BTreeMap<String, Vec<MatchBounds>>;
 
 pub const DEFAULT_SEARCH_OFFSET: fn() -> usize = || 0;
@@ -40,6 +54,8 @@ pub const DEFAULT_CROP_MARKER: fn() -> String = || "…".to_string();
 pub const DEFAULT_HIGHLIGHT_PRE_TAG: fn() -> String = || "<em>".to_string();
 pub const DEFAULT_HIGHLIGHT_POST_TAG: fn() -> String = || "</em>".to_string();
 pub const DEFAULT_SEMANTIC_RATIO: fn() -> SemanticRatio = || SemanticRatio(0.5);
+pub const DEFAULT_SUGGESTIONS_THRESHOLD: fn() -> usize = || 10;
+pub const DEFAULT_AUTOCOMPLETE_LIMIT: fn() -> usize = || 10;
 
 #[derive(Clone, Default, PartialEq, Deserr)]
 #[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
@@ -58,6 +74,10 @@ pub struct SearchQuery {
     pub page: Option<usize>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHitsPerPage>)]
     pub hits_per_page: Option<usize>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchSearchAfter>)]
+    pub search_after: Option<SearchAfter>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchShowCursor>, default)]
+    pub show_cursor: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToRetrieve>)]
     pub attributes_to_retrieve: Option<BTreeSet<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchRetrieveVectors>)]
@@ -78,10 +98,22 @@ pub struct SearchQuery {
     pub filter: Option<Value>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchSort>)]
     pub sort: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchRankingRules>)]
+    pub ranking_rules: Option<Vec<RankingRuleView>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchDistinct>)]
     pub distinct: Option<String>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchCollapse>)]
+    pub collapse: Option<CollapseQuery>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFacets>)]
     pub facets: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetRanges>)]
+    pub facet_ranges: Option<BTreeMap<String, FacetRangesQuery>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDisjunctiveFacets>)]
+    pub disjunctive_facets: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetStatsAggregations>)]
+    pub facet_stats_aggregations: Option<Vec<FacetStatsAggregation>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchGeoAggregation>)]
+    pub geo_aggregation: Option<GeoAggregationQuery>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPreTag>, default = DEFAULT_HIGHLIGHT_PRE_TAG())]
     pub highlight_pre_tag: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPostTag>, default = DEFAULT_HIGHLIGHT_POST_TAG())]
@@ -91,9 +123,17 @@ pub struct SearchQuery {
     #[deserr(default, error = DeserrJsonError<InvalidSearchMatchingStrategy>, default)]
     pub matching_strategy: MatchingStrategy,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToSearchOn>, default)]
-    pub attributes_to_search_on: Option<Vec<String>>,
+    pub attributes_to_search_on: Option<AttributesToSearchOn>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchRankingScoreThreshold>, default)]
     pub ranking_score_threshold: Option<RankingScoreThreshold>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchLocales>, default)]
+    pub locales: Option<Vec<Locale>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchTypoTolerantNegation>, default)]
+    pub typo_tolerant_negation: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchShowSuggestions>, default)]
+    pub show_suggestions: bool,
+    #[deserr(default = DEFAULT_SUGGESTIONS_THRESHOLD(), error = DeserrJsonError<InvalidSearchSuggestionsThreshold>)]
+    pub suggestions_threshold: usize,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Deserr)]
@@ -114,6 +154,89 @@ impl std::convert::TryFrom<f64> for RankingScoreThreshold {
     }
 }
 
+/// The attributes a search is restricted to, some of them given a weight
+/// overriding their order in the searchable attributes.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AttributesToSearchOn {
+    pub attributes: Vec<String>,
+    /// The attributes given a weight, the highest weights are ranked first by the attribute rule.
+    pub weights: Vec<(String, milli::Weight)>,
+}
+
+impl FromIterator<String> for AttributesToSearchOn {
+    fn from_iter<T: IntoIterator<Item = String>>(iter: T) -> Self {
+        AttributesToSearchOn { attributes: iter.into_iter().collect(), weights: Vec::new() }
+    }
+}
+
+impl<E: DeserializeError> Deserr<E> for AttributesToSearchOn {
+    fn deserialize_from_value<V: deserr::IntoValue>(
+        value: deserr::Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        let mut attributes_to_search_on = AttributesToSearchOn::default();
+        for attribute in Vec::<AttributeToSearchOn>::deserialize_from_value(value, location)? {
+            match attribute {
+                AttributeToSearchOn::Name(name) => attributes_to_search_on.attributes.push(name),
+                AttributeToSearchOn::Weighted(WeightedAttribute { attribute, weight }) => {
+                    attributes_to_search_on.attributes.push(attribute.clone());
+                    attributes_to_search_on.weights.push((attribute, weight));
+                }
+            }
+        }
+        Ok(attributes_to_search_on)
+    }
+}
+
+/// Either the name of an attribute or an attribute with its weight.
+enum AttributeToSearchOn {
+    Name(String),
+    Weighted(WeightedAttribute),
+}
+
+#[derive(Deserr)]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+struct WeightedAttribute {
+    attribute: String,
+    weight: milli::Weight,
+}
+
+impl<E: DeserializeError> Deserr<E> for AttributeToSearchOn {
+    fn deserialize_from_value<V: deserr::IntoValue>(
+        value: deserr::Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        match value {
+            deserr::Value::String(name) => Ok(AttributeToSearchOn::Name(name)),
+            deserr::Value::Map(_) => {
+                let weighted = WeightedAttribute::deserialize_from_value(value, location)?;
+                if weighted.weight > milli::MAX_ATTRIBUTE_WEIGHT {
+                    return Err(deserr::take_cf_content(E::error::<Infallible>(
+                        None,
+                        ErrorKind::Unexpected {
+                            msg: format!(
+                                "the `weight` must be between `0` and `{}`, but got `{}`",
+                                milli::MAX_ATTRIBUTE_WEIGHT,
+                                weighted.weight
+                            ),
+                        },
+                        location.push_key("weight"),
+                    )));
+                }
+                Ok(AttributeToSearchOn::Weighted(weighted))
+            }
+            value => Err(deserr::take_cf_content(E::error(
+                None,
+                ErrorKind::IncorrectValueKind {
+                    actual: value,
+                    accepted: &[deserr::ValueKind::String, deserr::ValueKind::Map],
+                },
+                location,
+            ))),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Deserr)]
 #[deserr(try_from(f64) = TryFrom::try_from -> InvalidSimilarRankingScoreThreshold)]
 pub struct RankingScoreThresholdSimilar(f64);
@@ -145,6 +268,8 @@ impl fmt::Debug for SearchQuery {
             limit,
             page,
             hits_per_page,
+            search_after,
+            show_cursor,
             attributes_to_retrieve,
             retrieve_vectors,
             attributes_to_crop,
@@ -155,14 +280,24 @@ impl fmt::Debug for SearchQuery {
             show_ranking_score_details,
             filter,
             sort,
+            ranking_rules,
             distinct,
+            collapse,
             facets,
+            facet_ranges,
+            disjunctive_facets,
+            facet_stats_aggregations,
+            geo_aggregation,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
             matching_strategy,
             attributes_to_search_on,
             ranking_score_threshold,
+            locales,
+            typo_tolerant_negation,
+            show_suggestions,
+            suggestions_threshold,
         } = self;
 
         let mut debug = f.debug_struct("SearchQuery");
@@ -175,6 +310,12 @@ impl fmt::Debug for SearchQuery {
         if let Some(hits_per_page) = hits_per_page {
             debug.field("hits_per_page", &hits_per_page);
         }
+        if let Some(search_after) = search_after {
+            debug.field("search_after", &search_after);
+        }
+        if *show_cursor {
+            debug.field("show_cursor", show_cursor);
+        }
 
         // Then, everything related to the queries
         if let Some(q) = q {
@@ -199,6 +340,16 @@ impl fmt::Debug for SearchQuery {
         if let Some(attributes_to_search_on) = attributes_to_search_on {
             debug.field("attributes_to_search_on", &attributes_to_search_on);
         }
+        if let Some(locales) = locales {
+            debug.field("locales", &locales);
+        }
+        if *typo_tolerant_negation {
+            debug.field("typo_tolerant_negation", typo_tolerant_negation);
+        }
+        if *show_suggestions {
+            debug.field("show_suggestions", show_suggestions);
+            debug.field("suggestions_threshold", suggestions_threshold);
+        }
         if let Some(filter) = filter {
             debug.field("filter", &filter);
         }
@@ -208,9 +359,27 @@ impl fmt::Debug for SearchQuery {
         if let Some(distinct) = distinct {
             debug.field("distinct", &distinct);
         }
+        if let Some(collapse) = collapse {
+            debug.field("collapse", &collapse);
+        }
         if let Some(facets) = facets {
             debug.field("facets", &facets);
         }
+        if let Some(facet_ranges) = facet_ranges {
+            debug.field("facet_ranges", &facet_ranges);
+        }
+        if let Some(disjunctive_facets) = disjunctive_facets {
+            debug.field("disjunctive_facets", &disjunctive_facets);
+        }
+        if let Some(facet_stats_aggregations) = facet_stats_aggregations {
+            debug.field("facet_stats_aggregations", &facet_stats_aggregations);
+        }
+        if let Some(geo_aggregation) = geo_aggregation {
+            debug.field("geo_aggregation", &geo_aggregation);
+        }
+        if let Some(ranking_rules) = ranking_rules {
+            debug.field("ranking_rules", &ranking_rules);
+        }
         debug.field("matching_strategy", &matching_strategy);
 
         // Then everything related to the formatting
@@ -257,6 +426,214 @@ pub struct HybridQuery {
     pub embedder: Option<String>,
 }
 
+#[derive(Debug, Clone, PartialEq, Deserr)]
+#[deserr(error = DeserrJsonError<InvalidSearchFacetRanges>, rename_all = camelCase, deny_unknown_fields, validate = validate_facet_ranges -> DeserrJsonError<InvalidSearchFacetRanges>)]
+pub struct FacetRangesQuery {
+    #[deserr(default)]
+    pub ranges: Option<Vec<FacetRangeQuery>>,
+    #[deserr(default)]
+    pub interval: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserr)]
+#[deserr(error = DeserrJsonError<InvalidSearchFacetRanges>, rename_all = camelCase, deny_unknown_fields)]
+pub struct FacetRangeQuery {
+    #[deserr(default)]
+    pub from: Option<f64>,
+    #[deserr(default)]
+    pub to: Option<f64>,
+}
+
+fn validate_facet_ranges<E: DeserializeError>(
+    facet_ranges: FacetRangesQuery,
+    location: ValuePointerRef,
+) -> Result<FacetRangesQuery, E> {
+    match (&facet_ranges.ranges, facet_ranges.interval) {
+        (Some(_), None) | (None, Some(_)) => Ok(facet_ranges),
+        _ => Err(deserr::take_cf_content(E::error::<Infallible>(
+            None,
+            ErrorKind::Unexpected {
+                msg: "exactly one of `ranges` or `interval` must be specified".to_string(),
+            },
+            location,
+        ))),
+    }
+}
+
+/// Counts the candidates per geohash cell or map tile.
+#[derive(Debug, Clone, PartialEq, Deserr)]
+#[deserr(error = DeserrJsonError<InvalidSearchGeoAggregation>, rename_all = camelCase, deny_unknown_fields, validate = validate_geo_aggregation -> DeserrJsonError<InvalidSearchGeoAggregation>)]
+pub struct GeoAggregationQuery {
+    /// The length of the geohashes.
+    #[deserr(default)]
+    pub precision: Option<u8>,
+    /// The zoom level of the map tiles.
+    #[deserr(default)]
+    pub zoom: Option<u8>,
+    /// Only the points in these bounds are aggregated.
+    #[deserr(default)]
+    pub bounds: Option<GeoBoundsQuery>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserr)]
+#[deserr(error = DeserrJsonError<InvalidSearchGeoAggregation>, rename_all = camelCase, deny_unknown_fields)]
+pub struct GeoBoundsQuery {
+    pub top_right: Vec<f64>,
+    pub bottom_left: Vec<f64>,
+}
+
+fn validate_geo_aggregation<E: DeserializeError>(
+    geo_aggregation: GeoAggregationQuery,
+    location: ValuePointerRef,
+) -> Result<GeoAggregationQuery, E> {
+    let error = |msg: String| {
+        Err(deserr::take_cf_content(E::error::<Infallible>(
+            None,
+            ErrorKind::Unexpected { msg },
+            location,
+        )))
+    };
+
+    match (geo_aggregation.precision, geo_aggregation.zoom) {
+        (Some(precision), None) if (1..=milli::MAX_GEOHASH_PRECISION).contains(&precision) => (),
+        (Some(precision), None) => {
+            return error(format!(
+                "the `precision` must be between `1` and `{}`, but got `{precision}`",
+                milli::MAX_GEOHASH_PRECISION
+            ))
+        }
+        (None, Some(zoom)) if zoom <= milli::MAX_TILE_ZOOM => (),
+        (None, Some(zoom)) => {
+            return error(format!(
+                "the `zoom` must be between `0` and `{}`, but got `{zoom}`",
+                milli::MAX_TILE_ZOOM
+            ))
+        }
+        _ => return error("exactly one of `precision` or `zoom` must be specified".to_string()),
+    }
+
+    if let Some(GeoBoundsQuery { top_right, bottom_left }) = &geo_aggregation.bounds {
+        for (name, point) in [("topRight", top_right), ("bottomLeft", bottom_left)] {
+            match point.as_slice() {
+                [lat, lng] if (-90.0..=90.0).contains(lat) && (-180.0..=180.0).contains(lng) => (),
+                _ => {
+                    return error(format!(
+                        "the `{name}` bound must be a `[latitude, longitude]` pair with a latitude between `-90` and `90` and a longitude between `-180` and `180`"
+                    ))
+                }
+            }
+        }
+        if top_right[0] < bottom_left[0] {
+            return error(
+                "the `topRight` bound must not be below the `bottomLeft` bound".to_string(),
+            );
+        }
+    }
+
+    Ok(geo_aggregation)
+}
+
+/// A cell of the geo aggregation, along with the number of candidates it contains.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GeoCellCount {
+    pub key: String,
+    pub count: u64,
+    pub centroid: GeoCentroid,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct GeoCentroid {
+    pub lat: f64,
+    pub lng: f64,
+}
+
+impl From<milli::GeoCell> for GeoCellCount {
+    fn from(milli::GeoCell { key, count, centroid: [lat, lng] }: milli::GeoCell) -> Self {
+        Self { key, count, centroid: GeoCentroid { lat, lng } }
+    }
+}
+
+/// Reports, along with each hit, the documents that the distinct rule collapsed under it.
+#[derive(Debug, Clone, Default, PartialEq, Deserr)]
+#[deserr(error = DeserrJsonError<InvalidSearchCollapse>, rename_all = camelCase, deny_unknown_fields)]
+pub struct CollapseQuery {
+    /// The number of collapsed documents to return, only their count is returned by default.
+    #[deserr(default)]
+    pub limit: usize,
+    /// The attributes of the collapsed documents to return, defaults to the primary key,
+    /// or to the displayed attributes when the primary key isn't displayed.
+    #[deserr(default)]
+    pub attributes_to_retrieve: Option<BTreeSet<String>>,
+}
+
+impl From<FacetRangesQuery> for milli::FacetRanges {
+    fn from(FacetRangesQuery { ranges, interval }: FacetRangesQuery) -> Self {
+        match (ranges, interval) {
+            (_, Some(interval)) => milli::FacetRanges::Interval(interval),
+            (ranges, None) => milli::FacetRanges::Ranges(
+                ranges
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|FacetRangeQuery { from, to }| milli::FacetRange { from, to })
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// An aggregation of the numeric values of the facets, reported in `facetStats`
+/// along with their `min` and `max`.
+#[derive(Debug, Clone, Copy, PartialEq, Deserr)]
+#[deserr(try_from(&String) = FromStr::from_str -> InvalidSearchFacetStatsAggregations)]
+pub enum FacetStatsAggregation {
+    Sum,
+    Avg,
+    Count,
+    /// A percentile between `0.0` and `100.0`, written `p<percentile>`.
+    ///
+    /// The percentiles are exact: computing them iterates over every distinct value
+    /// of the facet among the candidates, which is costly on facets with many values.
+    Percentile(f64),
+}
+
+impl FromStr for FacetStatsAggregation {
+    type Err = InvalidSearchFacetStatsAggregations;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sum" => Ok(Self::Sum),
+            "avg" => Ok(Self::Avg),
+            "count" => Ok(Self::Count),
+            _ => s
+                .strip_prefix('p')
+                .and_then(|percentile| percentile.parse::<f64>().ok())
+                .filter(|percentile| (0.0..=100.0).contains(percentile))
+                .map(Self::Percentile)
+                .ok_or(InvalidSearchFacetStatsAggregations),
+        }
+    }
+}
+
+/// The `cursor` of a previous search, the search resumes after its last hit.
+#[derive(Debug, Clone, PartialEq, Deserr)]
+#[deserr(try_from(&String) = FromStr::from_str -> InvalidSearchSearchAfter)]
+pub struct SearchAfter(pub milli::SearchCursorToken);
+
+impl FromStr for SearchAfter {
+    type Err = InvalidSearchSearchAfter;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(SearchAfter).map_err(|_| InvalidSearchSearchAfter)
+    }
+}
+
+/// The parameters changing the results of a search, the cursors it returns are
+/// only accepted by the searches with the same parameters.
+fn search_cursor_parameters(query: &SearchQuery) -> Result<Vec<u8>, MeilisearchHttpError> {
+    let SearchQuery { q, filter, sort, ranking_rules, .. } = query;
+    Ok(serde_json::to_vec(&(q, filter, sort, ranking_rules))?)
+}
+
 pub enum SearchKind {
     KeywordOnly,
     SemanticOnly { embedder_name: String, embedder: Arc<Embedder> },
@@ -381,6 +758,10 @@ pub struct SearchQueryWithIndex {
     pub page: Option<usize>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHitsPerPage>)]
     pub hits_per_page: Option<usize>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchSearchAfter>)]
+    pub search_after: Option<SearchAfter>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchShowCursor>, default)]
+    pub show_cursor: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToRetrieve>)]
     pub attributes_to_retrieve: Option<BTreeSet<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchRetrieveVectors>)]
@@ -401,10 +782,22 @@ pub struct SearchQueryWithIndex {
     pub filter: Option<Value>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchSort>)]
     pub sort: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchRankingRules>)]
+    pub ranking_rules: Option<Vec<RankingRuleView>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchDistinct>)]
     pub distinct: Option<String>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchCollapse>)]
+    pub collapse: Option<CollapseQuery>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFacets>)]
     pub facets: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetRanges>)]
+    pub facet_ranges: Option<BTreeMap<String, FacetRangesQuery>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDisjunctiveFacets>)]
+    pub disjunctive_facets: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetStatsAggregations>)]
+    pub facet_stats_aggregations: Option<Vec<FacetStatsAggregation>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchGeoAggregation>)]
+    pub geo_aggregation: Option<GeoAggregationQuery>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPreTag>, default = DEFAULT_HIGHLIGHT_PRE_TAG())]
     pub highlight_pre_tag: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPostTag>, default = DEFAULT_HIGHLIGHT_POST_TAG())]
@@ -414,13 +807,69 @@ pub struct SearchQueryWithIndex {
     #[deserr(default, error = DeserrJsonError<InvalidSearchMatchingStrategy>, default)]
     pub matching_strategy: MatchingStrategy,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToSearchOn>, default)]
-    pub attributes_to_search_on: Option<Vec<String>>,
+    pub attributes_to_search_on: Option<AttributesToSearchOn>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchRankingScoreThreshold>, default)]
     pub ranking_score_threshold: Option<RankingScoreThreshold>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchLocales>, default)]
+    pub locales: Option<Vec<Locale>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchTypoTolerantNegation>, default)]
+    pub typo_tolerant_negation: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchShowSuggestions>, default)]
+    pub show_suggestions: bool,
+    #[deserr(default = DEFAULT_SUGGESTIONS_THRESHOLD(), error = DeserrJsonError<InvalidSearchSuggestionsThreshold>)]
+    pub suggestions_threshold: usize,
+
+    #[deserr(default, error = DeserrJsonError<InvalidMultiSearchFederationOptions>)]
+    pub federation_options: Option<FederationOptions>,
 }
 
 impl SearchQueryWithIndex {
-    pub fn into_index_query(self) -> (IndexUid, SearchQuery) {
+    pub fn has_federation_options(&self) -> bool {
+        self.federation_options.is_some()
+    }
+
+    pub fn has_pagination(&self) -> Option<&'static str> {
+        if self.offset != DEFAULT_SEARCH_OFFSET() {
+            Some("offset")
+        } else if self.limit != DEFAULT_SEARCH_LIMIT() {
+            Some("limit")
+        } else if self.page.is_some() {
+            Some("page")
+        } else if self.hits_per_page.is_some() {
+            Some("hitsPerPage")
+        } else if self.search_after.is_some() {
+            Some("searchAfter")
+        } else {
+            None
+        }
+    }
+
+    pub fn has_facets(&self) -> bool {
+        self.facets.as_ref().is_some_and(|facets| !facets.is_empty())
+            || self.facet_ranges.as_ref().is_some_and(|ranges| !ranges.is_empty())
+            || self.disjunctive_facets.as_ref().is_some_and(|facets| !facets.is_empty())
+            || self
+                .facet_stats_aggregations
+                .as_ref()
+                .is_some_and(|aggregations| !aggregations.is_empty())
+            || self.geo_aggregation.is_some()
+    }
+
+    pub fn has_collapse(&self) -> bool {
+        self.collapse.is_some()
+    }
+
+    pub fn has_suggestions(&self) -> Option<&'static str> {
+        if self.show_suggestions {
+            Some("showSuggestions")
+        } else if self.suggestions_threshold != DEFAULT_SUGGESTIONS_THRESHOLD() {
+            Some("suggestionsThreshold")
+        } else {
+            None
+        }
+    }
+
+    pub fn into_index_query(self) -> (IndexUid, SearchQuery, Option<FederationOptions>) {
         let SearchQueryWithIndex {
             index_uid,
             q,
@@ -429,6 +878,8 @@ impl SearchQueryWithIndex {
             limit,
             page,
             hits_per_page,
+            search_after,
+            show_cursor,
             attributes_to_retrieve,
             retrieve_vectors,
             attributes_to_crop,
@@ -439,8 +890,14 @@ impl SearchQueryWithIndex {
             show_matches_position,
             filter,
             sort,
+            ranking_rules,
             distinct,
+            collapse,
             facets,
+            facet_ranges,
+            disjunctive_facets,
+            facet_stats_aggregations,
+            geo_aggregation,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
@@ -448,6 +905,11 @@ impl SearchQueryWithIndex {
             attributes_to_search_on,
             hybrid,
             ranking_score_threshold,
+            locales,
+            typo_tolerant_negation,
+            show_suggestions,
+            suggestions_threshold,
+            federation_options,
         } = self;
         (
             index_uid,
@@ -458,6 +920,8 @@ impl SearchQueryWithIndex {
                 limit,
                 page,
                 hits_per_page,
+                search_after,
+                show_cursor,
                 attributes_to_retrieve,
                 retrieve_vectors,
                 attributes_to_crop,
@@ -468,8 +932,14 @@ impl SearchQueryWithIndex {
                 show_matches_position,
                 filter,
                 sort,
+                ranking_rules,
                 distinct,
+                collapse,
                 facets,
+                facet_ranges,
+                disjunctive_facets,
+                facet_stats_aggregations,
+                geo_aggregation,
                 highlight_pre_tag,
                 highlight_post_tag,
                 crop_marker,
@@ -477,9 +947,14 @@ impl SearchQueryWithIndex {
                 attributes_to_search_on,
                 hybrid,
                 ranking_score_threshold,
+                locales,
+                typo_tolerant_negation,
+                show_suggestions,
+                suggestions_threshold,
                 // do not use ..Default::default() here,
                 // rather add any missing field from `SearchQuery` to `SearchQueryWithIndex`
             },
+            federation_options,
         )
     }
 }
@@ -600,6 +1075,16 @@ pub struct SearchHit {
     pub ranking_score: Option<f64>,
     #[serde(rename = "_rankingScoreDetails", skip_serializing_if = "Option::is_none")]
     pub ranking_score_details: Option<serde_json::Map<String, serde_json::Value>>,
+    #[serde(rename = "_federation", skip_serializing_if = "Option::is_none")]
+    pub federation: Option<HitFederation>,
+    #[serde(rename = "_collapsed", skip_serializing_if = "Option::is_none")]
+    pub collapsed: Option<CollapsedHits>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct CollapsedHits {
+    pub count: u64,
+    pub hits: Vec<Document>,
 }
 
 #[derive(Serialize, Clone, PartialEq)]
@@ -614,10 +1099,20 @@ pub struct SearchResult {
     pub facet_distribution: Option<BTreeMap<String, IndexMap<String, u64>>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub facet_stats: Option<BTreeMap<String, FacetStats>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facet_ranges: Option<BTreeMap<String, Vec<FacetRangeCount>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub geo_aggregation: Option<Vec<GeoCellCount>>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub semantic_hit_count: Option<u32>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+
     // These fields are only used for analytics purposes
     #[serde(skip)]
     pub degraded: bool,
@@ -634,7 +1129,11 @@ impl fmt::Debug for SearchResult {
             hits_info,
             facet_distribution,
             facet_stats,
+            facet_ranges,
+            geo_aggregation,
             semantic_hit_count,
+            suggestion,
+            cursor,
             degraded,
             used_negative_operator,
         } = self;
@@ -657,9 +1156,21 @@ impl fmt::Debug for SearchResult {
         if let Some(facet_stats) = facet_stats {
             debug.field("facet_stats", &facet_stats);
         }
+        if let Some(facet_ranges) = facet_ranges {
+            debug.field("facet_ranges", &facet_ranges);
+        }
+        if let Some(geo_aggregation) = geo_aggregation {
+            debug.field("geo_aggregation", &geo_aggregation);
+        }
         if let Some(semantic_hit_count) = semantic_hit_count {
             debug.field("semantic_hit_count", &semantic_hit_count);
         }
+        if let Some(suggestion) = suggestion {
+            debug.field("suggestion", &suggestion);
+        }
+        if let Some(cursor) = cursor {
+            debug.field("cursor", &cursor);
+        }
 
         debug.finish()
     }
@@ -696,6 +1207,15 @@ pub enum HitsInfo {
 pub struct FacetStats {
     pub min: f64,
     pub max: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sum: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u64>,
+    /// The requested percentiles, keyed by their name, e.g. `p95`.
+    #[serde(flatten)]
+    pub percentiles: IndexMap<String, f64>,
 }
 
 #[derive(Serialize, Debug, Clone, PartialEq)]
@@ -706,6 +1226,56 @@ pub struct FacetSearchResult {
     pub processing_time_ms: u128,
 }
 
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AutocompleteResult {
+    pub completions: Vec<Completion>,
+    pub query: String,
+    /// Whether all the words completing the query were ranked
+    pub exhaustive_completions: bool,
+    pub processing_time_ms: u128,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyzeResult {
+    #[serde(flatten)]
+    pub analysis: Analysis,
+    pub processing_time_ms: u128,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ExplainSearchResult {
+    pub hits: Vec<ExplainedHit>,
+    pub query: String,
+    pub ranking_rules: Vec<String>,
+    pub query_graph: Option<ExplainedQueryGraph>,
+    pub candidates: u64,
+    pub buckets: Vec<ExplainedBucket>,
+    pub documents: Vec<DocumentExplanation>,
+    pub processing_time_ms: u128,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ExplainedHit {
+    pub id: String,
+    pub ranking_score: f64,
+    pub ranking_score_details: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Where one of the requested documents landed in the buckets of the ranking rules.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentExplanation {
+    pub id: String,
+    pub candidate: bool,
+    pub position: Option<usize>,
+    pub skipped: bool,
+    pub buckets: Vec<BucketStep>,
+}
+
 /// Incorporate search rules in search query
 pub fn add_search_rules(filter: &mut Option<Value>, rules: IndexSearchRules) {
     *filter = match (filter.take(), rules.filter) {
@@ -775,7 +1345,29 @@ fn prepare_search<'t>(
     }
 
     if let Some(ref searchable) = query.attributes_to_search_on {
-        search.searchable_attributes(searchable);
+        search.searchable_attributes(&searchable.attributes);
+        if !searchable.weights.is_empty() {
+            search.attributes_weights(&searchable.weights);
+        }
+    }
+
+    if let Some(ref ranking_rules) = query.ranking_rules {
+        search.ranking_rules(ranking_rules.iter().cloned().map(Criterion::from).collect());
+    }
+
+    if let Some(ref locales) = query.locales {
+        search.locales(locales.iter().copied().map(Into::into).collect());
+    }
+
+    search.typo_tolerant_negation(query.typo_tolerant_negation);
+
+    // the hits of a hybrid search are merged from two searches and can't be resumed after
+    if let Some(SearchAfter(cursor)) = &query.search_after {
+        if let SearchKind::Hybrid { .. } = search_kind {
+            return Err(MeilisearchHttpError::SearchAfterInHybridSearch);
+        }
+        let parameters = search_cursor_parameters(query)?;
+        search.search_after(cursor.clone().into_cursor(index, rtxn, &parameters)?);
     }
 
     let is_finite_pagination = query.is_finite_pagination();
@@ -853,6 +1445,7 @@ pub fn perform_search(
 
     let (search, is_finite_pagination, max_total_hits, offset) =
         prepare_search(index, &rtxn, &query, &search_kind, time_budget)?;
+    let cursor_parameters = search_cursor_parameters(&query)?;
 
     let (
         milli::SearchResult {
@@ -860,18 +1453,28 @@ pub fn perform_search(
             matching_words,
             candidates,
             document_scores,
+            cursor,
             degraded,
             used_negative_operator,
         },
         semantic_hit_count,
-    ) = match &search_kind {
-        SearchKind::KeywordOnly => (search.execute()?, None),
-        SearchKind::SemanticOnly { .. } => {
-            let results = search.execute()?;
-            let semantic_hit_count = results.document_scores.len() as u32;
-            (results, Some(semantic_hit_count))
+    ) = search_from_kind(&search_kind, &search)?;
+
+    let collapsed = match &query.collapse {
+        Some(collapse) => {
+            if query.distinct.is_none() && index.distinct_field(&rtxn)?.is_none() {
+                return Err(MeilisearchHttpError::CollapseWithoutDistinct);
+            }
+            Some(search.collapsed_documents(&documents_ids, collapse.limit)?)
         }
-        SearchKind::Hybrid { semantic_ratio, .. } => search.execute_hybrid(*semantic_ratio)?,
+        None => None,
+    };
+
+    let disjunctive_candidates = match &query.disjunctive_facets {
+        Some(facets) if !facets.is_empty() => {
+            Some(search.disjunctive_facets_candidates(facets, &candidates)?)
+        }
+        _ => None,
     };
 
     let SearchQuery {
@@ -879,6 +1482,7 @@ pub fn perform_search(
         limit,
         page,
         hits_per_page,
+        show_cursor,
         attributes_to_retrieve,
         // use the enum passed as parameter
         retrieve_vectors: _,
@@ -890,6 +1494,11 @@ pub fn perform_search(
         show_ranking_score_details,
         sort,
         facets,
+        facet_ranges,
+        // already used to compute the disjunctive candidates
+        disjunctive_facets: _,
+        facet_stats_aggregations,
+        geo_aggregation,
         highlight_pre_tag,
         highlight_post_tag,
         crop_marker,
@@ -897,13 +1506,31 @@ pub fn perform_search(
         vector: _,
         hybrid: _,
         offset: _,
+        search_after: _,
         ranking_score_threshold: _,
         matching_strategy: _,
         attributes_to_search_on: _,
         filter: _,
+        ranking_rules: _,
         distinct: _,
+        collapse,
+        typo_tolerant_negation: _,
+        locales,
+        show_suggestions,
+        suggestions_threshold,
     } = query;
 
+    // the suggestion is only worth its cost when the query doesn't return enough hits
+    let suggestion = match &q {
+        Some(q) if show_suggestions && (candidates.len() as usize) < suggestions_threshold => {
+            let locales: Option<Vec<_>> =
+                locales.as_ref().map(|locales| locales.iter().copied().map(Into::into).collect());
+            let mut ctx = milli::SearchContext::new(index, &rtxn)?;
+            milli::suggest_query(&mut ctx, q, locales.as_ref())?
+        }
+        _ => None,
+    };
+
     let format = AttributesFormat {
         attributes_to_retrieve,
         retrieve_vectors,
@@ -917,11 +1544,20 @@ pub fn perform_search(
         sort,
         show_ranking_score,
         show_ranking_score_details,
+        locales: locales.map(|locales| locales.into_iter().map(Into::into).collect()),
     };
 
-    let documents =
+    let mut documents =
         make_hits(index, &rtxn, format, matching_words, documents_ids, document_scores)?;
 
+    if let Some((collapse, collapsed)) = collapse.zip(collapsed) {
+        let collapsed =
+            make_collapsed_hits(index, &rtxn, collapse.attributes_to_retrieve, collapsed)?;
+        for (hit, collapsed) in documents.iter_mut().zip(collapsed) {
+            hit.collapsed = Some(collapsed);
+        }
+    }
+
     let number_of_hits = min(candidates.len() as usize, max_total_hits);
     let hits_info = if is_finite_pagination {
         let hits_per_page = hits_per_page.unwrap_or_else(DEFAULT_SEARCH_LIMIT);
@@ -940,39 +1576,67 @@ pub fn perform_search(
         HitsInfo::OffsetLimit { limit, offset, estimated_total_hits: number_of_hits }
     };
 
-    let (facet_distribution, facet_stats) = match facets {
-        Some(ref fields) => {
+    let facet_ranges = match facet_ranges {
+        Some(ranges) => {
             let mut facet_distribution = index.facets_distribution(&rtxn);
+            let ranges = facet_distribution
+                .ranges(ranges.into_iter().map(|(name, ranges)| (name, ranges.into())))
+                .candidates(candidates.clone())
+                .compute_ranges()?;
+            Some(ranges)
+        }
+        None => None,
+    };
 
-            let max_values_by_facet = index
-                .max_values_per_facet(&rtxn)
-                .map_err(milli::Error::from)?
-                .map(|x| x as usize)
-                .unwrap_or(DEFAULT_VALUES_PER_FACET);
-            facet_distribution.max_values_per_facet(max_values_by_facet);
-
-            let sort_facet_values_by =
-                index.sort_facet_values_by(&rtxn).map_err(milli::Error::from)?;
-
-            if fields.iter().all(|f| f != "*") {
-                let fields: Vec<_> =
-                    fields.iter().map(|n| (n, sort_facet_values_by.get(n))).collect();
-                facet_distribution.facets(fields);
+    let geo_aggregation = match geo_aggregation {
+        Some(GeoAggregationQuery { precision, zoom, bounds }) => {
+            let grid = match (precision, zoom) {
+                (Some(precision), _) => milli::GeoGrid::Geohash(precision),
+                (None, zoom) => milli::GeoGrid::Tile(zoom.unwrap_or_default()),
+            };
+            let mut aggregation = milli::GeoAggregation::new(grid, index, &rtxn);
+            if let Some(GeoBoundsQuery { top_right, bottom_left }) = bounds {
+                aggregation.bounds(milli::GeoBoundingBox {
+                    top_right: [top_right[0], top_right[1]],
+                    bottom_left: [bottom_left[0], bottom_left[1]],
+                });
             }
+            let cells = aggregation.candidates(candidates.clone()).execute()?;
+            Some(cells.into_iter().map(GeoCellCount::from).collect())
+        }
+        None => None,
+    };
 
-            let distribution = facet_distribution
-                .candidates(candidates)
-                .default_order_by(sort_facet_values_by.get("*"))
-                .execute()?;
-            let stats = facet_distribution.compute_stats()?;
+    let aggregations = facet_stats_aggregations.unwrap_or_default();
+    let (facet_distribution, facet_stats) = match facets {
+        Some(ref fields) => {
+            let (distribution, stats) =
+                compute_facet_distribution(index, &rtxn, fields, &aggregations, candidates)?;
             (Some(distribution), Some(stats))
         }
         None => (None, None),
     };
 
-    let facet_stats = facet_stats.map(|stats| {
-        stats.into_iter().map(|(k, (min, max))| (k, FacetStats { min, max })).collect()
-    });
+    // the disjunctive facets are counted on the candidates ignoring their own filter clauses
+    let (facet_distribution, facet_stats) = match disjunctive_candidates {
+        Some(disjunctive_candidates) => {
+            let mut facet_distribution = facet_distribution.unwrap_or_default();
+            let mut facet_stats = facet_stats.unwrap_or_default();
+            for (facet, candidates) in disjunctive_candidates {
+                let (distribution, stats) = compute_facet_distribution(
+                    index,
+                    &rtxn,
+                    std::slice::from_ref(&facet),
+                    &aggregations,
+                    candidates,
+                )?;
+                facet_distribution.extend(distribution);
+                facet_stats.extend(stats);
+            }
+            (Some(facet_distribution), Some(facet_stats))
+        }
+        None => (facet_distribution, facet_stats),
+    };
 
     let result = SearchResult {
         hits: documents,
@@ -981,13 +1645,113 @@ pub fn perform_search(
         processing_time_ms: before_search.elapsed().as_millis(),
         facet_distribution,
         facet_stats,
+        facet_ranges,
+        geo_aggregation,
         degraded,
         used_negative_operator,
         semantic_hit_count,
+        suggestion,
+        cursor: cursor
+            .filter(|_| show_cursor)
+            .map(|cursor| milli::SearchCursorToken::new(cursor, index, &rtxn, &cursor_parameters))
+            .transpose()?
+            .map(|token| token.to_string()),
     };
     Ok(result)
 }
 
+fn search_from_kind(
+    search_kind: &SearchKind,
+    search: &milli::Search<'_>,
+) -> Result<(milli::SearchResult, Option<u32>), MeilisearchHttpError> {
+    let (milli_result, semantic_hit_count) = match search_kind {
+        SearchKind::KeywordOnly => (search.execute()?, None),
+        SearchKind::SemanticOnly { .. } => {
+            let results = search.execute()?;
+            let semantic_hit_count = results.document_scores.len() as u32;
+            (results, Some(semantic_hit_count))
+        }
+        SearchKind::Hybrid { semantic_ratio, .. } => search.execute_hybrid(*semantic_ratio)?,
+    };
+    Ok((milli_result, semantic_hit_count))
+}
+
+fn compute_facet_distribution(
+    index: &Index,
+    rtxn: &RoTxn,
+    fields: &[String],
+    aggregations: &[FacetStatsAggregation],
+    candidates: RoaringBitmap,
+) -> Result<
+    (BTreeMap<String, IndexMap<String, u64>>, BTreeMap<String, FacetStats>),
+    MeilisearchHttpError,
+> {
+    let mut facet_distribution = index.facets_distribution(rtxn);
+
+    let max_values_by_facet = index
+        .max_values_per_facet(rtxn)
+        .map_err(milli::Error::from)?
+        .map(|x| x as usize)
+        .unwrap_or(DEFAULT_VALUES_PER_FACET);
+    facet_distribution.max_values_per_facet(max_values_by_facet);
+
+    let sort_facet_values_by = index.sort_facet_values_by(rtxn).map_err(milli::Error::from)?;
+
+    if fields.iter().all(|f| f != "*") {
+        let fields: Vec<_> = fields.iter().map(|n| (n, sort_facet_values_by.get(n))).collect();
+        facet_distribution.facets(fields);
+    }
+
+    let distribution = facet_distribution
+        .candidates(candidates)
+        .default_order_by(sort_facet_values_by.get("*"))
+        .execute()?;
+    let stats = facet_distribution.compute_stats()?;
+    let mut stats: BTreeMap<_, _> = stats
+        .into_iter()
+        .map(|(name, (min, max))| {
+            let stats = FacetStats {
+                min,
+                max,
+                sum: None,
+                avg: None,
+                count: None,
+                percentiles: IndexMap::new(),
+            };
+            (name, stats)
+        })
+        .collect();
+
+    if !aggregations.is_empty() {
+        let percentiles: Vec<_> = aggregations
+            .iter()
+            .filter_map(|aggregation| match aggregation {
+                FacetStatsAggregation::Percentile(percentile) => Some(*percentile),
+                _ => None,
+            })
+            .collect();
+        let computed = facet_distribution.compute_stats_aggregations(&percentiles)?;
+        for (name, computed) in computed {
+            let Some(stats) = stats.get_mut(&name) else { continue };
+            for aggregation in aggregations {
+                match aggregation {
+                    FacetStatsAggregation::Sum => stats.sum = Some(computed.sum),
+                    FacetStatsAggregation::Avg => stats.avg = Some(computed.avg),
+                    FacetStatsAggregation::Count => stats.count = Some(computed.count),
+                    FacetStatsAggregation::Percentile(_) => (),
+                }
+            }
+            stats.percentiles = computed
+                .percentiles
+                .into_iter()
+                .map(|(percentile, value)| (format!("p{percentile}"), value))
+                .collect();
+        }
+    }
+
+    Ok((distribution, stats))
+}
+
 struct AttributesFormat {
     attributes_to_retrieve: Option<BTreeSet<String>>,
     retrieve_vectors: RetrieveVectors,
@@ -1001,6 +1765,7 @@ struct AttributesFormat {
     sort: Option<Vec<String>>,
     show_ranking_score: bool,
     show_ranking_score_details: bool,
+    locales: Option<Vec<Language>>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -1103,7 +1868,11 @@ fn make_hits(
     );
     let mut tokenizer_builder = TokenizerBuilder::default();
     tokenizer_builder.create_char_map(true);
-    let script_lang_map = index.script_language(rtxn)?;
+    // an empty list of locales is the same as not giving any, like when tokenizing the query
+    let script_lang_map = match &format.locales {
+        Some(locales) if !locales.is_empty() => script_language_allow_list(locales),
+        _ => index.script_language(rtxn)?,
+    };
     if !script_lang_map.is_empty() {
         tokenizer_builder.allow_list(&script_lang_map);
     }
@@ -1190,6 +1959,8 @@ fn make_hits(
             matches_position,
             ranking_score_details,
             ranking_score,
+            federation: None,
+            collapsed: None,
         };
         documents.push(hit);
     }
@@ -1230,6 +2001,132 @@ pub fn perform_facet_search(
     })
 }
 
+pub fn perform_autocomplete(
+    index: &Index,
+    query: String,
+    filter: Option<Value>,
+    limit: usize,
+) -> Result<AutocompleteResult, MeilisearchHttpError> {
+    let before_search = Instant::now();
+    let rtxn = index.read_txn()?;
+
+    let mut autocomplete = Autocomplete::new(query.clone(), limit, index, &rtxn);
+    if let Some(ref filter) = filter {
+        if let Some(facets) = parse_filter(filter)? {
+            autocomplete.filter(facets);
+        }
+    }
+
+    let Completions { completions, degraded } = autocomplete.execute()?;
+
+    Ok(AutocompleteResult {
+        completions,
+        query,
+        exhaustive_completions: !degraded,
+        processing_time_ms: before_search.elapsed().as_millis(),
+    })
+}
+
+pub fn perform_analyze(
+    index: &Index,
+    text: String,
+    attribute: Option<String>,
+) -> Result<AnalyzeResult, MeilisearchHttpError> {
+    let before_analyze = Instant::now();
+    let rtxn = index.read_txn()?;
+
+    let mut analyze = Analyze::new(text, index, &rtxn);
+    if let Some(attribute) = attribute {
+        analyze.attribute(attribute);
+    }
+
+    Ok(AnalyzeResult {
+        analysis: analyze.execute()?,
+        processing_time_ms: before_analyze.elapsed().as_millis(),
+    })
+}
+
+pub fn perform_search_explain(
+    index: &Index,
+    query: SearchQuery,
+    document_ids: Vec<String>,
+) -> Result<ExplainSearchResult, MeilisearchHttpError> {
+    let before_search = Instant::now();
+    let rtxn = index.read_txn()?;
+    let time_budget = match index.search_cutoff(&rtxn)? {
+        Some(cutoff) => TimeBudget::new(Duration::from_millis(cutoff)),
+        None => TimeBudget::default(),
+    };
+
+    let (mut search, _, _, _) =
+        prepare_search(index, &rtxn, &query, &SearchKind::KeywordOnly, time_budget)?;
+    search.scoring_strategy(ScoringStrategy::Detailed);
+
+    // Unknown documents are reported as non-candidates, exactly like filtered out ones,
+    // so that the route doesn't tell apart missing documents from hidden ones.
+    let external_documents_ids = index.external_documents_ids();
+    let mut internal_ids = Vec::with_capacity(document_ids.len());
+    for id in &document_ids {
+        internal_ids.push(external_documents_ids.get(&rtxn, id)?);
+    }
+    let known_ids: Vec<_> = internal_ids.iter().flatten().copied().collect();
+
+    let (result, explanation) = search.explain(&known_ids)?;
+
+    let hits_ids: Vec<String> = if result.documents_ids.is_empty() {
+        Vec::new()
+    } else {
+        index
+            .external_id_of(&rtxn, result.documents_ids.iter().copied())?
+            .into_iter()
+            .collect::<milli::Result<_>>()?
+    };
+    let hits = hits_ids
+        .into_iter()
+        .zip(&result.document_scores)
+        .map(|(id, score)| ExplainedHit {
+            id,
+            ranking_score: ScoreDetails::global_score(score.iter()),
+            ranking_score_details: ScoreDetails::to_json_map(score.iter()),
+        })
+        .collect();
+
+    let milli::SearchExplanation { ranking_rules, query_graph, candidates, buckets, documents } =
+        explanation;
+    let mut documents = documents.into_iter();
+    let documents = document_ids
+        .into_iter()
+        .zip(internal_ids)
+        .map(|(id, internal_id)| match internal_id.and_then(|_| documents.next()) {
+            Some(document) => DocumentExplanation {
+                id,
+                candidate: document.candidate,
+                position: document.position,
+                skipped: document.skipped,
+                buckets: document.buckets,
+            },
+            None => DocumentExplanation {
+                id,
+                candidate: false,
+                position: None,
+                skipped: false,
+                buckets: Vec::new(),
+            },
+        })
+        .collect();
+
+    Ok(ExplainSearchResult {
+        hits,
+        query: query.q.unwrap_or_default(),
+        ranking_rules,
+        query_graph,
+        candidates,
+        buckets,
+        documents,
+        processing_time_ms: before_search.elapsed().as_millis(),
+    })
+}
+
 pub fn perform_similar(
     index: &Index,
     query: SimilarQuery,
@@ -1283,6 +2180,7 @@ pub fn perform_similar(
         matching_words: _,
         candidates,
         document_scores,
+        cursor: _,
         degraded: _,
         used_negative_operator: _,
     } = similar.execute().map_err(|err| match err {
@@ -1305,6 +2203,7 @@ pub fn perform_similar(
         sort: None,
         show_ranking_score,
         show_ranking_score_details,
+        locales: None,
     };
 
     let hits = make_hits(index, &rtxn, format, Default::default(), documents_ids, document_scores)?;
@@ -1335,12 +2234,25 @@ fn insert_geo_distance(sorts: &[String], document: &mut Document) {
     if let Some(capture_group) = sorts.iter().find_map(|sort| GEO_REGEX.captures(sort)) {
         // TODO: TAMO: milli encountered an internal error, what do we want to do?
         let base = [capture_group[1].parse().unwrap(), capture_group[2].parse().unwrap()];
-        let geo_point = &document.get("_geo").unwrap_or(&json!(null));
-        if let Some((lat, lng)) =
-            extract_geo_value(&geo_point["lat"]).zip(extract_geo_value(&geo_point["lng"]))
-        {
-            let distance = milli::distance_between_two_points(&base, &[lat, lng]);
+        let distance_to = |geo_point: &Value| {
+            extract_geo_value(&geo_point["lat"])
+                .zip(extract_geo_value(&geo_point["lng"]))
+                .map(|(lat, lng)| milli::distance_between_two_points(&base, &[lat, lng]))
+        };
+        let nearest = match document.get("_geo") {
+            // when the document has several points we report the nearest one and its position
+            Some(Value::Array(geo_points)) => geo_points
+                .iter()
+                .enumerate()
+                .filter_map(|(index, geo_point)| Some((Some(index), distance_to(geo_point)?)))
+                .min_by(|(_, a), (_, b)| a.total_cmp(b)),
+            geo_point => distance_to(geo_point.unwrap_or(&json!(null))).map(|d| (None, d)),
+        };
+        if let Some((index, distance)) = nearest {
             document.insert("_geoDistance".to_string(), json!(distance.round() as usize));
+            if let Some(index) = index {
+                document.insert("_geoPointIndex".to_string(), json!(index));
+            }
         }
     }
 }
@@ -1456,6 +2368,52 @@ fn add_non_formatted_ids_to_formatted_options(
     }
 }
 
+fn make_collapsed_hits(
+    index: &Index,
+    rtxn: &RoTxn<'_>,
+    attributes_to_retrieve: Option<BTreeSet<String>>,
+    collapsed: Vec<milli::CollapsedDocuments>,
+) -> Result<Vec<CollapsedHits>, MeilisearchHttpError> {
+    let fields_ids_map = index.fields_ids_map(rtxn)?;
+    let displayed_ids = match index.displayed_fields_ids(rtxn)? {
+        Some(fields) => fields.into_iter().collect(),
+        None => fields_ids_map.iter().map(|(id, _)| id).collect(),
+    };
+    // only the identifiers of the collapsed documents are returned by default,
+    // unless they aren't displayed
+    let attributes_to_retrieve = match attributes_to_retrieve {
+        Some(attributes) => attributes,
+        None => match index.primary_key(rtxn)? {
+            Some(primary_key)
+                if fields_ids_map.id(primary_key).is_some_and(|id| displayed_ids.contains(&id)) =>
+            {
+                BTreeSet::from([primary_key.to_string()])
+            }
+            _ => BTreeSet::from([String::from("*")]),
+        },
+    };
+    let retrieve_all = attributes_to_retrieve.contains("*");
+
+    let mut collapsed_hits = Vec::with_capacity(collapsed.len());
+    for milli::CollapsedDocuments { count, documents_ids } in collapsed {
+        let mut hits = Vec::with_capacity(documents_ids.len());
+        for (_id, obkv) in index.documents(rtxn, documents_ids)? {
+            let mut document = make_document(&displayed_ids, &fields_ids_map, obkv)?;
+            document.remove(milli::vector::parsed_vectors::RESERVED_VECTORS_FIELD_NAME);
+            if !retrieve_all {
+                document = permissive_json_pointer::select_values(
+                    &document,
+                    attributes_to_retrieve.iter().map(String::as_str),
+                );
+            }
+            hits.push(document);
+        }
+        collapsed_hits.push(CollapsedHits { count, hits });
+    }
+
+    Ok(collapsed_hits)
+}
+
 fn make_document(
     displayed_attributes: &BTreeSet<FieldId>,
     field_ids_map: &FieldsIdsMap,
@@ -1727,6 +2685,40 @@ mod test {
         assert_eq!(document.get("_geoDistance"), None);
     }
 
+    #[test]
+    fn test_insert_geo_distance_with_several_points() {
+        let value: Document = serde_json::from_str(
+            r#"{
+              "_geo": [
+                { "lat": 48.8566, "lng": 2.3522 },
+                { "lat": "50.629973371633746", "lng": "3.0569447399419567" },
+                { "lat": 45.7640, "lng": 4.8357 }
+              ],
+              "id": "1"
+            }"#,
+        )
+        .unwrap();
+
+        let sorters = &["_geoPoint(50.629973371633746,3.0569447399419567):asc".to_string()];
+        let mut document = value.clone();
+        insert_geo_distance(sorters, &mut document);
+        assert_eq!(document.get("_geoDistance"), Some(&json!(0)));
+        assert_eq!(document.get("_geoPointIndex"), Some(&json!(1)));
+
+        let sorters = &["_geoPoint(45.7640,4.8357):desc".to_string()];
+        let mut document = value.clone();
+        insert_geo_distance(sorters, &mut document);
+        assert_eq!(document.get("_geoDistance"), Some(&json!(0)));
+        assert_eq!(document.get("_geoPointIndex"), Some(&json!(2)));
+
+        // there was no _geoPoint so nothing is inserted in the document
+        let sorters = &["chien:asc".to_string()];
+        let mut document = value;
+        insert_geo_distance(sorters, &mut document);
+        assert_eq!(document.get("_geoDistance"), None);
+        assert_eq!(document.get("_geoPointIndex"), None);
+    }
+
     #[test]
     fn test_insert_geo_distance_with_coords_as_string() {
         let value: Document = serde_json::from_str(
@@ -322,6 +322,31 @@ impl Index<'_> {
         self.service.put_encoded(url, settings, self.encoder).await
     }
 
+    pub async fn update_settings_localized_attributes(
+        &self,
+        settings: Value,
+    ) -> (Value, StatusCode) {
+        let url =
+            format!("/indexes/{}/settings/localized-attributes", urlencode(self.uid.as_ref()));
+        self.service.put_encoded(url, settings, self.encoder).await
+    }
+
+    pub async fn update_settings_infix_searchable_attributes(
+        &self,
+        settings: Value,
+    ) -> (Value, StatusCode) {
+        let url = format!(
+            "/indexes/{}/settings/infix-searchable-attributes",
+            urlencode(self.uid.as_ref())
+        );
+        self.service.put_encoded(url, settings, self.encoder).await
+    }
+
+    pub async fn update_settings_pinned_results(&self, settings: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/settings/pinned-results", urlencode(self.uid.as_ref()));
+        self.service.put_encoded(url, settings, self.encoder).await
+    }
+
     pub async fn delete_settings(&self) -> (Value, StatusCode) {
         let url = format!("/indexes/{}/settings", urlencode(self.uid.as_ref()));
         self.service.delete(url).await
@@ -411,6 +436,21 @@ impl Index<'_> {
         self.service.post_encoded(url, query, self.encoder).await
     }
 
+    pub async fn search_explain(&self, query: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/search/explain", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, query, self.encoder).await
+    }
+
+    pub async fn analyze(&self, query: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/analyze", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, query, self.encoder).await
+    }
+
+    pub async fn autocomplete(&self, query: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/autocomplete", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, query, self.encoder).await
+    }
+
     pub async fn update_distinct_attribute(&self, value: Value) -> (Value, StatusCode) {
         let url =
             format!("/indexes/{}/settings/{}", urlencode(self.uid.as_ref()), "distinct-attribute");
@@ -163,7 +163,8 @@ async fn delete_document_by_filter() {
       "fieldDistribution": {
         "color": 3,
         "id": 4
-      }
+      },
+      "infixDatabaseSize": 0
     }
     "###);
 
@@ -209,7 +210,8 @@ async fn delete_document_by_filter() {
       "fieldDistribution": {
         "color": 1,
         "id": 2
-      }
+      },
+      "infixDatabaseSize": 0
     }
     "###);
 
@@ -274,7 +276,8 @@ async fn delete_document_by_filter() {
       "fieldDistribution": {
         "color": 1,
         "id": 1
-      }
+      },
+      "infixDatabaseSize": 0
     }
     "###);
 
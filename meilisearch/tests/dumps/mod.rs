@@ -29,7 +29,7 @@ async fn import_dump_v1_movie_raw() {
     snapshot!(code, @"200 OK");
     assert_eq!(
         stats,
-        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }})
+        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }, "infixDatabaseSize": 0 })
     );
 
     let (settings, code) = index.settings().await;
@@ -78,7 +78,10 @@ async fn import_dump_v1_movie_raw() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "localizedAttributes": null,
+      "infixSearchableAttributes": [],
+      "pinnedResults": []
     }
     "###
     );
@@ -176,7 +179,8 @@ async fn import_dump_v1_movie_with_settings() {
         "poster": 53,
         "release_date": 53,
         "title": 53
-      }
+      },
+      "infixDatabaseSize": 0
     }
     "###
     );
@@ -240,7 +244,10 @@ async fn import_dump_v1_movie_with_settings() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "localizedAttributes": null,
+      "infixSearchableAttributes": [],
+      "pinnedResults": []
     }
     "###
     );
@@ -327,7 +334,7 @@ async fn import_dump_v1_rubygems_with_settings() {
     snapshot!(code, @"200 OK");
     assert_eq!(
         stats,
-        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"description": 53, "id": 53, "name": 53, "summary": 53, "total_downloads": 53, "version": 53 }})
+        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"description": 53, "id": 53, "name": 53, "summary": 53, "total_downloads": 53, "version": 53 }, "infixDatabaseSize": 0 })
     );
 
     let (settings, code) = index.settings().await;
@@ -388,7 +395,10 @@ async fn import_dump_v1_rubygems_with_settings() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "localizedAttributes": null,
+      "infixSearchableAttributes": [],
+      "pinnedResults": []
     }
     "###
     );
@@ -473,7 +483,7 @@ async fn import_dump_v2_movie_raw() {
     snapshot!(code, @"200 OK");
     assert_eq!(
         stats,
-        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }})
+        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }, "infixDatabaseSize": 0 })
     );
 
     let (settings, code) = index.settings().await;
@@ -522,7 +532,10 @@ async fn import_dump_v2_movie_raw() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "localizedAttributes": null,
+      "infixSearchableAttributes": [],
+      "pinnedResults": []
     }
     "###
     );
@@ -609,7 +622,7 @@ async fn import_dump_v2_movie_with_settings() {
     snapshot!(code, @"200 OK");
     assert_eq!(
         stats,
-        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }})
+        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }, "infixDatabaseSize": 0 })
     );
 
     let (settings, code) = index.settings().await;
@@ -668,7 +681,10 @@ async fn import_dump_v2_movie_with_settings() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "localizedAttributes": null,
+      "infixSearchableAttributes": [],
+      "pinnedResults": []
     }
     "###
     );
@@ -755,7 +771,7 @@ async fn import_dump_v2_rubygems_with_settings() {
     snapshot!(code, @"200 OK");
     assert_eq!(
         stats,
-        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"description": 53, "id": 53, "name": 53, "summary": 53, "total_downloads": 53, "version": 53 }})
+        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"description": 53, "id": 53, "name": 53, "summary": 53, "total_downloads": 53, "version": 53 }, "infixDatabaseSize": 0 })
     );
 
     let (settings, code) = index.settings().await;
@@ -813,7 +829,10 @@ async fn import_dump_v2_rubygems_with_settings() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "localizedAttributes": null,
+      "infixSearchableAttributes": [],
+      "pinnedResults": []
     }
     "###
     );
@@ -898,7 +917,7 @@ async fn import_dump_v3_movie_raw() {
     snapshot!(code, @"200 OK");
     assert_eq!(
         stats,
-        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }})
+        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }, "infixDatabaseSize": 0 })
     );
 
     let (settings, code) = index.settings().await;
@@ -947,7 +966,10 @@ async fn import_dump_v3_movie_raw() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "localizedAttributes": null,
+      "infixSearchableAttributes": [],
+      "pinnedResults": []
     }
     "###
     );
@@ -1034,7 +1056,7 @@ async fn import_dump_v3_movie_with_settings() {
     snapshot!(code, @"200 OK");
     assert_eq!(
         stats,
-        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }})
+        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }, "infixDatabaseSize": 0 })
     );
 
     let (settings, code) = index.settings().await;
@@ -1093,7 +1115,10 @@ async fn import_dump_v3_movie_with_settings() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "localizedAttributes": null,
+      "infixSearchableAttributes": [],
+      "pinnedResults": []
     }
     "###
     );
@@ -1180,7 +1205,7 @@ async fn import_dump_v3_rubygems_with_settings() {
     snapshot!(code, @"200 OK");
     assert_eq!(
         stats,
-        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"description": 53, "id": 53, "name": 53, "summary": 53, "total_downloads": 53, "version": 53 }})
+        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"description": 53, "id": 53, "name": 53, "summary": 53, "total_downloads": 53, "version": 53 }, "infixDatabaseSize": 0 })
     );
 
     let (settings, code) = index.settings().await;
@@ -1238,7 +1263,10 @@ async fn import_dump_v3_rubygems_with_settings() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "localizedAttributes": null,
+      "infixSearchableAttributes": [],
+      "pinnedResults": []
     }
     "###
     );
@@ -1323,7 +1351,7 @@ async fn import_dump_v4_movie_raw() {
     snapshot!(code, @"200 OK");
     assert_eq!(
         stats,
-        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }})
+        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }, "infixDatabaseSize": 0 })
     );
 
     let (settings, code) = index.settings().await;
@@ -1372,7 +1400,10 @@ async fn import_dump_v4_movie_raw() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "localizedAttributes": null,
+      "infixSearchableAttributes": [],
+      "pinnedResults": []
     }
     "###
     );
@@ -1459,7 +1490,7 @@ async fn import_dump_v4_movie_with_settings() {
     snapshot!(code, @"200 OK");
     assert_eq!(
         stats,
-        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }})
+        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"genres": 53, "id": 53, "overview": 53, "poster": 53, "release_date": 53, "title": 53 }, "infixDatabaseSize": 0 })
     );
 
     let (settings, code) = index.settings().await;
@@ -1518,7 +1549,10 @@ async fn import_dump_v4_movie_with_settings() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "localizedAttributes": null,
+      "infixSearchableAttributes": [],
+      "pinnedResults": []
     }
     "###
     );
@@ -1605,7 +1639,7 @@ async fn import_dump_v4_rubygems_with_settings() {
     snapshot!(code, @"200 OK");
     assert_eq!(
         stats,
-        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"description": 53, "id": 53, "name": 53, "summary": 53, "total_downloads": 53, "version": 53 }})
+        json!({ "numberOfDocuments": 53, "isIndexing": false, "fieldDistribution": {"description": 53, "id": 53, "name": 53, "summary": 53, "total_downloads": 53, "version": 53 }, "infixDatabaseSize": 0 })
     );
 
     let (settings, code) = index.settings().await;
@@ -1663,7 +1697,10 @@ async fn import_dump_v4_rubygems_with_settings() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "localizedAttributes": null,
+      "infixSearchableAttributes": [],
+      "pinnedResults": []
     }
     "###
     );
@@ -1768,7 +1805,8 @@ async fn import_dump_v5() {
             "title": 10,
             "vote_average": 10,
             "vote_count": 10
-        }
+        },
+        "infixDatabaseSize": 0
     });
 
     let index1 = server.index("test");
@@ -1907,7 +1945,10 @@ async fn import_dump_v6_containing_experimental_features() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "localizedAttributes": null,
+      "infixSearchableAttributes": [],
+      "pinnedResults": []
     }
     "###);
 
@@ -2081,7 +2122,10 @@ async fn generate_and_import_dump_containing_vectors() {
           "documentTemplate": "{{doc.doggo}}"
         }
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "localizedAttributes": null,
+      "infixSearchableAttributes": [],
+      "pinnedResults": []
     }
     "###);
 
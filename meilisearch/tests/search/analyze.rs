@@ -0,0 +1,159 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+
+use crate::common::index::Index;
+use crate::common::{Server, Value};
+use crate::json;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        { "id": 0, "title": "send an email" },
+        { "id": 1, "title": "the mailbox. emails" },
+    ])
+});
+
+async fn create_index(server: &Server) -> Index<'_> {
+    let index = server.index("test");
+    index.update_settings(json!({ "stopWords": ["the"] })).await;
+    index.wait_task(0).await;
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(1).await;
+    index
+}
+
+#[actix_rt::test]
+async fn analyze_tokens() {
+    let server = Server::new().await;
+    let index = create_index(&server).await;
+
+    let (response, code) = index.analyze(json!({ "text": "The e-mail. Sent" })).await;
+    snapshot!(code, @"200 OK");
+    let tokens: Vec<_> = response["tokens"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|token| json!([token["lemma"], token["kind"], token["position"]]))
+        .collect();
+    snapshot!(json_string!(tokens), @r###"
+    [
+      [
+        "the",
+        "stopWord",
+        null
+      ],
+      [
+        " ",
+        "softSeparator",
+        null
+      ],
+      [
+        "e",
+        "word",
+        1
+      ],
+      [
+        "-",
+        "softSeparator",
+        null
+      ],
+      [
+        "mail",
+        "word",
+        2
+      ],
+      [
+        ".",
+        "hardSeparator",
+        null
+      ],
+      [
+        " ",
+        "softSeparator",
+        null
+      ],
+      [
+        "sent",
+        "word",
+        10
+      ]
+    ]
+    "###);
+    snapshot!(response["tokens"][0]["original"], @r###""The""###);
+    snapshot!(response["tokens"][4]["charStart"], @"6");
+    snapshot!(response["tokens"][4]["charEnd"], @"10");
+}
+
+#[actix_rt::test]
+async fn analyze_query_terms() {
+    let server = Server::new().await;
+    let index = create_index(&server).await;
+
+    let (response, code) = index.analyze(json!({ "text": "e-mail" })).await;
+    snapshot!(code, @"200 OK");
+    let terms: Vec<_> =
+        response["queryTerms"].as_array().unwrap().iter().map(|term| &term["original"]).collect();
+    snapshot!(json_string!(terms), @r###"
+    [
+      "e",
+      "mail",
+      "email"
+    ]
+    "###);
+    // the two words are concatenated into a term matching `email`
+    snapshot!(json_string!(response["queryTerms"][2]), @r###"
+    {
+      "original": "email",
+      "positions": [
+        0,
+        1
+      ],
+      "ngramWords": [
+        "e",
+        "mail"
+      ],
+      "phrase": null,
+      "isPrefix": true,
+      "maxTypos": 1,
+      "derivations": {
+        "exact": "email",
+        "prefixOf": [
+          "emails"
+        ],
+        "infixOf": [],
+        "synonyms": [],
+        "splitWords": null,
+        "oneTypo": [],
+        "twoTypos": [],
+        "prefixDb": null
+      }
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn analyze_errors() {
+    let server = Server::new().await;
+    let index = create_index(&server).await;
+
+    let (response, code) = index.analyze(json!({ "attribute": "title" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing field `text`",
+      "code": "missing_analyze_text",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#missing_analyze_text"
+    }
+    "###);
+
+    let (response, code) = index.analyze(json!({ "text": ["e-mail"] })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.text`: expected a string, but found an array: `[\"e-mail\"]`",
+      "code": "invalid_analyze_text",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_analyze_text"
+    }
+    "###);
+}
@@ -0,0 +1,155 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+
+use crate::common::{Server, Value};
+use crate::json;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        { "id": 0, "title": "Summer dress", "color": "red" },
+        { "id": 1, "title": "Summer dress with flowers", "color": "blue" },
+        { "id": 2, "title": "Summer drink", "color": "red" },
+        { "id": 3, "title": "Winter dress", "color": "red" },
+        { "id": 4, "title": "Winter drum", "color": "blue" },
+    ])
+});
+
+#[actix_rt::test]
+async fn simple_autocomplete() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.autocomplete(json!({"q": "dr"})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".processingTimeMs" => "[time]" }), @r###"
+    {
+      "completions": [
+        {
+          "query": "dress",
+          "word": "dress",
+          "count": 3
+        },
+        {
+          "query": "drink",
+          "word": "drink",
+          "count": 1
+        },
+        {
+          "query": "drum",
+          "word": "drum",
+          "count": 1
+        }
+      ],
+      "query": "dr",
+      "exhaustiveCompletions": true,
+      "processingTimeMs": "[time]"
+    }
+    "###);
+
+    let (response, code) = index.autocomplete(json!({"q": "Summer dr", "limit": 1})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".processingTimeMs" => "[time]" }), @r###"
+    {
+      "completions": [
+        {
+          "query": "Summer dress",
+          "word": "dress",
+          "count": 2
+        }
+      ],
+      "query": "Summer dr",
+      "exhaustiveCompletions": true,
+      "processingTimeMs": "[time]"
+    }
+    "###);
+
+    let (response, code) = index.autocomplete(json!({"q": "Winter dri"})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["completions"], @"[]");
+}
+
+#[actix_rt::test]
+async fn autocomplete_with_filter() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.update_settings_filterable_attributes(json!(["color"])).await;
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.autocomplete(json!({"q": "dr", "filter": "color = blue"})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["completions"]), @r###"
+    [
+      {
+        "query": "dress",
+        "word": "dress",
+        "count": 1
+      },
+      {
+        "query": "drum",
+        "word": "drum",
+        "count": 1
+      }
+    ]
+    "###);
+
+    let (response, code) = index.autocomplete(json!({"q": "dr", "filter": "title = dress"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Attribute `title` is not filterable. Available filterable attributes are: `color`.\n1:6 title = dress",
+      "code": "invalid_search_filter",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn autocomplete_bad_parameters() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.autocomplete(json!({"q": "dr", "limit": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.limit`: expected a positive integer, but found a string: `\"doggo\"`",
+      "code": "invalid_search_limit",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_limit"
+    }
+    "###);
+
+    let (response, code) = index.autocomplete(json!({"q": "dr", "limit": 101})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.limit`: the value of `limit` is invalid, expected a positive integer lower than or equal to `100`.",
+      "code": "invalid_search_limit",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_limit"
+    }
+    "###);
+
+    let (response, code) = index.autocomplete(json!({"q": "dr", "doggo": "bork"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown field `doggo`: expected one of `q`, `filter`, `limit`",
+      "code": "bad_request",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#bad_request"
+    }
+    "###);
+}
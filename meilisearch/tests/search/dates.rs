@@ -0,0 +1,134 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+
+use crate::common::index::Index;
+use crate::common::{Server, Value};
+use crate::json;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        { "id": 0, "published": "2023-12-31" },
+        { "id": 1, "published": "2024-01-01T00:00:00Z" },
+        { "id": 2, "published": "2024-03-15" },
+        { "id": 3, "published": "2024-01-15" },
+    ])
+});
+
+async fn index_with_dates(server: &Server) -> Index<'_> {
+    let index = server.index("test");
+    index
+        .update_settings(json!({
+            "filterableAttributes": ["published"],
+            "sortableAttributes": ["published"],
+        }))
+        .await;
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(1).await;
+    index
+}
+
+#[actix_rt::test]
+async fn filter_on_dates() {
+    let server = Server::new().await;
+    let index = index_with_dates(&server).await;
+
+    index
+        .search(
+            json!({ "filter": "published > 2024-01-01", "attributesToRetrieve": ["id"] }),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 2
+                  },
+                  {
+                    "id": 3
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    // the dates can still be compared as strings
+    index
+        .search(
+            json!({ "filter": "published = \"2024-01-15\"", "attributesToRetrieve": ["id"] }),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 3
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn sort_on_dates() {
+    let server = Server::new().await;
+    let index = index_with_dates(&server).await;
+
+    index
+        .search(json!({ "sort": ["published:desc"] }), |response, code| {
+            snapshot!(code, @"200 OK");
+            // the dates are returned unchanged
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 2,
+                "published": "2024-03-15"
+              },
+              {
+                "id": 3,
+                "published": "2024-01-15"
+              },
+              {
+                "id": 1,
+                "published": "2024-01-01T00:00:00Z"
+              },
+              {
+                "id": 0,
+                "published": "2023-12-31"
+              }
+            ]
+            "###);
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn facet_distribution_on_dates() {
+    let server = Server::new().await;
+    let index = index_with_dates(&server).await;
+
+    index
+        .search(json!({ "facets": ["published"] }), |response, code| {
+            snapshot!(code, @"200 OK");
+            // the dates are counted once, as their original string
+            snapshot!(json_string!(response["facetDistribution"]), @r###"
+            {
+              "published": {
+                "2023-12-31": 1,
+                "2024-01-01T00:00:00Z": 1,
+                "2024-01-15": 1,
+                "2024-03-15": 1
+              }
+            }
+            "###);
+            snapshot!(json_string!(response["facetStats"]), @r###"
+            {
+              "published": {
+                "min": 1703980800.0,
+                "max": 1710460800.0
+              }
+            }
+            "###);
+        })
+        .await;
+}
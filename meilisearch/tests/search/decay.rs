@@ -0,0 +1,87 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+
+use crate::common::{Server, Value};
+use crate::json;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        { "id": 0, "price": 100, "rank": 2 },
+        { "id": 1, "price": 103, "rank": 0 },
+        { "id": 2, "price": 96, "rank": 1 },
+        { "id": 3, "price": 110, "rank": 0 },
+        { "id": 4, "price": 130, "rank": 0 },
+        { "id": 5, "rank": 0 },
+    ])
+});
+
+#[actix_rt::test]
+async fn decay_ranks_closest_values_first() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({"rankingRules": ["price:gauss(100, 10)"]})).await;
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.search_post(json!({ "attributesToRetrieve": ["id"] })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 0
+      },
+      {
+        "id": 1
+      },
+      {
+        "id": 2
+      },
+      {
+        "id": 3
+      },
+      {
+        "id": 4
+      },
+      {
+        "id": 5
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn decay_lets_next_rules_order_equal_scores() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    // all the prices within 5 of 100 get the same score, they are then ordered by rank
+    index.update_settings(json!({"rankingRules": ["price:linear(100, 10, 5)", "rank:asc"]})).await;
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.search_post(json!({ "attributesToRetrieve": ["id"] })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1
+      },
+      {
+        "id": 2
+      },
+      {
+        "id": 0
+      },
+      {
+        "id": 3
+      },
+      {
+        "id": 4
+      },
+      {
+        "id": 5
+      }
+    ]
+    "###);
+}
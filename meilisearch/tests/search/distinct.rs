@@ -1,4 +1,4 @@
-use meili_snap::snapshot;
+use meili_snap::{json_string, snapshot};
 use once_cell::sync::Lazy;
 
 use crate::common::{Server, Value};
@@ -304,3 +304,130 @@ async fn distinct_at_search_time() {
     snapshot!(response["totalPages"], @"1");
     snapshot!(response["totalHits"], @"3");
 }
+
+#[actix_rt::test]
+async fn distinct_collapse() {
+    let server = Server::new().await;
+    let index = server.index("tamo");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, Some(DOCUMENT_PRIMARY_KEY)).await;
+    let (task, _) = index.update_distinct_attribute(json!(DOCUMENT_DISTINCT_KEY)).await;
+    index.wait_task(task.uid()).await;
+
+    let (response, code) =
+        index.search_post(json!({"q": "Sweater", "collapse": { "limit": 2 }})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 9,
+        "description": "Sweater",
+        "brand": "Gap",
+        "product_id": "234567",
+        "color": "Green",
+        "_collapsed": {
+          "count": 2,
+          "hits": [
+            {
+              "id": 10
+            },
+            {
+              "id": 11
+            }
+          ]
+        }
+      }
+    ]
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({
+            "q": "Sweater",
+            "attributesToRetrieve": ["id"],
+            "collapse": { "limit": 1, "attributesToRetrieve": ["id", "color"] }
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 9,
+        "_collapsed": {
+          "count": 2,
+          "hits": [
+            {
+              "id": 10,
+              "color": "Red"
+            }
+          ]
+        }
+      }
+    ]
+    "###);
+
+    // only the number of collapsed documents is returned by default
+    let (response, code) = index
+        .search_post(json!({
+            "q": "Jacket",
+            "attributesToRetrieve": ["id"],
+            "collapse": {}
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1,
+        "_collapsed": {
+          "count": 2,
+          "hits": []
+        }
+      }
+    ]
+    "###);
+
+    // the hidden primary key isn't returned in the collapsed documents
+    let (task, _) =
+        index.update_settings_displayed_attributes(json!(["description", "color"])).await;
+    index.wait_task(task.uid()).await;
+    let (response, code) =
+        index.search_post(json!({"q": "Sweater", "collapse": { "limit": 2 }})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "description": "Sweater",
+        "color": "Green",
+        "_collapsed": {
+          "count": 2,
+          "hits": [
+            {
+              "description": "Sweater",
+              "color": "Red"
+            },
+            {
+              "description": "Sweater",
+              "color": "Blue"
+            }
+          ]
+        }
+      }
+    ]
+    "###);
+
+    let index = server.index("no_distinct");
+    let (task, _) = index.add_documents(DOCUMENTS.clone(), Some(DOCUMENT_PRIMARY_KEY)).await;
+    index.wait_task(task.uid()).await;
+
+    let (response, code) = index.search_post(json!({"q": "Sweater", "collapse": {}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Using `collapse` requires a distinct attribute.\n Hint: add `distinct` to the request or set the `distinctAttribute` of the index.",
+      "code": "invalid_search_collapse",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_collapse"
+    }
+    "###);
+}
@@ -371,6 +371,157 @@ async fn search_bad_show_matches_position() {
     "###);
 }
 
+#[actix_rt::test]
+async fn search_bad_typo_tolerant_negation() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"typoTolerantNegation": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.typoTolerantNegation`: expected a boolean, but found a string: `\"doggo\"`",
+      "code": "invalid_search_typo_tolerant_negation",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_typo_tolerant_negation"
+    }
+    "###);
+
+    let (response, code) = index.search_get("?typoTolerantNegation=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `typoTolerantNegation`: could not parse `doggo` as a boolean, expected either `true` or `false`",
+      "code": "invalid_search_typo_tolerant_negation",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_typo_tolerant_negation"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_show_suggestions() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"showSuggestions": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.showSuggestions`: expected a boolean, but found a string: `\"doggo\"`",
+      "code": "invalid_search_show_suggestions",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_show_suggestions"
+    }
+    "###);
+
+    let (response, code) = index.search_get("?showSuggestions=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `showSuggestions`: could not parse `doggo` as a boolean, expected either `true` or `false`",
+      "code": "invalid_search_show_suggestions",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_show_suggestions"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_suggestions_threshold() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"suggestionsThreshold": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.suggestionsThreshold`: expected a positive integer, but found a string: `\"doggo\"`",
+      "code": "invalid_search_suggestions_threshold",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_suggestions_threshold"
+    }
+    "###);
+
+    let (response, code) = index.search_get("?suggestionsThreshold=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `suggestionsThreshold`: could not parse `doggo` as a positive integer",
+      "code": "invalid_search_suggestions_threshold",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_suggestions_threshold"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_search_after() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"searchAfter": 42})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.searchAfter`: expected a string, but found a positive integer: `42`",
+      "code": "invalid_search_search_after",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_search_after"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({"searchAfter": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.searchAfter`: the value of `searchAfter` is invalid, expected the `cursor` returned by a previous search.",
+      "code": "invalid_search_search_after",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_search_after"
+    }
+    "###);
+
+    let (response, code) = index.search_get("?searchAfter=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `searchAfter`: the value of `searchAfter` is invalid, expected the `cursor` returned by a previous search.",
+      "code": "invalid_search_search_after",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_search_after"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_show_cursor() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"showCursor": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.showCursor`: expected a boolean, but found a string: `\"doggo\"`",
+      "code": "invalid_search_show_cursor",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_show_cursor"
+    }
+    "###);
+
+    let (response, code) = index.search_get("?showCursor=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `showCursor`: could not parse `doggo` as a boolean, expected either `true` or `false`",
+      "code": "invalid_search_show_cursor",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_show_cursor"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn search_bad_facets() {
     let server = Server::new().await;
@@ -389,6 +540,200 @@ async fn search_bad_facets() {
     // Can't make the `attributes_to_highlight` fail with a get search since it'll accept anything as an array of strings.
 }
 
+#[actix_rt::test]
+async fn search_bad_collapse() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"collapse": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.collapse`: expected an object, but found a string: `\"doggo\"`",
+      "code": "invalid_search_collapse",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_collapse"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({"collapse": {"doggo": 1}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown field `doggo` inside `.collapse`: expected one of `limit`, `attributesToRetrieve`",
+      "code": "invalid_search_collapse",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_collapse"
+    }
+    "###);
+    // Can't make the `collapse` fail with a get search since it's only available with a post search.
+}
+
+#[actix_rt::test]
+async fn search_bad_facet_ranges() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"facetRanges": ["price"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.facetRanges`: expected an object, but found an array: `[\"price\"]`",
+      "code": "invalid_search_facet_ranges",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_ranges"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"facetRanges": {"price": {"interval": 10, "ranges": []}}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.facetRanges.price`: exactly one of `ranges` or `interval` must be specified",
+      "code": "invalid_search_facet_ranges",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_ranges"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"facetRanges": {"price": {"ranges": [{"from": "doggo"}]}}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.facetRanges.price.ranges[0].from`: expected a number, but found a string: `\"doggo\"`",
+      "code": "invalid_search_facet_ranges",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_ranges"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_geo_aggregation() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"geoAggregation": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.geoAggregation`: expected an object, but found a string: `\"doggo\"`",
+      "code": "invalid_search_geo_aggregation",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_geo_aggregation"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"geoAggregation": {"precision": 5, "zoom": 3}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.geoAggregation`: exactly one of `precision` or `zoom` must be specified",
+      "code": "invalid_search_geo_aggregation",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_geo_aggregation"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({"geoAggregation": {"precision": 13}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.geoAggregation`: the `precision` must be between `1` and `12`, but got `13`",
+      "code": "invalid_search_geo_aggregation",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_geo_aggregation"
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({"geoAggregation": {"zoom": 3, "bounds": {"topRight": [10, 200], "bottomLeft": [0, 0]}}}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.geoAggregation`: the `topRight` bound must be a `[latitude, longitude]` pair with a latitude between `-90` and `90` and a longitude between `-180` and `180`",
+      "code": "invalid_search_geo_aggregation",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_geo_aggregation"
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({"geoAggregation": {"zoom": 3, "bounds": {"topRight": [0, 10], "bottomLeft": [10, 0]}}}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.geoAggregation`: the `topRight` bound must not be below the `bottomLeft` bound",
+      "code": "invalid_search_geo_aggregation",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_geo_aggregation"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_disjunctive_facets() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"disjunctiveFacets": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.disjunctiveFacets`: expected an array, but found a string: `\"doggo\"`",
+      "code": "invalid_search_disjunctive_facets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_disjunctive_facets"
+    }
+    "###);
+    // Can't make the `disjunctive_facets` fail with a get search since it'll accept anything as an array of strings.
+}
+
+#[actix_rt::test]
+async fn search_bad_facet_stats_aggregations() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"facetStatsAggregations": "sum"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.facetStatsAggregations`: expected an array, but found a string: `\"sum\"`",
+      "code": "invalid_search_facet_stats_aggregations",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_stats_aggregations"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"facetStatsAggregations": ["sum", "p101"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.facetStatsAggregations[1]`: the value of `facetStatsAggregations` is invalid, expected one of `sum`, `avg`, `count` or a percentile between `p0` and `p100`.",
+      "code": "invalid_search_facet_stats_aggregations",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_stats_aggregations"
+    }
+    "###);
+
+    let (response, code) = index.search_get("?facetStatsAggregations=median").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `facetStatsAggregations`: the value of `facetStatsAggregations` is invalid, expected one of `sum`, `avg`, `count` or a percentile between `p0` and `p100`.",
+      "code": "invalid_search_facet_stats_aggregations",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_stats_aggregations"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn search_bad_threshold() {
     let server = Server::new().await;
@@ -598,6 +943,45 @@ async fn search_bad_crop_marker() {
     // Can't make the `crop_marker` fail with a get search since it'll accept anything as a strings.
 }
 
+#[actix_rt::test]
+async fn search_bad_locales() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"locales": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.locales`: expected an array, but found a string: `\"doggo\"`",
+      "code": "invalid_search_locales",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_locales"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({"locales": ["doggo"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `doggo` at `.locales[0]`: expected one of `epo`, `eng`, `rus`, `cmn`, `spa`, `por`, `ita`, `ben`, `fra`, `deu`, `ukr`, `kat`, `ara`, `hin`, `jpn`, `heb`, `yid`, `pol`, `amh`, `jav`, `kor`, `nob`, `dan`, `swe`, `fin`, `tur`, `nld`, `hun`, `ces`, `ell`, `bul`, `bel`, `mar`, `kan`, `ron`, `slv`, `hrv`, `srp`, `mkd`, `lit`, `lav`, `est`, `tam`, `vie`, `urd`, `tha`, `guj`, `uzb`, `pan`, `aze`, `ind`, `tel`, `pes`, `mal`, `ori`, `mya`, `nep`, `sin`, `khm`, `tuk`, `aka`, `zul`, `sna`, `afr`, `lat`, `slk`, `cat`, `tgl`, `hye`",
+      "code": "invalid_search_locales",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_locales"
+    }
+    "###);
+
+    let (response, code) = index.search_get("?locales=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `locales`: Unsupported locale `doggo`, expected one of `epo`, `eng`, `rus`, `cmn`, `spa`, `por`, `ita`, `ben`, `fra`, `deu`, `ukr`, `kat`, `ara`, `hin`, `jpn`, `heb`, `yid`, `pol`, `amh`, `jav`, `kor`, `nob`, `dan`, `swe`, `fin`, `tur`, `nld`, `hun`, `ces`, `ell`, `bul`, `bel`, `mar`, `kan`, `ron`, `slv`, `hrv`, `srp`, `mkd`, `lit`, `lav`, `est`, `tam`, `vie`, `urd`, `tha`, `guj`, `uzb`, `pan`, `aze`, `ind`, `tel`, `pes`, `mal`, `ori`, `mya`, `nep`, `sin`, `khm`, `tuk`, `aka`, `zul`, `sna`, `afr`, `lat`, `slk`, `cat`, `tgl`, `hye`",
+      "code": "invalid_search_locales",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_locales"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn search_bad_matching_strategy() {
     let server = Server::new().await;
@@ -649,7 +1033,7 @@ async fn filter_invalid_syntax_object() {
     index.wait_task(1).await;
 
     let expected_response = json!({
-        "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `title & Glass`.\n1:14 title & Glass",
+        "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, `_geoBoundingBox`, or `_geoPolygon` at `title & Glass`.\n1:14 title & Glass",
         "code": "invalid_search_filter",
         "type": "invalid_request",
         "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -674,7 +1058,7 @@ async fn filter_invalid_syntax_array() {
     index.wait_task(1).await;
 
     let expected_response = json!({
-        "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `title & Glass`.\n1:14 title & Glass",
+        "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, `_geoBoundingBox`, or `_geoPolygon` at `title & Glass`.\n1:14 title & Glass",
         "code": "invalid_search_filter",
         "type": "invalid_request",
         "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -774,7 +1158,7 @@ async fn filter_reserved_geo_attribute_array() {
     index.wait_task(1).await;
 
     let expected_response = json!({
-        "message": "`_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:13 _geo = Glass",
+        "message": "`_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])`, or `_geoPolygon([latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.\n1:13 _geo = Glass",
         "code": "invalid_search_filter",
         "type": "invalid_request",
         "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -799,7 +1183,7 @@ async fn filter_reserved_geo_attribute_string() {
     index.wait_task(1).await;
 
     let expected_response = json!({
-        "message": "`_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:13 _geo = Glass",
+        "message": "`_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])`, or `_geoPolygon([latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.\n1:13 _geo = Glass",
         "code": "invalid_search_filter",
         "type": "invalid_request",
         "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -824,7 +1208,7 @@ async fn filter_reserved_attribute_array() {
     index.wait_task(1).await;
 
     let expected_response = json!({
-        "message": "`_geoDistance` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:21 _geoDistance = Glass",
+        "message": "`_geoDistance` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])`, or `_geoPolygon([latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.\n1:21 _geoDistance = Glass",
         "code": "invalid_search_filter",
         "type": "invalid_request",
         "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -849,7 +1233,7 @@ async fn filter_reserved_attribute_string() {
     index.wait_task(1).await;
 
     let expected_response = json!({
-       "message": "`_geoDistance` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:21 _geoDistance = Glass",
+       "message": "`_geoDistance` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])`, or `_geoPolygon([latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.\n1:21 _geoDistance = Glass",
         "code": "invalid_search_filter",
         "type": "invalid_request",
         "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -874,7 +1258,7 @@ async fn filter_reserved_geo_point_array() {
     index.wait_task(1).await;
 
     let expected_response = json!({
-        "message": "`_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:18 _geoPoint = Glass",
+        "message": "`_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])`, or `_geoPolygon([latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.\n1:18 _geoPoint = Glass",
         "code": "invalid_search_filter",
         "type": "invalid_request",
         "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -899,7 +1283,7 @@ async fn filter_reserved_geo_point_string() {
     index.wait_task(1).await;
 
     let expected_response = json!({
-       "message": "`_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:18 _geoPoint = Glass",
+       "message": "`_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])`, or `_geoPolygon([latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.\n1:18 _geoPoint = Glass",
         "code": "invalid_search_filter",
         "type": "invalid_request",
         "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -1141,6 +1525,82 @@ async fn search_on_unknown_field_plus_joker() {
         .await;
 }
 
+#[actix_rt::test]
+async fn search_on_invalid_weighted_field() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.update_settings_searchable_attributes(json!(["id", "title"])).await;
+    index.wait_task(0).await;
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index
+        .search_post(json!({"q": "Captain Marvel", "attributesToSearchOn": ["*", { "attribute": "unknown", "weight": 1 }]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Attribute `unknown` is not searchable. Available searchable attributes are: `id, title`.",
+      "code": "invalid_search_attributes_to_search_on",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_attributes_to_search_on"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"q": "Captain Marvel", "attributesToSearchOn": [1]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.attributesToSearchOn[0]`: expected a string or an object, but found a positive integer: `1`",
+      "code": "invalid_search_attributes_to_search_on",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_attributes_to_search_on"
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({"q": "Captain Marvel", "attributesToSearchOn": [{ "attribute": "title", "weight": 1001 }]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.attributesToSearchOn[0].weight`: the `weight` must be between `0` and `1000`, but got `1001`",
+      "code": "invalid_search_attributes_to_search_on",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_attributes_to_search_on"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_on_unsearchable_field_in_query() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.update_settings_searchable_attributes(json!(["id"])).await;
+    index.wait_task(0).await;
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    index
+        .search(json!({"q": "title:Captain Marvel"}), |response, code| {
+            snapshot!(code, @"400 Bad Request");
+            snapshot!(json_string!(response), @r###"
+            {
+              "message": "Attribute `title` used in the search query is not searchable. Available searchable attributes are: `id`.",
+              "code": "invalid_search_q",
+              "type": "invalid_request",
+              "link": "https://docs.meilisearch.com/errors#invalid_search_q"
+            }
+            "###);
+        })
+        .await;
+}
+
 #[actix_rt::test]
 async fn distinct_at_search_time() {
     let server = Server::new().await;
@@ -1203,3 +1663,55 @@ async fn distinct_at_search_time() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn search_bad_ranking_rules() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"rankingRules": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.rankingRules`: expected an array, but found a string: `\"doggo\"`",
+      "code": "invalid_search_ranking_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_ranking_rules"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({"rankingRules": ["doggo"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.rankingRules[0]`: `doggo` ranking rule is invalid. Valid ranking rules are words, typo, sort, proximity, attribute, exactness and custom ranking rules.",
+      "code": "invalid_search_ranking_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_ranking_rules"
+    }
+    "###);
+    // Can't make the `rankingRules` fail with a get search since it isn't supported.
+}
+
+#[actix_rt::test]
+async fn search_ranking_rules_unsortable_attribute() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({"sortableAttributes": ["id"]})).await;
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.search_post(json!({"rankingRules": ["words", "title:asc"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Attribute `title` is not sortable and thus, cannot be used in the ranking rules of a search. Available sortable attributes are: `id`.",
+      "code": "invalid_search_ranking_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_ranking_rules"
+    }
+    "###);
+}
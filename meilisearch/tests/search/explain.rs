@@ -0,0 +1,242 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+
+use crate::common::index::Index;
+use crate::common::{Server, Value};
+use crate::json;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        { "id": 0, "text": "a jumping horse" },
+        { "id": 1, "text": "a jumping house" },
+        { "id": 2, "text": "a jumping dog" },
+        { "id": 3, "text": "a sleeping cat" },
+    ])
+});
+
+async fn create_index(server: &Server) -> Index<'_> {
+    let index = server.index("test");
+    index.update_settings(json!({ "rankingRules": ["words", "typo"] })).await;
+    index.wait_task(0).await;
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(1).await;
+    index
+}
+
+#[actix_rt::test]
+async fn explain_documents() {
+    let server = Server::new().await;
+    let index = create_index(&server).await;
+
+    let (response, code) = index
+        .search_explain(json!({
+            "q": "jumping horse",
+            "matchingStrategy": "last",
+            "documentIds": ["1", "2", "3"],
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["rankingRules"]), @r###"
+    [
+      "words",
+      "typo"
+    ]
+    "###);
+    snapshot!(response["candidates"], @"3");
+    let hits: Vec<_> = response["hits"].as_array().unwrap().iter().map(|hit| &hit["id"]).collect();
+    snapshot!(json_string!(hits), @r###"
+    [
+      "0",
+      "1",
+      "2"
+    ]
+    "###);
+    snapshot!(json_string!(response["documents"]), @r###"
+    [
+      {
+        "id": "1",
+        "candidate": true,
+        "position": 1,
+        "skipped": false,
+        "buckets": [
+          {
+            "rankingRule": "words",
+            "rank": 0,
+            "size": 2
+          },
+          {
+            "rankingRule": "typo",
+            "rank": 1,
+            "size": 1
+          }
+        ]
+      },
+      {
+        "id": "2",
+        "candidate": true,
+        "position": 2,
+        "skipped": false,
+        "buckets": [
+          {
+            "rankingRule": "words",
+            "rank": 1,
+            "size": 1
+          },
+          {
+            "rankingRule": "typo",
+            "rank": 0,
+            "size": 1
+          }
+        ]
+      },
+      {
+        "id": "3",
+        "candidate": false,
+        "position": null,
+        "skipped": false,
+        "buckets": []
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn explain_query_graph() {
+    let server = Server::new().await;
+    let index = create_index(&server).await;
+
+    let (response, code) = index.search_explain(json!({ "q": "jumping horse" })).await;
+    snapshot!(code, @"200 OK");
+    let horse = response["queryGraph"]["nodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|node| &node["term"])
+        .find(|term| term["original"] == "horse")
+        .unwrap();
+    snapshot!(json_string!(horse), @r###"
+    {
+      "original": "horse",
+      "positions": [
+        1,
+        1
+      ],
+      "maxTypos": 1,
+      "derivations": {
+        "exact": [
+          "horse"
+        ],
+        "oneTypo": [
+          "house"
+        ],
+        "twoTypos": [],
+        "phrases": [],
+        "prefixDb": null
+      }
+    }
+    "###);
+    // the buckets of the typo rule are nested in the first bucket of the words rule
+    snapshot!(response["buckets"][0]["buckets"][1]["rankingRule"], @r###""typo""###);
+
+    let (response, code) = index.search_explain(json!({})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["queryGraph"], @"null");
+}
+
+#[actix_rt::test]
+async fn explain_unknown_documents() {
+    let server = Server::new().await;
+    let index = create_index(&server).await;
+    index.update_settings(json!({ "filterableAttributes": ["id"] })).await;
+    index.wait_task(2).await;
+
+    // an unknown document is reported like a filtered out one
+    let (response, code) = index
+        .search_explain(json!({
+            "q": "horse",
+            "filter": "id != 1",
+            "documentIds": ["doggo", "1", "0"],
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["documents"]), @r###"
+    [
+      {
+        "id": "doggo",
+        "candidate": false,
+        "position": null,
+        "skipped": false,
+        "buckets": []
+      },
+      {
+        "id": "1",
+        "candidate": false,
+        "position": null,
+        "skipped": false,
+        "buckets": []
+      },
+      {
+        "id": "0",
+        "candidate": true,
+        "position": 0,
+        "skipped": false,
+        "buckets": [
+          {
+            "rankingRule": "words",
+            "rank": 0,
+            "size": 1
+          },
+          {
+            "rankingRule": "typo",
+            "rank": 0,
+            "size": 1
+          }
+        ]
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn explain_errors() {
+    let server = Server::new().await;
+    let index = create_index(&server).await;
+
+    let (response, code) = index
+        .search_explain(
+            json!({ "q": "horse", "hybrid": { "semanticRatio": 0.5, "embedder": "default" } }),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Only keyword searches can be explained.\n Hint: remove `hybrid` from the request.",
+      "code": "invalid_hybrid_query",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_hybrid_query"
+    }
+    "###);
+
+    let (response, code) = index.search_explain(json!({ "vector": [1.0, 0.5] })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Only keyword searches can be explained.\n Hint: remove `vector` from the request.",
+      "code": "invalid_search_vector",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_vector"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_explain(json!({ "q": "horse", "documentIds": "doggo" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.documentIds`: expected an array, but found a string: `\"doggo\"`",
+      "code": "invalid_search_explain_document_ids",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_explain_document_ids"
+    }
+    "###);
+}
@@ -184,3 +184,234 @@ async fn bug_4640() {
         )
         .await;
 }
+
+#[actix_rt::test]
+async fn geo_polygon_with_string_and_number() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.update_settings_filterable_attributes(json!(["_geo"])).await;
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    index
+        .search(
+            json!({
+                "filter": "_geoPolygon([60, -10], [60, 30], [35, 30], [35, -10])",
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response, { ".processingTimeMs" => "[time]" }), @r###"
+                {
+                  "hits": [
+                    {
+                      "id": 2,
+                      "name": "La Bella Italia",
+                      "address": "456 Elm Street, Townsville",
+                      "type": "Italian",
+                      "rating": 9,
+                      "_geo": {
+                        "lat": "45.4777599",
+                        "lng": "9.1967508"
+                      }
+                    }
+                  ],
+                  "query": "",
+                  "processingTimeMs": "[time]",
+                  "limit": 20,
+                  "offset": 0,
+                  "estimatedTotalHits": 1
+                }
+                "###);
+            },
+        )
+        .await;
+
+    // the polygon crosses the antimeridian and thus doesn't contain any document
+    index
+        .search(
+            json!({
+                "filter": "_geoPolygon([60, 170], [60, -170], [-60, -170], [-60, 170])",
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @"[]");
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn geo_with_several_points() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        {
+            "id": 1,
+            "_geo": [{ "lat": 34.0522, "lng": -118.2437 }, { "lat": 48.8566, "lng": 2.3522 }]
+        },
+        {
+            "id": 2,
+            "_geo": { "lat": 45.4777599, "lng": 9.1967508 }
+        },
+        {
+            "id": 3,
+            "_geo": [{ "lat": "40.7128", "lng": "-74.006" }]
+        }
+    ]);
+    index.update_settings_filterable_attributes(json!(["_geo"])).await;
+    index.update_settings_sortable_attributes(json!(["_geo"])).await;
+    let (ret, _code) = index.add_documents(documents, None).await;
+    index.wait_task(ret.uid()).await;
+
+    // the documents are matched by any of their points
+    index
+        .search(
+            json!({
+                "filter": "_geoRadius(34.0522, -118.2437, 1000)",
+                "attributesToRetrieve": ["id"],
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 1
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    // the documents are sorted by their nearest point
+    index
+        .search(
+            json!({
+                "sort": ["_geoPoint(45.4777599, 9.1967508):asc"],
+                "attributesToRetrieve": ["id", "_geo"],
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 2,
+                    "_geo": {
+                      "lat": 45.4777599,
+                      "lng": 9.1967508
+                    },
+                    "_geoDistance": 0
+                  },
+                  {
+                    "id": 1,
+                    "_geo": [
+                      {
+                        "lat": 34.0522,
+                        "lng": -118.2437
+                      },
+                      {
+                        "lat": 48.8566,
+                        "lng": 2.3522
+                      }
+                    ],
+                    "_geoDistance": 639039,
+                    "_geoPointIndex": 1
+                  },
+                  {
+                    "id": 3,
+                    "_geo": [
+                      {
+                        "lat": "40.7128",
+                        "lng": "-74.006"
+                      }
+                    ],
+                    "_geoDistance": 6464029,
+                    "_geoPointIndex": 0
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn geo_aggregation() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.update_settings_filterable_attributes(json!(["_geo", "type"])).await;
+    let (ret, _code) = index.add_documents(documents, None).await;
+    index.wait_task(ret.uid()).await;
+
+    let (response, code) = index
+        .search_post(json!({
+            "geoAggregation": { "precision": 3 },
+            "limit": 0,
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["geoAggregation"], { "[].centroid" => "[centroid]" }), @r###"
+    [
+      {
+        "key": "9q5",
+        "count": 1,
+        "centroid": "[centroid]"
+      },
+      {
+        "key": "u0n",
+        "count": 1,
+        "centroid": "[centroid]"
+      }
+    ]
+    "###);
+
+    // the cells are computed on the filtered documents within the bounds
+    let (response, code) = index
+        .search_post(json!({
+            "filter": "type = Italian OR type = Mexican",
+            "geoAggregation": { "zoom": 2, "bounds": { "topRight": [60, 20], "bottomLeft": [30, -10] } },
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["geoAggregation"], { "[].centroid" => "[centroid]" }), @r###"
+    [
+      {
+        "key": "2/2/1",
+        "count": 1,
+        "centroid": "[centroid]"
+      }
+    ]
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({
+            "filter": "type = Mexican",
+            "geoAggregation": { "zoom": 0 },
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    let centroid = &response["geoAggregation"][0]["centroid"];
+    assert!((centroid["lat"].as_f64().unwrap() - 34.0522).abs() < 1e-6, "{centroid}");
+    assert!((centroid["lng"].as_f64().unwrap() + 118.2437).abs() < 1e-6, "{centroid}");
+
+    let server = Server::new().await;
+    let index = server.index("test");
+    let (ret, _code) = index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(ret.uid()).await;
+
+    let (response, code) = index.search_post(json!({ "geoAggregation": { "zoom": 0 } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The `_geo` field must be filterable or sortable to aggregate the documents on a geo grid. Add `_geo` to the `filterableAttributes` index setting.",
+      "code": "invalid_search_geo_aggregation",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_geo_aggregation"
+    }
+    "###);
+}
@@ -179,6 +179,29 @@ async fn limit_offset() {
     assert_eq!(response["hits"].as_array().unwrap().len(), 1);
 }
 
+#[actix_rt::test]
+async fn disjunctive_facets() {
+    let server = Server::new().await;
+    let index = index_with_documents_user_provided(&server, &SIMPLE_SEARCH_DOCUMENTS_VEC).await;
+
+    let (response, code) = index.update_settings_filterable_attributes(json!(["id"])).await;
+    assert_eq!(202, code, "{:?}", response);
+    index.wait_task(response.uid()).await;
+
+    // every document is a candidate of the semantic search, not only the ones matching the query
+    let (response, code) = index
+        .search_post(json!({
+            "q": "Planet",
+            "vector": [1.0, 1.0],
+            "hybrid": {"semanticRatio": 0.5},
+            "filter": "id = 1",
+            "disjunctiveFacets": ["id"]
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["facetDistribution"], @r###"{"id":{"1":1,"2":1,"3":1}}"###);
+}
+
 #[actix_rt::test]
 async fn simple_search_hf() {
     let server = Server::new().await;
@@ -0,0 +1,76 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+
+use crate::common::{Server, Value};
+use crate::json;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        {
+            "id": 0,
+            "name_ja": "東京のお寿司。",
+            "name_en": "Sushi in Tokyo.",
+        },
+        {
+            "id": 1,
+            "name_ja": "京都のお寿司。",
+            "name_en": "Sushi in Kyoto.",
+        },
+    ])
+});
+
+#[actix_rt::test]
+async fn search_with_locales() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, _) = index
+        .update_settings(json!({
+            "localizedAttributes": [
+                { "attributePatterns": ["*_ja"], "locales": ["jpn"] },
+            ]
+        }))
+        .await;
+    index.wait_task(response.uid()).await;
+    let (response, _) = index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(response.uid()).await;
+
+    index
+        .search(
+            json!({"q": "東京", "locales": ["jpn"], "attributesToHighlight": ["name_ja"]}),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 0,
+                "name_ja": "東京のお寿司。",
+                "name_en": "Sushi in Tokyo.",
+                "_formatted": {
+                  "id": "0",
+                  "name_ja": "<em>東京</em>のお寿司。",
+                  "name_en": "Sushi in Tokyo."
+                }
+              }
+            ]
+            "###);
+            },
+        )
+        .await;
+
+    index
+        .search(json!({"q": "Kyoto", "locales": ["eng"]}), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(response["hits"].as_array().unwrap().len(), @"1");
+        })
+        .await;
+
+    // an empty list of locales is the same as not giving any
+    let (without_locales, _) =
+        index.search_post(json!({"q": "東京", "attributesToHighlight": ["name_ja"]})).await;
+    let (response, code) = index
+        .search_post(json!({"q": "東京", "locales": [], "attributesToHighlight": ["name_ja"]}))
+        .await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(response["hits"], without_locales["hits"]);
+}
@@ -1,12 +1,18 @@
 // This modules contains all the test concerning search. Each particular feature of the search
 // should be tested in its own module to isolate tests and keep the tests readable.
 
+mod analyze;
+mod autocomplete;
+mod dates;
+mod decay;
 mod distinct;
 mod errors;
+mod explain;
 mod facet_search;
 mod formatted;
 mod geo;
 mod hybrid;
+mod locales;
 mod matching_strategy;
 mod multi;
 mod pagination;
@@ -264,6 +270,155 @@ async fn negative_word_search() {
         .await;
 }
 
+#[actix_rt::test]
+async fn typo_tolerant_negative_word_search() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    // By default the negated words must match exactly
+    index
+        .search(json!({"q": "-escpe" }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            let hits = response["hits"].as_array().unwrap();
+            assert_eq!(hits.len(), 5);
+        })
+        .await;
+
+    index
+        .search(json!({"q": "-escpe", "typoTolerantNegation": true }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            let hits = response["hits"].as_array().unwrap();
+            assert_eq!(hits.len(), 4);
+            assert_eq!(hits[0]["id"], "287947");
+            assert_eq!(hits[1]["id"], "299537");
+            assert_eq!(hits[2]["id"], "166428");
+            assert_eq!(hits[3]["id"], "450465");
+        })
+        .await;
+
+    // The typo tolerance settings of the index still apply to the negated words
+    let (_, code) = index.update_settings(json!({"typoTolerance": {"enabled": false}})).await;
+    meili_snap::snapshot!(code, @"202 Accepted");
+    index.wait_task(1).await;
+
+    index
+        .search(json!({"q": "-escpe", "typoTolerantNegation": true }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            let hits = response["hits"].as_array().unwrap();
+            assert_eq!(hits.len(), 5);
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn search_suggestions() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    // The suggestions must be requested
+    index
+        .search(json!({"q": "escpe room" }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert!(response.get("suggestion").is_none(), "{}", response);
+        })
+        .await;
+
+    index
+        .search(json!({"q": "escpe room", "showSuggestions": true }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["suggestion"], json!("escape room"));
+        })
+        .await;
+
+    // There is nothing to suggest when all the words are known
+    index
+        .search(json!({"q": "escape room", "showSuggestions": true }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert!(response.get("suggestion").is_none(), "{}", response);
+        })
+        .await;
+
+    // The suggestions are only computed when there are fewer hits than the threshold
+    index
+        .search(
+            json!({"q": "escpe room", "showSuggestions": true, "suggestionsThreshold": 1 }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                assert_eq!(response["hits"].as_array().unwrap().len(), 1);
+                assert!(response.get("suggestion").is_none(), "{}", response);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn infix_search() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 0, "sku": "AB-12345", "name": "blue widget" },
+        { "id": 1, "sku": "CD-99123", "name": "red widget" },
+        { "id": 2, "sku": "EF-77777", "name": "green 612345 gadget" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    index
+        .search(json!({"q": "2345" }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            let hits = response["hits"].as_array().unwrap();
+            assert_eq!(hits.len(), 0);
+        })
+        .await;
+    let (stats, _) = index.stats().await;
+    assert_eq!(stats["infixDatabaseSize"], 0);
+
+    let (_, code) = index.update_settings(json!({"infixSearchableAttributes": ["sku"]})).await;
+    meili_snap::snapshot!(code, @"202 Accepted");
+    index.wait_task(1).await;
+
+    // Only the words of the infix searchable attributes are found from their parts
+    index
+        .search(json!({"q": "2345" }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            let hits = response["hits"].as_array().unwrap();
+            assert_eq!(hits.len(), 1);
+            assert_eq!(hits[0]["id"], 0);
+        })
+        .await;
+    index
+        .search(json!({"q": "912 widget" }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            let hits = response["hits"].as_array().unwrap();
+            assert_eq!(hits.len(), 1);
+            assert_eq!(hits[0]["id"], 1);
+        })
+        .await;
+    let (stats, _) = index.stats().await;
+    assert!(stats["infixDatabaseSize"].as_u64().unwrap() > 0);
+
+    let (_, code) = index.update_settings(json!({"infixSearchableAttributes": null})).await;
+    meili_snap::snapshot!(code, @"202 Accepted");
+    index.wait_task(2).await;
+
+    index
+        .search(json!({"q": "2345" }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            let hits = response["hits"].as_array().unwrap();
+            assert_eq!(hits.len(), 0);
+        })
+        .await;
+}
+
 #[actix_rt::test]
 async fn non_negative_search() {
     let server = Server::new().await;
@@ -611,6 +766,66 @@ async fn search_with_multiple_sort() {
     assert_eq!(response["hits"].as_array().unwrap().len(), 5);
 }
 
+#[actix_rt::test]
+async fn search_with_ranking_rules_override() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({"sortableAttributes": ["id"], "rankingRules": ["id:asc"]})).await;
+
+    let documents = NESTED_DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.search_post(json!({ "attributesToRetrieve": ["id"] })).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 654
+      },
+      {
+        "id": 750
+      },
+      {
+        "id": 852
+      },
+      {
+        "id": 951
+      }
+    ]
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({ "rankingRules": ["id:desc"], "attributesToRetrieve": ["id"] }))
+        .await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 951
+      },
+      {
+        "id": 852
+      },
+      {
+        "id": 750
+      },
+      {
+        "id": 654
+      }
+    ]
+    "###);
+
+    // the ranking rules of the index are left untouched
+    let (response, _code) = index.settings().await;
+    meili_snap::snapshot!(meili_snap::json_string!(response["rankingRules"]), @r###"
+    [
+      "id:asc"
+    ]
+    "###);
+}
+
 #[actix_rt::test]
 async fn search_facet_distribution() {
     let server = Server::new().await;
@@ -728,6 +943,279 @@ async fn search_facet_distribution() {
         .await;
 }
 
+#[actix_rt::test]
+async fn search_facet_ranges() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({"filterableAttributes": ["price", "title"]})).await;
+    index
+        .add_documents(
+            json!([
+                { "id": 1, "title": "Shazam!", "price": 5 },
+                { "id": 2, "title": "Captain Marvel", "price": 12.5 },
+                { "id": 3, "title": "Escape Room", "price": 19 },
+                { "id": 4, "title": "How to Train Your Dragon", "price": 20 },
+                { "id": 5, "title": "Gläss", "price": 42 },
+                { "id": 6, "title": "Glass Onion", "price": "free" },
+            ]),
+            None,
+        )
+        .await;
+    index.wait_task(1).await;
+
+    let (response, code) = index
+        .search_post(json!({
+            "facetRanges": {
+                "price": { "ranges": [{ "to": 10 }, { "from": 10, "to": 20 }, { "from": 20 }] }
+            }
+        }))
+        .await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response["facetRanges"]), @r###"
+    {
+      "price": [
+        {
+          "to": 10.0,
+          "count": 1
+        },
+        {
+          "from": 10.0,
+          "to": 20.0,
+          "count": 2
+        },
+        {
+          "from": 20.0,
+          "count": 2
+        }
+      ]
+    }
+    "###);
+
+    // the histogram only spans the values of the matching documents
+    let (response, code) = index
+        .search_post(json!({
+            "filter": "price < 20",
+            "facetRanges": { "price": { "interval": 10 } }
+        }))
+        .await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response["facetRanges"]), @r###"
+    {
+      "price": [
+        {
+          "from": 0.0,
+          "to": 10.0,
+          "count": 1
+        },
+        {
+          "from": 10.0,
+          "to": 20.0,
+          "count": 2
+        }
+      ]
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"facetRanges": { "price": { "interval": 0.01 } }})).await;
+    meili_snap::snapshot!(code, @"400 Bad Request");
+    meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
+    {
+      "message": "Invalid histogram interval `0.01` for the `price` facet, the interval must be a positive number splitting the facet values into at most 1000 buckets.",
+      "code": "invalid_search_facet_ranges",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_ranges"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"facetRanges": { "id": { "interval": 10 } }})).await;
+    meili_snap::snapshot!(code, @"400 Bad Request");
+    meili_snap::snapshot!(meili_snap::json_string!(response["code"]), @r###""invalid_search_facets""###);
+}
+
+#[actix_rt::test]
+async fn search_disjunctive_facets() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({"filterableAttributes": ["color", "size"]})).await;
+    index
+        .add_documents(
+            json!([
+                { "id": 0, "title": "red shirt", "color": "red", "size": "S" },
+                { "id": 1, "title": "blue shirt", "color": "blue", "size": "M" },
+                { "id": 2, "title": "red dress", "color": "red", "size": "M" },
+                { "id": 3, "title": "green shirt", "color": "green", "size": "S" },
+                { "id": 4, "title": "blue shirt", "color": "blue", "size": "S" },
+            ]),
+            None,
+        )
+        .await;
+    index.wait_task(1).await;
+
+    index
+        .search(
+            json!({
+                "q": "shirt",
+                "filter": "color = red AND size = S",
+                "facets": ["color", "size"],
+                "disjunctiveFacets": ["color"]
+            }),
+            |response, code| {
+                meili_snap::snapshot!(code, @"200 OK");
+                assert_eq!(response["hits"].as_array().unwrap().len(), 1);
+                // the counts of `color` ignore the `color = red` clause but not the `size = S` one
+                meili_snap::snapshot!(meili_snap::json_string!(response["facetDistribution"]), @r###"
+                {
+                  "color": {
+                    "blue": 1,
+                    "green": 1,
+                    "red": 1
+                  },
+                  "size": {
+                    "S": 1
+                  }
+                }
+                "###);
+            },
+        )
+        .await;
+
+    // the disjunctive facets are returned even if they are not part of `facets`
+    index
+        .search(
+            json!({
+                "q": "shirt",
+                "filter": "color = red AND size = S",
+                "disjunctiveFacets": ["size"]
+            }),
+            |response, code| {
+                meili_snap::snapshot!(code, @"200 OK");
+                meili_snap::snapshot!(meili_snap::json_string!(response["facetDistribution"]), @r###"
+                {
+                  "size": {
+                    "S": 1
+                  }
+                }
+                "###);
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({
+                "filter": "color = blue",
+                "disjunctiveFacets": ["color", "size"]
+            }),
+            |response, code| {
+                meili_snap::snapshot!(code, @"200 OK");
+                meili_snap::snapshot!(meili_snap::json_string!(response["facetDistribution"]), @r###"
+                {
+                  "color": {
+                    "blue": 2,
+                    "green": 1,
+                    "red": 2
+                  },
+                  "size": {
+                    "M": 1,
+                    "S": 1
+                  }
+                }
+                "###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn search_facet_stats_aggregations() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({"filterableAttributes": ["price"]})).await;
+    index
+        .add_documents(
+            json!([
+                { "id": 0, "price": 5 },
+                { "id": 1, "price": 10 },
+                { "id": 2, "price": 15 },
+                { "id": 3, "price": 20 },
+                { "id": 4, "price": 50 },
+                { "id": 5 },
+            ]),
+            None,
+        )
+        .await;
+    index.wait_task(1).await;
+
+    index
+        .search(
+            json!({
+                "facets": ["price"],
+                "facetStatsAggregations": ["sum", "avg", "count", "p50", "p90"]
+            }),
+            |response, code| {
+                meili_snap::snapshot!(code, @"200 OK");
+                meili_snap::snapshot!(meili_snap::json_string!(response["facetStats"]), @r###"
+                {
+                  "price": {
+                    "min": 5.0,
+                    "max": 50.0,
+                    "sum": 100.0,
+                    "avg": 20.0,
+                    "count": 5,
+                    "p50": 15.0,
+                    "p90": 50.0
+                  }
+                }
+                "###);
+            },
+        )
+        .await;
+
+    // the aggregations are computed over the matching documents only
+    index
+        .search(
+            json!({
+                "filter": "price > 10",
+                "facets": ["price"],
+                "facetStatsAggregations": ["avg", "p50"]
+            }),
+            |response, code| {
+                meili_snap::snapshot!(code, @"200 OK");
+                meili_snap::snapshot!(meili_snap::json_string!(response["facetStats"]), @r###"
+                {
+                  "price": {
+                    "min": 15.0,
+                    "max": 50.0,
+                    "avg": 28.333333333333332,
+                    "p50": 20.0
+                  }
+                }
+                "###);
+            },
+        )
+        .await;
+
+    // without aggregations only the extremes are returned
+    index
+        .search(json!({"facets": ["price"]}), |response, code| {
+            meili_snap::snapshot!(code, @"200 OK");
+            meili_snap::snapshot!(meili_snap::json_string!(response["facetStats"]), @r###"
+            {
+              "price": {
+                "min": 5.0,
+                "max": 50.0
+              }
+            }
+            "###);
+        })
+        .await;
+}
+
 #[actix_rt::test]
 async fn displayed_attributes() {
     let server = Server::new().await;
@@ -376,3 +376,215 @@ async fn search_multiple_query_errors() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn federation_multiple_indexes() {
+    let server = Server::new().await;
+
+    let index = server.index("test");
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let index = server.index("nested");
+    let documents = NESTED_DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = server
+        .multi_search(json!({"federation": {}, "queries": [
+        {"indexUid" : "test", "q": "glass"},
+        {"indexUid": "nested", "q": "jean"},
+        ]}))
+        .await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(response["limit"], json!(20));
+    assert_eq!(response["offset"], json!(0));
+    assert_eq!(response["estimatedTotalHits"], json!(3));
+
+    let hits = response["hits"].as_array().unwrap();
+    assert_eq!(hits.len(), 3);
+    let mut previous_score = f64::MAX;
+    for hit in hits {
+        let federation = &hit["_federation"];
+        let index_uid = federation["indexUid"].as_str().unwrap();
+        let queries_position = federation["queriesPosition"].as_u64().unwrap();
+        match index_uid {
+            "test" => assert_eq!(queries_position, 0),
+            "nested" => assert_eq!(queries_position, 1),
+            _ => panic!("unexpected index `{index_uid}` in {response}"),
+        }
+        let score = federation["weightedRankingScore"].as_f64().unwrap();
+        assert!(score <= previous_score, "hits are not sorted by score: {response}");
+        previous_score = score;
+    }
+}
+
+#[actix_rt::test]
+async fn federation_pagination_and_weights() {
+    let server = Server::new().await;
+
+    let index = server.index("test");
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    // `Gläss` is an exact match and ranks before `Captain Marvel` with the default weights
+    let (response, code) = server
+        .multi_search(json!({"federation": {}, "queries": [
+        {"indexUid" : "test", "q": "glass"},
+        {"indexUid": "test", "q": "captain"},
+        ]}))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"][0]["title"], @r###""Gläss""###);
+    snapshot!(response["hits"][1]["title"], @r###""Captain Marvel""###);
+    snapshot!(response["estimatedTotalHits"], @"2");
+
+    let (response, code) = server
+        .multi_search(json!({"federation": {}, "queries": [
+        {"indexUid" : "test", "q": "glass", "federationOptions": {"weight": 0.5}},
+        {"indexUid": "test", "q": "captain"},
+        ]}))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"][0]["title"], @r###""Captain Marvel""###);
+    snapshot!(response["hits"][1]["title"], @r###""Gläss""###);
+
+    let (response, code) = server
+        .multi_search(json!({"federation": {"offset": 1, "limit": 1}, "queries": [
+        {"indexUid" : "test", "q": "glass", "federationOptions": {"weight": 0.5}},
+        {"indexUid": "test", "q": "captain"},
+        ]}))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"].as_array().unwrap().len(), @"1");
+    snapshot!(response["hits"][0]["title"], @r###""Gläss""###);
+    snapshot!(response["offset"], @"1");
+    snapshot!(response["limit"], @"1");
+    snapshot!(response["estimatedTotalHits"], @"2");
+}
+
+#[actix_rt::test]
+async fn federation_same_document_returned_once() {
+    let server = Server::new().await;
+
+    let index = server.index("test");
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = server
+        .multi_search(json!({"federation": {}, "queries": [
+        {"indexUid" : "test", "q": "glass"},
+        {"indexUid": "test", "q": "glass", "federationOptions": {"weight": 2.0}},
+        ]}))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"].as_array().unwrap().len(), @"1");
+    snapshot!(response["hits"][0]["_federation"]["queriesPosition"], @"1");
+    snapshot!(response["estimatedTotalHits"], @"1");
+}
+
+#[actix_rt::test]
+async fn federation_invalid_parameters() {
+    let server = Server::new().await;
+
+    let index = server.index("test");
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = server
+        .multi_search(json!({"queries": [
+        {"indexUid" : "test", "q": "glass", "federationOptions": {"weight": 2.0}},
+        ]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Inside `.queries[0]`: Using `federationOptions` is not allowed in a non-federated search.\n Hint: remove `federationOptions` from query #0 or add `federation` to the request.",
+      "code": "invalid_multi_search_federation_options",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_multi_search_federation_options"
+    }
+    "###);
+
+    let (response, code) = server
+        .multi_search(json!({"federation": {}, "queries": [
+        {"indexUid" : "test", "q": "glass"},
+        {"indexUid" : "test", "q": "captain", "limit": 5},
+        ]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Inside `.queries[1]`: Using pagination options is not allowed in federated queries.\n Hint: remove `limit` from query #1 or remove `federation` from the request.",
+      "code": "invalid_multi_search_query_pagination",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_multi_search_query_pagination"
+    }
+    "###);
+
+    let (response, code) = server
+        .multi_search(json!({"federation": {}, "queries": [
+        {"indexUid" : "test", "q": "glass", "facets": ["title"]},
+        ]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_multi_search_query_facets""###);
+
+    let (response, code) = server
+        .multi_search(json!({"federation": {}, "queries": [
+        {"indexUid" : "test", "q": "glass", "facetStatsAggregations": ["sum"]},
+        ]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_multi_search_query_facets""###);
+
+    let (response, code) = server
+        .multi_search(json!({"federation": {}, "queries": [
+        {"indexUid" : "test", "q": "glass", "collapse": {"limit": 2}},
+        ]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Inside `.queries[0]`: Using `collapse` is not allowed in federated queries.\n Hint: remove `collapse` from query #0 or remove `federation` from the request.",
+      "code": "invalid_multi_search_query_collapse",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_multi_search_query_collapse"
+    }
+    "###);
+
+    let (response, code) = server
+        .multi_search(json!({"federation": {}, "queries": [
+        {"indexUid" : "test", "q": "glass", "showSuggestions": true},
+        ]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Inside `.queries[0]`: Using suggestion options is not allowed in federated queries.\n Hint: remove `showSuggestions` from query #0 or remove `federation` from the request.",
+      "code": "invalid_multi_search_query_suggestions",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_multi_search_query_suggestions"
+    }
+    "###);
+
+    let (response, code) = server
+        .multi_search(json!({"federation": {}, "queries": [
+        {"indexUid" : "test", "q": "glass", "suggestionsThreshold": 3},
+        ]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_multi_search_query_suggestions""###);
+
+    let (response, code) = server
+        .multi_search(json!({"federation": {}, "queries": [
+        {"indexUid" : "test", "q": "glass", "federationOptions": {"weight": -1.0}},
+        ]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_multi_search_weight""###);
+}
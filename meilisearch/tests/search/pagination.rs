@@ -163,3 +163,92 @@ async fn ensure_placeholder_search_hit_count_valid() {
             .await;
     }
 }
+
+#[actix_rt::test]
+async fn search_after_goes_beyond_max_total_hits() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents: Vec<_> = (0..25).map(|i| json!({ "id": i, "rank": i % 5 })).collect();
+    index.add_documents(documents.into(), None).await;
+    index.wait_task(0).await;
+    index
+        .update_settings(json!({
+            "sortableAttributes": ["rank"],
+            "pagination": { "maxTotalHits": 10 },
+        }))
+        .await;
+    index.wait_task(1).await;
+
+    let mut ids = Vec::new();
+    let mut search_after = None;
+    loop {
+        let mut query = json!({ "sort": ["rank:asc"], "limit": 4, "showCursor": true });
+        if let Some(search_after) = search_after.take() {
+            query["searchAfter"] = search_after;
+        }
+        let (response, code) = index.search_post(query).await;
+        assert_eq!(code, 200, "{}", response);
+
+        let hits = response["hits"].as_array().unwrap();
+        ids.extend(hits.iter().map(|hit| hit["id"].as_u64().unwrap()));
+        if hits.len() < 4 {
+            break;
+        }
+        search_after = Some(response["cursor"].clone());
+    }
+
+    let expected: Vec<_> = (0..5).flat_map(|rank| (0..5).map(move |i| i * 5 + rank)).collect();
+    assert_eq!(ids, expected);
+
+    // the cursor is only returned on demand
+    let (response, code) = index.search_post(json!({ "sort": ["rank:asc"] })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert!(response.get("cursor").is_none());
+}
+
+#[actix_rt::test]
+async fn search_after_stale_cursor() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents: Vec<_> = (0..10).map(|i| json!({ "id": i })).collect();
+    index.add_documents(documents.into(), None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.search_post(json!({ "limit": 4, "showCursor": true })).await;
+    assert_eq!(code, 200, "{}", response);
+    let cursor = response["cursor"].clone();
+
+    let (response, code) = index.search_post(json!({ "limit": 4, "searchAfter": cursor })).await;
+    assert_eq!(code, 200, "{}", response);
+    let ids: Vec<_> = response["hits"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|hit| hit["id"].as_u64().unwrap())
+        .collect();
+    assert_eq!(ids, vec![4, 5, 6, 7]);
+
+    // the cursor can't be used by a search with another query
+    let (response, code) =
+        index.search_post(json!({ "q": "1", "limit": 4, "searchAfter": cursor })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_search_search_after");
+    assert_eq!(
+        response["message"],
+        "The search cursor was returned by a search with a different query, filter, sort or ranking rules. The search must be started again without cursor."
+    );
+
+    // the cursor can't be used once the index changed
+    index.add_documents(json!([{ "id": 10 }]), None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.search_post(json!({ "limit": 4, "searchAfter": cursor })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_search_search_after");
+    assert_eq!(
+        response["message"],
+        "The search cursor is stale: the index changed since it was returned. The search must be started again without cursor."
+    );
+}
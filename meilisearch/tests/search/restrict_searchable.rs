@@ -297,6 +297,73 @@ async fn attributes_ranking_rule_order() {
         .await;
 }
 
+#[actix_rt::test]
+async fn attributes_weights_ranking_rule_order() {
+    let server = Server::new().await;
+    let index = index_with_documents(
+        &server,
+        &json!([
+        {
+            "id": "1",
+            "name": "Captain Marvel",
+            "desc": "a super hero",
+        },
+        {
+            "id": "2",
+            "name": "a super hero",
+            "desc": "Captain Marvel",
+        }]),
+    )
+    .await;
+
+    // Document 1 matches in the name which comes first in the searchable attributes.
+    let (response, code) =
+        index.search_post(json!({"q": "Captain Marvel", "attributesToRetrieve": ["id"]})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": "1"
+      },
+      {
+        "id": "2"
+      }
+    ]
+    "###);
+
+    // Document 2 matches in the description which is given a weight.
+    let (response, code) = index
+        .search_post(json!({"q": "Captain Marvel", "attributesToRetrieve": ["id"], "attributesToSearchOn": ["*", { "attribute": "desc", "weight": 1 }]}))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": "2"
+      },
+      {
+        "id": "1"
+      }
+    ]
+    "###);
+
+    // The highest weight comes first.
+    let (response, code) = index
+        .search_post(json!({"q": "Captain Marvel", "attributesToRetrieve": ["id"], "attributesToSearchOn": [{ "attribute": "name", "weight": 1 }, { "attribute": "desc", "weight": 2 }]}))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": "2"
+      },
+      {
+        "id": "1"
+      }
+    ]
+    "###);
+}
+
 #[actix_rt::test]
 async fn exactness_ranking_rule_order() {
     let server = Server::new().await;
@@ -367,3 +434,38 @@ async fn search_on_exact_field() {
         })
         .await;
 }
+
+#[actix_rt::test]
+async fn field_scoped_query() {
+    let server = Server::new().await;
+    let index = index_with_documents(&server, &SIMPLE_SEARCH_DOCUMENTS).await;
+
+    index
+        .search(json!({"q": "title:Captain desc:Marvel", "matchingStrategy": "all", "attributesToRetrieve": ["id"]}), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": "2"
+              }
+            ]
+            "###);
+        })
+        .await;
+
+    index
+        .search(
+            json!({"q": "desc:\"Captain Marvel\"", "attributesToRetrieve": ["id"]}),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": "1"
+              }
+            ]
+            "###);
+            },
+        )
+        .await;
+}
@@ -365,3 +365,115 @@ async fn settings_bad_search_cutoff_ms() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn settings_bad_localized_attributes() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.update_settings(json!({ "localizedAttributes": "doggo" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.localizedAttributes`: expected an array, but found a string: `\"doggo\"`",
+      "code": "invalid_settings_localized_attributes",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_localized_attributes"
+    }
+    "###);
+
+    let (response, code) = index.update_settings_localized_attributes(json!("doggo")).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type: expected an array, but found a string: `\"doggo\"`",
+      "code": "invalid_settings_localized_attributes",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_localized_attributes"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_infix_searchable_attributes() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) =
+        index.update_settings(json!({ "infixSearchableAttributes": "doggo" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.infixSearchableAttributes`: expected an array, but found a string: `\"doggo\"`",
+      "code": "invalid_settings_infix_searchable_attributes",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_infix_searchable_attributes"
+    }
+    "###);
+
+    let (response, code) = index.update_settings_infix_searchable_attributes(json!("doggo")).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type: expected an array, but found a string: `\"doggo\"`",
+      "code": "invalid_settings_infix_searchable_attributes",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_infix_searchable_attributes"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_pinned_results() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.update_settings(json!({ "pinnedResults": "doggo" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.pinnedResults`: expected an array, but found a string: `\"doggo\"`",
+      "code": "invalid_settings_pinned_results",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_pinned_results"
+    }
+    "###);
+
+    let (response, code) =
+        index.update_settings(json!({ "pinnedResults": [{ "query": "doggo" }] })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing field `pinned` inside `.pinnedResults[0]`",
+      "code": "invalid_settings_pinned_results",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_pinned_results"
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings(
+            json!({ "pinnedResults": [{ "query": "doggo", "pinned": [{ "id": "1" }] }] }),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing field `position` inside `.pinnedResults[0].pinned[0]`",
+      "code": "invalid_settings_pinned_results",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_pinned_results"
+    }
+    "###);
+
+    let (response, code) = index.update_settings_pinned_results(json!("doggo")).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type: expected an array, but found a string: `\"doggo\"`",
+      "code": "invalid_settings_pinned_results",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_pinned_results"
+    }
+    "###);
+}
@@ -36,6 +36,9 @@ static DEFAULT_SETTINGS_VALUES: Lazy<HashMap<&'static str, Value>> = Lazy::new(|
         }),
     );
     map.insert("search_cutoff_ms", json!(null));
+    map.insert("localized_attributes", json!(null));
+    map.insert("infix_searchable_attributes", json!([]));
+    map.insert("pinned_results", json!([]));
     map
 });
 
@@ -55,7 +58,7 @@ async fn get_settings() {
     let (response, code) = index.settings().await;
     assert_eq!(code, 200);
     let settings = response.as_object().unwrap();
-    assert_eq!(settings.keys().len(), 16);
+    assert_eq!(settings.keys().len(), 19);
     assert_eq!(settings["displayedAttributes"], json!(["*"]));
     assert_eq!(settings["searchableAttributes"], json!(["*"]));
     assert_eq!(settings["filterableAttributes"], json!([]));
@@ -86,6 +89,53 @@ async fn get_settings() {
     );
     assert_eq!(settings["proximityPrecision"], json!("byWord"));
     assert_eq!(settings["searchCutoffMs"], json!(null));
+    assert_eq!(settings["localizedAttributes"], json!(null));
+    assert_eq!(settings["infixSearchableAttributes"], json!([]));
+    assert_eq!(settings["pinnedResults"], json!([]));
+}
+
+#[actix_rt::test]
+async fn set_and_reset_localized_attributes() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, _code) = index
+        .update_settings(json!({
+            "localizedAttributes": [
+                { "attributePatterns": ["*_ja"], "locales": ["jpn"] },
+                { "attributePatterns": ["*"], "locales": [] }
+            ]
+        }))
+        .await;
+    index.wait_task(response.uid()).await;
+
+    let (response, code) = index.settings().await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response["localizedAttributes"]), @r###"
+    [
+      {
+        "attributePatterns": [
+          "*_ja"
+        ],
+        "locales": [
+          "jpn"
+        ]
+      },
+      {
+        "attributePatterns": [
+          "*"
+        ],
+        "locales": []
+      }
+    ]
+    "###);
+
+    let (response, _code) = index.update_settings(json!({ "localizedAttributes": null })).await;
+    index.wait_task(response.uid()).await;
+
+    let (response, code) = index.settings().await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response["localizedAttributes"]), @"null");
 }
 
 #[actix_rt::test]
@@ -200,7 +250,10 @@ async fn secrets_are_hidden_in_settings() {
           "inputType": "text"
         }
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "localizedAttributes": null,
+      "infixSearchableAttributes": [],
+      "pinnedResults": []
     }
     "###);
 
@@ -420,7 +473,10 @@ test_setting_routes!(
     synonyms put,
     pagination patch,
     faceting patch,
-    search_cutoff_ms put
+    search_cutoff_ms put,
+    localized_attributes put,
+    infix_searchable_attributes put,
+    pinned_results put
 );
 
 #[actix_rt::test]
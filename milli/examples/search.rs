@@ -64,10 +64,13 @@ fn main() -> Result<(), Box<dyn Error>> {
                 0,
                 20,
                 None,
+                None,
                 &mut DefaultSearchLogger,
                 logger,
                 TimeBudget::max(),
                 None,
+                None,
+                false,
             )?;
             if let Some((logger, dir)) = detailed_logger {
                 logger.finish(&mut ctx, Path::new(dir))?;
@@ -3,7 +3,10 @@ use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
 
+use crate::error::is_reserved_keyword;
 use crate::{AscDesc, Member};
 
 #[derive(Error, Debug)]
@@ -22,6 +25,8 @@ pub enum CriterionError {
 `{name}` can only be used for filtering at search time"
     )]
     ReservedNameForFilter { name: String },
+    #[error("`{name}` decay ranking rule is invalid. {reason}")]
+    InvalidDecay { name: String, reason: String },
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -45,6 +50,9 @@ pub enum Criterion {
     Asc(String),
     /// Sorted by the decreasing value of the field specified.
     Desc(String),
+    /// Sorted by the decreasing score of a decay function applied to the distance between the
+    /// value of the field specified and an origin.
+    Decay(Decay),
 }
 
 impl Criterion {
@@ -52,6 +60,7 @@ impl Criterion {
     pub fn field_name(&self) -> Option<&str> {
         match self {
             Criterion::Asc(name) | Criterion::Desc(name) => Some(name),
+            Criterion::Decay(decay) => Some(&decay.field),
             _otherwise => None,
         }
     }
@@ -68,6 +77,7 @@ impl FromStr for Criterion {
             "attribute" => Ok(Criterion::Attribute),
             "sort" => Ok(Criterion::Sort),
             "exactness" => Ok(Criterion::Exactness),
+            text if Decay::looks_like_decay(text) => Decay::from_str(text).map(Criterion::Decay),
             text => match AscDesc::from_str(text)? {
                 AscDesc::Asc(Member::Field(field)) => Ok(Criterion::Asc(field)),
                 AscDesc::Desc(Member::Field(field)) => Ok(Criterion::Desc(field)),
@@ -103,10 +113,282 @@ impl fmt::Display for Criterion {
             Exactness => f.write_str("exactness"),
             Asc(attr) => write!(f, "{}:asc", attr),
             Desc(attr) => write!(f, "{}:desc", attr),
+            Decay(decay) => write!(f, "{}", decay),
         }
     }
 }
 
+/// The shape of the curve used by a [`Decay`] ranking rule.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum DecayFunction {
+    Gauss,
+    Linear,
+    Exp,
+}
+
+impl DecayFunction {
+    fn name(&self) -> &'static str {
+        match self {
+            DecayFunction::Gauss => "gauss",
+            DecayFunction::Linear => "linear",
+            DecayFunction::Exp => "exp",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "gauss" => Some(DecayFunction::Gauss),
+            "linear" => Some(DecayFunction::Linear),
+            "exp" => Some(DecayFunction::Exp),
+            _ => None,
+        }
+    }
+}
+
+/// The value around which a [`Decay`] ranking rule gives the best score.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum DecayOrigin {
+    /// The time at which the search is executed, as a unix timestamp in seconds.
+    Now,
+    /// A fixed date, compared to the field values as a unix timestamp in seconds.
+    Date(#[serde(with = "time::serde::rfc3339")] OffsetDateTime),
+    Number(f64),
+}
+
+impl DecayOrigin {
+    /// Returns the origin as a number comparable to the facet values.
+    pub fn value(&self) -> f64 {
+        match self {
+            DecayOrigin::Now => OffsetDateTime::now_utc().unix_timestamp() as f64,
+            DecayOrigin::Date(date) => date.unix_timestamp() as f64,
+            DecayOrigin::Number(number) => *number,
+        }
+    }
+}
+
+impl FromStr for DecayOrigin {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "now" {
+            return Ok(DecayOrigin::Now);
+        }
+        if let Ok(number) = s.parse::<f64>() {
+            return if number.is_finite() {
+                Ok(DecayOrigin::Number(number))
+            } else {
+                Err(format!("The origin `{s}` must be a finite number."))
+            };
+        }
+        OffsetDateTime::parse(s, &Rfc3339)
+            .map(DecayOrigin::Date)
+            .map_err(|_| format!("The origin `{s}` must be `now`, a number or an RFC 3339 date."))
+    }
+}
+
+impl fmt::Display for DecayOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecayOrigin::Now => f.write_str("now"),
+            DecayOrigin::Date(date) => f.write_str(&date.format(&Rfc3339).map_err(|_| fmt::Error)?),
+            DecayOrigin::Number(number) => write!(f, "{number}"),
+        }
+    }
+}
+
+/// The unit of a [`DecayDistance`], durations are expressed in seconds.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum DecayUnit {
+    None,
+    Seconds,
+    Minutes,
+    Hours,
+    Days,
+    Weeks,
+}
+
+impl DecayUnit {
+    fn suffix(&self) -> &'static str {
+        match self {
+            DecayUnit::None => "",
+            DecayUnit::Seconds => "s",
+            DecayUnit::Minutes => "m",
+            DecayUnit::Hours => "h",
+            DecayUnit::Days => "d",
+            DecayUnit::Weeks => "w",
+        }
+    }
+
+    fn factor(&self) -> f64 {
+        match self {
+            DecayUnit::None | DecayUnit::Seconds => 1.,
+            DecayUnit::Minutes => 60.,
+            DecayUnit::Hours => 60. * 60.,
+            DecayUnit::Days => 24. * 60. * 60.,
+            DecayUnit::Weeks => 7. * 24. * 60. * 60.,
+        }
+    }
+}
+
+/// A distance used as the scale or the offset of a [`Decay`] ranking rule, e.g. `12.5` or `7d`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct DecayDistance {
+    pub amount: f64,
+    pub unit: DecayUnit,
+}
+
+impl DecayDistance {
+    /// Returns the distance in the unit of the facet values.
+    pub fn value(&self) -> f64 {
+        self.amount * self.unit.factor()
+    }
+}
+
+impl FromStr for DecayDistance {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let units = [
+            DecayUnit::Seconds,
+            DecayUnit::Minutes,
+            DecayUnit::Hours,
+            DecayUnit::Days,
+            DecayUnit::Weeks,
+        ];
+        let (amount, unit) = units
+            .into_iter()
+            .find_map(|unit| s.strip_suffix(unit.suffix()).map(|amount| (amount, unit)))
+            .unwrap_or((s, DecayUnit::None));
+
+        match amount.parse::<f64>() {
+            Ok(amount) if amount.is_finite() && amount >= 0. => Ok(DecayDistance { amount, unit }),
+            _ => Err(format!(
+                "The distance `{s}` must be a positive number, optionally followed by one of the `s`, `m`, `h`, `d` or `w` units."
+            )),
+        }
+    }
+}
+
+impl fmt::Display for DecayDistance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.amount, self.unit.suffix())
+    }
+}
+
+/// A ranking rule scoring documents by how close the value of a numeric field is to an origin.
+///
+/// It is written `field:function(origin, scale[, offset[, decay]])`, e.g. `release_date:gauss(now, 7d)`.
+/// Documents whose value is within `offset` of the `origin` get a score of 1, and documents whose
+/// value is `offset + scale` away from the `origin` get a score of `decay` (0.5 by default).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Decay {
+    pub field: String,
+    pub function: DecayFunction,
+    pub origin: DecayOrigin,
+    pub scale: DecayDistance,
+    pub offset: Option<DecayDistance>,
+    pub decay: Option<f64>,
+}
+
+// The floats are checked to be finite when parsing the rule.
+impl Eq for Decay {}
+
+impl Decay {
+    pub const DEFAULT_DECAY: f64 = 0.5;
+
+    fn looks_like_decay(text: &str) -> bool {
+        let Some((head, _)) = text.strip_suffix(')').and_then(|text| text.rsplit_once('(')) else {
+            return false;
+        };
+        head.rsplit_once(':')
+            .is_some_and(|(_, function)| DecayFunction::from_name(function).is_some())
+    }
+
+    /// Returns the score, between 0 and 1, of a value that is `distance` away from the origin.
+    pub fn score(&self, distance: f64) -> f64 {
+        let offset = self.offset.map_or(0., |offset| offset.value());
+        let distance = (distance.abs() - offset).max(0.);
+        let scale = self.scale.value();
+        let decay = self.decay.unwrap_or(Self::DEFAULT_DECAY);
+
+        match self.function {
+            DecayFunction::Gauss => decay.powf((distance / scale).powi(2)),
+            DecayFunction::Linear => (1. - distance * (1. - decay) / scale).max(0.),
+            DecayFunction::Exp => decay.powf(distance / scale),
+        }
+    }
+}
+
+impl FromStr for Decay {
+    type Err = CriterionError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid =
+            |reason: String| CriterionError::InvalidDecay { name: text.to_string(), reason };
+        let invalid_name = || CriterionError::InvalidName { name: text.to_string() };
+
+        let (head, args) = text
+            .strip_suffix(')')
+            .and_then(|text| text.rsplit_once('('))
+            .ok_or_else(invalid_name)?;
+        let (field, function) = head.rsplit_once(':').ok_or_else(invalid_name)?;
+        let function = DecayFunction::from_name(function).ok_or_else(invalid_name)?;
+
+        if field.is_empty() {
+            return Err(invalid_name());
+        }
+        if is_reserved_keyword(field) {
+            return Err(CriterionError::ReservedName { name: field.to_string() });
+        }
+
+        let args: Vec<_> = args.split(',').map(str::trim).collect();
+        let (origin, scale, offset, decay) = match args.as_slice() {
+            [origin, scale] => (origin, scale, None, None),
+            [origin, scale, offset] => (origin, scale, Some(offset), None),
+            [origin, scale, offset, decay] => (origin, scale, Some(offset), Some(decay)),
+            _ => {
+                return Err(invalid(String::from(
+                    "Expected an origin, a scale, and optionally an offset and a decay.",
+                )))
+            }
+        };
+
+        let origin = origin.parse().map_err(invalid)?;
+        let scale: DecayDistance = scale.parse().map_err(invalid)?;
+        if scale.amount == 0. {
+            return Err(invalid(String::from("The scale must be greater than 0.")));
+        }
+        let offset = offset.map(|offset| offset.parse()).transpose().map_err(invalid)?;
+        let decay = match decay {
+            Some(decay) => match decay.parse::<f64>() {
+                Ok(decay) if decay > 0. && decay < 1. => Some(decay),
+                _ => {
+                    return Err(invalid(format!(
+                        "The decay `{decay}` must be a number between 0 and 1, both excluded."
+                    )))
+                }
+            },
+            None => None,
+        };
+
+        Ok(Decay { field: field.to_string(), function, origin, scale, offset, decay })
+    }
+}
+
+impl fmt::Display for Decay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Decay { field, function, origin, scale, offset, decay } = self;
+        write!(f, "{field}:{}({origin}, {scale}", function.name())?;
+        match (offset, decay) {
+            (Some(offset), Some(decay)) => write!(f, ", {offset}, {decay}")?,
+            (None, Some(decay)) => write!(f, ", 0, {decay}")?,
+            (Some(offset), None) => write!(f, ", {offset}")?,
+            (None, None) => (),
+        }
+        f.write_str(")")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use big_s::S;
@@ -129,6 +411,39 @@ mod tests {
             ("truc:machin:desc", Criterion::Desc(S("truc:machin"))),
             ("hello-world!:desc", Criterion::Desc(S("hello-world!"))),
             ("it's spacy over there:asc", Criterion::Asc(S("it's spacy over there"))),
+            (
+                "release_date:gauss(now, 7d)",
+                Criterion::Decay(Decay {
+                    field: S("release_date"),
+                    function: DecayFunction::Gauss,
+                    origin: DecayOrigin::Now,
+                    scale: DecayDistance { amount: 7., unit: DecayUnit::Days },
+                    offset: None,
+                    decay: None,
+                }),
+            ),
+            (
+                "price:linear(100, 20.5, 5, 0.25)",
+                Criterion::Decay(Decay {
+                    field: S("price"),
+                    function: DecayFunction::Linear,
+                    origin: DecayOrigin::Number(100.),
+                    scale: DecayDistance { amount: 20.5, unit: DecayUnit::None },
+                    offset: Some(DecayDistance { amount: 5., unit: DecayUnit::None }),
+                    decay: Some(0.25),
+                }),
+            ),
+            (
+                "a:b:exp(2024-01-01T00:00:00Z, 12h, 30m)",
+                Criterion::Decay(Decay {
+                    field: S("a:b"),
+                    function: DecayFunction::Exp,
+                    origin: DecayOrigin::Date(time::macros::datetime!(2024-01-01 00:00:00 UTC)),
+                    scale: DecayDistance { amount: 12., unit: DecayUnit::Hours },
+                    offset: Some(DecayDistance { amount: 30., unit: DecayUnit::Minutes }),
+                    decay: None,
+                }),
+            ),
         ];
 
         for (input, expected) in valid_criteria {
@@ -153,6 +468,44 @@ mod tests {
             ("price:aasc", InvalidName { name: S("price:aasc") }),
             ("price:asc and desc", InvalidName { name: S("price:asc and desc") }),
             ("price:asc:truc", InvalidName { name: S("price:asc:truc") }),
+            ("price:gaus(0, 1)", InvalidName { name: S("price:gaus(0, 1)") }),
+            (":gauss(0, 1)", InvalidName { name: S(":gauss(0, 1)") }),
+            (
+                "price:gauss(0)",
+                InvalidDecay {
+                    name: S("price:gauss(0)"),
+                    reason: S("Expected an origin, a scale, and optionally an offset and a decay."),
+                },
+            ),
+            (
+                "price:gauss(yesterday, 1)",
+                InvalidDecay {
+                    name: S("price:gauss(yesterday, 1)"),
+                    reason: S("The origin `yesterday` must be `now`, a number or an RFC 3339 date."),
+                },
+            ),
+            (
+                "price:gauss(0, 0)",
+                InvalidDecay {
+                    name: S("price:gauss(0, 0)"),
+                    reason: S("The scale must be greater than 0."),
+                },
+            ),
+            (
+                "price:gauss(0, 3y)",
+                InvalidDecay {
+                    name: S("price:gauss(0, 3y)"),
+                    reason: S("The distance `3y` must be a positive number, optionally followed by one of the `s`, `m`, `h`, `d` or `w` units."),
+                },
+            ),
+            (
+                "price:exp(0, 1, 0, 1)",
+                InvalidDecay {
+                    name: S("price:exp(0, 1, 0, 1)"),
+                    reason: S("The decay `1` must be a number between 0 and 1, both excluded."),
+                },
+            ),
+            ("_geo:gauss(0, 1)", ReservedName { name: S("_geo") }),
             ("_geo:asc", ReservedName { name: S("_geo") }),
             ("_geoDistance:asc", ReservedName { name: S("_geoDistance") }),
             ("_geoPoint:asc", ReservedNameForSort { name: S("_geoPoint") }),
@@ -164,6 +517,11 @@ mod tests {
                 "_geoBoundingBox([42, 75], [75, 59]):asc",
                 ReservedNameForFilter { name: S("_geoBoundingBox") },
             ),
+            ("_geoPolygon:asc", ReservedNameForFilter { name: S("_geoPolygon") }),
+            (
+                "_geoPolygon([42, 75], [75, 59], [12, 13]):asc",
+                ReservedNameForFilter { name: S("_geoPolygon") },
+            ),
         ];
 
         for (input, expected) in invalid_criteria {
@@ -185,4 +543,33 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn decay_criterion_display_roundtrip() {
+        for rule in [
+            "release_date:gauss(now, 7d)",
+            "price:linear(100, 20.5, 5, 0.25)",
+            "price:exp(-3.5, 1w, 0, 0.1)",
+            "published_at:exp(2024-01-01T00:00:00Z, 12h, 30m)",
+        ] {
+            let criterion: Criterion = rule.parse().unwrap();
+            assert_eq!(criterion.to_string(), rule);
+        }
+    }
+
+    #[test]
+    fn decay_score() {
+        for function in ["gauss", "linear", "exp"] {
+            let decay: Decay = format!("price:{function}(100, 10, 5)").parse().unwrap();
+            assert_eq!(decay.score(0.), 1.);
+            assert_eq!(decay.score(-5.), 1.);
+            assert!((decay.score(15.) - 0.5).abs() < f64::EPSILON, "{function}");
+            assert!((decay.score(-15.) - 0.5).abs() < f64::EPSILON, "{function}");
+            assert!(decay.score(20.) < 0.5);
+            assert!(decay.score(20.) > decay.score(40.) || decay.score(40.) == 0.);
+        }
+
+        let linear: Decay = "price:linear(0, 1d)".parse().unwrap();
+        assert_eq!(linear.score(2. * 24. * 60. * 60.), 0.);
+    }
 }
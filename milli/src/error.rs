@@ -13,7 +13,8 @@ use crate::thread_pool_no_abort::PanicCatched;
 use crate::{CriterionError, DocumentId, FieldId, Object, SortError};
 
 pub fn is_reserved_keyword(keyword: &str) -> bool {
-    ["_geo", "_geoDistance", "_geoPoint", "_geoRadius", "_geoBoundingBox"].contains(&keyword)
+    ["_geo", "_geoDistance", "_geoPoint", "_geoRadius", "_geoBoundingBox", "_geoPolygon"]
+        .contains(&keyword)
 }
 
 #[derive(Error, Debug)]
@@ -64,6 +65,8 @@ pub enum InternalError {
     AbortedIndexation,
     #[error("The matching words list contains at least one invalid member")]
     InvalidMatchingWords,
+    #[error("The geo faceted document {docid} doesn't contain any valid point")]
+    GeoFacetedDocumentWithoutPoint { docid: DocumentId },
     #[error(transparent)]
     ArroyError(#[from] arroy::Error),
     #[error(transparent)]
@@ -113,8 +116,12 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
         invalid_facets_name: BTreeSet<String>,
         valid_facets_name: BTreeSet<String>,
     },
+    #[error("Invalid histogram interval `{interval}` for the `{facet}` facet, the interval must be a positive number splitting the facet values into at most {max} buckets.")]
+    InvalidFacetHistogramInterval { facet: String, interval: f64, max: usize },
     #[error(transparent)]
     InvalidGeoField(#[from] GeoError),
+    #[error("The `_geo` field must be filterable or sortable to aggregate the documents on a geo grid. Add `_geo` to the `filterableAttributes` index setting.")]
+    InvalidGeoAggregation,
     #[error("Invalid vector dimensions: expected: `{}`, found: `{}`.", .expected, .found)]
     InvalidVectorDimensions { expected: usize, found: usize },
     #[error("The `_vectors` field in the document with id: `{document_id}` is not an object. Was expecting an object with a key for each embedder with manually provided vectors, but instead got `{value}`")]
@@ -136,6 +143,21 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
         }
     )]
     InvalidSortableAttribute { field: String, valid_fields: BTreeSet<String>, hidden_fields: bool },
+    #[error("Attribute `{}` is not sortable and thus, cannot be used in the ranking rules of a search. {}",
+        .field,
+        match .valid_fields.is_empty() {
+            true => "This index does not have configured sortable attributes.".to_string(),
+            false => format!("Available sortable attributes are: `{}{}`.",
+                    valid_fields.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", "),
+                    .hidden_fields.then_some(", <..hidden-attributes>").unwrap_or(""),
+                ),
+        }
+    )]
+    InvalidRankingRuleAttribute {
+        field: String,
+        valid_fields: BTreeSet<String>,
+        hidden_fields: bool,
+    },
     #[error("Attribute `{}` is not filterable and thus, cannot be used as distinct attribute. {}",
         .field,
         match .valid_fields.is_empty() {
@@ -172,6 +194,20 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
         valid_fields: BTreeSet<String>,
         hidden_fields: bool,
     },
+    #[error("Attribute `{}` used in the search query is not searchable. Available searchable attributes are: `{}{}`.",
+        .field,
+        .valid_fields.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", "),
+        .hidden_fields.then_some(", <..hidden-attributes>").unwrap_or(""),
+    )]
+    InvalidFieldScopedSearch { field: String, valid_fields: BTreeSet<String>, hidden_fields: bool },
+    #[error("Phrase slop `~{slop}` is too large. The slop of a phrase can be at most `{max}`.")]
+    InvalidPhraseSlop { slop: String, max: u8 },
+    #[error("The search cursor is stale: the index changed since it was returned. The search must be started again without cursor.")]
+    StaleSearchCursor,
+    #[error("The search cursor was returned by a search with a different query, filter, sort or ranking rules. The search must be started again without cursor.")]
+    SearchCursorMismatch,
+    #[error("The search cursor is invalid: it doesn't locate a document among the results of the search.")]
+    InvalidSearchCursor,
     #[error("an environment is already opened with different options")]
     InvalidLmdbOpenOptions,
     #[error("You must specify where `sort` is listed in the rankingRules setting to use the sort parameter at search time.")]
@@ -294,7 +330,7 @@ impl From<arroy::Error> for Error {
 
 #[derive(Error, Debug)]
 pub enum GeoError {
-    #[error("The `_geo` field in the document with the id: `{document_id}` is not an object. Was expecting an object with the `_geo.lat` and `_geo.lng` fields but instead got `{value}`.")]
+    #[error("The `_geo` field in the document with the id: `{document_id}` is not an object. Was expecting an object with the `_geo.lat` and `_geo.lng` fields, or an array of such objects, but instead got `{value}`.")]
     NotAnObject { document_id: Value, value: Value },
     #[error("The `_geo` field in the document with the id: `{document_id}` contains the following unexpected fields: `{value}`.")]
     UnexpectedExtraFields { document_id: Value, value: Value },
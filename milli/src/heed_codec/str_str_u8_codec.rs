@@ -60,3 +60,29 @@ impl<'a> heed::BytesEncode<'a> for UncheckedU8StrStrCodec {
         Ok(Cow::Owned(bytes))
     }
 }
+
+pub struct StrStrCodec;
+
+impl<'a> heed::BytesDecode<'a> for StrStrCodec {
+    type DItem = (&'a str, &'a str);
+
+    fn bytes_decode(bytes: &'a [u8]) -> Result<Self::DItem, BoxedError> {
+        let cstr = CStr::from_bytes_until_nul(bytes)?;
+        let s1 = cstr.to_str()?;
+        // skip '\0' byte between the two strings.
+        let s2 = str::from_utf8(&bytes[s1.len() + 1..])?;
+        Ok((s1, s2))
+    }
+}
+
+impl<'a> heed::BytesEncode<'a> for StrStrCodec {
+    type EItem = (&'a str, &'a str);
+
+    fn bytes_encode((s1, s2): &Self::EItem) -> Result<Cow<[u8]>, BoxedError> {
+        let mut bytes = Vec::with_capacity(s1.len() + s2.len() + 1);
+        bytes.extend_from_slice(s1.as_bytes());
+        bytes.push(0);
+        bytes.extend_from_slice(s2.as_bytes());
+        Ok(Cow::Owned(bytes))
+    }
+}
@@ -20,9 +20,11 @@ use crate::heed_codec::facet::{
     FieldIdCodec, OrderedF64Codec,
 };
 use crate::heed_codec::{
-    BEU16StrCodec, FstSetCodec, ScriptLanguageCodec, StrBEU16Codec, StrRefCodec,
+    BEU16StrCodec, FstSetCodec, ScriptLanguageCodec, StrBEU16Codec, StrRefCodec, StrStrCodec,
 };
+use crate::localized_attributes_rules::LocalizedAttributesRule;
 use crate::order_by_map::OrderByMap;
+use crate::pinned_results::PinnedResultsRule;
 use crate::proximity::ProximityPrecision;
 use crate::vector::parsed_vectors::RESERVED_VECTORS_FIELD_NAME;
 use crate::vector::{Embedding, EmbeddingConfig};
@@ -73,6 +75,9 @@ pub mod main_key {
     pub const PROXIMITY_PRECISION: &str = "proximity-precision";
     pub const EMBEDDING_CONFIGS: &str = "embedding_configs";
     pub const SEARCH_CUTOFF: &str = "search_cutoff";
+    pub const LOCALIZED_ATTRIBUTES_RULES: &str = "localized_attributes_rules";
+    pub const INFIX_SEARCHABLE_ATTRIBUTES: &str = "infix-searchable-attributes";
+    pub const PINNED_RESULTS: &str = "pinned-results";
 }
 
 pub mod db_name {
@@ -88,6 +93,7 @@ pub mod db_name {
     pub const WORD_FIELD_ID_DOCIDS: &str = "word-field-id-docids";
     pub const WORD_PREFIX_POSITION_DOCIDS: &str = "word-prefix-position-docids";
     pub const WORD_PREFIX_FIELD_ID_DOCIDS: &str = "word-prefix-field-id-docids";
+    pub const NGRAM_WORD_DOCIDS: &str = "ngram-word-docids";
     pub const FIELD_ID_WORD_COUNT_DOCIDS: &str = "field-id-word-count-docids";
     pub const FACET_ID_F64_DOCIDS: &str = "facet-id-f64-docids";
     pub const FACET_ID_EXISTS_DOCIDS: &str = "facet-id-exists-docids";
@@ -142,6 +148,10 @@ pub struct Index {
     /// Maps the word prefix and a field id with all the docids where the prefix appears inside the field
     pub word_prefix_fid_docids: Database<StrBEU16Codec, CboRoaringBitmapCodec>,
 
+    /// Maps a character trigram and a word containing it with the docids where this word
+    /// appears in one of the infix searchable attributes.
+    pub ngram_word_docids: Database<StrStrCodec, CboRoaringBitmapCodec>,
+
     /// Maps the script and language with all the docids that corresponds to it.
     pub script_language_docids: Database<ScriptLanguageCodec, RoaringBitmapCodec>,
 
@@ -184,7 +194,7 @@ impl Index {
     ) -> Result<Index> {
         use db_name::*;
 
-        options.max_dbs(25);
+        options.max_dbs(26);
 
         let env = unsafe { options.open(path) }?;
         let mut wtxn = env.write_txn()?;
@@ -208,6 +218,7 @@ impl Index {
             env.create_database(&mut wtxn, Some(WORD_PREFIX_POSITION_DOCIDS))?;
         let word_prefix_fid_docids =
             env.create_database(&mut wtxn, Some(WORD_PREFIX_FIELD_ID_DOCIDS))?;
+        let ngram_word_docids = env.create_database(&mut wtxn, Some(NGRAM_WORD_DOCIDS))?;
         let facet_id_f64_docids = env.create_database(&mut wtxn, Some(FACET_ID_F64_DOCIDS))?;
         let facet_id_string_docids =
             env.create_database(&mut wtxn, Some(FACET_ID_STRING_DOCIDS))?;
@@ -248,6 +259,7 @@ impl Index {
             word_fid_docids,
             word_prefix_position_docids,
             word_prefix_fid_docids,
+            ngram_word_docids,
             field_id_word_count_docids,
             facet_id_f64_docids,
             facet_id_string_docids,
@@ -311,6 +323,13 @@ impl Index {
         Ok(self.env.non_free_pages_size()?)
     }
 
+    /// Returns the size taken by the pages of the n-gram database used for infix search, in bytes.
+    pub fn ngram_word_docids_size(&self, rtxn: &RoTxn) -> Result<u64> {
+        let stat = self.ngram_word_docids.stat(rtxn)?;
+        let pages = stat.branch_pages + stat.leaf_pages + stat.overflow_pages;
+        Ok(pages as u64 * stat.page_size as u64)
+    }
+
     /// Returns the real size used by the index.
     pub fn on_disk_size(&self) -> Result<u64> {
         Ok(self.env.real_disk_size()?)
@@ -902,7 +921,7 @@ impl Index {
 
     /// Returns the user defined faceted fields names.
     ///
-    /// The user faceted fields are the union of all the filterable, sortable, distinct, and Asc/Desc fields.
+    /// The user faceted fields are the union of all the filterable, sortable, distinct, Asc/Desc and Decay fields.
     pub fn user_defined_faceted_fields(&self, rtxn: &RoTxn) -> Result<HashSet<String>> {
         let filterable_fields = self.filterable_fields(rtxn)?;
         let sortable_fields = self.sortable_fields(rtxn)?;
@@ -910,6 +929,7 @@ impl Index {
         let asc_desc_fields =
             self.criteria(rtxn)?.into_iter().filter_map(|criterion| match criterion {
                 Criterion::Asc(field) | Criterion::Desc(field) => Some(field),
+                Criterion::Decay(decay) => Some(decay.field),
                 _otherwise => None,
             });
 
@@ -1463,6 +1483,46 @@ impl Index {
         self.main.remap_key_type::<Str>().delete(txn, main_key::EXACT_ATTRIBUTES)
     }
 
+    /// Returns the infix searchable attributes: attributes whose words can be found from any
+    /// part of them.
+    pub fn infix_searchable_attributes<'t>(&self, txn: &'t RoTxn) -> Result<Vec<&'t str>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeBincode<Vec<&str>>>()
+            .get(txn, main_key::INFIX_SEARCHABLE_ATTRIBUTES)?
+            .unwrap_or_default())
+    }
+
+    /// Returns the field ids of the infix searchable attributes and of their nested fields.
+    pub fn infix_searchable_attributes_ids(&self, txn: &RoTxn) -> Result<HashSet<FieldId>> {
+        let attrs = self.infix_searchable_attributes(txn)?;
+        let fid_map = self.fields_ids_map(txn)?;
+        Ok(fid_map
+            .iter()
+            .filter(|(_fid, field)| crate::is_faceted(field, &attrs))
+            .map(|(fid, _field)| fid)
+            .collect())
+    }
+
+    /// Writes the infix searchable attributes to the database.
+    pub(crate) fn put_infix_searchable_attributes(
+        &self,
+        txn: &mut RwTxn,
+        attrs: &[&str],
+    ) -> Result<()> {
+        self.main.remap_types::<Str, SerdeBincode<&[&str]>>().put(
+            txn,
+            main_key::INFIX_SEARCHABLE_ATTRIBUTES,
+            &attrs,
+        )?;
+        Ok(())
+    }
+
+    /// Clears the infix searchable attributes from the store.
+    pub(crate) fn delete_infix_searchable_attributes(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::INFIX_SEARCHABLE_ATTRIBUTES)
+    }
+
     pub fn max_values_per_facet(&self, txn: &RoTxn) -> heed::Result<Option<u64>> {
         self.main.remap_types::<Str, BEU64>().get(txn, main_key::MAX_VALUES_PER_FACET)
     }
@@ -1629,6 +1689,58 @@ impl Index {
         self.main.remap_key_type::<Str>().delete(wtxn, main_key::SEARCH_CUTOFF)
     }
 
+    pub fn localized_attributes_rules(
+        &self,
+        rtxn: &RoTxn<'_>,
+    ) -> heed::Result<Option<Vec<LocalizedAttributesRule>>> {
+        self.main
+            .remap_types::<Str, SerdeJson<Vec<LocalizedAttributesRule>>>()
+            .get(rtxn, main_key::LOCALIZED_ATTRIBUTES_RULES)
+    }
+
+    pub(crate) fn put_localized_attributes_rules(
+        &self,
+        txn: &mut RwTxn<'_>,
+        val: Vec<LocalizedAttributesRule>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<Vec<LocalizedAttributesRule>>>().put(
+            txn,
+            main_key::LOCALIZED_ATTRIBUTES_RULES,
+            &val,
+        )
+    }
+
+    pub(crate) fn delete_localized_attributes_rules(
+        &self,
+        txn: &mut RwTxn<'_>,
+    ) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::LOCALIZED_ATTRIBUTES_RULES)
+    }
+
+    pub fn pinned_results(&self, rtxn: &RoTxn<'_>) -> heed::Result<Vec<PinnedResultsRule>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<Vec<PinnedResultsRule>>>()
+            .get(rtxn, main_key::PINNED_RESULTS)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_pinned_results(
+        &self,
+        txn: &mut RwTxn<'_>,
+        val: &[PinnedResultsRule],
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<&[PinnedResultsRule]>>().put(
+            txn,
+            main_key::PINNED_RESULTS,
+            &val,
+        )
+    }
+
+    pub(crate) fn delete_pinned_results(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::PINNED_RESULTS)
+    }
+
     pub fn embeddings(
         &self,
         rtxn: &RoTxn<'_>,
@@ -2111,6 +2223,162 @@ pub(crate) mod tests {
         );
     }
 
+    #[test]
+    fn test_basic_geo_polygon() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("_geo") });
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 0, "_geo": { "lat": "0", "lng": "0" } },
+                { "id": 1, "_geo": { "lat": 0, "lng": "-175" } },
+                { "id": 2, "_geo": { "lat": "0", "lng": 175 } },
+                { "id": 3, "_geo": { "lat": 85, "lng": 0 } },
+                { "id": 4, "_geo": { "lat": "-85", "lng": "0" } },
+                { "id": 5, "_geo": { "lat": 5, "lng": 7 } },
+                { "id": 6, "_geo": { "lat": 5, "lng": -7 } },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let mut search = index.search(&rtxn);
+
+        // match the documents in a triangle
+        let search_result = search
+            .filter(
+                Filter::from_str("_geoPolygon([10, -10], [10, 10], [-10, 0])").unwrap().unwrap(),
+            )
+            .execute()
+            .unwrap();
+        insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[0, 5, 6]>");
+
+        // the documents in the bounding box of a concave polygon but outside of it are not matched
+        let search_result = search
+            .filter(
+                Filter::from_str("_geoPolygon([10, -10], [-5, 0], [10, 10], [-10, 0])")
+                    .unwrap()
+                    .unwrap(),
+            )
+            .execute()
+            .unwrap();
+        insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[5, 6]>");
+
+        // the documents on the edges and the vertices are matched
+        let search_result = search
+            .filter(
+                Filter::from_str("_geoPolygon([0, 0], [0, 10], [-10, 10], [-10, 0])")
+                    .unwrap()
+                    .unwrap(),
+            )
+            .execute()
+            .unwrap();
+        insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[0]>");
+        let search_result = search
+            .filter(
+                Filter::from_str("_geoPolygon([85, 0], [0, 10], [-85, 0], [0, -10])")
+                    .unwrap()
+                    .unwrap(),
+            )
+            .execute()
+            .unwrap();
+        insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[0, 3, 4, 5, 6]>");
+
+        // cross the antimeridian
+        let search_result = search
+            .filter(
+                Filter::from_str("_geoPolygon([10, 170], [10, -170], [-10, -170], [-10, 170])")
+                    .unwrap()
+                    .unwrap(),
+            )
+            .execute()
+            .unwrap();
+        insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[1, 2]>");
+
+        // the polygons going around a pole are rejected
+        let error = search
+            .filter(
+                Filter::from_str("_geoPolygon([80, 0], [80, 120], [80, -120])").unwrap().unwrap(),
+            )
+            .execute()
+            .unwrap_err();
+        insta::assert_display_snapshot!(
+            error,
+            @r###"
+        The `_geoPolygon` filter goes around a pole. Split it into several polygons that do not contain a pole.
+        14:16 _geoPolygon([80, 0], [80, 120], [80, -120])
+        "###
+        );
+    }
+
+    #[test]
+    fn test_geo_filters_with_several_points() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("_geo") });
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 0, "_geo": [{ "lat": 0, "lng": 0 }, { "lat": 40, "lng": 40 }] },
+                { "id": 1, "_geo": [{ "lat": "20", "lng": "-20" }] },
+                { "id": 2, "_geo": [{ "lat": -40, "lng": 40 }, { "lat": 0, "lng": 178 }] },
+                { "id": 3, "_geo": { "lat": 40, "lng": 0 } },
+                { "id": 4, "_geo": [] },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let mut search = index.search(&rtxn);
+
+        // a document is matched as soon as one of its points is matched
+        let search_result = search
+            .filter(Filter::from_str("_geoRadius(40, 40, 1000)").unwrap().unwrap())
+            .execute()
+            .unwrap();
+        insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[0]>");
+        let search_result = search
+            .filter(Filter::from_str("_geoRadius(0, 0, 3200000)").unwrap().unwrap())
+            .execute()
+            .unwrap();
+        insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[0, 1]>");
+
+        let search_result = search
+            .filter(Filter::from_str("_geoBoundingBox([45, 45], [35, 35])").unwrap().unwrap())
+            .execute()
+            .unwrap();
+        insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[0]>");
+        let search_result = search
+            .filter(Filter::from_str("_geoBoundingBox([10, -170], [-10, 170])").unwrap().unwrap())
+            .execute()
+            .unwrap();
+        insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[2]>");
+
+        let search_result = search
+            .filter(
+                Filter::from_str("_geoPolygon([-30, 30], [-30, 50], [-50, 50], [-50, 30])")
+                    .unwrap()
+                    .unwrap(),
+            )
+            .execute()
+            .unwrap();
+        insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[2]>");
+        let search_result = search
+            .filter(
+                Filter::from_str("_geoPolygon([50, -10], [50, 50], [30, 50], [30, -10])")
+                    .unwrap()
+                    .unwrap(),
+            )
+            .execute()
+            .unwrap();
+        insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[0, 3]>");
+    }
+
     #[test]
     fn replace_documents_external_ids_and_soft_deletion_check() {
         use big_s::S;
@@ -16,7 +16,9 @@ pub mod facet;
 mod fields_ids_map;
 pub mod heed_codec;
 pub mod index;
+pub mod localized_attributes_rules;
 pub mod order_by_map;
+pub mod pinned_results;
 pub mod prompt;
 pub mod proximity;
 pub mod score_details;
@@ -40,15 +42,20 @@ pub use filter_parser::{Condition, FilterCondition, Span, Token};
 use fxhash::{FxHasher32, FxHasher64};
 pub use grenad::CompressionType;
 pub use search::new::{
-    execute_search, filtered_universe, DefaultSearchLogger, GeoSortStrategy, SearchContext,
-    SearchLogger, VisualSearchLogger,
+    execute_search, filtered_universe, suggest_query, BucketStep, DefaultSearchLogger,
+    ExplainSearchLogger, ExplainedBucket, ExplainedDocument, ExplainedQueryGraph,
+    ExplainedQueryNode, ExplainedTerm, GeoSortStrategy, SearchContext, SearchExplanation,
+    SearchLogger, TermDerivations, VisualSearchLogger,
 };
 use serde_json::Value;
 pub use thread_pool_no_abort::{PanicCatched, ThreadPoolNoAbort, ThreadPoolNoAbortBuilder};
 pub use {charabia as tokenizer, heed};
 
 pub use self::asc_desc::{AscDesc, AscDescError, Member, SortError};
-pub use self::criterion::{default_criteria, Criterion, CriterionError};
+pub use self::criterion::{
+    default_criteria, Criterion, CriterionError, Decay, DecayDistance, DecayFunction, DecayOrigin,
+    DecayUnit,
+};
 pub use self::error::{
     Error, FieldIdMapMissingEntry, InternalError, SerializationError, UserError,
 };
@@ -62,11 +69,23 @@ pub use self::heed_codec::{
     UncheckedU8StrStrCodec,
 };
 pub use self::index::Index;
+pub use self::localized_attributes_rules::LocalizedAttributesRule;
+pub use self::search::analyze::{
+    Analysis, Analyze, AnalyzedQueryTerm, AnalyzedToken, QueryTermDerivations,
+};
+pub use self::search::autocomplete::{
+    Autocomplete, Completion, Completions, MAX_AUTOCOMPLETE_LIMIT,
+};
 pub use self::search::facet::{FacetValueHit, SearchForFacetValues};
+pub use self::search::geo_aggregation::{
+    GeoAggregation, GeoBoundingBox, GeoCell, GeoGrid, MAX_GEOHASH_PRECISION, MAX_TILE_ZOOM,
+};
 pub use self::search::similar::Similar;
 pub use self::search::{
-    FacetDistribution, Filter, FormatOptions, MatchBounds, MatcherBuilder, MatchingWords, OrderBy,
-    Search, SearchResult, SemanticSearch, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
+    CollapsedDocuments, FacetDistribution, FacetRange, FacetRangeCount, FacetRanges,
+    FacetStatsAggregations, Filter, FormatOptions, InvalidSearchCursor, MatchBounds,
+    MatcherBuilder, MatchingWords, OrderBy, Search, SearchCursor, SearchCursorToken, SearchResult,
+    SemanticSearch, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
 };
 
 pub type Result<T> = std::result::Result<T, error::Error>;
@@ -109,8 +128,15 @@ pub const MAX_FACET_VALUE_LENGTH: usize = MAX_LMDB_KEY_LENGTH - 32;
 /// The maximum length a word can be
 pub const MAX_WORD_LENGTH: usize = MAX_LMDB_KEY_LENGTH / 2;
 
+/// The number of characters of the n-grams indexed for infix search
+pub const INFIX_NGRAM_LENGTH: usize = 3;
+
 pub const MAX_POSITION_PER_ATTRIBUTE: u32 = u16::MAX as u32 + 1;
 
+/// The highest weight a search can give to an attribute, the attributes without a weight
+/// are ranked after the weighted ones, in the room left below `Weight::MAX`.
+pub const MAX_ATTRIBUTE_WEIGHT: Weight = 1000;
+
 #[derive(Clone)]
 pub struct TimeBudget {
     started_at: std::time::Instant,
@@ -362,6 +388,31 @@ pub fn normalize_facet(original: &str) -> String {
     CompatibilityDecompositionNormalizer.normalize_str(original.trim()).to_lowercase()
 }
 
+/// Returns the unix timestamp, in seconds, of an RFC 3339 date-time (`2024-01-01T10:00:00Z`)
+/// or full-date (`2024-01-01`, at midnight UTC).
+///
+/// Such dates are faceted as numbers so that they are filtered and sorted chronologically.
+pub fn date_to_timestamp(value: &str) -> Option<f64> {
+    use time::format_description::well_known::Rfc3339;
+    use time::macros::format_description;
+    use time::{Date, OffsetDateTime};
+
+    let value = value.trim();
+    // cheap check to avoid trying to parse every string facet value as a date
+    if value.len() < 10 || value.as_bytes()[4] != b'-' {
+        return None;
+    }
+
+    let datetime = match OffsetDateTime::parse(value, &Rfc3339) {
+        Ok(datetime) => datetime,
+        Err(_) => Date::parse(value, format_description!("[year]-[month]-[day]"))
+            .ok()?
+            .midnight()
+            .assume_utc(),
+    };
+    Some(datetime.unix_timestamp() as f64 + datetime.nanosecond() as f64 / 1_000_000_000.)
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::json;
@@ -396,6 +447,21 @@ mod tests {
         assert_eq!(string, "name: John Doe. . 43. hello. I. am. fine. . ");
     }
 
+    #[test]
+    fn date_to_timestamp_formats() {
+        assert_eq!(date_to_timestamp("2024-01-01"), Some(1704067200.));
+        assert_eq!(date_to_timestamp("2024-01-01T00:00:00Z"), Some(1704067200.));
+        assert_eq!(date_to_timestamp("2024-01-01T02:00:00+02:00"), Some(1704067200.));
+        assert_eq!(date_to_timestamp("2024-01-01T00:00:00.5Z"), Some(1704067200.5));
+        assert_eq!(date_to_timestamp("1969-12-31T23:59:59Z"), Some(-1.));
+
+        assert_eq!(date_to_timestamp("2024"), None);
+        assert_eq!(date_to_timestamp("2024-13-01"), None);
+        assert_eq!(date_to_timestamp("2024-01-01T00:00:00"), None);
+        assert_eq!(date_to_timestamp("hello world"), None);
+        assert_eq!(date_to_timestamp("01/01/2024"), None);
+    }
+
     #[test]
     fn test_relative_position_conversion() {
         assert_eq!((0x0000, 0x0000), relative_from_absolute_position(0x00000000));
@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+
+use charabia::{Language, Script};
+use serde::{Deserialize, Serialize};
+
+use crate::fields_ids_map::FieldsIdsMap;
+use crate::{is_faceted_by, FieldId};
+
+/// A rule that defines which locales are supported for a given attribute.
+///
+/// The attribute patterns are matched against the attribute names using the `*` wildcard,
+/// which can be used at the start, at the end, or both, of a pattern.
+/// A pattern without wildcard also matches the fields nested under the attribute.
+///
+/// The locales are used to constrain the language detection of the tokenizer,
+/// an empty list of locales doesn't constrain anything.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalizedAttributesRule {
+    pub attribute_patterns: Vec<String>,
+    #[serde(with = "languages")]
+    pub locales: Vec<Language>,
+}
+
+impl LocalizedAttributesRule {
+    pub fn new(attribute_patterns: Vec<String>, locales: Vec<Language>) -> Self {
+        Self { attribute_patterns, locales }
+    }
+
+    pub fn match_str(&self, attribute: &str) -> bool {
+        self.attribute_patterns.iter().any(|pattern| match_pattern(pattern, attribute))
+    }
+
+    pub fn locales(&self) -> &[Language] {
+        &self.locales
+    }
+}
+
+fn match_pattern(pattern: &str, attribute: &str) -> bool {
+    if pattern == "*" {
+        return true;
+    }
+
+    match (pattern.strip_prefix('*'), pattern.strip_suffix('*')) {
+        (Some(_), Some(_)) => attribute.contains(&pattern[1..pattern.len() - 1]),
+        (Some(suffix), None) => attribute.ends_with(suffix),
+        (None, Some(prefix)) => attribute.starts_with(prefix),
+        (None, None) => is_faceted_by(attribute, pattern),
+    }
+}
+
+/// The locales of the fields of an index, computed from the [`LocalizedAttributesRule`]s.
+///
+/// The first rule matching a field applies, fields without rules are not localized.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LocalizedFieldIds {
+    field_id_to_locales: HashMap<FieldId, Vec<Language>>,
+}
+
+impl LocalizedFieldIds {
+    pub fn new<I: Iterator<Item = FieldId>>(
+        rules: &Option<Vec<LocalizedAttributesRule>>,
+        fields_ids_map: &FieldsIdsMap,
+        fields_ids: I,
+    ) -> Self {
+        let mut field_id_to_locales = HashMap::new();
+
+        if let Some(rules) = rules {
+            for field_id in fields_ids {
+                let Some(field_name) = fields_ids_map.name(field_id) else { continue };
+                let Some(rule) = rules.iter().find(|rule| rule.match_str(field_name)) else {
+                    continue;
+                };
+                if !rule.locales.is_empty() {
+                    field_id_to_locales.insert(field_id, rule.locales.clone());
+                }
+            }
+        }
+
+        Self { field_id_to_locales }
+    }
+
+    pub fn locales(&self, field_id: FieldId) -> Option<&[Language]> {
+        self.field_id_to_locales.get(&field_id).map(Vec::as_slice)
+    }
+
+    /// Groups the localized fields by locales and returns, for each group,
+    /// the allow list to give to the tokenizer.
+    pub fn allow_lists(&self) -> Vec<(Vec<FieldId>, HashMap<Script, Vec<Language>>)> {
+        let mut fields_by_locales: HashMap<&[Language], Vec<FieldId>> = HashMap::new();
+        for (field_id, locales) in &self.field_id_to_locales {
+            fields_by_locales.entry(locales.as_slice()).or_default().push(*field_id);
+        }
+
+        fields_by_locales
+            .into_iter()
+            .map(|(locales, field_ids)| (field_ids, script_language_allow_list(locales)))
+            .collect()
+    }
+}
+
+/// Builds the tokenizer allow list restricting the language detection to the given locales.
+///
+/// Each locale is registered under the script it is written in,
+/// the scripts without any locale are detected as usual.
+pub fn script_language_allow_list(locales: &[Language]) -> HashMap<Script, Vec<Language>> {
+    let mut allow_list: HashMap<Script, Vec<Language>> = HashMap::new();
+    for &locale in locales {
+        let languages = allow_list.entry(language_script(locale)).or_default();
+        if !languages.contains(&locale) {
+            languages.push(locale);
+        }
+    }
+    allow_list
+}
+
+/// The script a language is written in, the languages using the latin alphabet are the default.
+fn language_script(language: Language) -> Script {
+    match language {
+        Language::Cmn | Language::Jpn => Script::Cj,
+        Language::Kor => Script::Hangul,
+        Language::Tha => Script::Thai,
+        Language::Heb | Language::Yid => Script::Hebrew,
+        Language::Ara | Language::Urd | Language::Pes => Script::Arabic,
+        Language::Rus
+        | Language::Ukr
+        | Language::Bel
+        | Language::Bul
+        | Language::Mkd
+        | Language::Srp => Script::Cyrillic,
+        Language::Ell => Script::Greek,
+        Language::Khm => Script::Khmer,
+        Language::Hin | Language::Mar | Language::Nep => Script::Devanagari,
+        Language::Kat => Script::Georgian,
+        Language::Hye => Script::Armenian,
+        Language::Ben => Script::Bengali,
+        Language::Tam => Script::Tamil,
+        Language::Tel => Script::Telugu,
+        Language::Kan => Script::Kannada,
+        Language::Mal => Script::Malayalam,
+        Language::Guj => Script::Gujarati,
+        Language::Pan => Script::Gurmukhi,
+        Language::Ori => Script::Oriya,
+        Language::Mya => Script::Myanmar,
+        Language::Sin => Script::Sinhala,
+        Language::Amh => Script::Ethiopic,
+        _ => Script::Latin,
+    }
+}
+
+/// (De)serializes the languages by their ISO 639-3 code.
+mod languages {
+    use charabia::Language;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(languages: &[Language], s: S) -> Result<S::Ok, S::Error> {
+        s.collect_seq(languages.iter().map(|language| language.name()))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<Language>, D::Error> {
+        let names: Vec<String> = Deserialize::deserialize(d)?;
+        Ok(names.iter().map(|name| Language::from_name(name.as_str())).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn match_attribute_patterns() {
+        assert!(match_pattern("*", "title"));
+        assert!(match_pattern("title", "title"));
+        assert!(match_pattern("title", "title.ja"));
+        assert!(!match_pattern("title", "titles"));
+        assert!(match_pattern("title_*", "title_ja"));
+        assert!(!match_pattern("title_*", "subtitle_ja"));
+        assert!(match_pattern("*_ja", "description_ja"));
+        assert!(!match_pattern("*_ja", "description_th"));
+        assert!(match_pattern("*desc*", "long_description_ja"));
+    }
+
+    #[test]
+    fn allow_list_groups_locales_by_script() {
+        let allow_list = script_language_allow_list(&[Language::Jpn, Language::Eng, Language::Cmn]);
+        assert_eq!(allow_list.get(&Script::Cj), Some(&vec![Language::Jpn, Language::Cmn]));
+        assert_eq!(allow_list.get(&Script::Latin), Some(&vec![Language::Eng]));
+        assert_eq!(allow_list.get(&Script::Thai), None);
+    }
+}
@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+
+/// A curation rule placing documents at fixed positions of the results of the queries it matches.
+///
+/// The pinned documents are placed at their positions as long as they are part of the filtered
+/// documents, the ranked documents filling the other positions. The hidden documents are removed
+/// from the results.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PinnedResultsRule {
+    pub query: String,
+    #[serde(default)]
+    pub matching: QueryMatching,
+    /// The documents to pin along with their positions.
+    pub pinned: Vec<PinnedDocument>,
+    /// The external ids of the documents to hide.
+    #[serde(default)]
+    pub hidden: Vec<String>,
+}
+
+/// A document placed at a fixed position of the results by a [`PinnedResultsRule`].
+///
+/// When several documents are pinned at the same position, the first listed takes it and the
+/// next ones are shifted after it. When there are not enough ranked documents to reach its
+/// position, the document directly follows the last ranked one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PinnedDocument {
+    /// The external id of the document.
+    pub id: String,
+    /// The position of the document in the results, starting at `0`.
+    pub position: usize,
+}
+
+/// How the query of a [`PinnedResultsRule`] is compared to the query of a search request.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum QueryMatching {
+    /// The queries must be strictly equal.
+    Exact,
+    /// The queries must be equal once trimmed, lowercased and with their whitespaces collapsed.
+    #[default]
+    Normalized,
+}
+
+impl PinnedResultsRule {
+    pub fn matches(&self, query: &str) -> bool {
+        match self.matching {
+            QueryMatching::Exact => self.query == query,
+            QueryMatching::Normalized => normalize_query(&self.query) == normalize_query(query),
+        }
+    }
+}
+
+fn normalize_query(query: &str) -> String {
+    crate::normalize_facet(&query.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use big_s::S;
+
+    use super::*;
+
+    #[test]
+    fn rule_matches_query() {
+        let rule = |matching| PinnedResultsRule {
+            query: S("Christmas  Gifts"),
+            matching,
+            pinned: vec![PinnedDocument { id: S("1"), position: 0 }],
+            hidden: vec![],
+        };
+
+        let exact = rule(QueryMatching::Exact);
+        assert!(exact.matches("Christmas  Gifts"));
+        assert!(!exact.matches("christmas gifts"));
+
+        let normalized = rule(QueryMatching::Normalized);
+        assert!(normalized.matches("Christmas  Gifts"));
+        assert!(normalized.matches("  christmas gifts "));
+        assert!(normalized.matches("CHRISTMAS\tGIFTS"));
+        assert!(!normalized.matches("christmas gift"));
+        assert!(!normalized.matches("christmas"));
+    }
+}
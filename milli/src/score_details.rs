@@ -17,6 +17,9 @@ pub enum ScoreDetails {
     Sort(Sort),
     Vector(Vector),
     GeoSort(GeoSort),
+    Decay(Decay),
+    /// Returned for the documents placed in the results by a pinned results rule
+    Pinned(Pinned),
 
     /// Returned when we don't have the time to finish applying all the subsequent ranking-rules
     Skipped,
@@ -53,6 +56,8 @@ impl ScoreDetails {
             ScoreDetails::Sort(_) => None,
             ScoreDetails::GeoSort(_) => None,
             ScoreDetails::Vector(_) => None,
+            ScoreDetails::Decay(_) => None,
+            ScoreDetails::Pinned(_) => Some(Rank { rank: 1, max_rank: 1 }),
             ScoreDetails::Skipped => Some(Rank { rank: 0, max_rank: 1 }),
         }
     }
@@ -101,6 +106,8 @@ impl ScoreDetails {
             ScoreDetails::Vector(vector) => {
                 RankOrValue::Score(vector.similarity.as_ref().map(|s| *s as f64).unwrap_or(0.0f64))
             }
+            ScoreDetails::Decay(decay) => RankOrValue::Score(decay.score),
+            ScoreDetails::Pinned(_) => RankOrValue::Rank(Rank { rank: 1, max_rank: 1 }),
             ScoreDetails::Skipped => RankOrValue::Rank(Rank { rank: 0, max_rank: 1 }),
         }
     }
@@ -258,6 +265,33 @@ impl ScoreDetails {
                     details_map.insert("vectorSort".into(), details);
                     order += 1;
                 }
+                ScoreDetails::Decay(details) => {
+                    let rule = if details.redacted {
+                        format!("<hidden-rule-{order}>")
+                    } else {
+                        details.rule.clone()
+                    };
+                    let value = match details.value {
+                        _ if details.redacted => "<hidden>".into(),
+                        Some(value) => serde_json::json!(value),
+                        None => serde_json::Value::Null,
+                    };
+                    let decay_details = serde_json::json!({
+                        "order": order,
+                        "value": value,
+                        "score": details.score,
+                    });
+                    details_map.insert(rule, decay_details);
+                    order += 1;
+                }
+                ScoreDetails::Pinned(details) => {
+                    let pinned_details = serde_json::json!({
+                        "order": order,
+                        "position": details.position,
+                    });
+                    details_map.insert("pinned".into(), pinned_details);
+                    order += 1;
+                }
                 ScoreDetails::Skipped => {
                     details_map
                         .insert("skipped".to_string(), serde_json::json!({ "order": order }));
@@ -494,6 +528,22 @@ pub struct Vector {
     pub similarity: Option<f32>,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decay {
+    /// The ranking rule as written in the settings, e.g. `release_date:gauss(now, 7d)`.
+    pub rule: String,
+    pub redacted: bool,
+    /// The value closest to the origin among the documents sharing this score.
+    pub value: Option<f64>,
+    pub score: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pinned {
+    /// The position of the document in the results, before pagination.
+    pub position: usize,
+}
+
 impl GeoSort {
     pub fn distance(&self) -> Option<f64> {
         self.value.map(|value| distance_between_two_points(&self.target_point, &value))
@@ -0,0 +1,261 @@
+use std::collections::HashMap;
+
+use charabia::{Language, SeparatorKind, TokenKind, TokenizerBuilder};
+use serde::Serialize;
+
+use crate::localized_attributes_rules::script_language_allow_list;
+use crate::search::new::analyze_query;
+use crate::search::DEFAULT_WORDS_LIMIT;
+use crate::update::process_tokens;
+use crate::{Index, Result, SearchContext};
+
+/// Describes how a text is tokenized by an index, and the query terms
+/// a search for this text would be made of.
+pub struct Analyze<'a> {
+    text: String,
+    /// The attribute the text is tokenized for, which may restrict its locales
+    attribute: Option<String>,
+    rtxn: &'a heed::RoTxn<'a>,
+    index: &'a Index,
+}
+
+impl<'a> Analyze<'a> {
+    pub fn new(text: String, index: &'a Index, rtxn: &'a heed::RoTxn<'a>) -> Self {
+        Self { text, attribute: None, rtxn, index }
+    }
+
+    pub fn attribute(&mut self, attribute: String) -> &mut Self {
+        self.attribute = Some(attribute);
+        self
+    }
+
+    pub fn execute(&self) -> Result<Analysis> {
+        let locales = self.locales()?;
+        let tokens = self.tokens(locales.as_deref().unwrap_or_default())?;
+        let mut ctx = SearchContext::new(self.index, self.rtxn)?;
+        let query_terms =
+            analyze_query(&mut ctx, &self.text, locales.as_ref(), DEFAULT_WORDS_LIMIT)?;
+        Ok(Analysis { tokens, query_terms })
+    }
+
+    /// The locales of the first rule matching the attribute, if any.
+    fn locales(&self) -> Result<Option<Vec<Language>>> {
+        let Some(attribute) = &self.attribute else { return Ok(None) };
+        Ok(self
+            .index
+            .localized_attributes_rules(self.rtxn)?
+            .unwrap_or_default()
+            .into_iter()
+            .find(|rule| rule.match_str(attribute))
+            .map(|rule| rule.locales))
+    }
+
+    /// Tokenizes the text the same way the documents are when they are indexed.
+    fn tokens(&self, locales: &[Language]) -> Result<Vec<AnalyzedToken>> {
+        let mut builder = TokenizerBuilder::new();
+        let stop_words = self.index.stop_words(self.rtxn)?;
+        if let Some(ref stop_words) = stop_words {
+            builder.stop_words(stop_words);
+        }
+        let separators = self.index.allowed_separators(self.rtxn)?;
+        let separators: Option<Vec<_>> =
+            separators.as_ref().map(|x| x.iter().map(String::as_str).collect());
+        if let Some(ref separators) = separators {
+            builder.separators(separators);
+        }
+        let dictionary = self.index.dictionary(self.rtxn)?;
+        let dictionary: Option<Vec<_>> =
+            dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
+        if let Some(ref dictionary) = dictionary {
+            builder.words_dict(dictionary);
+        }
+        let allow_list = script_language_allow_list(locales);
+        if !allow_list.is_empty() {
+            builder.allow_list(&allow_list);
+        }
+
+        let tokenizer = builder.build();
+        let tokens: Vec<_> = tokenizer.tokenize(&self.text).collect();
+
+        // the words are given the positions they are indexed at, keyed by their first char
+        let positions: HashMap<_, _> = process_tokens(tokens.iter().cloned())
+            .map(|(position, token)| (token.char_start, position))
+            .collect();
+
+        Ok(tokens
+            .into_iter()
+            .map(|token| {
+                let kind = match token.kind {
+                    TokenKind::Word | TokenKind::StopWord if token.lemma().is_empty() => {
+                        TokenKind::Unknown
+                    }
+                    kind => kind,
+                };
+                AnalyzedToken {
+                    lemma: token.lemma().to_owned(),
+                    original: self.text[token.byte_start..token.byte_end].to_owned(),
+                    kind: match kind {
+                        TokenKind::Word => "word",
+                        TokenKind::StopWord => "stopWord",
+                        TokenKind::Separator(SeparatorKind::Hard) => "hardSeparator",
+                        TokenKind::Separator(SeparatorKind::Soft) => "softSeparator",
+                        TokenKind::Unknown => "unknown",
+                    },
+                    char_start: token.char_start,
+                    char_end: token.char_end,
+                    script: token.script.name().to_owned(),
+                    language: token.language.map(|language| language.name().to_owned()),
+                    // the stop words are not indexed
+                    position: positions.get(&token.char_start).copied(),
+                }
+            })
+            .collect())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Analysis {
+    pub tokens: Vec<AnalyzedToken>,
+    pub query_terms: Vec<AnalyzedQueryTerm>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyzedToken {
+    /// The normalized token
+    pub lemma: String,
+    /// The part of the text the token was made from
+    pub original: String,
+    /// `word`, `stopWord`, `hardSeparator`, `softSeparator`, or `unknown`
+    pub kind: &'static str,
+    pub char_start: usize,
+    pub char_end: usize,
+    pub script: String,
+    /// The detected language of the token, if any
+    pub language: Option<String>,
+    /// The position the word is indexed at in the attribute, `None` for the tokens not indexed
+    pub position: Option<usize>,
+}
+
+/// A term of the query, or an n-gram of consecutive terms, with the words it is matched with.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyzedQueryTerm {
+    pub original: String,
+    /// The first and last positions in the query of the words making the term.
+    pub positions: [u16; 2],
+    /// The consecutive words concatenated into this term, for an n-gram.
+    pub ngram_words: Option<Vec<String>>,
+    /// The phrase this term is made of, when quoted in the query.
+    pub phrase: Option<String>,
+    pub is_prefix: bool,
+    pub max_typos: u8,
+    pub derivations: QueryTermDerivations,
+}
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryTermDerivations {
+    pub exact: Option<String>,
+    pub prefix_of: Vec<String>,
+    pub infix_of: Vec<String>,
+    pub synonyms: Vec<String>,
+    /// The term split into two words found in the index.
+    pub split_words: Option<String>,
+    pub one_typo: Vec<String>,
+    pub two_typos: Vec<String>,
+    /// The prefix whose documents are retrieved from the prefix databases.
+    pub prefix_db: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use big_s::S;
+
+    use super::*;
+    use crate::index::tests::TempIndex;
+
+    fn create_index() -> TempIndex {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|s| {
+                s.set_primary_key("id".to_owned());
+                s.set_searchable_fields(vec![S("title")]);
+                s.set_stop_words(maplit::btreeset! { S("the") });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "title": "send an email" },
+                { "id": 1, "title": "the mailbox. emails" },
+            ]))
+            .unwrap();
+        index
+    }
+
+    #[test]
+    fn analyze_tokens() {
+        let index = create_index();
+        let rtxn = index.read_txn().unwrap();
+
+        let analysis = Analyze::new(S("The e-mail. Sent"), &index, &rtxn).execute().unwrap();
+        let tokens: Vec<_> = analysis
+            .tokens
+            .iter()
+            .map(|token| (token.lemma.as_str(), token.kind, token.position))
+            .collect();
+        assert_eq!(
+            tokens,
+            vec![
+                ("the", "stopWord", None),
+                (" ", "softSeparator", None),
+                ("e", "word", Some(1)),
+                ("-", "softSeparator", None),
+                ("mail", "word", Some(2)),
+                (".", "hardSeparator", None),
+                (" ", "softSeparator", None),
+                ("sent", "word", Some(10)),
+            ]
+        );
+        assert_eq!(analysis.tokens[4].original, "mail");
+        assert_eq!((analysis.tokens[4].char_start, analysis.tokens[4].char_end), (6, 10));
+    }
+
+    #[test]
+    fn analyze_query_terms() {
+        let index = create_index();
+        let rtxn = index.read_txn().unwrap();
+
+        let analysis = Analyze::new(S("e-mail"), &index, &rtxn).execute().unwrap();
+        let terms: Vec<_> =
+            analysis.query_terms.iter().map(|term| term.original.as_str()).collect();
+        assert_eq!(terms, vec!["e", "mail", "email"]);
+
+        // the concatenation of the two words matches the documents containing `email`
+        let email = &analysis.query_terms[2];
+        assert_eq!(email.positions, [0, 1]);
+        assert_eq!(email.ngram_words, Some(vec![S("e"), S("mail")]));
+        assert!(email.is_prefix);
+        assert_eq!(email.derivations.exact, Some(S("email")));
+        assert_eq!(email.derivations.prefix_of, vec![S("emails")]);
+
+        let mail = &analysis.query_terms[1];
+        assert_eq!(mail.derivations.prefix_of, vec![S("mailbox")]);
+    }
+
+    #[test]
+    fn analyze_field_scoped_query() {
+        let index = create_index();
+        let rtxn = index.read_txn().unwrap();
+
+        // the `field:` prefix is removed like in a search
+        let analysis = Analyze::new(S("title:mailbox"), &index, &rtxn).execute().unwrap();
+        let terms: Vec<_> =
+            analysis.query_terms.iter().map(|term| term.original.as_str()).collect();
+        assert_eq!(terms, vec!["mailbox"]);
+        assert_eq!(analysis.query_terms[0].derivations.exact, Some(S("mailbox")));
+    }
+}
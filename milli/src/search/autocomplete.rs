@@ -0,0 +1,334 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use charabia::TokenKind;
+use fst::automaton::Str;
+use fst::{Automaton, IntoStreamer, Streamer};
+use roaring::RoaringBitmap;
+
+use crate::search::new::tokenize_query;
+use crate::{filtered_universe, CboRoaringBitmapLenCodec, Filter, Index, Result, SearchContext};
+
+/// The maximum number of completions that can be requested.
+pub const MAX_AUTOCOMPLETE_LIMIT: usize = 100;
+
+/// The maximum number of words of the index completing the query that are considered.
+const MAX_SCANNED_WORDS: usize = 10_000;
+
+/// The maximum number of words of the index whose documents are intersected with the
+/// documents matching the query to rank the completions.
+const MAX_RANKED_WORDS: usize = 1_000;
+
+/// Completes the last word of a partial query with the words of the index.
+///
+/// Only the words appearing in documents that also contain the previous words of the query
+/// are returned, from the most to the least frequent.
+///
+/// The words are ranked from the most to the least frequent in the whole index, which bounds
+/// the number of documents they can appear in, and the ranking stops as soon as no remaining
+/// word can outrank the completions found so far. Only the [`MAX_RANKED_WORDS`] most frequent
+/// of the first [`MAX_SCANNED_WORDS`] words completing the query are ranked, the completions
+/// are reported as degraded when the other words could have outranked them.
+pub struct Autocomplete<'a> {
+    query: String,
+    // this should be linked to the String in the query
+    filter: Option<Filter<'a>>,
+    limit: usize,
+    rtxn: &'a heed::RoTxn<'a>,
+    index: &'a Index,
+}
+
+impl<'a> Autocomplete<'a> {
+    pub fn new(query: String, limit: usize, index: &'a Index, rtxn: &'a heed::RoTxn<'a>) -> Self {
+        Self { query, filter: None, limit, rtxn, index }
+    }
+
+    pub fn filter(&mut self, filter: Filter<'a>) -> &mut Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    pub fn execute(&self) -> Result<Completions> {
+        let ctx = SearchContext::new(self.index, self.rtxn)?;
+        let tokens = tokenize_query(&ctx, &self.query, None)?;
+
+        // a query ending with a separator has no word to complete
+        let Some((last, previous)) = tokens.split_last() else { return Ok(Completions::default()) };
+        if !matches!(last.kind, TokenKind::Word | TokenKind::StopWord) || self.limit == 0 {
+            return Ok(Completions::default());
+        }
+        let prefix = last.lemma();
+
+        let mut universe = filtered_universe(self.index, self.rtxn, &self.filter)?;
+        for token in previous.iter().filter(|token| token.is_word()) {
+            universe &= self.word_docids(token.lemma())?;
+            if universe.is_empty() {
+                return Ok(Completions::default());
+            }
+        }
+
+        // the prefix databases tell us early when no word can complete the query
+        if self.index.words_prefixes_fst(self.rtxn)?.contains(prefix) {
+            let mut prefix_docids =
+                self.index.word_prefix_docids.get(self.rtxn, prefix)?.unwrap_or_default();
+            prefix_docids |=
+                self.index.exact_word_prefix_docids.get(self.rtxn, prefix)?.unwrap_or_default();
+            if prefix_docids.is_disjoint(&universe) {
+                return Ok(Completions::default());
+            }
+        }
+
+        // reading the number of documents of a word doesn't decode its bitmap,
+        // the least frequent of the most frequent words is at the top of the heap
+        let words_fst = self.index.words_fst(self.rtxn)?;
+        let mut stream = words_fst.search(Str::new(prefix).starts_with()).into_stream();
+        let mut most_frequent = BinaryHeap::new();
+        let mut scanned = 0;
+        let mut degraded = false;
+        while let Some(word) = stream.next() {
+            if scanned == MAX_SCANNED_WORDS {
+                degraded = true;
+                break;
+            }
+            scanned += 1;
+            let word = std::str::from_utf8(word)?;
+            most_frequent.push(Reverse((self.word_docids_len(word)?, Reverse(word.to_owned()))));
+            if most_frequent.len() > MAX_RANKED_WORDS {
+                most_frequent.pop();
+            }
+        }
+        // the words left out may have been better completions than the ranked ones
+        let left_out = scanned > most_frequent.len();
+
+        // the words are ranked by decreasing frequency, then in alphabetical order,
+        // the worst of the best completions found so far is at the top of the heap
+        let mut best = BinaryHeap::with_capacity(self.limit.min(most_frequent.len()) + 1);
+        let mut ranked_all = true;
+        for Reverse((max_count, Reverse(word))) in most_frequent.into_sorted_vec() {
+            if best.len() == self.limit {
+                let Reverse((count, Reverse(worst))) = best.peek().unwrap();
+                if (max_count, Reverse(&word)) < (*count, Reverse(worst)) {
+                    ranked_all = false;
+                    break;
+                }
+            }
+
+            let count = self.word_docids(&word)?.intersection_len(&universe);
+            if count > 0 {
+                best.push(Reverse((count, Reverse(word))));
+                if best.len() > self.limit {
+                    best.pop();
+                }
+            }
+        }
+        degraded |= ranked_all && left_out;
+
+        let beginning = &self.query[..last.byte_start];
+        let completions = best
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((count, Reverse(word)))| Completion {
+                query: format!("{beginning}{word}"),
+                word,
+                count,
+            })
+            .collect();
+
+        Ok(Completions { completions, degraded })
+    }
+
+    fn word_docids(&self, word: &str) -> Result<RoaringBitmap> {
+        let mut docids = self.index.word_docids.get(self.rtxn, word)?.unwrap_or_default();
+        docids |= self.index.exact_word_docids.get(self.rtxn, word)?.unwrap_or_default();
+        Ok(docids)
+    }
+
+    /// The number of documents containing the word, an upper bound of its number of completions.
+    fn word_docids_len(&self, word: &str) -> Result<u64> {
+        let word_docids = self.index.word_docids.remap_data_type::<CboRoaringBitmapLenCodec>();
+        let exact_word_docids =
+            self.index.exact_word_docids.remap_data_type::<CboRoaringBitmapLenCodec>();
+        Ok(word_docids.get(self.rtxn, word)?.unwrap_or_default()
+            + exact_word_docids.get(self.rtxn, word)?.unwrap_or_default())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Completions {
+    /// The completions, from the most to the least frequent
+    pub completions: Vec<Completion>,
+    /// Whether the ranking stopped before all the words completing the query were ranked
+    pub degraded: bool,
+}
+
+#[derive(Debug, Clone, serde::Serialize, PartialEq)]
+pub struct Completion {
+    /// The query with its last word completed
+    pub query: String,
+    /// The word of the index completing the last word of the query
+    pub word: String,
+    /// The number of documents containing the completed query
+    pub count: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use big_s::S;
+    use maplit::hashset;
+
+    use super::*;
+    use crate::index::tests::TempIndex;
+
+    fn create_index() -> TempIndex {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|s| {
+                s.set_primary_key("id".to_owned());
+                s.set_searchable_fields(vec![S("title")]);
+                s.set_filterable_fields(hashset! { S("id") });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "title": "summer dress" },
+                { "id": 1, "title": "summer dress with flowers" },
+                { "id": 2, "title": "summer drink" },
+                { "id": 3, "title": "winter dress" },
+                { "id": 4, "title": "winter drum" },
+                { "id": 5, "title": "winter dress" },
+            ]))
+            .unwrap();
+        index
+    }
+
+    fn completions(index: &TempIndex, query: &str, filter: Option<&str>) -> Vec<(String, u64)> {
+        let rtxn = index.read_txn().unwrap();
+        let mut autocomplete = Autocomplete::new(query.to_owned(), 10, index, &rtxn);
+        if let Some(filter) = filter {
+            autocomplete.filter(Filter::from_str(filter).unwrap().unwrap());
+        }
+        autocomplete
+            .execute()
+            .unwrap()
+            .completions
+            .into_iter()
+            .map(|c| (c.query, c.count))
+            .collect()
+    }
+
+    #[test]
+    fn complete_last_word() {
+        let index = create_index();
+
+        assert_eq!(
+            completions(&index, "dr", None),
+            vec![(S("dress"), 4), (S("drink"), 1), (S("drum"), 1)]
+        );
+        // only the words co-occurring with the previous words are returned
+        assert_eq!(
+            completions(&index, "Summer  dr", None),
+            vec![(S("Summer  dress"), 2), (S("Summer  drink"), 1)]
+        );
+        assert_eq!(completions(&index, "winter dri", None), vec![]);
+        // there is no word to complete
+        assert_eq!(completions(&index, "summer ", None), vec![]);
+        assert_eq!(completions(&index, "autumn dr", None), vec![]);
+    }
+
+    #[test]
+    fn complete_with_filter() {
+        let index = create_index();
+
+        assert_eq!(
+            completions(&index, "dr", Some("id >= 3")),
+            vec![(S("dress"), 2), (S("drum"), 1)]
+        );
+    }
+
+    /// More words than can be ranked complete the query, the most frequent one sorting last
+    /// when it appears in more documents than the filler words.
+    fn create_large_index(filler_documents: u32) -> TempIndex {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|s| {
+                s.set_primary_key("id".to_owned());
+                s.set_searchable_fields(vec![S("title")]);
+            })
+            .unwrap();
+
+        let filler = (0..MAX_RANKED_WORDS + 100).map(|i| format!("w{i:04}")).collect::<Vec<_>>();
+        let filler = filler.join(" ");
+        let mut documents: Vec<_> = (0..filler_documents)
+            .map(|id| serde_json::json!({ "id": id, "title": format!("blue {filler}") }))
+            .collect();
+        documents.push(serde_json::json!({ "id": 100, "title": "red wzzz" }));
+        documents.push(serde_json::json!({ "id": 101, "title": "red wzzz" }));
+        documents.push(serde_json::json!({ "id": 102, "title": "green wzzz" }));
+        index.add_documents(documents!(documents)).unwrap();
+        index
+    }
+
+    #[test]
+    fn complete_with_many_candidates() {
+        let index = create_large_index(2);
+        let rtxn = index.read_txn().unwrap();
+
+        let Completions { completions, degraded } =
+            Autocomplete::new(S("w"), 1, &index, &rtxn).execute().unwrap();
+        assert_eq!(completions, vec![Completion { query: S("wzzz"), word: S("wzzz"), count: 3 }]);
+        assert!(!degraded);
+
+        let Completions { completions, degraded } =
+            Autocomplete::new(S("w"), 3, &index, &rtxn).execute().unwrap();
+        let completions: Vec<_> = completions.into_iter().map(|c| (c.word, c.count)).collect();
+        assert_eq!(completions, vec![(S("wzzz"), 3), (S("w0000"), 2), (S("w0001"), 2)]);
+        assert!(!degraded);
+    }
+
+    #[test]
+    fn complete_with_too_many_words() {
+        let index = TempIndex::new();
+        index
+            .update_settings(|s| {
+                s.set_primary_key("id".to_owned());
+                s.set_searchable_fields(vec![S("title")]);
+            })
+            .unwrap();
+        let words = (0..MAX_SCANNED_WORDS + 1).map(|i| format!("w{i:05}")).collect::<Vec<_>>();
+        index.add_documents(documents!([{ "id": 0, "title": words.join(" ") }])).unwrap();
+        let rtxn = index.read_txn().unwrap();
+
+        // the last word is never scanned
+        let Completions { completions, degraded } =
+            Autocomplete::new(S("w"), 1, &index, &rtxn).execute().unwrap();
+        assert_eq!(
+            completions,
+            vec![Completion { query: S("w00000"), word: S("w00000"), count: 1 }]
+        );
+        assert!(degraded);
+
+        let Completions { completions, degraded } =
+            Autocomplete::new(S("w1"), 1, &index, &rtxn).execute().unwrap();
+        assert_eq!(
+            completions,
+            vec![Completion { query: S("w10000"), word: S("w10000"), count: 1 }]
+        );
+        assert!(!degraded);
+    }
+
+    #[test]
+    fn complete_with_too_many_candidates() {
+        let index = create_large_index(4);
+        let rtxn = index.read_txn().unwrap();
+
+        // the filler words are more frequent but don't appear with the previous word,
+        // and there are too many of them to rank the word completing the query
+        let Completions { completions, degraded } =
+            Autocomplete::new(S("red w"), 1, &index, &rtxn).execute().unwrap();
+        assert_eq!(completions, vec![]);
+        assert!(degraded);
+    }
+}
@@ -1,5 +1,5 @@
-use std::collections::{BTreeMap, HashMap, HashSet};
-use std::ops::ControlFlow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::ops::{Bound, ControlFlow};
 use std::{fmt, mem};
 
 use heed::types::Bytes;
@@ -17,6 +17,7 @@ use crate::heed_codec::{BytesRefCodec, StrRefCodec};
 use crate::search::facet::facet_distribution_iter::{
     count_iterate_over_facet_distribution, lexicographically_iterate_over_facet_distribution,
 };
+use crate::search::facet::find_docids_of_facet_within_bounds;
 use crate::{FieldId, Index, Result};
 
 /// The default number of values by facets that will
@@ -27,6 +28,9 @@ pub const DEFAULT_VALUES_PER_FACET: usize = 100;
 /// the system to choose between one algorithm or another.
 const CANDIDATES_THRESHOLD: u64 = 3000;
 
+/// The maximum number of buckets a facet histogram can be split into.
+pub const MAX_HISTOGRAM_BUCKETS: usize = 1000;
+
 /// How should we fetch the facets?
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum OrderBy {
@@ -37,8 +41,50 @@ pub enum OrderBy {
     Count,
 }
 
+/// The buckets in which the numeric values of a facet are counted.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FacetRanges {
+    /// Arbitrary ranges, returned in the order they were given.
+    Ranges(Vec<FacetRange>),
+    /// Contiguous buckets of the given width, aligned on its multiples and
+    /// spanning from the smallest to the biggest value of the candidates.
+    Interval(f64),
+}
+
+/// A range of facet values, the lower bound is included and the upper bound is excluded.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct FacetRange {
+    pub from: Option<f64>,
+    pub to: Option<f64>,
+}
+
+/// The number of candidates having a facet value in a range.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct FacetRangeCount {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<f64>,
+    pub count: u64,
+}
+
+/// Aggregations of the numeric values of the candidates for a facet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FacetStatsAggregations {
+    /// The number of candidates having at least one numeric value.
+    pub count: u64,
+    /// The sum of the values, a document with several values is counted once per value.
+    pub sum: f64,
+    /// The average of the values, weighted like the sum.
+    pub avg: f64,
+    /// The requested percentiles, in the order they were requested,
+    /// associated with the nearest value at their rank.
+    pub percentiles: Vec<(f64, f64)>,
+}
+
 pub struct FacetDistribution<'a> {
     facets: Option<HashMap<String, OrderBy>>,
+    ranges: Option<BTreeMap<String, FacetRanges>>,
     candidates: Option<RoaringBitmap>,
     max_values_per_facet: usize,
     default_order_by: OrderBy,
@@ -50,6 +96,7 @@ impl<'a> FacetDistribution<'a> {
     pub fn new(rtxn: &'a heed::RoTxn, index: &'a Index) -> FacetDistribution<'a> {
         FacetDistribution {
             facets: None,
+            ranges: None,
             candidates: None,
             max_values_per_facet: DEFAULT_VALUES_PER_FACET,
             default_order_by: OrderBy::default(),
@@ -71,6 +118,19 @@ impl<'a> FacetDistribution<'a> {
         self
     }
 
+    pub fn ranges<I: IntoIterator<Item = (A, FacetRanges)>, A: AsRef<str>>(
+        &mut self,
+        names_ranges: I,
+    ) -> &mut Self {
+        self.ranges = Some(
+            names_ranges
+                .into_iter()
+                .map(|(name, ranges)| (name.as_ref().to_string(), ranges))
+                .collect(),
+        );
+        self
+    }
+
     pub fn max_values_per_facet(&mut self, max: usize) -> &mut Self {
         self.max_values_per_facet = max;
         self
@@ -236,13 +296,14 @@ impl<'a> FacetDistribution<'a> {
     ) -> heed::Result<IndexMap<String, u64>> {
         use FacetType::{Number, String};
 
-        let mut distribution = IndexMap::new();
+        let mut numbers = IndexMap::new();
+        let mut strings = IndexMap::new();
         match (order_by, &self.candidates) {
             (OrderBy::Lexicographic, Some(cnd)) if cnd.len() <= CANDIDATES_THRESHOLD => {
                 // Classic search, candidates were specified, we must return facet values only related
                 // to those candidates. We also enter here for facet strings for performance reasons.
-                self.facet_distribution_from_documents(field_id, Number, cnd, &mut distribution)?;
-                self.facet_distribution_from_documents(field_id, String, cnd, &mut distribution)?;
+                self.facet_distribution_from_documents(field_id, Number, cnd, &mut numbers)?;
+                self.facet_distribution_from_documents(field_id, String, cnd, &mut strings)?;
             }
             _ => {
                 let universe;
@@ -258,17 +319,22 @@ impl<'a> FacetDistribution<'a> {
                     field_id,
                     candidates,
                     order_by,
-                    &mut distribution,
+                    &mut numbers,
                 )?;
                 self.facet_strings_distribution_from_facet_levels(
                     field_id,
                     candidates,
                     order_by,
-                    &mut distribution,
+                    &mut strings,
                 )?;
             }
         };
 
+        remove_date_timestamps(&mut numbers, &strings);
+        let mut distribution = numbers;
+        distribution.extend(strings);
+        distribution.truncate(self.max_values_per_facet);
+
         Ok(distribution)
     }
 
@@ -281,25 +347,7 @@ impl<'a> FacetDistribution<'a> {
             return Ok(Default::default());
         };
 
-        let fields = match &self.facets {
-            Some(facets) => {
-                let invalid_fields: HashSet<_> = facets
-                    .iter()
-                    .map(|(name, _)| name)
-                    .filter(|facet| !crate::is_faceted(facet, &filterable_fields))
-                    .collect();
-                if !invalid_fields.is_empty() {
-                    return Err(UserError::InvalidFacetsDistribution {
-                        invalid_facets_name: invalid_fields.into_iter().cloned().collect(),
-                        valid_facets_name: filterable_fields.into_iter().collect(),
-                    }
-                    .into());
-                } else {
-                    facets.iter().map(|(name, _)| name).cloned().collect()
-                }
-            }
-            None => filterable_fields,
-        };
+        let fields = self.stats_fields(filterable_fields)?;
 
         let mut distribution = BTreeMap::new();
         for (fid, name) in fields_ids_map.iter() {
@@ -332,6 +380,268 @@ impl<'a> FacetDistribution<'a> {
         Ok(distribution)
     }
 
+    /// Computes the sum, the average, the number of documents with a value and the
+    /// given percentiles of the numeric values of the candidates, for the same facets
+    /// as [`Self::compute_stats`].
+    ///
+    /// The percentiles must be between `0.0` and `100.0`, they are computed exactly with the
+    /// nearest-rank method, iterating over every distinct value of the candidates.
+    pub fn compute_stats_aggregations(
+        &self,
+        percentiles: &[f64],
+    ) -> Result<BTreeMap<String, FacetStatsAggregations>> {
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let filterable_fields = self.index.filterable_fields(self.rtxn)?;
+        let candidates = match &self.candidates {
+            Some(candidates) => candidates,
+            None => return Ok(Default::default()),
+        };
+
+        let fields = self.stats_fields(filterable_fields)?;
+
+        let mut aggregations = BTreeMap::new();
+        for (fid, name) in fields_ids_map.iter() {
+            if crate::is_faceted(name, &fields) {
+                let values = self.numbers_with_count(fid, candidates)?;
+                let values_count: u64 = values.iter().map(|(_, count)| count).sum();
+                if values_count == 0 {
+                    continue;
+                }
+
+                let sum: f64 = values.iter().map(|(value, count)| value * *count as f64).sum();
+                let percentiles = percentiles
+                    .iter()
+                    .map(|&percentile| {
+                        (percentile, nearest_rank(&values, values_count, percentile))
+                    })
+                    .collect();
+                let count = self.facet_range_count(fid, None, None, candidates)?;
+
+                aggregations.insert(
+                    name.to_string(),
+                    FacetStatsAggregations {
+                        count,
+                        sum,
+                        avg: sum / values_count as f64,
+                        percentiles,
+                    },
+                );
+            }
+        }
+
+        Ok(aggregations)
+    }
+
+    /// The facets on which the stats are computed: the requested facets when
+    /// they all are filterable, all the filterable attributes otherwise.
+    fn stats_fields(&self, filterable_fields: HashSet<String>) -> Result<HashSet<String>> {
+        match &self.facets {
+            Some(facets) => {
+                let invalid_fields: HashSet<_> = facets
+                    .iter()
+                    .map(|(name, _)| name)
+                    .filter(|facet| !crate::is_faceted(facet, &filterable_fields))
+                    .collect();
+                if !invalid_fields.is_empty() {
+                    Err(UserError::InvalidFacetsDistribution {
+                        invalid_facets_name: invalid_fields.into_iter().cloned().collect(),
+                        valid_facets_name: filterable_fields.into_iter().collect(),
+                    }
+                    .into())
+                } else {
+                    Ok(facets.iter().map(|(name, _)| name).cloned().collect())
+                }
+            }
+            None => Ok(filterable_fields),
+        }
+    }
+
+    /// Returns the numeric values of the candidates in ascending order, along with
+    /// the number of candidates having each of them.
+    fn numbers_with_count(
+        &self,
+        field_id: FieldId,
+        candidates: &RoaringBitmap,
+    ) -> heed::Result<Vec<(f64, u64)>> {
+        let mut values = Vec::new();
+
+        if candidates.len() <= CANDIDATES_THRESHOLD {
+            let mut key_buffer: Vec<_> = field_id.to_be_bytes().to_vec();
+            let db = self.index.field_id_docid_facet_f64s;
+            for docid in candidates {
+                key_buffer.truncate(mem::size_of::<FieldId>());
+                key_buffer.extend_from_slice(&docid.to_be_bytes());
+                let iter = db
+                    .remap_key_type::<Bytes>()
+                    .prefix_iter(self.rtxn, &key_buffer)?
+                    .remap_key_type::<FieldDocIdFacetF64Codec>();
+
+                for result in iter {
+                    let ((_, _, value), ()) = result?;
+                    values.push((value, 1));
+                }
+            }
+            values.sort_unstable_by(|(a, _), (b, _)| a.total_cmp(b));
+        } else {
+            lexicographically_iterate_over_facet_distribution(
+                self.rtxn,
+                self.index
+                    .facet_id_f64_docids
+                    .remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>(),
+                field_id,
+                candidates,
+                |facet_key, nbr_docids, _| {
+                    let value = OrderedF64Codec::bytes_decode(facet_key).unwrap();
+                    values.push((value, nbr_docids));
+                    Ok(ControlFlow::Continue(()))
+                },
+            )?;
+        }
+
+        Ok(values)
+    }
+
+    /// Counts the candidates in each of the requested ranges of numeric facet values.
+    ///
+    /// The documents of a range are fetched from the facet levels, so the cost of a range
+    /// doesn't depend on the number of distinct values it contains.
+    pub fn compute_ranges(&self) -> Result<BTreeMap<String, Vec<FacetRangeCount>>> {
+        let ranges = match &self.ranges {
+            Some(ranges) => ranges,
+            None => return Ok(Default::default()),
+        };
+
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let filterable_fields = self.index.filterable_fields(self.rtxn)?;
+        let invalid_fields: BTreeSet<_> = ranges
+            .keys()
+            .filter(|facet| !crate::is_faceted(facet, &filterable_fields))
+            .cloned()
+            .collect();
+        if !invalid_fields.is_empty() {
+            return Err(UserError::InvalidFacetsDistribution {
+                invalid_facets_name: invalid_fields,
+                valid_facets_name: filterable_fields.into_iter().collect(),
+            }
+            .into());
+        }
+
+        let universe;
+        let candidates = match &self.candidates {
+            Some(cnd) => cnd,
+            None => {
+                universe = self.index.documents_ids(self.rtxn)?;
+                &universe
+            }
+        };
+
+        let mut distribution = BTreeMap::new();
+        for (name, ranges) in ranges {
+            let field_id = fields_ids_map.id(name);
+            let ranges = match ranges {
+                FacetRanges::Ranges(ranges) => ranges.clone(),
+                FacetRanges::Interval(interval) => {
+                    self.histogram_ranges(name, field_id, *interval, candidates)?
+                }
+            };
+
+            let mut counts = Vec::with_capacity(ranges.len());
+            for FacetRange { from, to } in ranges {
+                let count = match field_id {
+                    Some(field_id) => self.facet_range_count(field_id, from, to, candidates)?,
+                    None => 0,
+                };
+                counts.push(FacetRangeCount { from, to, count });
+            }
+            distribution.insert(name.to_string(), counts);
+        }
+
+        Ok(distribution)
+    }
+
+    /// Splits the values of the candidates into contiguous buckets of `interval` width.
+    fn histogram_ranges(
+        &self,
+        name: &str,
+        field_id: Option<FieldId>,
+        interval: f64,
+        candidates: &RoaringBitmap,
+    ) -> Result<Vec<FacetRange>> {
+        let invalid_interval = || UserError::InvalidFacetHistogramInterval {
+            facet: name.to_string(),
+            interval,
+            max: MAX_HISTOGRAM_BUCKETS,
+        };
+        if !(interval.is_finite() && interval > 0.0) {
+            return Err(invalid_interval().into());
+        }
+
+        let field_id = match field_id {
+            Some(field_id) => field_id,
+            None => return Ok(Vec::new()),
+        };
+        let min = crate::search::facet::facet_min_value(
+            self.index,
+            self.rtxn,
+            field_id,
+            candidates.clone(),
+        )?;
+        let max = crate::search::facet::facet_max_value(
+            self.index,
+            self.rtxn,
+            field_id,
+            candidates.clone(),
+        )?;
+        let (min, max) = match min.zip(max) {
+            Some(extremes) => extremes,
+            None => return Ok(Vec::new()),
+        };
+
+        let first_bucket = (min / interval).floor();
+        let last_bucket = (max / interval).floor();
+        let buckets = last_bucket - first_bucket + 1.0;
+        if !buckets.is_finite() || buckets > MAX_HISTOGRAM_BUCKETS as f64 {
+            return Err(invalid_interval().into());
+        }
+
+        // computing every bound from its bucket number avoids accumulating rounding errors
+        Ok((0..buckets as usize)
+            .map(|bucket| {
+                let bucket = first_bucket + bucket as f64;
+                FacetRange { from: Some(bucket * interval), to: Some((bucket + 1.0) * interval) }
+            })
+            .collect())
+    }
+
+    fn facet_range_count(
+        &self,
+        field_id: FieldId,
+        from: Option<f64>,
+        to: Option<f64>,
+        candidates: &RoaringBitmap,
+    ) -> Result<u64> {
+        if let (Some(from), Some(to)) = (from, to) {
+            if from >= to {
+                return Ok(0);
+            }
+        }
+
+        let left = from.map_or(Bound::Unbounded, Bound::Included);
+        let right = to.map_or(Bound::Unbounded, Bound::Excluded);
+        let mut docids = RoaringBitmap::new();
+        find_docids_of_facet_within_bounds::<OrderedF64Codec>(
+            self.rtxn,
+            self.index.facet_id_f64_docids,
+            field_id,
+            &left,
+            &right,
+            Some(candidates),
+            &mut docids,
+        )?;
+
+        Ok(docids.intersection_len(candidates))
+    }
+
     pub fn execute(&self) -> Result<BTreeMap<String, IndexMap<String, u64>>> {
         let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
         let filterable_fields = self.index.filterable_fields(self.rtxn)?;
@@ -373,10 +683,48 @@ impl<'a> FacetDistribution<'a> {
     }
 }
 
+/// The dates are faceted both as their original string and as their timestamp,
+/// only their original string is kept in the distribution.
+fn remove_date_timestamps(numbers: &mut IndexMap<String, u64>, strings: &IndexMap<String, u64>) {
+    let mut dates_count = HashMap::new();
+    for (original, count) in strings {
+        if let Some(timestamp) = crate::date_to_timestamp(original) {
+            *dates_count.entry(timestamp.to_string()).or_insert(0) += count;
+        }
+    }
+    if dates_count.is_empty() {
+        return;
+    }
+
+    numbers.retain(|value, count| match dates_count.get(value) {
+        Some(dates_count) => {
+            *count = count.saturating_sub(*dates_count);
+            *count > 0
+        }
+        None => true,
+    });
+}
+
+/// Returns the smallest value such that at least `percentile` percent of the values
+/// are lower or equal to it, `values` must be sorted and contain `values_count` values.
+fn nearest_rank(values: &[(f64, u64)], values_count: u64, percentile: f64) -> f64 {
+    let rank = ((percentile / 100.0) * values_count as f64).ceil().max(1.0) as u64;
+    let mut seen = 0;
+    for &(value, count) in values {
+        seen += count;
+        if seen >= rank {
+            return value;
+        }
+    }
+    // the rank can't be greater than the number of values
+    values.last().map_or(f64::NAN, |(value, _)| *value)
+}
+
 impl fmt::Debug for FacetDistribution<'_> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let FacetDistribution {
             facets,
+            ranges,
             candidates,
             max_values_per_facet,
             default_order_by,
@@ -386,6 +734,7 @@ impl fmt::Debug for FacetDistribution<'_> {
 
         f.debug_struct("FacetDistribution")
             .field("facets", facets)
+            .field("ranges", ranges)
             .field("candidates", candidates)
             .field("max_values_per_facet", max_values_per_facet)
             .field("default_order_by", default_order_by)
@@ -399,10 +748,11 @@ mod tests {
 
     use big_s::S;
     use maplit::hashset;
+    use roaring::RoaringBitmap;
 
     use crate::documents::documents_batch_reader_from_objects;
     use crate::index::tests::TempIndex;
-    use crate::{milli_snap, FacetDistribution, OrderBy};
+    use crate::{milli_snap, FacetDistribution, FacetRange, FacetRanges, OrderBy};
 
     #[test]
     fn few_candidates_few_facet_values() {
@@ -836,4 +1186,107 @@ mod tests {
 
         milli_snap!(format!("{map:?}"), "candidates_217_777", @r###"{"colour": (218.0, 1776.0)}"###);
     }
+
+    #[test]
+    fn facet_ranges() {
+        let mut index = TempIndex::new_with_map_size(4096 * 10_000);
+        index.index_documents_config.autogenerate_docids = true;
+
+        index
+            .update_settings(|settings| settings.set_filterable_fields(hashset! { S("price") }))
+            .unwrap();
+
+        let mut documents = vec![];
+        for i in 0..10_000 {
+            let document = serde_json::json!({ "price": i % 1000 }).as_object().unwrap().clone();
+            documents.push(document);
+        }
+
+        let documents = documents_batch_reader_from_objects(documents);
+
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        let counts = |ranges: FacetRanges, candidates: Option<RoaringBitmap>| {
+            let mut distribution = FacetDistribution::new(&txn, &index);
+            distribution.ranges(iter::once(("price", ranges)));
+            if let Some(candidates) = candidates {
+                distribution.candidates(candidates);
+            }
+            let map = distribution.compute_ranges().unwrap();
+            map["price"].iter().map(|c| (c.from, c.to, c.count)).collect::<Vec<_>>()
+        };
+
+        let ranges = FacetRanges::Ranges(vec![
+            FacetRange { from: None, to: Some(100.0) },
+            FacetRange { from: Some(100.0), to: Some(250.0) },
+            FacetRange { from: Some(500.0), to: Some(500.0) },
+            FacetRange { from: Some(900.0), to: None },
+        ]);
+        milli_snap!(format!("{:?}", counts(ranges.clone(), None)), @"[(None, Some(100.0), 1000), (Some(100.0), Some(250.0), 1500), (Some(500.0), Some(500.0), 0), (Some(900.0), None, 1000)]");
+        milli_snap!(format!("{:?}", counts(ranges, Some((0..1000).collect()))), @"[(None, Some(100.0), 100), (Some(100.0), Some(250.0), 150), (Some(500.0), Some(500.0), 0), (Some(900.0), None, 100)]");
+
+        milli_snap!(format!("{:?}", counts(FacetRanges::Interval(250.0), Some((0..1000).collect()))), @"[(Some(0.0), Some(250.0), 250), (Some(250.0), Some(500.0), 250), (Some(500.0), Some(750.0), 250), (Some(750.0), Some(1000.0), 250)]");
+        milli_snap!(format!("{:?}", counts(FacetRanges::Interval(250.0), Some((217..777).collect()))), @"[(Some(0.0), Some(250.0), 33), (Some(250.0), Some(500.0), 250), (Some(500.0), Some(750.0), 250), (Some(750.0), Some(1000.0), 27)]");
+        milli_snap!(format!("{:?}", counts(FacetRanges::Interval(250.0), Some(RoaringBitmap::new()))), @"[]");
+
+        for interval in [0.0, -10.0, 0.5] {
+            let err = FacetDistribution::new(&txn, &index)
+                .ranges(iter::once(("price", FacetRanges::Interval(interval))))
+                .compute_ranges()
+                .unwrap_err();
+            assert!(matches!(
+                err,
+                crate::Error::UserError(crate::UserError::InvalidFacetHistogramInterval { .. })
+            ));
+        }
+
+        let err = FacetDistribution::new(&txn, &index)
+            .ranges(iter::once(("colour", FacetRanges::Interval(10.0))))
+            .compute_ranges()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            crate::Error::UserError(crate::UserError::InvalidFacetsDistribution { .. })
+        ));
+    }
+
+    #[test]
+    fn facet_stats_aggregations() {
+        let mut index = TempIndex::new_with_map_size(4096 * 10_000);
+        index.index_documents_config.autogenerate_docids = true;
+
+        index
+            .update_settings(|settings| settings.set_filterable_fields(hashset! { S("price") }))
+            .unwrap();
+
+        let mut documents = vec![];
+        for i in 0..10_000 {
+            let document = serde_json::json!({ "price": i % 1000 }).as_object().unwrap().clone();
+            documents.push(document);
+        }
+
+        let documents = documents_batch_reader_from_objects(documents);
+
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        let aggregations = |candidates: RoaringBitmap| {
+            let map = FacetDistribution::new(&txn, &index)
+                .facets(iter::once(("price", OrderBy::default())))
+                .candidates(candidates)
+                .compute_stats_aggregations(&[0.0, 50.0, 90.0, 100.0])
+                .unwrap();
+            format!("{:?}", map)
+        };
+
+        // the values of a few candidates are fetched from the documents
+        milli_snap!(aggregations((0..10).collect()), @r###"{"price": FacetStatsAggregations { count: 10, sum: 45.0, avg: 4.5, percentiles: [(0.0, 0.0), (50.0, 4.0), (90.0, 8.0), (100.0, 9.0)] }}"###);
+        milli_snap!(aggregations((9990..10_010).collect()), @r###"{"price": FacetStatsAggregations { count: 10, sum: 9945.0, avg: 994.5, percentiles: [(0.0, 990.0), (50.0, 994.0), (90.0, 998.0), (100.0, 999.0)] }}"###);
+        // while the ones of many candidates are fetched from the facet levels
+        milli_snap!(aggregations((0..10_000).collect()), @r###"{"price": FacetStatsAggregations { count: 10000, sum: 4995000.0, avg: 499.5, percentiles: [(0.0, 0.0), (50.0, 499.0), (90.0, 899.0), (100.0, 999.0)] }}"###);
+        milli_snap!(aggregations(RoaringBitmap::new()), @"{}");
+    }
 }
@@ -5,6 +5,7 @@ use std::ops::Bound::{self, Excluded, Included};
 use either::Either;
 pub use filter_parser::{Condition, Error as FPError, FilterCondition, Token};
 use roaring::{MultiOps, RoaringBitmap};
+use rstar::AABB;
 use serde_json::Value;
 
 use super::facet_range_search;
@@ -27,6 +28,7 @@ pub enum BadGeoError {
     Lat(f64),
     Lng(f64),
     BoundingBoxTopIsBelowBottom(f64, f64),
+    PolygonAroundPole,
 }
 
 impl std::error::Error for BadGeoError {}
@@ -37,6 +39,10 @@ impl Display for BadGeoError {
             Self::BoundingBoxTopIsBelowBottom(top, bottom) => {
                 write!(f, "The top latitude `{top}` is below the bottom latitude `{bottom}`.")
             }
+            Self::PolygonAroundPole => write!(
+                f,
+                "The `_geoPolygon` filter goes around a pole. Split it into several polygons that do not contain a pole."
+            ),
             Self::Lat(lat) => write!(
                 f,
                 "Bad latitude `{}`. Latitude must be contained between -90 and 90 degrees. ",
@@ -218,6 +224,36 @@ impl<'a> Filter<'a> {
 
         Ok(Some(Self { condition }))
     }
+
+    /// Splits the filter into the clauses that must all match for a document to be selected.
+    pub fn conjuncts(&self) -> Vec<Filter<'a>> {
+        match &self.condition {
+            FilterCondition::And(conditions) => {
+                conditions.iter().cloned().map(Self::from).collect()
+            }
+            condition => vec![Self::from(condition.clone())],
+        }
+    }
+
+    /// Returns `true` if the filter is applied on the given field or on one of its nested fields.
+    pub fn refers_to(&self, field: &str) -> bool {
+        fn refers_to(condition: &FilterCondition, field: &str) -> bool {
+            match condition {
+                FilterCondition::Not(condition) => refers_to(condition, field),
+                FilterCondition::Condition { fid, .. } | FilterCondition::In { fid, .. } => {
+                    crate::is_faceted_by(fid.value(), field)
+                }
+                FilterCondition::Or(conditions) | FilterCondition::And(conditions) => {
+                    conditions.iter().any(|condition| refers_to(condition, field))
+                }
+                FilterCondition::GeoLowerThan { .. }
+                | FilterCondition::GeoBoundingBox { .. }
+                | FilterCondition::GeoPolygon { .. } => field == "_geo",
+            }
+        }
+
+        refers_to(&self.condition, field)
+    }
 }
 
 impl<'a> Filter<'a> {
@@ -243,17 +279,17 @@ impl<'a> Filter<'a> {
 
         let (left, right) = match operator {
             Condition::GreaterThan(val) => {
-                (Excluded(val.parse_finite_float()?), Included(f64::MAX))
+                (Excluded(parse_number_or_date(val)?), Included(f64::MAX))
             }
             Condition::GreaterThanOrEqual(val) => {
-                (Included(val.parse_finite_float()?), Included(f64::MAX))
+                (Included(parse_number_or_date(val)?), Included(f64::MAX))
             }
-            Condition::LowerThan(val) => (Included(f64::MIN), Excluded(val.parse_finite_float()?)),
+            Condition::LowerThan(val) => (Included(f64::MIN), Excluded(parse_number_or_date(val)?)),
             Condition::LowerThanOrEqual(val) => {
-                (Included(f64::MIN), Included(val.parse_finite_float()?))
+                (Included(f64::MIN), Included(parse_number_or_date(val)?))
             }
             Condition::Between { from, to } => {
-                (Included(from.parse_finite_float()?), Included(to.parse_finite_float()?))
+                (Included(parse_number_or_date(from)?), Included(parse_number_or_date(to)?))
             }
             Condition::Null => {
                 let is_null = index.null_faceted_documents_ids(rtxn, field_id)?;
@@ -279,7 +315,7 @@ impl<'a> Filter<'a> {
                     )?
                     .map(|v| v.bitmap)
                     .unwrap_or_default();
-                let number = val.parse_finite_float().ok();
+                let number = parse_number_or_date(val).ok();
                 let number_docids = match number {
                     Some(n) => numbers_db
                         .get(rtxn, &FacetGroupKey { field_id, level: 0, left_bound: n })?
@@ -501,97 +537,37 @@ impl<'a> Filter<'a> {
                         ))?;
                     }
 
-                    // Instead of writing a custom `GeoBoundingBox` filter we're simply going to re-use the range
-                    // filter to create the following filter;
-                    // `_geo.lat {top_right[0]} TO {bottom_left[0]} AND _geo.lng {top_right[1]} TO {bottom_left[1]}`
-                    // As we can see, we need to use a bunch of tokens that don't exist in the original filter,
-                    // thus we're going to create tokens that point to a random span but contain our text.
-
-                    let geo_lat_token = Token::new(
-                        top_right_point[0].original_span(),
-                        Some("_geo.lat".to_string()),
-                    );
-
-                    let condition_lat = FilterCondition::Condition {
-                        fid: geo_lat_token,
-                        op: Condition::Between {
-                            from: bottom_left_point[0].clone(),
-                            to: top_right_point[0].clone(),
-                        },
+                    let rtree = match index.geo_rtree(rtxn)? {
+                        Some(rtree) => rtree,
+                        None => return Ok(RoaringBitmap::new()),
                     };
 
-                    let selected_lat = Filter { condition: condition_lat }.inner_evaluate(
-                        rtxn,
-                        index,
-                        filterable_fields,
-                        universe,
-                    )?;
-
-                    let geo_lng_token = Token::new(
-                        top_right_point[1].original_span(),
-                        Some("_geo.lng".to_string()),
-                    );
-                    let selected_lng = if top_right[1] < bottom_left[1] {
-                        // In this case the bounding box is wrapping around the earth (going from 180 to -180).
-                        // We need to update the lng part of the filter from;
-                        // `_geo.lng {top_right[1]} TO {bottom_left[1]}` to
-                        // `_geo.lng {bottom_left[1]} TO 180 AND _geo.lng -180 TO {top_right[1]}`
-
-                        let min_lng_token = Token::new(
-                            top_right_point[1].original_span(),
-                            Some("-180.0".to_string()),
-                        );
-                        let max_lng_token = Token::new(
-                            top_right_point[1].original_span(),
-                            Some("180.0".to_string()),
-                        );
-
-                        let condition_left = FilterCondition::Condition {
-                            fid: geo_lng_token.clone(),
-                            op: Condition::Between {
-                                from: bottom_left_point[1].clone(),
-                                to: max_lng_token,
-                            },
-                        };
-                        let left = Filter { condition: condition_left }.inner_evaluate(
-                            rtxn,
-                            index,
-                            filterable_fields,
-                            universe,
-                        )?;
-
-                        let condition_right = FilterCondition::Condition {
-                            fid: geo_lng_token,
-                            op: Condition::Between {
-                                from: min_lng_token,
-                                to: top_right_point[1].clone(),
-                            },
-                        };
-                        let right = Filter { condition: condition_right }.inner_evaluate(
-                            rtxn,
-                            index,
-                            filterable_fields,
-                            universe,
-                        )?;
-
-                        left | right
+                    // When the right longitude is lower than the left one, the bounding box is
+                    // wrapping around the earth (going from 180 to -180). In this case we shift
+                    // the right longitude by 360 degrees, as we do with the vertices of the polygons.
+                    let min = bottom_left;
+                    let max = if top_right[1] < bottom_left[1] {
+                        [top_right[0], top_right[1] + 360.0]
                     } else {
-                        let condition_lng = FilterCondition::Condition {
-                            fid: geo_lng_token,
-                            op: Condition::Between {
-                                from: bottom_left_point[1].clone(),
-                                to: top_right_point[1].clone(),
-                            },
-                        };
-                        Filter { condition: condition_lng }.inner_evaluate(
-                            rtxn,
-                            index,
-                            filterable_fields,
-                            universe,
-                        )?
+                        top_right
                     };
 
-                    Ok(selected_lat & selected_lng)
+                    // A document is matched as soon as one of its points is in the bounding box,
+                    // we can't combine the ranges of the `_geo.lat` and `_geo.lng` facets because
+                    // they could come from two different points.
+                    let result = rtree
+                        .locate_in_envelope(&lat_lng_envelope(min, max))
+                        .filter(|point| {
+                            let [lat, lng] = point.data.1;
+                            (min[0]..=max[0]).contains(&lat)
+                                && [lng, lng + 360.0]
+                                    .iter()
+                                    .any(|lng| (min[1]..=max[1]).contains(lng))
+                        })
+                        .map(|point| point.data.0)
+                        .collect();
+
+                    Ok(result)
                 } else {
                     Err(top_right_point[0].as_external_error(
                         FilterError::AttributeNotFilterable {
@@ -601,6 +577,50 @@ impl<'a> Filter<'a> {
                     ))?
                 }
             }
+            FilterCondition::GeoPolygon { points } => {
+                if filterable_fields.contains("_geo") {
+                    let mut vertices = Vec::with_capacity(points.len());
+                    for [lat_token, lng_token] in points {
+                        let point =
+                            [lat_token.parse_finite_float()?, lng_token.parse_finite_float()?];
+                        if !(-90.0..=90.0).contains(&point[0]) {
+                            return Err(lat_token.as_external_error(BadGeoError::Lat(point[0])))?;
+                        }
+                        if !(-180.0..=180.0).contains(&point[1]) {
+                            return Err(lng_token.as_external_error(BadGeoError::Lng(point[1])))?;
+                        }
+                        vertices.push(point);
+                    }
+                    let polygon = match GeoPolygon::new(&vertices) {
+                        Some(polygon) => polygon,
+                        None => {
+                            return Err(
+                                points[0][0].as_external_error(BadGeoError::PolygonAroundPole)
+                            )?
+                        }
+                    };
+
+                    let rtree = match index.geo_rtree(rtxn)? {
+                        Some(rtree) => rtree,
+                        None => return Ok(RoaringBitmap::new()),
+                    };
+
+                    // The rtree gives us the points in the bounding box of the polygon,
+                    // that we then check one by one.
+                    let result = rtree
+                        .locate_in_envelope(&polygon.envelope())
+                        .filter(|point| polygon.contains(point.data.1))
+                        .map(|point| point.data.0)
+                        .collect();
+
+                    Ok(result)
+                } else {
+                    Err(points[0][0].as_external_error(FilterError::AttributeNotFilterable {
+                        attribute: "_geo",
+                        filterable_fields: filterable_fields.clone(),
+                    }))?
+                }
+            }
         }
     }
 }
@@ -611,6 +631,167 @@ impl<'a> From<FilterCondition<'a>> for Filter<'a> {
     }
 }
 
+/// Parses a filter value as a number or, when it is an RFC 3339 date, as its unix timestamp.
+fn parse_number_or_date<'a>(token: &Token<'a>) -> std::result::Result<f64, FPError<'a>> {
+    token.parse_finite_float().or_else(|error| crate::date_to_timestamp(token.value()).ok_or(error))
+}
+
+/// A polygon given to the `_geoPolygon` filter.
+///
+/// Its edges are straight lines between the latitudes and longitudes of its vertices, and each of
+/// them goes the shortest way around the earth: an edge going from a longitude of `170` to a
+/// longitude of `-170` crosses the antimeridian. To handle that, the longitude of every vertex is
+/// shifted by 360 degrees when it is more than 180 degrees away from the previous one, which means
+/// that the longitudes of the polygon can go beyond the `-180..=180` range.
+#[derive(Debug)]
+struct GeoPolygon {
+    vertices: Vec<[f64; 2]>,
+    /// The lowest latitude and longitude of the vertices.
+    min: [f64; 2],
+    /// The highest latitude and longitude of the vertices.
+    max: [f64; 2],
+}
+
+impl GeoPolygon {
+    /// The tolerance, in degrees, used to decide whether a point lies on an edge.
+    const EPSILON: f64 = 1e-9;
+
+    /// Returns `None` if the polygon goes around a pole.
+    fn new(points: &[[f64; 2]]) -> Option<Self> {
+        let mut vertices: Vec<[f64; 2]> = Vec::with_capacity(points.len());
+        for &[lat, lng] in points {
+            let lng = match vertices.last() {
+                Some(&[_, previous_lng]) => unwrap_longitude(lng, previous_lng),
+                None => lng,
+            };
+            vertices.push([lat, lng]);
+        }
+
+        // when the closing edge does not lead back to the first vertex but to one of its copies
+        // 360 degrees away, the polygon goes around the earth and thus around a pole
+        let [_, first_lng] = vertices[0];
+        let [_, last_lng] = vertices[vertices.len() - 1];
+        if (unwrap_longitude(first_lng, last_lng) - first_lng).abs() > 180.0 {
+            return None;
+        }
+
+        let lat = bounds(vertices.iter().map(|[lat, _]| *lat));
+        let lng = bounds(vertices.iter().map(|[_, lng]| *lng));
+        Some(GeoPolygon { vertices, min: [lat[0], lng[0]], max: [lat[1], lng[1]] })
+    }
+
+    /// Returns the box, in the cartesian coordinates of the geo rtree, that contains every point
+    /// between the lowest and highest latitudes and longitudes of the polygon.
+    fn envelope(&self) -> AABB<[f64; 3]> {
+        lat_lng_envelope(self.min, self.max)
+    }
+
+    /// Returns `true` if the point is inside the polygon or on one of its edges.
+    fn contains(&self, [lat, lng]: [f64; 2]) -> bool {
+        if lat < self.min[0] || lat > self.max[0] {
+            return false;
+        }
+        // the longitude of the point is shifted in the same way as the longitudes of the vertices
+        [lng, lng - 360.0, lng + 360.0]
+            .into_iter()
+            .filter(|lng| (self.min[1]..=self.max[1]).contains(lng))
+            .any(|lng| self.contains_unwrapped([lat, lng]))
+    }
+
+    fn contains_unwrapped(&self, point: [f64; 2]) -> bool {
+        let [lat, lng] = point;
+        let mut inside = false;
+        let edges = self.vertices.iter().zip(self.vertices.iter().cycle().skip(1));
+        for (&a, &b) in edges {
+            if Self::on_edge(point, a, b) {
+                return true;
+            }
+            // we cast a ray from the point towards the increasing longitudes
+            // and count the number of edges it crosses
+            if (a[0] > lat) != (b[0] > lat) {
+                let crossing_lng = a[1] + (lat - a[0]) * (b[1] - a[1]) / (b[0] - a[0]);
+                if lng < crossing_lng {
+                    inside = !inside;
+                }
+            }
+        }
+        inside
+    }
+
+    fn on_edge(point: [f64; 2], a: [f64; 2], b: [f64; 2]) -> bool {
+        let in_box = (0..2).all(|i| {
+            point[i] >= a[i].min(b[i]) - Self::EPSILON && point[i] <= a[i].max(b[i]) + Self::EPSILON
+        });
+        let cross = (b[0] - a[0]) * (point[1] - a[1]) - (b[1] - a[1]) * (point[0] - a[0]);
+        let length = (b[0] - a[0]).hypot(b[1] - a[1]);
+        in_box && cross.abs() <= Self::EPSILON * length.max(1.0)
+    }
+}
+
+/// Returns the box, in the cartesian coordinates of the geo rtree, that contains every point
+/// between the `min` and `max` latitudes and longitudes.
+///
+/// The longitudes can go beyond the `-180..=180` range to describe an area crossing the antimeridian.
+pub(crate) fn lat_lng_envelope(min: [f64; 2], max: [f64; 2]) -> AABB<[f64; 3]> {
+    const MARGIN: f64 = 1e-9;
+
+    let [min_lat, min_lng] = min.map(f64::to_radians);
+    let [max_lat, max_lng] = max.map(f64::to_radians);
+
+    // the cosine of the latitude is the largest on the equator
+    let mut cos_lat = bounds([min_lat.cos(), max_lat.cos()]);
+    if min_lat <= 0.0 && 0.0 <= max_lat {
+        cos_lat[1] = 1.0;
+    }
+
+    // the cosine and sine of the longitude reach their extremums on the multiples of 90 degrees
+    let quarters = (min[1] / 90.0).ceil() as i64..=(max[1] / 90.0).floor() as i64;
+    let cardinals = quarters.map(|quarter| match quarter.rem_euclid(4) {
+        0 => [1.0, 0.0],
+        1 => [0.0, 1.0],
+        2 => [-1.0, 0.0],
+        _ => [0.0, -1.0],
+    });
+    let directions: Vec<[f64; 2]> =
+        [[min_lng.cos(), min_lng.sin()], [max_lng.cos(), max_lng.sin()]]
+            .into_iter()
+            .chain(cardinals)
+            .collect();
+    let cos_lng = bounds(directions.iter().map(|[cos, _]| *cos));
+    let sin_lng = bounds(directions.iter().map(|[_, sin]| *sin));
+
+    // the cosine of the latitude is positive, so the bounds of the products
+    // are among the products of the bounds
+    let x = bounds(cos_lat.into_iter().flat_map(|c| cos_lng.map(|cos| c * cos)));
+    let y = bounds(cos_lat.into_iter().flat_map(|c| sin_lng.map(|sin| c * sin)));
+    let z = [min_lat.sin(), max_lat.sin()];
+
+    // the bounds are slightly widened to not miss points on the edges because of rounding errors
+    AABB::from_corners(
+        [x[0] - MARGIN, y[0] - MARGIN, z[0] - MARGIN],
+        [x[1] + MARGIN, y[1] + MARGIN, z[1] + MARGIN],
+    )
+}
+
+/// Shifts the longitude by a multiple of 360 degrees to bring it at most 180 degrees away from the
+/// previous one.
+fn unwrap_longitude(mut lng: f64, previous_lng: f64) -> f64 {
+    while lng - previous_lng > 180.0 {
+        lng -= 360.0;
+    }
+    while lng - previous_lng < -180.0 {
+        lng += 360.0;
+    }
+    lng
+}
+
+/// Returns the lowest and highest values.
+fn bounds(values: impl IntoIterator<Item = f64>) -> [f64; 2] {
+    values.into_iter().fold([f64::INFINITY, f64::NEG_INFINITY], |[min, max], value| {
+        [min.min(value), max.max(value)]
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use std::fmt::Write;
@@ -989,6 +1170,55 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn geo_polygon_error() {
+        let index = TempIndex::new();
+
+        let rtxn = index.read_txn().unwrap();
+        let filter =
+            Filter::from_str("_geoPolygon([10, 10], [20, 10], [20, 20])").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().starts_with(
+            "Attribute `_geo` is not filterable. This index does not have configured filterable attributes."
+        ));
+        drop(rtxn);
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("_geo") });
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+
+        // geopolygon have a bad latitude
+        let filter =
+            Filter::from_str("_geoPolygon([10, 10], [90.0000001, 10], [20, 20])").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().contains(
+            "Bad latitude `90.0000001`. Latitude must be contained between -90 and 90 degrees."
+        ));
+
+        // geopolygon have a bad longitude
+        let filter = Filter::from_str("_geoPolygon([10, 10], [20, 10], [20, -180.000001])")
+            .unwrap()
+            .unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().contains(
+            "Bad longitude `-180.000001`. Longitude must be contained between -180 and 180 degrees."
+        ));
+
+        // geopolygon goes around the south pole
+        let filter =
+            Filter::from_str("_geoPolygon([-60, -180], [-60, -60], [-60, 60], [-60, 180])")
+                .unwrap()
+                .unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().starts_with(
+            "The `_geoPolygon` filter goes around a pole. Split it into several polygons that do not contain a pole."
+        ));
+    }
+
     #[test]
     fn filter_depth() {
         // generates a big (2 MiB) filter with too much of ORs.
@@ -1149,4 +1379,54 @@ mod tests {
         let result = filter.evaluate(&rtxn, &index).unwrap();
         assert_eq!(result, RoaringBitmap::from_iter((0..100).filter(|x| x % 10 != 0)));
     }
+
+    #[test]
+    fn filter_date() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_primary_key("id".to_owned());
+                settings.set_filterable_fields(hashset! { S("published") });
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 0, "published": "2023-12-31" },
+                { "id": 1, "published": "2024-01-01T00:00:00Z" },
+                { "id": 2, "published": "2024-01-01T12:00:00+02:00" },
+                { "id": 3, "published": "2024-03-15" },
+                { "id": 4, "published": "not a date" },
+                { "id": 5, "published": 1704067200 },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let filtered = |filter: &str| {
+            let filter = Filter::from_str(filter).unwrap().unwrap();
+            filter.evaluate(&rtxn, &index).unwrap().into_iter().collect::<Vec<_>>()
+        };
+
+        assert_eq!(filtered("published > 2024-01-01"), vec![2, 3]);
+        assert_eq!(filtered("published >= 2024-01-01"), vec![1, 2, 3, 5]);
+        assert_eq!(filtered("published < \"2024-01-01T10:00:00Z\""), vec![0, 1, 5]);
+        assert_eq!(filtered("published 2023-12-31 TO 2024-01-01"), vec![0, 1, 5]);
+        assert_eq!(filtered("published = 2024-01-01"), vec![1, 5]);
+        assert_eq!(filtered("published = \"2024-01-01T00:00:00Z\""), vec![1, 5]);
+        assert_eq!(filtered("published != 2024-01-01"), vec![0, 2, 3, 4]);
+        assert_eq!(filtered("published = \"not a date\""), vec![4]);
+
+        let filter = Filter::from_str("published > \"not a date\"").unwrap().unwrap();
+        assert!(matches!(
+            filter.evaluate(&rtxn, &index),
+            Err(crate::Error::UserError(crate::error::UserError::InvalidFilter(_)))
+        ));
+
+        // the dates are returned unchanged in the documents
+        let fid = index.fields_ids_map(&rtxn).unwrap().id("published").unwrap();
+        let (_, document) = index.documents(&rtxn, Some(2)).unwrap().pop().unwrap();
+        let published: serde_json::Value =
+            serde_json::from_slice(document.get(fid).unwrap()).unwrap();
+        assert_eq!(published, serde_json::json!("2024-01-01T12:00:00+02:00"));
+    }
 }
@@ -4,7 +4,12 @@ use heed::types::{Bytes, DecodeIgnore};
 use heed::{BytesDecode, RoTxn};
 use roaring::RoaringBitmap;
 
-pub use self::facet_distribution::{FacetDistribution, OrderBy, DEFAULT_VALUES_PER_FACET};
+pub use self::facet_distribution::{
+    FacetDistribution, FacetRange, FacetRangeCount, FacetRanges, FacetStatsAggregations, OrderBy,
+    DEFAULT_VALUES_PER_FACET, MAX_HISTOGRAM_BUCKETS,
+};
+pub(crate) use self::facet_range_search::find_docids_of_facet_within_bounds;
+pub(crate) use self::filter::lat_lng_envelope;
 pub use self::filter::{BadGeoError, Filter};
 pub use self::search::{FacetValueHit, SearchForFacetValues};
 use crate::heed_codec::facet::{FacetGroupKeyCodec, OrderedF64Codec};
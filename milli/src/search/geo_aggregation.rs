@@ -0,0 +1,342 @@
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+use roaring::RoaringBitmap;
+
+use crate::error::UserError;
+use crate::search::facet::lat_lng_envelope;
+use crate::{lat_lng_to_xyz, Index, Result};
+
+/// The maximum number of cells returned by a geo aggregation, the densest ones are kept.
+pub const MAX_GEO_CELLS: usize = 10_000;
+
+/// The maximum length of the geohashes a geo aggregation can use.
+pub const MAX_GEOHASH_PRECISION: u8 = 12;
+
+/// The maximum zoom level of the map tiles a geo aggregation can use.
+pub const MAX_TILE_ZOOM: u8 = 29;
+
+/// The latitudes covered by the web mercator projection used by the map tiles.
+const MAX_MERCATOR_LATITUDE: f64 = 85.05112878;
+
+const GEOHASH_ALPHABET: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+/// The grid the geo points are aggregated on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeoGrid {
+    /// The geohash cells of the given length, from 1 to 12.
+    Geohash(u8),
+    /// The web mercator map tiles of the given zoom level, from 0 to 29, identified by `zoom/x/y`.
+    Tile(u8),
+}
+
+impl GeoGrid {
+    fn cell_of(&self, point: [f64; 2]) -> String {
+        match *self {
+            GeoGrid::Geohash(precision) => geohash(point, precision),
+            GeoGrid::Tile(zoom) => tile(point, zoom),
+        }
+    }
+}
+
+/// An area delimited by its top right and bottom left corners.
+///
+/// When the right longitude is lower than the left one the area crosses the antimeridian.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeoBoundingBox {
+    pub top_right: [f64; 2],
+    pub bottom_left: [f64; 2],
+}
+
+/// The documents having at least one of their points in a cell of the grid.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeoCell {
+    /// The geohash or the `zoom/x/y` coordinates of the tile.
+    pub key: String,
+    /// The number of candidates with a point in the cell, a document is counted once per cell.
+    pub count: u64,
+    /// The latitude and longitude of the center of mass of the points in the cell.
+    pub centroid: [f64; 2],
+}
+
+/// Counts the candidates per cell of a geohash or map tiles grid.
+///
+/// The points are read from the geo rtree of the index, a document with several
+/// points is counted in every cell containing one of them.
+pub struct GeoAggregation<'a> {
+    grid: GeoGrid,
+    bounds: Option<GeoBoundingBox>,
+    candidates: Option<RoaringBitmap>,
+    rtxn: &'a heed::RoTxn<'a>,
+    index: &'a Index,
+}
+
+impl<'a> GeoAggregation<'a> {
+    pub fn new(grid: GeoGrid, index: &'a Index, rtxn: &'a heed::RoTxn<'a>) -> Self {
+        Self { grid, bounds: None, candidates: None, rtxn, index }
+    }
+
+    /// Only aggregates the points contained in the given area.
+    pub fn bounds(&mut self, bounds: GeoBoundingBox) -> &mut Self {
+        self.bounds = Some(bounds);
+        self
+    }
+
+    pub fn candidates(&mut self, candidates: RoaringBitmap) -> &mut Self {
+        self.candidates = Some(candidates);
+        self
+    }
+
+    /// Returns the cells containing at least one candidate, from the densest to the sparsest.
+    pub fn execute(&self) -> Result<Vec<GeoCell>> {
+        let filterable_fields = self.index.filterable_fields(self.rtxn)?;
+        let sortable_fields = self.index.sortable_fields(self.rtxn)?;
+        if !filterable_fields.contains("_geo") && !sortable_fields.contains("_geo") {
+            return Err(UserError::InvalidGeoAggregation.into());
+        }
+
+        let rtree = match self.index.geo_rtree(self.rtxn)? {
+            Some(rtree) => rtree,
+            None => return Ok(Vec::new()),
+        };
+        let mut candidates = self.index.geo_faceted_documents_ids(self.rtxn)?;
+        if let Some(ref universe) = self.candidates {
+            candidates &= universe;
+        }
+        if candidates.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let points: Box<dyn Iterator<Item = _>> = match self.bounds {
+            Some(GeoBoundingBox { top_right, bottom_left }) => {
+                // we shift the right longitude by 360 degrees when crossing the antimeridian,
+                // as the bounding box filter does.
+                let min = bottom_left;
+                let max = if top_right[1] < bottom_left[1] {
+                    [top_right[0], top_right[1] + 360.0]
+                } else {
+                    top_right
+                };
+                Box::new(rtree.locate_in_envelope(&lat_lng_envelope(min, max)).filter(
+                    move |point| {
+                        let [lat, lng] = point.data.1;
+                        (min[0]..=max[0]).contains(&lat)
+                            && [lng, lng + 360.0].iter().any(|lng| (min[1]..=max[1]).contains(lng))
+                    },
+                ))
+            }
+            None => Box::new(rtree.iter()),
+        };
+
+        let mut cells: HashMap<String, (RoaringBitmap, [f64; 3])> = HashMap::new();
+        for point in points {
+            let (docid, lat_lng) = point.data;
+            if !candidates.contains(docid) {
+                continue;
+            }
+            let (docids, sum) = cells.entry(self.grid.cell_of(lat_lng)).or_default();
+            docids.insert(docid);
+            for (sum, coord) in sum.iter_mut().zip(lat_lng_to_xyz(&lat_lng)) {
+                *sum += coord;
+            }
+        }
+
+        let mut cells: Vec<_> = cells
+            .into_iter()
+            .map(|(key, (docids, [x, y, z]))| GeoCell {
+                key,
+                count: docids.len(),
+                centroid: [z.atan2(x.hypot(y)).to_degrees(), y.atan2(x).to_degrees()],
+            })
+            .collect();
+        cells.sort_unstable_by(|a, b| b.count.cmp(&a.count).then_with(|| a.key.cmp(&b.key)));
+        cells.truncate(MAX_GEO_CELLS);
+
+        Ok(cells)
+    }
+}
+
+/// Returns the geohash of the given length containing the point.
+fn geohash([lat, lng]: [f64; 2], precision: u8) -> String {
+    let mut lat_range = [-90.0, 90.0];
+    let mut lng_range = [-180.0, 180.0];
+    // the bits of the geohash alternate between the longitude and the latitude
+    let mut is_lng = true;
+
+    let mut hash = String::with_capacity(precision as usize);
+    for _ in 0..precision {
+        let mut index = 0;
+        for _ in 0..5 {
+            let (range, value) = if is_lng { (&mut lng_range, lng) } else { (&mut lat_range, lat) };
+            let middle = (range[0] + range[1]) / 2.0;
+            index <<= 1;
+            if value >= middle {
+                index |= 1;
+                range[0] = middle;
+            } else {
+                range[1] = middle;
+            }
+            is_lng = !is_lng;
+        }
+        hash.push(GEOHASH_ALPHABET[index] as char);
+    }
+    hash
+}
+
+/// Returns the `zoom/x/y` coordinates of the map tile containing the point.
+fn tile([lat, lng]: [f64; 2], zoom: u8) -> String {
+    let tiles = 2f64.powi(zoom as i32);
+    let lat = lat.clamp(-MAX_MERCATOR_LATITUDE, MAX_MERCATOR_LATITUDE).to_radians();
+    let x = ((lng + 180.0) / 360.0 * tiles).floor().clamp(0.0, tiles - 1.0);
+    let y = ((1.0 - lat.tan().asinh() / PI) / 2.0 * tiles).floor().clamp(0.0, tiles - 1.0);
+    format!("{zoom}/{}/{}", x as u32, y as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use big_s::S;
+    use maplit::hashset;
+
+    use super::*;
+    use crate::index::tests::TempIndex;
+
+    fn create_index() -> TempIndex {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|s| {
+                s.set_primary_key("id".to_owned());
+                s.set_filterable_fields(hashset! { S("_geo"), S("city") });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "city": "paris", "_geo": { "lat": 48.8566, "lng": 2.3522 } },
+                { "id": 1, "city": "paris", "_geo": { "lat": 48.8606, "lng": 2.3376 } },
+                { "id": 2, "city": "milan", "_geo": { "lat": 45.4777599, "lng": 9.1967508 } },
+                { "id": 3, "city": "los angeles", "_geo": { "lat": 34.0522, "lng": -118.2437 } },
+                { "id": 4, "city": "paris", "_geo": [
+                    { "lat": 48.8584, "lng": 2.2945 },
+                    { "lat": 48.8530, "lng": 2.3499 },
+                ] },
+                { "id": 5, "city": "nowhere" },
+            ]))
+            .unwrap();
+
+        index
+    }
+
+    #[test]
+    fn geohash_cells() {
+        assert_eq!(geohash([57.64911, 10.40744], 11), "u4pruydqqvj");
+        assert_eq!(geohash([48.8566, 2.3522], 5), "u09tv");
+        assert_eq!(geohash([34.0522, -118.2437], 3), "9q5");
+        assert_eq!(geohash([0.0, 0.0], 1), "s");
+    }
+
+    #[test]
+    fn tile_cells() {
+        assert_eq!(tile([48.8566, 2.3522], 0), "0/0/0");
+        assert_eq!(tile([48.8566, 2.3522], 10), "10/518/352");
+        assert_eq!(tile([90.0, 180.0], 2), "2/3/0");
+        assert_eq!(tile([-90.0, -180.0], 2), "2/0/3");
+    }
+
+    #[test]
+    fn aggregate_on_geohashes() {
+        let index = create_index();
+        let rtxn = index.read_txn().unwrap();
+
+        let cells = GeoAggregation::new(GeoGrid::Geohash(3), &index, &rtxn).execute().unwrap();
+        let cells: Vec<_> = cells.iter().map(|cell| (cell.key.as_str(), cell.count)).collect();
+        insta::assert_debug_snapshot!(cells, @r###"
+        [
+            (
+                "u09",
+                3,
+            ),
+            (
+                "9q5",
+                1,
+            ),
+            (
+                "u0n",
+                1,
+            ),
+        ]
+        "###);
+
+        // the document with two points in the same cell is counted once
+        let mut aggregation = GeoAggregation::new(GeoGrid::Geohash(9), &index, &rtxn);
+        let cells = aggregation.candidates(RoaringBitmap::from_iter([4])).execute().unwrap();
+        let cells: Vec<_> = cells.iter().map(|cell| (cell.key.as_str(), cell.count)).collect();
+        insta::assert_debug_snapshot!(cells, @r###"
+        [
+            (
+                "u09tunquc",
+                1,
+            ),
+            (
+                "u09tvmrjk",
+                1,
+            ),
+        ]
+        "###);
+
+        let cells = GeoAggregation::new(GeoGrid::Geohash(1), &index, &rtxn)
+            .candidates(RoaringBitmap::from_iter([0, 1]))
+            .execute()
+            .unwrap();
+        assert_eq!(cells.len(), 1);
+        assert_eq!(cells[0].key, "u");
+        assert_eq!(cells[0].count, 2);
+        let [lat, lng] = cells[0].centroid;
+        assert!((lat - 48.8586).abs() < 1e-3, "{lat}");
+        assert!((lng - 2.3449).abs() < 1e-3, "{lng}");
+    }
+
+    #[test]
+    fn aggregate_on_tiles_within_bounds() {
+        let index = create_index();
+        let rtxn = index.read_txn().unwrap();
+
+        let cells = GeoAggregation::new(GeoGrid::Tile(4), &index, &rtxn)
+            .bounds(GeoBoundingBox { top_right: [50.0, 10.0], bottom_left: [40.0, 0.0] })
+            .execute()
+            .unwrap();
+        let cells: Vec<_> = cells.iter().map(|cell| (cell.key.as_str(), cell.count)).collect();
+        insta::assert_debug_snapshot!(cells, @r###"
+        [
+            (
+                "4/8/5",
+                4,
+            ),
+        ]
+        "###);
+
+        // crossing the antimeridian
+        let cells = GeoAggregation::new(GeoGrid::Tile(0), &index, &rtxn)
+            .bounds(GeoBoundingBox { top_right: [50.0, -100.0], bottom_left: [30.0, 170.0] })
+            .execute()
+            .unwrap();
+        let cells: Vec<_> = cells.iter().map(|cell| (cell.key.as_str(), cell.count)).collect();
+        insta::assert_debug_snapshot!(cells, @r###"
+        [
+            (
+                "0/0/0",
+                1,
+            ),
+        ]
+        "###);
+    }
+
+    #[test]
+    fn aggregate_without_geo_field() {
+        let index = TempIndex::new();
+        let rtxn = index.read_txn().unwrap();
+
+        let error = GeoAggregation::new(GeoGrid::Geohash(3), &index, &rtxn).execute().unwrap_err();
+        insta::assert_display_snapshot!(error, @"The `_geo` field must be filterable or sortable to aggregate the documents on a geo grid. Add `_geo` to the `filterableAttributes` index setting.");
+    }
+}
@@ -142,6 +142,7 @@ impl ScoreWithRatioResult {
                 candidates: vector_results.candidates | keyword_results.candidates,
                 documents_ids,
                 document_scores,
+                cursor: None,
                 degraded: vector_results.degraded | keyword_results.degraded,
                 used_negative_operator: vector_results.used_negative_operator
                     | keyword_results.used_negative_operator,
@@ -161,9 +162,12 @@ impl<'a> Search<'a> {
             filter: self.filter.clone(),
             offset: 0,
             limit: self.limit + self.offset,
+            search_after: None,
             sort_criteria: self.sort_criteria.clone(),
+            ranking_rules: self.ranking_rules.clone(),
             distinct: self.distinct.clone(),
             searchable_attributes: self.searchable_attributes,
+            attributes_weights: self.attributes_weights,
             geo_strategy: self.geo_strategy,
             terms_matching_strategy: self.terms_matching_strategy,
             scoring_strategy: ScoringStrategy::Detailed,
@@ -174,6 +178,8 @@ impl<'a> Search<'a> {
             semantic: self.semantic.clone(),
             time_budget: self.time_budget.clone(),
             ranking_score_threshold: self.ranking_score_threshold,
+            locales: self.locales.clone(),
+            typo_tolerant_negation: self.typo_tolerant_negation,
         };
 
         let semantic = search.semantic.take();
@@ -184,8 +190,9 @@ impl<'a> Search<'a> {
             return Ok(return_keyword_results(self.limit, self.offset, keyword_results));
         }
 
-        // no vector search against placeholder search
-        let Some(query) = search.query.take() else {
+        // no vector search against placeholder search,
+        // the query is kept to resolve the pinned documents of the semantic search
+        let Some(query) = search.query.clone() else {
             return Ok(return_keyword_results(self.limit, self.offset, keyword_results));
         };
         // no embedder, no semantic search
@@ -254,6 +261,7 @@ fn return_keyword_results(
         candidates,
         mut documents_ids,
         mut document_scores,
+        cursor: _,
         degraded,
         used_negative_operator,
     }: SearchResult,
@@ -280,6 +288,8 @@ fn return_keyword_results(
             candidates,
             documents_ids,
             document_scores,
+            // the hits of a hybrid search can't be resumed after
+            cursor: None,
             degraded,
             used_negative_operator,
         },
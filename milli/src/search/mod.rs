@@ -1,18 +1,28 @@
+use std::collections::BTreeMap;
 use std::fmt;
 use std::sync::Arc;
 
+use charabia::Language;
 use levenshtein_automata::{LevenshteinAutomatonBuilder as LevBuilder, DFA};
 use once_cell::sync::Lazy;
 use roaring::bitmap::RoaringBitmap;
 
-pub use self::facet::{FacetDistribution, Filter, OrderBy, DEFAULT_VALUES_PER_FACET};
+pub use self::facet::{
+    FacetDistribution, FacetRange, FacetRangeCount, FacetRanges, FacetStatsAggregations, Filter,
+    OrderBy, DEFAULT_VALUES_PER_FACET,
+};
 pub use self::new::matches::{FormatOptions, MatchBounds, MatcherBuilder, MatchingWords};
-use self::new::{execute_vector_search, PartialSearchResult};
+use self::new::{
+    distinct_single_docid, execute_search_candidates, execute_vector_search, PartialSearchResult,
+    PlaceholderQuery, QueryGraph,
+};
+pub use self::new::{InvalidSearchCursor, SearchCursor, SearchCursorToken};
 use crate::score_details::{ScoreDetails, ScoringStrategy};
 use crate::vector::Embedder;
 use crate::{
-    execute_search, filtered_universe, AscDesc, DefaultSearchLogger, DocumentId, Error, Index,
-    Result, SearchContext, TimeBudget, UserError,
+    execute_search, filtered_universe, AscDesc, Criterion, DefaultSearchLogger, DocumentId, Error,
+    ExplainSearchLogger, FilterCondition, Index, Result, SearchContext, SearchExplanation,
+    SearchLogger, TimeBudget, UserError, Weight,
 };
 
 // Building these factories is not free.
@@ -20,8 +30,14 @@ static LEVDIST0: Lazy<LevBuilder> = Lazy::new(|| LevBuilder::new(0, true));
 static LEVDIST1: Lazy<LevBuilder> = Lazy::new(|| LevBuilder::new(1, true));
 static LEVDIST2: Lazy<LevBuilder> = Lazy::new(|| LevBuilder::new(2, true));
 
+/// The number of words of a query that are searched for by default.
+pub(crate) const DEFAULT_WORDS_LIMIT: usize = 10;
+
+pub mod analyze;
+pub mod autocomplete;
 pub mod facet;
 mod fst_utils;
+pub mod geo_aggregation;
 pub mod hybrid;
 pub mod new;
 pub mod similar;
@@ -39,9 +55,12 @@ pub struct Search<'a> {
     filter: Option<Filter<'a>>,
     offset: usize,
     limit: usize,
+    search_after: Option<SearchCursor>,
     sort_criteria: Option<Vec<AscDesc>>,
+    ranking_rules: Option<Vec<Criterion>>,
     distinct: Option<String>,
     searchable_attributes: Option<&'a [String]>,
+    attributes_weights: Option<&'a [(String, Weight)]>,
     geo_strategy: new::GeoSortStrategy,
     terms_matching_strategy: TermsMatchingStrategy,
     scoring_strategy: ScoringStrategy,
@@ -52,6 +71,8 @@ pub struct Search<'a> {
     semantic: Option<SemanticSearch>,
     time_budget: TimeBudget,
     ranking_score_threshold: Option<f64>,
+    locales: Option<Vec<Language>>,
+    typo_tolerant_negation: bool,
 }
 
 impl<'a> Search<'a> {
@@ -61,19 +82,24 @@ impl<'a> Search<'a> {
             filter: None,
             offset: 0,
             limit: 20,
+            search_after: None,
             sort_criteria: None,
+            ranking_rules: None,
             distinct: None,
             searchable_attributes: None,
+            attributes_weights: None,
             geo_strategy: new::GeoSortStrategy::default(),
             terms_matching_strategy: TermsMatchingStrategy::default(),
             scoring_strategy: Default::default(),
             exhaustive_number_hits: false,
-            words_limit: 10,
+            words_limit: DEFAULT_WORDS_LIMIT,
             rtxn,
             index,
             semantic: None,
             time_budget: TimeBudget::max(),
             ranking_score_threshold: None,
+            locales: None,
+            typo_tolerant_negation: false,
         }
     }
 
@@ -102,11 +128,23 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Resumes the search after the document the cursor was returned for.
+    pub fn search_after(&mut self, cursor: SearchCursor) -> &mut Search<'a> {
+        self.search_after = Some(cursor);
+        self
+    }
+
     pub fn sort_criteria(&mut self, criteria: Vec<AscDesc>) -> &mut Search<'a> {
         self.sort_criteria = Some(criteria);
         self
     }
 
+    /// Replaces the ranking rules of the index for this search only.
+    pub fn ranking_rules(&mut self, ranking_rules: Vec<Criterion>) -> &mut Search<'a> {
+        self.ranking_rules = Some(ranking_rules);
+        self
+    }
+
     pub fn distinct(&mut self, distinct: String) -> &mut Search<'a> {
         self.distinct = Some(distinct);
         self
@@ -117,6 +155,13 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Overrides the order in which the attribute ranking rule sorts the searchable attributes,
+    /// the attributes given a weight come first by decreasing weight.
+    pub fn attributes_weights(&mut self, weights: &'a [(String, Weight)]) -> &mut Search<'a> {
+        self.attributes_weights = Some(weights);
+        self
+    }
+
     pub fn terms_matching_strategy(&mut self, value: TermsMatchingStrategy) -> &mut Search<'a> {
         self.terms_matching_strategy = value;
         self
@@ -160,6 +205,18 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Forces the query to be tokenized using the given locales instead of detecting its language.
+    pub fn locales(&mut self, locales: Vec<Language>) -> &mut Search<'a> {
+        self.locales = Some(locales);
+        self
+    }
+
+    /// Also excludes the documents containing typo derivations of the negated words.
+    pub fn typo_tolerant_negation(&mut self, value: bool) -> &mut Search<'a> {
+        self.typo_tolerant_negation = value;
+        self
+    }
+
     pub fn execute_for_candidates(&self, has_vector_search: bool) -> Result<RoaringBitmap> {
         if has_vector_search {
             let ctx = SearchContext::new(self.index, self.rtxn)?;
@@ -170,37 +227,44 @@ impl<'a> Search<'a> {
     }
 
     pub fn execute(&self) -> Result<SearchResult> {
-        let mut ctx = SearchContext::new(self.index, self.rtxn)?;
-
-        if let Some(searchable_attributes) = self.searchable_attributes {
-            ctx.attributes_to_search_on(searchable_attributes)?;
-        }
+        let universe = filtered_universe(self.index, self.rtxn, &self.filter)?;
+        self.execute_in_universe(self.search_context()?, universe)
+    }
 
-        if let Some(distinct) = &self.distinct {
-            let filterable_fields = ctx.index.filterable_fields(ctx.txn)?;
-            if !crate::is_faceted(distinct, &filterable_fields) {
-                let (valid_fields, hidden_fields) =
-                    ctx.index.remove_hidden_fields(ctx.txn, filterable_fields)?;
-                return Err(Error::UserError(UserError::InvalidDistinctAttribute {
-                    field: distinct.clone(),
-                    valid_fields,
-                    hidden_fields,
-                }));
-            }
-        }
+    /// Executes the keyword search and explains how the ranking rules sorted its candidates,
+    /// and where the given documents landed in their buckets.
+    pub fn explain(&self, documents: &[DocumentId]) -> Result<(SearchResult, SearchExplanation)> {
+        let universe = filtered_universe(self.index, self.rtxn, &self.filter)?;
+        let mut ctx = self.search_context()?;
+
+        let mut placeholder_logger = ExplainSearchLogger::new(documents);
+        let mut query_graph_logger = ExplainSearchLogger::new(documents);
+        let result = self.execute_keyword_search(
+            &mut ctx,
+            universe,
+            &mut placeholder_logger,
+            &mut query_graph_logger,
+        )?;
+
+        // only one of the loggers is used, depending on whether the search is a placeholder one
+        let logger =
+            if query_graph_logger.is_used() { query_graph_logger } else { placeholder_logger };
+        let explanation = logger.finish(&mut ctx, &result.documents_ids)?;
+
+        Ok((search_result(ctx, result), explanation))
+    }
 
-        let universe = filtered_universe(ctx.index, ctx.txn, &self.filter)?;
-        let PartialSearchResult {
-            located_query_terms,
-            candidates,
-            documents_ids,
-            document_scores,
-            degraded,
-            used_negative_operator,
-        } = match self.semantic.as_ref() {
+    /// Executes the search on the given documents, instead of the ones matching the filter.
+    fn execute_in_universe(
+        &self,
+        mut ctx: SearchContext<'a>,
+        universe: RoaringBitmap,
+    ) -> Result<SearchResult> {
+        let result = match self.semantic.as_ref() {
             Some(SemanticSearch { vector: Some(vector), embedder_name, embedder }) => {
                 execute_vector_search(
                     &mut ctx,
+                    self.query.as_deref(),
                     vector,
                     self.scoring_strategy,
                     universe,
@@ -209,46 +273,308 @@ impl<'a> Search<'a> {
                     self.geo_strategy,
                     self.offset,
                     self.limit,
+                    self.search_after.as_ref(),
                     embedder_name,
                     embedder,
                     self.time_budget.clone(),
                     self.ranking_score_threshold,
                 )?
             }
-            _ => execute_search(
+            _ => self.execute_keyword_search(
+                &mut ctx,
+                universe,
+                &mut DefaultSearchLogger,
+                &mut DefaultSearchLogger,
+            )?,
+        };
+
+        Ok(search_result(ctx, result))
+    }
+
+    fn search_context(&self) -> Result<SearchContext<'a>> {
+        let mut ctx = SearchContext::new(self.index, self.rtxn)?;
+
+        if let Some(searchable_attributes) = self.searchable_attributes {
+            ctx.attributes_to_search_on(searchable_attributes)?;
+        }
+
+        if let Some(attributes_weights) = self.attributes_weights {
+            ctx.attributes_weights(attributes_weights)?;
+        }
+
+        if let Some(ranking_rules) = &self.ranking_rules {
+            ctx.ranking_rules(ranking_rules.clone())?;
+        }
+
+        if let Some(distinct) = &self.distinct {
+            let filterable_fields = ctx.index.filterable_fields(ctx.txn)?;
+            if !crate::is_faceted(distinct, &filterable_fields) {
+                let (valid_fields, hidden_fields) =
+                    ctx.index.remove_hidden_fields(ctx.txn, filterable_fields)?;
+                return Err(Error::UserError(UserError::InvalidDistinctAttribute {
+                    field: distinct.clone(),
+                    valid_fields,
+                    hidden_fields,
+                }));
+            }
+        }
+
+        Ok(ctx)
+    }
+
+    fn execute_keyword_search(
+        &self,
+        ctx: &mut SearchContext<'a>,
+        universe: RoaringBitmap,
+        placeholder_logger: &mut dyn SearchLogger<PlaceholderQuery>,
+        query_graph_logger: &mut dyn SearchLogger<QueryGraph>,
+    ) -> Result<PartialSearchResult> {
+        execute_search(
+            ctx,
+            self.query.as_deref(),
+            self.terms_matching_strategy,
+            self.scoring_strategy,
+            self.exhaustive_number_hits,
+            universe,
+            &self.sort_criteria,
+            &self.distinct,
+            self.geo_strategy,
+            self.offset,
+            self.limit,
+            self.search_after.as_ref(),
+            Some(self.words_limit),
+            placeholder_logger,
+            query_graph_logger,
+            self.time_budget.clone(),
+            self.ranking_score_threshold,
+            self.locales.as_ref(),
+            self.typo_tolerant_negation,
+        )
+    }
+
+    /// Returns, for each of the given facets, the candidates of the search when the
+    /// clauses of the filter applied on this facet are ignored.
+    ///
+    /// The documents matching the query are resolved a single time, without ranking them, in
+    /// the universe filtered by the clauses that aren't applied on the given facets, and every
+    /// document of this universe is a candidate of a semantic or hybrid search. Each of the
+    /// other clauses is then evaluated once and intersected with the candidates of the facets
+    /// it isn't applied on. The given candidates of the search are always kept.
+    pub fn disjunctive_facets_candidates(
+        &self,
+        facets: &[String],
+        candidates: &RoaringBitmap,
+    ) -> Result<BTreeMap<String, RoaringBitmap>> {
+        let conjuncts = self.filter.as_ref().map_or_else(Vec::new, Filter::conjuncts);
+        let (disjunctive, mut others): (Vec<_>, Vec<_>) = conjuncts
+            .into_iter()
+            .partition(|conjunct| facets.iter().any(|facet| conjunct.refers_to(facet)));
+        if disjunctive.is_empty() {
+            return Ok(facets.iter().map(|facet| (facet.clone(), candidates.clone())).collect());
+        }
+
+        let relaxed_filter = match others.len() {
+            0 | 1 => others.pop(),
+            _ => Some(Filter::from(FilterCondition::And(
+                others.into_iter().map(FilterCondition::from).collect(),
+            ))),
+        };
+        let relaxed_universe = filtered_universe(self.index, self.rtxn, &relaxed_filter)?;
+
+        let relaxed_candidates = if self.semantic.is_some() {
+            // the semantic search considers every document of the universe as a candidate
+            relaxed_universe
+        } else {
+            let mut ctx = self.search_context()?;
+            execute_search_candidates(
                 &mut ctx,
                 self.query.as_deref(),
                 self.terms_matching_strategy,
                 self.scoring_strategy,
                 self.exhaustive_number_hits,
-                universe,
+                relaxed_universe,
                 &self.sort_criteria,
                 &self.distinct,
                 self.geo_strategy,
-                self.offset,
-                self.limit,
+                self.offset.saturating_add(self.limit),
                 Some(self.words_limit),
-                &mut DefaultSearchLogger,
-                &mut DefaultSearchLogger,
                 self.time_budget.clone(),
                 self.ranking_score_threshold,
-            )?,
+                self.locales.as_ref(),
+                self.typo_tolerant_negation,
+            )?
         };
 
-        // consume context and located_query_terms to build MatchingWords.
-        let matching_words = match located_query_terms {
-            Some(located_query_terms) => MatchingWords::new(ctx, located_query_terms),
-            None => MatchingWords::default(),
+        let disjunctive = disjunctive
+            .into_iter()
+            .map(|conjunct| Ok((conjunct.evaluate(self.rtxn, self.index)?, conjunct)))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut facets_candidates = BTreeMap::new();
+        for facet in facets {
+            let mut facet_candidates = relaxed_candidates.clone();
+            for (docids, conjunct) in &disjunctive {
+                if !conjunct.refers_to(facet) {
+                    facet_candidates &= docids;
+                }
+            }
+            facet_candidates |= candidates;
+            facets_candidates.insert(facet.clone(), facet_candidates);
+        }
+
+        Ok(facets_candidates)
+    }
+
+    /// Returns, for each of the given documents, the documents that the distinct rule
+    /// collapsed under it: the number of candidates sharing a distinct value with it and
+    /// the first `limit` of them in ranking order.
+    ///
+    /// The collapsed documents of all the documents are ranked together by a single search
+    /// restricted to them, then dispatched under the documents they share a distinct value
+    /// with. This search stops after `limit` documents per given document, a document can then
+    /// be given fewer than `limit` of them when the ones of the others rank first. Only their
+    /// number is computed, without ranking them, when `limit` is zero. The documents are all
+    /// considered unique when there is no distinct attribute.
+    pub fn collapsed_documents(
+        &self,
+        documents_ids: &[DocumentId],
+        limit: usize,
+    ) -> Result<Vec<CollapsedDocuments>> {
+        let distinct_field = match &self.distinct {
+            Some(distinct) => Some(distinct.as_str()),
+            None => self.index.distinct_field(self.rtxn)?,
+        };
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let distinct_fid = match distinct_field.and_then(|field| fields_ids_map.id(field)) {
+            Some(fid) => fid,
+            None => return Ok(vec![CollapsedDocuments::default(); documents_ids.len()]),
+        };
+
+        let universe = filtered_universe(self.index, self.rtxn, &self.filter)?;
+        let mut all_siblings = RoaringBitmap::new();
+        let mut siblings = Vec::with_capacity(documents_ids.len());
+        for &docid in documents_ids {
+            let mut docid_siblings = RoaringBitmap::new();
+            distinct_single_docid(self.index, self.rtxn, distinct_fid, docid, &mut docid_siblings)?;
+            docid_siblings.remove(docid);
+            docid_siblings &= &universe;
+            all_siblings |= &docid_siblings;
+            siblings.push(docid_siblings);
+        }
+        if all_siblings.is_empty() {
+            return Ok(vec![CollapsedDocuments::default(); documents_ids.len()]);
+        }
+
+        // the collapsed documents share distinct values and must not be collapsed again
+        if limit == 0 {
+            let candidates = if self.semantic.is_some() {
+                // the semantic search considers every document of the universe as a candidate
+                all_siblings
+            } else {
+                let mut ctx = self.search_context()?;
+                ctx.ignore_index_distinct = true;
+                let length = all_siblings.len() as usize;
+                execute_search_candidates(
+                    &mut ctx,
+                    self.query.as_deref(),
+                    self.terms_matching_strategy,
+                    ScoringStrategy::Skip,
+                    self.exhaustive_number_hits,
+                    all_siblings,
+                    &self.sort_criteria,
+                    &None,
+                    self.geo_strategy,
+                    length,
+                    Some(self.words_limit),
+                    self.time_budget.clone(),
+                    self.ranking_score_threshold,
+                    self.locales.as_ref(),
+                    self.typo_tolerant_negation,
+                )?
+            };
+            return Ok(siblings
+                .iter()
+                .map(|siblings| CollapsedDocuments {
+                    count: siblings.intersection_len(&candidates),
+                    documents_ids: Vec::new(),
+                })
+                .collect());
+        }
+
+        let search = Search {
+            query: self.query.clone(),
+            filter: None,
+            offset: 0,
+            limit: limit.saturating_mul(documents_ids.len()).min(all_siblings.len() as usize),
+            search_after: None,
+            sort_criteria: self.sort_criteria.clone(),
+            ranking_rules: self.ranking_rules.clone(),
+            distinct: None,
+            searchable_attributes: self.searchable_attributes,
+            attributes_weights: self.attributes_weights,
+            geo_strategy: self.geo_strategy,
+            terms_matching_strategy: self.terms_matching_strategy,
+            scoring_strategy: ScoringStrategy::Skip,
+            words_limit: self.words_limit,
+            exhaustive_number_hits: self.exhaustive_number_hits,
+            rtxn: self.rtxn,
+            index: self.index,
+            semantic: self.semantic.clone(),
+            time_budget: self.time_budget.clone(),
+            ranking_score_threshold: self.ranking_score_threshold,
+            locales: self.locales.clone(),
+            typo_tolerant_negation: self.typo_tolerant_negation,
         };
+        let mut ctx = search.search_context()?;
+        ctx.ignore_index_distinct = true;
+        let SearchResult { candidates, documents_ids: ranked, .. } =
+            search.execute_in_universe(ctx, all_siblings)?;
+
+        let mut collapsed: Vec<_> = siblings
+            .iter()
+            .map(|siblings| CollapsedDocuments {
+                count: siblings.intersection_len(&candidates),
+                documents_ids: Vec::new(),
+            })
+            .collect();
+        for docid in ranked {
+            // a document can share distinct values with several documents of the results
+            for (siblings, collapsed) in siblings.iter().zip(&mut collapsed) {
+                if collapsed.documents_ids.len() < limit && siblings.contains(docid) {
+                    collapsed.documents_ids.push(docid);
+                }
+            }
+        }
+
+        Ok(collapsed)
+    }
+}
+
+/// Consumes the context and the located query terms to build the [`MatchingWords`].
+fn search_result(ctx: SearchContext, result: PartialSearchResult) -> SearchResult {
+    let PartialSearchResult {
+        located_query_terms,
+        candidates,
+        documents_ids,
+        document_scores,
+        cursor,
+        degraded,
+        used_negative_operator,
+    } = result;
+
+    let matching_words = match located_query_terms {
+        Some(located_query_terms) => MatchingWords::new(ctx, located_query_terms),
+        None => MatchingWords::default(),
+    };
 
-        Ok(SearchResult {
-            matching_words,
-            candidates,
-            document_scores,
-            documents_ids,
-            degraded,
-            used_negative_operator,
-        })
+    SearchResult {
+        matching_words,
+        candidates,
+        document_scores,
+        documents_ids,
+        cursor,
+        degraded,
+        used_negative_operator,
     }
 }
 
@@ -259,9 +585,12 @@ impl fmt::Debug for Search<'_> {
             filter,
             offset,
             limit,
+            search_after,
             sort_criteria,
+            ranking_rules,
             distinct,
             searchable_attributes,
+            attributes_weights,
             geo_strategy: _,
             terms_matching_strategy,
             scoring_strategy,
@@ -272,6 +601,8 @@ impl fmt::Debug for Search<'_> {
             semantic,
             time_budget,
             ranking_score_threshold,
+            locales,
+            typo_tolerant_negation,
         } = self;
         f.debug_struct("Search")
             .field("query", query)
@@ -279,9 +610,12 @@ impl fmt::Debug for Search<'_> {
             .field("filter", filter)
             .field("offset", offset)
             .field("limit", limit)
+            .field("search_after", search_after)
             .field("sort_criteria", sort_criteria)
+            .field("ranking_rules", ranking_rules)
             .field("distinct", distinct)
             .field("searchable_attributes", searchable_attributes)
+            .field("attributes_weights", attributes_weights)
             .field("terms_matching_strategy", terms_matching_strategy)
             .field("scoring_strategy", scoring_strategy)
             .field("exhaustive_number_hits", exhaustive_number_hits)
@@ -292,6 +626,8 @@ impl fmt::Debug for Search<'_> {
             )
             .field("time_budget", time_budget)
             .field("ranking_score_threshold", ranking_score_threshold)
+            .field("locales", locales)
+            .field("typo_tolerant_negation", typo_tolerant_negation)
             .finish()
     }
 }
@@ -302,10 +638,21 @@ pub struct SearchResult {
     pub candidates: RoaringBitmap,
     pub documents_ids: Vec<DocumentId>,
     pub document_scores: Vec<Vec<ScoreDetails>>,
+    /// The position of the last document, to resume the search after it.
+    pub cursor: Option<SearchCursor>,
     pub degraded: bool,
     pub used_negative_operator: bool,
 }
 
+/// The documents collapsed by the distinct rule under a document of the results.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct CollapsedDocuments {
+    /// The number of candidates sharing a distinct value with the document.
+    pub count: u64,
+    /// The first of these candidates, in ranking order.
+    pub documents_ids: Vec<DocumentId>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TermsMatchingStrategy {
     // remove last word first
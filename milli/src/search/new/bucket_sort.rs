@@ -1,20 +1,416 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::Hasher;
+use std::str::FromStr;
+
+use base64::Engine;
+use fxhash::FxHasher;
+use heed::RoTxn;
 use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
 
 use super::logger::SearchLogger;
 use super::ranking_rules::{BoxRankingRule, RankingRuleQueryTrait};
 use super::SearchContext;
-use crate::score_details::{ScoreDetails, ScoringStrategy};
+use crate::score_details::{self, ScoreDetails, ScoringStrategy};
 use crate::search::new::distinct::{apply_distinct_rule, distinct_single_docid, DistinctOutput};
-use crate::{Result, TimeBudget};
+use crate::{DocumentId, Index, Result, TimeBudget, UserError};
 
 pub struct BucketSortOutput {
     pub docids: Vec<u32>,
     pub scores: Vec<Vec<ScoreDetails>>,
     pub all_candidates: RoaringBitmap,
+    /// The position of the last ranked document of the results.
+    pub cursor: Option<RankedCursor>,
 
     pub degraded: bool,
 }
 
+/// The position of a document in the results of a search, used to resume the search after it.
+///
+/// The ranked documents are located by the path of the buckets the ranking rules sorted them in,
+/// i.e. the rank of their bucket at each level of the bucket sort. The ranking rules being
+/// deterministic, a cursor stays valid as long as neither the index nor the search change.
+///
+/// The cursors are exchanged with the users as a [`SearchCursorToken`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchCursor {
+    /// The position of the document in the results.
+    pub position: usize,
+    /// The number of documents sorted by the ranking rules up to this position,
+    /// the other ones being placed by a pinned results rule.
+    pub ranked_count: usize,
+    /// The last document sorted by the ranking rules up to this position, `None` when the
+    /// ranked documents must be skipped again to resume the search.
+    pub ranked: Option<RankedCursor>,
+}
+
+/// The location of a document sorted by the ranking rules.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RankedCursor {
+    /// The rank of the bucket of the document at each level of the bucket sort.
+    path: Vec<u32>,
+    /// Whether the last bucket of the path is the remainder of its level, i.e. all the documents
+    /// that were not part of the first buckets of the level when it was added to the results.
+    remainder: bool,
+    docid: DocumentId,
+}
+
+/// Where a bucket is located relatively to the bucket of a cursor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CursorPosition {
+    /// All the documents of the bucket come before the cursor.
+    Before,
+    /// The bucket of the cursor is one of the sub-buckets of this bucket.
+    Ancestor,
+    /// The documents of the bucket come before the cursor up to its document id.
+    Within,
+    /// All the documents of the bucket come after the cursor.
+    After,
+}
+
+impl RankedCursor {
+    fn locate(&self, path: &[u32], remainder: bool) -> CursorPosition {
+        for (i, (&rank, &cursor_rank)) in path.iter().zip(&self.path).enumerate() {
+            // a remainder is made of all the buckets of its level starting at its rank
+            if remainder && i + 1 == path.len() {
+                return if cursor_rank >= rank {
+                    CursorPosition::Within
+                } else {
+                    CursorPosition::After
+                };
+            }
+            if self.remainder && i + 1 == self.path.len() {
+                return if rank >= cursor_rank {
+                    CursorPosition::Within
+                } else {
+                    CursorPosition::Before
+                };
+            }
+            match rank.cmp(&cursor_rank) {
+                Ordering::Less => return CursorPosition::Before,
+                Ordering::Greater => return CursorPosition::After,
+                Ordering::Equal => (),
+            }
+        }
+
+        if path.len() < self.path.len() {
+            CursorPosition::Ancestor
+        } else {
+            CursorPosition::Within
+        }
+    }
+}
+
+/// The version of the encoding of the [`SearchCursorToken`]s, to bump whenever it changes.
+const SEARCH_CURSOR_VERSION: u8 = 2;
+
+/// A [`SearchCursor`] along with the state of the index it was computed on, exchanged with
+/// the users as an opaque string: the base64 encoding of the versioned token.
+///
+/// A token can only be turned back into a cursor as long as the index was not updated, and by
+/// a search with the same parameters as the one that returned it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchCursorToken {
+    version: u8,
+    /// The last update of the index, in nanoseconds since the Unix epoch.
+    index_updated_at: i128,
+    /// The hash of the parameters of the search changing its results.
+    parameters_hash: u64,
+    cursor: SearchCursor,
+}
+
+impl SearchCursorToken {
+    /// Creates the token of a cursor returned by a search, `search_parameters` being the
+    /// serialized parameters of the search changing its results.
+    pub fn new(
+        cursor: SearchCursor,
+        index: &Index,
+        rtxn: &RoTxn,
+        search_parameters: &[u8],
+    ) -> Result<Self> {
+        let index_updated_at = index.updated_at(rtxn)?.unix_timestamp_nanos();
+        let parameters_hash = Self::hash_parameters(search_parameters);
+        Ok(Self { version: SEARCH_CURSOR_VERSION, index_updated_at, parameters_hash, cursor })
+    }
+
+    /// Returns the cursor of the token if the index didn't change since it was created
+    /// and the search has the same parameters.
+    pub fn into_cursor(
+        self,
+        index: &Index,
+        rtxn: &RoTxn,
+        search_parameters: &[u8],
+    ) -> Result<SearchCursor> {
+        if index.updated_at(rtxn)?.unix_timestamp_nanos() != self.index_updated_at {
+            return Err(UserError::StaleSearchCursor.into());
+        }
+        if Self::hash_parameters(search_parameters) != self.parameters_hash {
+            return Err(UserError::SearchCursorMismatch.into());
+        }
+        Ok(self.cursor)
+    }
+
+    fn hash_parameters(search_parameters: &[u8]) -> u64 {
+        // the hasher must not depend on the process, the token outlives it
+        let mut hasher = FxHasher::default();
+        hasher.write(search_parameters);
+        hasher.finish()
+    }
+}
+
+impl fmt::Display for SearchCursorToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bytes = bincode::serialize(self).map_err(|_| fmt::Error)?;
+        f.write_str(&base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("invalid search cursor")]
+pub struct InvalidSearchCursor;
+
+impl FromStr for SearchCursorToken {
+    type Err = InvalidSearchCursor;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(s)
+            .map_err(|_| InvalidSearchCursor)?;
+        // the version is checked before the rest of the token is decoded
+        if bytes.first() != Some(&SEARCH_CURSOR_VERSION) {
+            return Err(InvalidSearchCursor);
+        }
+        let token: Self = bincode::deserialize(&bytes).map_err(|_| InvalidSearchCursor)?;
+        if let Some(RankedCursor { path, remainder: true, .. }) = &token.cursor.ranked {
+            if path.is_empty() {
+                return Err(InvalidSearchCursor);
+            }
+        }
+        Ok(token)
+    }
+}
+
+/// The documents placed in the results by the first pinned results rule matching the query.
+///
+/// The pinned and hidden documents are excluded from the universe given to the bucket sort,
+/// the pinned ones are then spliced at their positions in its output. When a distinct attribute
+/// is set, only the first pinned document of each distinct value is kept and the other documents
+/// sharing its value are excluded too.
+#[derive(Default)]
+pub struct PinnedDocuments {
+    /// The pinned documents that are part of the universe, by increasing positions.
+    pub documents: Vec<(usize, DocumentId)>,
+    /// The pinned and hidden documents, and the documents sharing a distinct value with
+    /// a pinned one.
+    pub excluded: RoaringBitmap,
+}
+
+/// A page of results, located among the pinned and the ranked documents.
+pub struct PinnedPage {
+    /// The position of the first document of the page.
+    from: usize,
+    length: usize,
+    /// The number of pinned documents coming before the page.
+    pinned_before: usize,
+    /// The number of ranked documents coming before the page.
+    pub ranked_before: usize,
+    /// The `from` and `length` to give to the bucket sort, relative to `search_after`.
+    pub bucket_sort_from: usize,
+    pub bucket_sort_length: usize,
+    /// The ranked document the bucket sort resumes after.
+    pub search_after: Option<RankedCursor>,
+}
+
+impl PinnedDocuments {
+    pub fn resolve(
+        ctx: &SearchContext,
+        query: &str,
+        distinct: Option<&str>,
+        universe: &RoaringBitmap,
+    ) -> Result<Self> {
+        let rules = ctx.index.pinned_results(ctx.txn)?;
+        let Some(rule) = rules.iter().find(|rule| rule.matches(query)) else {
+            return Ok(Self::default());
+        };
+
+        let distinct_field = match distinct {
+            Some(distinct) => Some(distinct),
+            None if ctx.ignore_index_distinct => None,
+            None => ctx.index.distinct_field(ctx.txn)?,
+        };
+        let distinct_fid = if let Some(field) = distinct_field {
+            ctx.index.fields_ids_map(ctx.txn)?.id(field)
+        } else {
+            None
+        };
+
+        let external_documents_ids = ctx.index.external_documents_ids();
+        let mut pinned = Self::default();
+        let mut distinct_excluded = RoaringBitmap::new();
+        for document in &rule.pinned {
+            if let Some(docid) = external_documents_ids.get(ctx.txn, &document.id)? {
+                // the pinned documents must respect the filters and the distinct attribute
+                if !universe.contains(docid) || distinct_excluded.contains(docid) {
+                    continue;
+                }
+                if pinned.excluded.insert(docid) {
+                    pinned.documents.push((document.position, docid));
+                    if let Some(distinct_fid) = distinct_fid {
+                        distinct_single_docid(
+                            ctx.index,
+                            ctx.txn,
+                            distinct_fid,
+                            docid,
+                            &mut distinct_excluded,
+                        )?;
+                    }
+                }
+            }
+        }
+        pinned.excluded |= distinct_excluded;
+        for external_id in &rule.hidden {
+            if let Some(docid) = external_documents_ids.get(ctx.txn, external_id)? {
+                pinned.excluded.insert(docid);
+            }
+        }
+
+        // the documents pinned at a position already taken are shifted after it
+        pinned.documents.sort_by_key(|&(position, _)| position);
+        let mut next_position = 0;
+        for (position, _) in &mut pinned.documents {
+            *position = (*position).max(next_position);
+            next_position = position.saturating_add(1);
+        }
+
+        Ok(pinned)
+    }
+
+    /// Locates the requested page among the pinned and the ranked documents, the page
+    /// starting `from` documents after the `search_after` cursor when there is one.
+    ///
+    /// The cursor is rejected when it can't be the one of a page of results of the search.
+    pub fn page(
+        &self,
+        from: usize,
+        length: usize,
+        search_after: Option<&SearchCursor>,
+    ) -> Result<PinnedPage> {
+        let (start, pinned_seen, ranked_seen, search_after) = match search_after {
+            Some(SearchCursor { position, ranked_count, ranked }) => {
+                let start = position.checked_add(1).ok_or(UserError::InvalidSearchCursor)?;
+                // the documents up to the cursor are either pinned or ranked
+                let pinned_seen =
+                    start.checked_sub(*ranked_count).ok_or(UserError::InvalidSearchCursor)?;
+                if pinned_seen > self.documents.len() || (*ranked_count == 0 && ranked.is_some()) {
+                    return Err(UserError::InvalidSearchCursor.into());
+                }
+                (start, pinned_seen, *ranked_count, ranked.clone())
+            }
+            None => (0, 0, 0, None),
+        };
+        let from = start.checked_add(from).ok_or(UserError::InvalidSearchCursor)?;
+        let end = from.saturating_add(length);
+
+        // the pinned documents coming after the cursor, placed before the given position
+        let pinned_after_cursor = &self.documents[pinned_seen..];
+        let pinned_before =
+            |position: usize| pinned_after_cursor.partition_point(|&(pinned, _)| pinned < position);
+        let ranked_before = ranked_seen + (from - start).saturating_sub(pinned_before(from));
+        let bucket_sort_from = match search_after {
+            Some(_) => ranked_before - ranked_seen,
+            None => ranked_before,
+        };
+        let bucket_sort_length = length - (pinned_before(end) - pinned_before(from));
+
+        Ok(PinnedPage {
+            from,
+            length,
+            pinned_before: pinned_seen + pinned_before(from),
+            ranked_before,
+            bucket_sort_from,
+            bucket_sort_length,
+            search_after,
+        })
+    }
+
+    /// Places the pinned documents of the page among the ranked documents returned by the bucket
+    /// sort and returns the cursor of the last document of the page.
+    ///
+    /// When there are not enough ranked documents to reach the position of a pinned document,
+    /// it is placed right after the last ranked one. When the page starts after all the ranked
+    /// documents, their number is estimated from the candidates of the bucket sort.
+    pub fn splice(
+        &self,
+        page: &PinnedPage,
+        output: BucketSortOutput,
+    ) -> (BucketSortOutput, Option<SearchCursor>) {
+        let BucketSortOutput { docids, scores, mut all_candidates, cursor, degraded } = output;
+        all_candidates.extend(self.documents.iter().map(|&(_, docid)| docid));
+
+        // the bucket sort only resumes from the cursor of the last ranked document
+        // it returned, or the one it was given when it didn't have to skip any document.
+        let ranked_cursor = match cursor {
+            Some(cursor) => Some(cursor),
+            None if page.bucket_sort_from == 0 => page.search_after.clone(),
+            None => None,
+        };
+
+        // the total number of ranked documents, known once the bucket sort ran out of them
+        let ranked_total = if docids.len() >= page.bucket_sort_length {
+            None
+        } else if !docids.is_empty() || page.bucket_sort_from == 0 {
+            Some(page.ranked_before + docids.len())
+        } else {
+            let ranked_candidates = all_candidates.len() as usize - self.documents.len();
+            Some(ranked_candidates.min(page.ranked_before))
+        };
+
+        let mut pinned = self
+            .documents
+            .iter()
+            .enumerate()
+            .map(|(i, &(position, docid))| match ranked_total {
+                Some(ranked_total) => (i, position.min(ranked_total + i), docid),
+                None => (i, position, docid),
+            })
+            .skip(page.pinned_before)
+            .skip_while(|&(_, position, _)| position < page.from)
+            .peekable();
+        let mut ranked = docids.into_iter().zip(scores);
+
+        let mut page_docids = Vec::new();
+        let mut page_scores = Vec::new();
+        for position in page.from..page.from.saturating_add(page.length) {
+            if let Some((_, _, docid)) = pinned.next_if(|&(_, pinned, _)| pinned == position) {
+                page_docids.push(docid);
+                page_scores.push(vec![ScoreDetails::Pinned(score_details::Pinned { position })]);
+            } else if let Some((docid, score)) = ranked.next() {
+                page_docids.push(docid);
+                page_scores.push(score);
+            } else {
+                break;
+            }
+        }
+
+        // the pinned documents up to the cursor are the ones before the next pinned document
+        let pinned_count = pinned.peek().map_or(self.documents.len(), |&(i, _, _)| i);
+        let cursor = page_docids.len().checked_sub(1).map(|last| SearchCursor {
+            position: page.from + last,
+            ranked_count: (page.from + last + 1).saturating_sub(pinned_count),
+            ranked: ranked_cursor,
+        });
+        let output = BucketSortOutput {
+            docids: page_docids,
+            scores: page_scores,
+            all_candidates,
+            cursor: None,
+            degraded,
+        };
+        (output, cursor)
+    }
+}
+
 // TODO: would probably be good to regroup some of these inside of a struct?
 #[allow(clippy::too_many_arguments)]
 #[tracing::instrument(level = "trace", skip_all, target = "search::bucket_sort")]
@@ -26,6 +422,7 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
     universe: &RoaringBitmap,
     from: usize,
     length: usize,
+    mut search_after: Option<&RankedCursor>,
     scoring_strategy: ScoringStrategy,
     logger: &mut dyn SearchLogger<Q>,
     time_budget: TimeBudget,
@@ -37,6 +434,7 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
 
     let distinct_field = match distinct {
         Some(distinct) => Some(distinct),
+        None if ctx.ignore_index_distinct => None,
         None => ctx.index.distinct_field(ctx.txn)?,
     };
 
@@ -51,15 +449,26 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
             docids: vec![],
             scores: vec![],
             all_candidates: universe.clone(),
+            cursor: None,
             degraded: false,
         });
     }
     if ranking_rules.is_empty() {
+        // without ranking rules the documents are returned in the order of their ids
+        let after_cursor = |docid: &DocumentId| match search_after {
+            Some(search_after) => *docid > search_after.docid,
+            None => true,
+        };
+        let ranked_cursor = |docids: &[DocumentId]| {
+            docids.last().map(|&docid| RankedCursor { path: vec![], remainder: false, docid })
+        };
+
         if let Some(distinct_fid) = distinct_fid {
             let mut excluded = RoaringBitmap::new();
             let mut results = vec![];
+            let mut before_cursor = 0;
             for docid in universe.iter() {
-                if results.len() >= from + length {
+                if results.len() >= before_cursor + from + length {
                     break;
                 }
                 if excluded.contains(docid) {
@@ -67,6 +476,9 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
                 }
 
                 distinct_single_docid(ctx.index, ctx.txn, distinct_fid, docid, &mut excluded)?;
+                if !after_cursor(&docid) {
+                    before_cursor += 1;
+                }
                 results.push(docid);
             }
 
@@ -75,22 +487,25 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
             // drain the results of the skipped elements
             // this **must** be done **after** writing the entire results in `all_candidates` to ensure
             // e.g. estimatedTotalHits is correct.
-            if results.len() >= from {
-                results.drain(..from);
+            if results.len() >= before_cursor + from {
+                results.drain(..before_cursor + from);
             } else {
                 results.clear();
             }
 
             return Ok(BucketSortOutput {
                 scores: vec![Default::default(); results.len()],
+                cursor: ranked_cursor(&results),
                 docids: results,
                 all_candidates,
                 degraded: false,
             });
         } else {
-            let docids: Vec<u32> = universe.iter().skip(from).take(length).collect();
+            let docids: Vec<u32> =
+                universe.iter().filter(after_cursor).skip(from).take(length).collect();
             return Ok(BucketSortOutput {
                 scores: vec![Default::default(); docids.len()],
+                cursor: ranked_cursor(&docids),
                 docids,
                 all_candidates: universe.clone(),
                 degraded: false,
@@ -105,6 +520,8 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
     ranking_rules[0].start_iteration(ctx, logger, universe, query)?;
 
     let mut ranking_rule_scores: Vec<ScoreDetails> = vec![];
+    // the number of buckets returned so far by each ranking rule
+    let mut ranking_rule_ranks: Vec<u32> = vec![0; ranking_rules_len];
 
     let mut ranking_rule_universes: Vec<RoaringBitmap> =
         vec![RoaringBitmap::default(); ranking_rules_len];
@@ -144,9 +561,13 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
     let mut valid_docids = vec![];
     let mut valid_scores = vec![];
     let mut cur_offset = 0usize;
+    let mut cursor = None;
 
+    /// Add the candidates of the current bucket (or of the remainder of
+    /// the current ranking rule) to the results and update the cursor.
     macro_rules! maybe_add_to_results {
-        ($candidates:expr) => {
+        ($candidates:expr, $remainder:expr) => {
+            let valid_docids_len = valid_docids.len();
             maybe_add_to_results(
                 ctx,
                 from,
@@ -163,6 +584,55 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
                 &ranking_rule_scores,
                 $candidates,
             )?;
+            if valid_docids.len() > valid_docids_len {
+                cursor = valid_docids.last().map(|&docid| RankedCursor {
+                    path: bucket_path(&ranking_rule_ranks, cur_ranking_rule_index, $remainder),
+                    remainder: $remainder,
+                    docid,
+                });
+            }
+        };
+    }
+
+    /// Skip the candidates coming before the cursor: all of them when `$after` is `None`,
+    /// the ones up to the given document id otherwise. Return the remaining candidates.
+    macro_rules! skip_to_cursor {
+        ($candidates:expr, $after:expr) => {
+            skip_to_cursor(
+                ctx,
+                logger,
+                &mut all_candidates,
+                &mut ranking_rule_universes,
+                &mut ranking_rules,
+                cur_ranking_rule_index,
+                distinct_fid,
+                $candidates,
+                $after,
+            )?
+        };
+    }
+
+    /// Locate the remainder of the current ranking rule relatively to the cursor
+    /// the search resumes after and return the candidates coming after it.
+    macro_rules! resume_remainder {
+        ($bucket:ident) => {
+            match search_after {
+                Some(search_after_cursor) if !$bucket.is_empty() => {
+                    let path = bucket_path(&ranking_rule_ranks, cur_ranking_rule_index, true);
+                    match search_after_cursor.locate(&path, true) {
+                        CursorPosition::After => {
+                            search_after = None;
+                            $bucket
+                        }
+                        position => {
+                            let after = (position != CursorPosition::Before)
+                                .then_some(search_after_cursor.docid);
+                            skip_to_cursor!($bucket, after)
+                        }
+                    }
+                }
+                _ => $bucket,
+            }
         };
     }
 
@@ -182,7 +652,8 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
                     }
                 }
 
-                maybe_add_to_results!(bucket);
+                let bucket = resume_remainder!(bucket);
+                maybe_add_to_results!(bucket, true);
 
                 ranking_rule_scores.pop();
 
@@ -197,6 +668,7 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
                 scores: valid_scores,
                 docids: valid_docids,
                 all_candidates,
+                cursor,
                 degraded: true,
             });
         }
@@ -208,7 +680,8 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
                 && ranking_rule_universes[cur_ranking_rule_index].len() == 1)
         {
             let bucket = std::mem::take(&mut ranking_rule_universes[cur_ranking_rule_index]);
-            maybe_add_to_results!(bucket);
+            let bucket = resume_remainder!(bucket);
+            maybe_add_to_results!(bucket, true);
             back!();
             continue;
         }
@@ -223,6 +696,7 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
             continue;
         };
 
+        ranking_rule_ranks[cur_ranking_rule_index] += 1;
         ranking_rule_scores.push(next_bucket.score);
 
         logger.next_bucket_ranking_rule(
@@ -249,16 +723,39 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
 
         ranking_rule_universes[cur_ranking_rule_index] -= &next_bucket.candidates;
 
-        if cur_ranking_rule_index == ranking_rules_len - 1
-            || (scoring_strategy == ScoringStrategy::Skip && next_bucket.candidates.len() <= 1)
-            || cur_offset + (next_bucket.candidates.len() as usize) < from
+        // when resuming after a cursor, skip the buckets coming before it
+        // and only sort further the ones leading to it.
+        let mut leads_to_cursor = false;
+        if let Some(search_after_cursor) = search_after {
+            let path = bucket_path(&ranking_rule_ranks, cur_ranking_rule_index, false);
+            match search_after_cursor.locate(&path, false) {
+                CursorPosition::After => search_after = None,
+                CursorPosition::Ancestor if cur_ranking_rule_index < ranking_rules_len - 1 => {
+                    leads_to_cursor = true
+                }
+                position => {
+                    let after =
+                        (position != CursorPosition::Before).then_some(search_after_cursor.docid);
+                    let candidates = skip_to_cursor!(next_bucket.candidates, after);
+                    maybe_add_to_results!(candidates, false);
+                    ranking_rule_scores.pop();
+                    continue;
+                }
+            }
+        }
+
+        if !leads_to_cursor
+            && (cur_ranking_rule_index == ranking_rules_len - 1
+                || (scoring_strategy == ScoringStrategy::Skip && next_bucket.candidates.len() <= 1)
+                || cur_offset + (next_bucket.candidates.len() as usize) < from)
         {
-            maybe_add_to_results!(next_bucket.candidates);
+            maybe_add_to_results!(next_bucket.candidates, false);
             ranking_rule_scores.pop();
             continue;
         }
 
         cur_ranking_rule_index += 1;
+        ranking_rule_ranks[cur_ranking_rule_index] = 0;
         ranking_rule_universes[cur_ranking_rule_index].clone_from(&next_bucket.candidates);
         logger.start_iteration_ranking_rule(
             cur_ranking_rule_index,
@@ -278,10 +775,70 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
         docids: valid_docids,
         scores: valid_scores,
         all_candidates,
+        cursor,
         degraded: false,
     })
 }
 
+/// The path of the current bucket (or of the remainder of the current ranking rule),
+/// i.e. its rank at each level of the bucket sort.
+fn bucket_path(
+    ranking_rule_ranks: &[u32],
+    cur_ranking_rule_index: usize,
+    remainder: bool,
+) -> Vec<u32> {
+    // the current bucket of each level is the last one returned by its ranking rule,
+    // while the remainder of a level comes after all of them.
+    let mut path: Vec<u32> =
+        ranking_rule_ranks[..cur_ranking_rule_index].iter().map(|rank| rank - 1).collect();
+    let rank = ranking_rule_ranks[cur_ranking_rule_index];
+    path.push(if remainder { rank } else { rank - 1 });
+    path
+}
+
+/// Skip the candidates coming before the cursor the search resumes after: all of them when
+/// `after` is `None`, the ones up to `after` otherwise. The distinct rule is applied on the
+/// candidates as if they were returned by a previous search. Return the remaining candidates.
+#[allow(clippy::too_many_arguments)]
+fn skip_to_cursor<'ctx, Q: RankingRuleQueryTrait>(
+    ctx: &mut SearchContext<'ctx>,
+    logger: &mut dyn SearchLogger<Q>,
+    all_candidates: &mut RoaringBitmap,
+    ranking_rule_universes: &mut [RoaringBitmap],
+    ranking_rules: &mut [BoxRankingRule<'ctx, Q>],
+    cur_ranking_rule_index: usize,
+    distinct_fid: Option<u16>,
+    candidates: RoaringBitmap,
+    after: Option<DocumentId>,
+) -> Result<RoaringBitmap> {
+    let candidates = if let Some(distinct_fid) = distinct_fid {
+        let DistinctOutput { remaining, excluded } =
+            apply_distinct_rule(ctx, distinct_fid, &candidates)?;
+        for universe in ranking_rule_universes.iter_mut() {
+            *universe -= &excluded;
+        }
+        *all_candidates -= &excluded;
+        remaining
+    } else {
+        candidates
+    };
+    *all_candidates |= &candidates;
+
+    let mut remaining = RoaringBitmap::new();
+    if let Some(after) = after {
+        remaining |= &candidates;
+        remaining.remove_range(..=after);
+    }
+
+    logger.skip_bucket_ranking_rule(
+        cur_ranking_rule_index,
+        ranking_rules[cur_ranking_rule_index].as_ref(),
+        &(candidates - &remaining),
+    );
+
+    Ok(remaining)
+}
+
 /// Add the candidates to the results. Take `distinct`, `from`, `length`, and `cur_offset`
 /// into account and inform the logger.
 #[allow(clippy::too_many_arguments)]
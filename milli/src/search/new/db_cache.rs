@@ -8,6 +8,7 @@ use heed::{BytesEncode, Database, RoTxn};
 use roaring::RoaringBitmap;
 
 use super::interner::Interned;
+use super::query_term::first_ngram;
 use super::Word;
 use crate::heed_codec::{BytesDecodeOwned, StrBEU16Codec};
 use crate::proximity::ProximityPrecision;
@@ -44,6 +45,8 @@ pub struct DatabaseCache<'ctx> {
     pub word_prefix_fid_docids: FxHashMap<(Interned<String>, u16), Option<Cow<'ctx, [u8]>>>,
     pub word_fids: FxHashMap<Interned<String>, Vec<u16>>,
     pub word_prefix_fids: FxHashMap<Interned<String>, Vec<u16>>,
+
+    pub infix_word_docids: FxHashMap<(Interned<String>, Interned<String>), Option<Cow<'ctx, [u8]>>>,
 }
 impl<'ctx> DatabaseCache<'ctx> {
     fn get_value<'v, K1, KC, DC>(
@@ -479,6 +482,24 @@ impl<'ctx> SearchContext<'ctx> {
         )
     }
 
+    /// Returns the documents in which the `infix` word, which contains the given `word`,
+    /// appears in one of the infix searchable attributes.
+    pub fn get_db_infix_word_docids(
+        &mut self,
+        word: Interned<String>,
+        infix: Interned<String>,
+    ) -> Result<Option<RoaringBitmap>> {
+        let Some(ngram) = first_ngram(self.word_interner.get(word)) else { return Ok(None) };
+
+        DatabaseCache::get_value::<_, _, CboRoaringBitmapCodec>(
+            self.txn,
+            (word, infix),
+            &(ngram, self.word_interner.get(infix).as_str()),
+            &mut self.db_cache.infix_word_docids,
+            self.index.ngram_word_docids.remap_data_type::<Bytes>(),
+        )
+    }
+
     pub fn get_db_word_prefix_fid_docids(
         &mut self,
         word_prefix: Interned<String>,
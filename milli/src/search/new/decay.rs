@@ -0,0 +1,242 @@
+use std::iter::Peekable;
+use std::ops::Bound;
+
+use heed::BytesDecode;
+use roaring::RoaringBitmap;
+
+use super::logger::SearchLogger;
+use super::sort::{RankingRuleOutputIter, RankingRuleOutputIterWrapper};
+use super::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait, SearchContext};
+use crate::heed_codec::facet::{FacetGroupKeyCodec, OrderedF64Codec};
+use crate::heed_codec::BytesRefCodec;
+use crate::score_details::{self, ScoreDetails};
+use crate::search::facet::{
+    ascending_facet_sort, descending_facet_sort, find_docids_of_facet_within_bounds,
+};
+use crate::{FieldId, Index, Result};
+
+type ValueIter<'ctx> = Box<dyn Iterator<Item = Result<(RoaringBitmap, f64)>> + 'ctx>;
+
+/// Ranks the documents by the score of a decay function applied to the distance between
+/// the value of a numeric field and an origin.
+///
+/// The facet values are walked from the origin outward, on both sides at the same time,
+/// so that the buckets are returned from the closest to the furthest values without having
+/// to compute the score of every candidate beforehand.
+pub struct Decay<'ctx, Query> {
+    rule: crate::Decay,
+    field_id: Option<FieldId>,
+    original_query: Option<Query>,
+    iter: Option<RankingRuleOutputIterWrapper<'ctx, Query>>,
+    must_redact: bool,
+}
+
+impl<'ctx, Query> Decay<'ctx, Query> {
+    pub fn new(index: &Index, rtxn: &'ctx heed::RoTxn, rule: crate::Decay) -> Result<Self> {
+        let fields_ids_map = index.fields_ids_map(rtxn)?;
+        let field_id = fields_ids_map.id(&rule.field);
+        let must_redact = match index.displayed_fields(rtxn)? {
+            Some(displayed_fields) => !displayed_fields.iter().any(|&field| field == rule.field),
+            None => false,
+        };
+
+        Ok(Self { rule, field_id, original_query: None, iter: None, must_redact })
+    }
+
+    fn score_details(&self, value: Option<f64>, score: f64) -> ScoreDetails {
+        ScoreDetails::Decay(score_details::Decay {
+            rule: self.rule.to_string(),
+            redacted: self.must_redact,
+            value,
+            score,
+        })
+    }
+}
+
+impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for Decay<'ctx, Query> {
+    fn id(&self) -> String {
+        self.rule.to_string()
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::decay")]
+    fn start_iteration(
+        &mut self,
+        ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        parent_candidates: &RoaringBitmap,
+        parent_query: &Query,
+    ) -> Result<()> {
+        let iter: RankingRuleOutputIterWrapper<Query> = match self.field_id {
+            Some(field_id) => {
+                // `now` is only evaluated once per search so that all the buckets share the same origin.
+                let origin = self.rule.origin.value();
+
+                let mut above_origin = RoaringBitmap::new();
+                find_docids_of_facet_within_bounds::<OrderedF64Codec>(
+                    ctx.txn,
+                    ctx.index.facet_id_f64_docids,
+                    field_id,
+                    &Bound::Included(origin),
+                    &Bound::Unbounded,
+                    Some(parent_candidates),
+                    &mut above_origin,
+                )?;
+                let mut below_origin = RoaringBitmap::new();
+                find_docids_of_facet_within_bounds::<OrderedF64Codec>(
+                    ctx.txn,
+                    ctx.index.facet_id_f64_docids,
+                    field_id,
+                    &Bound::Unbounded,
+                    &Bound::Excluded(origin),
+                    Some(parent_candidates),
+                    &mut below_origin,
+                )?;
+
+                // Documents with multiple values can be on both sides of the origin, the values
+                // of the wrong side are skipped as they are reached from the other side.
+                let number_db = ctx
+                    .index
+                    .facet_id_f64_docids
+                    .remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>();
+                let above_iter: ValueIter = Box::new(
+                    ascending_facet_sort(ctx.txn, number_db, field_id, above_origin)?
+                        .map(decode_facet_number)
+                        .filter(move |r| r.as_ref().map_or(true, |(_, value)| *value >= origin)),
+                );
+                let below_iter: ValueIter = Box::new(
+                    descending_facet_sort(ctx.txn, number_db, field_id, below_origin)?
+                        .map(decode_facet_number)
+                        .filter(move |r| r.as_ref().map_or(true, |(_, value)| *value < origin)),
+                );
+
+                let query = parent_query.clone();
+                let rule = self.rule.clone();
+                let rule_id = self.rule.to_string();
+                let must_redact = self.must_redact;
+                let closest_first = ClosestFirst {
+                    above: above_iter.peekable(),
+                    below: below_iter.peekable(),
+                    origin,
+                };
+                let same_score = SameScore { values: closest_first.peekable(), rule, origin };
+                RankingRuleOutputIterWrapper::new(Box::new(same_score.map(move |r| {
+                    let (docids, value, score) = r?;
+                    Ok(RankingRuleOutput {
+                        query: query.clone(),
+                        candidates: docids,
+                        score: ScoreDetails::Decay(score_details::Decay {
+                            rule: rule_id.clone(),
+                            redacted: must_redact,
+                            value: Some(value),
+                            score,
+                        }),
+                    })
+                })))
+            }
+            None => RankingRuleOutputIterWrapper::new(Box::new(std::iter::empty())),
+        };
+        self.original_query = Some(parent_query.clone());
+        self.iter = Some(iter);
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::decay")]
+    fn next_bucket(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        universe: &RoaringBitmap,
+    ) -> Result<Option<RankingRuleOutput<Query>>> {
+        let iter = self.iter.as_mut().unwrap();
+        if let Some(mut bucket) = iter.next_bucket()? {
+            bucket.candidates &= universe;
+            Ok(Some(bucket))
+        } else {
+            // the documents without a numeric value for the field are returned last
+            let query = self.original_query.as_ref().unwrap().clone();
+            Ok(Some(RankingRuleOutput {
+                query,
+                candidates: universe.clone(),
+                score: self.score_details(None, 0.),
+            }))
+        }
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::decay")]
+    fn end_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+    ) {
+        self.original_query = None;
+        self.iter = None;
+    }
+}
+
+fn decode_facet_number(r: heed::Result<(RoaringBitmap, &[u8])>) -> Result<(RoaringBitmap, f64)> {
+    let (docids, bytes) = r?;
+    Ok((docids, OrderedF64Codec::bytes_decode(bytes).expect("some number")))
+}
+
+/// Merges the facet values above and below the origin, yielding the closest one first.
+struct ClosestFirst<'ctx> {
+    above: Peekable<ValueIter<'ctx>>,
+    below: Peekable<ValueIter<'ctx>>,
+    origin: f64,
+}
+
+impl<'ctx> Iterator for ClosestFirst<'ctx> {
+    type Item = Result<(RoaringBitmap, f64)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let above_distance = match self.above.peek() {
+            Some(Ok((_, value))) => Some(value - self.origin),
+            Some(Err(_)) => return self.above.next(),
+            None => None,
+        };
+        let below_distance = match self.below.peek() {
+            Some(Ok((_, value))) => Some(self.origin - value),
+            Some(Err(_)) => return self.below.next(),
+            None => None,
+        };
+
+        match (above_distance, below_distance) {
+            (Some(above), Some(below)) if below < above => self.below.next(),
+            (Some(_), _) => self.above.next(),
+            (None, _) => self.below.next(),
+        }
+    }
+}
+
+/// Merges the consecutive facet values sharing the same score into a single bucket,
+/// e.g. all the values within the offset of the origin, so that the next ranking rules
+/// can order their documents.
+///
+/// The value yielded along with a bucket is its value closest to the origin.
+struct SameScore<'ctx> {
+    values: Peekable<ClosestFirst<'ctx>>,
+    rule: crate::Decay,
+    origin: f64,
+}
+
+impl<'ctx> Iterator for SameScore<'ctx> {
+    type Item = Result<(RoaringBitmap, f64, f64)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (mut docids, value) = match self.values.next()? {
+            Ok(next) => next,
+            Err(e) => return Some(Err(e)),
+        };
+        let score = self.rule.score(value - self.origin);
+
+        while let Some(Ok((_, next_value))) = self.values.peek() {
+            if self.rule.score(next_value - self.origin) != score {
+                break;
+            }
+            let Some(Ok((next_docids, _))) = self.values.next() else { unreachable!() };
+            docids |= next_docids;
+        }
+
+        Some(Ok((docids, value, score)))
+    }
+}
@@ -1,18 +1,21 @@
-use std::collections::VecDeque;
+use std::collections::{BinaryHeap, VecDeque};
 use std::iter::FromIterator;
 
 use heed::types::{Bytes, Unit};
 use heed::{RoPrefix, RoTxn};
+use ordered_float::OrderedFloat;
 use roaring::RoaringBitmap;
 use rstar::RTree;
+use serde_json::Value;
 
 use super::facet_string_values;
 use super::ranking_rules::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait};
 use crate::heed_codec::facet::{FieldDocIdFacetCodec, OrderedF64Codec};
 use crate::score_details::{self, ScoreDetails};
+use crate::update::extract_finite_float_from_value;
 use crate::{
-    distance_between_two_points, lat_lng_to_xyz, GeoPoint, Index, Result, SearchContext,
-    SearchLogger,
+    distance_between_two_points, lat_lng_to_xyz, FieldId, GeoPoint, Index, InternalError, Result,
+    SearchContext, SearchLogger,
 };
 
 const FID_SIZE: usize = 2;
@@ -79,7 +82,8 @@ pub struct GeoSort<Q: RankingRuleQueryTrait> {
     strategy: Strategy,
     ascending: bool,
     point: [f64; 2],
-    field_ids: Option<[u16; 2]>,
+    /// The ids of the `_geo`, `_geo.lat` and `_geo.lng` fields.
+    field_ids: Option<[u16; 3]>,
     rtree: Option<RTree<GeoPoint>>,
 
     cached_sorted_docids: VecDeque<(u32, [f64; 2])>,
@@ -113,6 +117,8 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
         geo_candidates: &RoaringBitmap,
     ) -> Result<()> {
         debug_assert!(self.field_ids.is_some(), "fill_buffer can't be called without the lat&lng");
+        let field_ids = self.field_ids.unwrap();
+        let target = self.point;
         debug_assert!(self.cached_sorted_docids.is_empty());
 
         // lazily initialize the rtree if needed by the strategy, and cache it in `self.rtree`
@@ -132,10 +138,12 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
 
         let cache_size = self.strategy.cache_size();
         if let Some(rtree) = rtree {
+            // a document can have several points, the first one we meet is its nearest one
+            let mut seen = RoaringBitmap::new();
             if self.ascending {
-                let point = lat_lng_to_xyz(&self.point);
+                let point = lat_lng_to_xyz(&target);
                 for point in rtree.nearest_neighbor_iter(&point) {
-                    if geo_candidates.contains(point.data.0) {
+                    if geo_candidates.contains(point.data.0) && seen.insert(point.data.0) {
                         self.cached_sorted_docids.push_back(point.data);
                         if self.cached_sorted_docids.len() >= cache_size {
                             break;
@@ -144,64 +152,131 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
                 }
             } else {
                 // in the case of the desc geo sort we look for the closest point to the opposite of the queried point
-                // and we insert the points in reverse order they get reversed when emptying the cache later on
-                let point = lat_lng_to_xyz(&opposite_of(self.point));
+                // and we insert the points in reverse order they get reversed when emptying the cache later on.
+                // We meet the points from the farthest to the nearest, but the documents are sorted by their
+                // nearest point, so we keep the documents we met in a heap until all the points we have not met
+                // yet are nearer than their nearest point.
+                let point = lat_lng_to_xyz(&opposite_of(target));
+                let mut pending = BinaryHeap::new();
+                let mut exhausted = true;
                 for point in rtree.nearest_neighbor_iter(&point) {
-                    if geo_candidates.contains(point.data.0) {
-                        self.cached_sorted_docids.push_front(point.data);
-                        if self.cached_sorted_docids.len() >= cache_size {
+                    let (docid, lat_lng) = point.data;
+                    let threshold = distance_between_two_points(&target, &lat_lng);
+                    while let Some((distance, ..)) = pending.peek() {
+                        if *distance < OrderedFloat(threshold) {
                             break;
                         }
+                        let (_, docid, nearest) = pending.pop().unwrap();
+                        self.cached_sorted_docids.push_front((docid, nearest.map(|c| c.0)));
+                    }
+                    if self.cached_sorted_docids.len() >= cache_size {
+                        exhausted = false;
+                        break;
+                    }
+                    if geo_candidates.contains(docid) && seen.insert(docid) {
+                        let points = geo_points(docid, field_ids, ctx.index, ctx.txn)?;
+                        let (nearest, distance) = nearest_point(docid, &target, &points)?;
+                        pending.push((OrderedFloat(distance), docid, nearest.map(OrderedFloat)));
+                    }
+                }
+                // when we met all the points, the remaining documents can be sorted
+                if exhausted {
+                    while let Some((_, docid, nearest)) = pending.pop() {
+                        self.cached_sorted_docids.push_front((docid, nearest.map(|c| c.0)));
                     }
                 }
             }
         } else {
             // the iterative version
-            let [lat, lng] = self.field_ids.unwrap();
-
             let mut documents = geo_candidates
                 .iter()
-                .map(|id| -> Result<_> { Ok((id, geo_value(id, lat, lng, ctx.index, ctx.txn)?)) })
-                .collect::<Result<Vec<(u32, [f64; 2])>>>()?;
-            // computing the distance between two points is expensive thus we cache the result
-            documents
-                .sort_by_cached_key(|(_, p)| distance_between_two_points(&self.point, p) as usize);
-            self.cached_sorted_docids.extend(documents);
+                .map(|id| -> Result<_> {
+                    let points = geo_points(id, field_ids, ctx.index, ctx.txn)?;
+                    let (nearest, distance) = nearest_point(id, &target, &points)?;
+                    Ok((id, nearest, distance))
+                })
+                .collect::<Result<Vec<(u32, [f64; 2], f64)>>>()?;
+            documents.sort_by_key(|(_, _, distance)| *distance as usize);
+            self.cached_sorted_docids
+                .extend(documents.into_iter().map(|(id, nearest, _)| (id, nearest)));
         };
 
         Ok(())
     }
 }
 
-/// Extracts the lat and long values from a single document.
+/// Extracts the lat and long values of the points of a single document.
 ///
-/// If it is not able to find it in the facet number index it will extract it
-/// from the facet string index and parse it as f64 (as the geo extraction behaves).
-fn geo_value(
+/// If it is not able to find them in the facet number index it will extract them
+/// from the facet string index and parse them as f64 (as the geo extraction behaves).
+/// The facet indexes can't tell which latitude goes with which longitude though, so when
+/// the document has several points they are extracted from the document itself.
+fn geo_points(
     docid: u32,
-    field_lat: u16,
-    field_lng: u16,
+    [field_geo, field_lat, field_lng]: [u16; 3],
     index: &Index,
     rtxn: &RoTxn,
-) -> Result<[f64; 2]> {
-    let extract_geo = |geo_field: u16| -> Result<f64> {
-        match facet_number_values(docid, geo_field, index, rtxn)?.next() {
-            Some(Ok(((_, _, geo), ()))) => Ok(geo),
-            Some(Err(e)) => Err(e.into()),
-            None => match facet_string_values(docid, geo_field, index, rtxn)?.next() {
-                Some(Ok((_, geo))) => {
-                    Ok(geo.parse::<f64>().expect("cannot parse geo field as f64"))
-                }
-                Some(Err(e)) => Err(e.into()),
-                None => panic!("A geo faceted document doesn't contain any lat or lng"),
-            },
+) -> Result<Vec<[f64; 2]>> {
+    let extract_geo = |geo_field: u16| -> Result<Vec<f64>> {
+        let mut values = Vec::new();
+        for result in facet_number_values(docid, geo_field, index, rtxn)? {
+            let ((_, _, geo), ()) = result?;
+            values.push(geo);
+        }
+        if values.is_empty() {
+            for result in facet_string_values(docid, geo_field, index, rtxn)? {
+                let (_, geo) = result?;
+                values.push(geo.parse::<f64>().expect("cannot parse geo field as f64"));
+            }
         }
+        Ok(values)
+    };
+
+    match (extract_geo(field_lat)?.as_slice(), extract_geo(field_lng)?.as_slice()) {
+        ([], _) | (_, []) => panic!("A geo faceted document doesn't contain any lat or lng"),
+        ([lat], [lng]) => Ok(vec![[*lat, *lng]]),
+        _ => document_geo_points(docid, field_geo, index, rtxn),
+    }
+}
+
+/// Extracts the lat and long values of the points stored in the `_geo` field of the document.
+fn document_geo_points(
+    docid: u32,
+    field_geo: FieldId,
+    index: &Index,
+    rtxn: &RoTxn,
+) -> Result<Vec<[f64; 2]>> {
+    let documents = index.documents(rtxn, Some(docid))?;
+    let geo = match documents.first().and_then(|(_, document)| document.get(field_geo)) {
+        Some(geo) => serde_json::from_slice(geo).map_err(InternalError::SerdeJson)?,
+        None => Value::Null,
+    };
+    let points = match geo {
+        Value::Array(points) => points,
+        point => vec![point],
     };
 
-    let lat = extract_geo(field_lat)?;
-    let lng = extract_geo(field_lng)?;
+    let points = points
+        .into_iter()
+        .filter_map(|mut point| {
+            let lat = extract_finite_float_from_value(point.get_mut("lat")?.take()).ok()?;
+            let lng = extract_finite_float_from_value(point.get_mut("lng")?.take()).ok()?;
+            Some([lat, lng])
+        })
+        .collect();
 
-    Ok([lat, lng])
+    Ok(points)
+}
+
+/// Returns the point of the document that is the nearest to the target and its distance in meters.
+///
+/// Fails when none of the points of the document are valid.
+fn nearest_point(docid: u32, target: &[f64; 2], points: &[[f64; 2]]) -> Result<([f64; 2], f64)> {
+    points
+        .iter()
+        .map(|point| (*point, distance_between_two_points(target, point)))
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .ok_or_else(|| InternalError::GeoFacetedDocumentWithoutPoint { docid }.into())
 }
 
 impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for GeoSort<Q> {
@@ -228,9 +303,10 @@ impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for GeoSort<Q> {
         }
 
         let fid_map = ctx.index.fields_ids_map(ctx.txn)?;
+        let geo = fid_map.id("_geo").expect("geo candidates but no fid for geo");
         let lat = fid_map.id("_geo.lat").expect("geo candidates but no fid for lat");
         let lng = fid_map.id("_geo.lng").expect("geo candidates but no fid for lng");
-        self.field_ids = Some([lat, lng]);
+        self.field_ids = Some([geo, lat, lng]);
         self.fill_buffer(ctx, &geo_candidates)?;
         Ok(())
     }
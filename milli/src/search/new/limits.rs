@@ -1,8 +1,16 @@
 /// Maximum number of tokens we consider in a single search.
 pub const MAX_TOKEN_COUNT: usize = 1_000;
 
+/// Maximum number of words allowed between two consecutive words of a phrase.
+///
+/// Bounded by the greatest proximity stored in the word pair proximity database.
+/// A phrase with a greater slop is rejected.
+pub const MAX_PHRASE_SLOP: u8 = crate::proximity::MAX_DISTANCE as u8 - 2;
+
 /// Maximum number of prefixes that can be derived from a single word.
 pub const MAX_PREFIX_COUNT: usize = 1_000;
+/// Maximum number of words containing a single word that can be derived from the n-gram database.
+pub const MAX_INFIX_COUNT: usize = 1_000;
 /// Maximum number of words that can be derived from a single word with a distance of one to that word.
 pub const MAX_ONE_TYPO_COUNT: usize = 150;
 /// Maximum number of words that can be derived from a single word with a distance of two to that word.
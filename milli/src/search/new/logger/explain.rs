@@ -0,0 +1,370 @@
+use std::any::Any;
+use std::collections::BTreeMap;
+
+use roaring::RoaringBitmap;
+use serde::Serialize;
+
+use crate::search::new::query_graph::QueryNodeData;
+use crate::search::new::query_term::{LocatedQueryTermSubset, QueryTermSubset};
+use crate::search::new::ranking_rules::BoxRankingRule;
+use crate::search::new::{
+    QueryGraph, RankingRule, RankingRuleQueryTrait, SearchContext, SearchLogger,
+};
+use crate::{DocumentId, Result};
+
+/// A [`SearchLogger`] recording the buckets returned by the ranking rules,
+/// in order to explain how the candidates of a search were sorted.
+pub struct ExplainSearchLogger {
+    documents: Vec<DocumentId>,
+    initial_query: Option<QueryGraph>,
+    initial_universe: Option<RoaringBitmap>,
+    ranking_rules_ids: Option<Vec<String>>,
+    /// The buckets of the ranking rules currently iterating, one level per ranking rule
+    levels: Vec<Vec<LoggedBucket>>,
+    buckets: Vec<LoggedBucket>,
+    /// The buckets each of the explained documents was found in, from the first ranking rule
+    paths: BTreeMap<DocumentId, Vec<BucketStep>>,
+    skipped: RoaringBitmap,
+}
+
+struct LoggedBucket {
+    ranking_rule: String,
+    rank: u32,
+    size: u64,
+    query: Option<QueryGraph>,
+    buckets: Vec<LoggedBucket>,
+}
+
+impl ExplainSearchLogger {
+    /// Creates a logger explaining, in addition to the buckets, where the given documents landed.
+    pub fn new(documents: &[DocumentId]) -> Self {
+        Self {
+            documents: documents.to_vec(),
+            initial_query: None,
+            initial_universe: None,
+            ranking_rules_ids: None,
+            levels: Vec::new(),
+            buckets: Vec::new(),
+            paths: BTreeMap::new(),
+            skipped: RoaringBitmap::new(),
+        }
+    }
+
+    /// Returns `true` if a bucket sort was logged, i.e. this logger was the one used by the search.
+    pub fn is_used(&self) -> bool {
+        self.ranking_rules_ids.is_some()
+    }
+
+    /// Builds the explanation of the logged search. The position of the documents
+    /// is given by the final results, `documents_ids`.
+    pub fn finish(
+        mut self,
+        ctx: &mut SearchContext,
+        documents_ids: &[DocumentId],
+    ) -> Result<SearchExplanation> {
+        // the bucket sort may stop before the ranking rules finish their iterations
+        while let Some(level) = self.levels.pop() {
+            self.close_level(level);
+        }
+
+        let query_graph = match &self.initial_query {
+            Some(graph) => Some(explain_query_graph(ctx, graph)?),
+            None => None,
+        };
+        let buckets = std::mem::take(&mut self.buckets)
+            .into_iter()
+            .map(|bucket| explain_bucket(ctx, bucket))
+            .collect::<Result<_>>()?;
+
+        let documents = self
+            .documents
+            .iter()
+            .map(|&id| ExplainedDocument {
+                id,
+                candidate: self.initial_universe.as_ref().map_or(false, |u| u.contains(id)),
+                position: documents_ids.iter().position(|&docid| docid == id),
+                skipped: self.skipped.contains(id),
+                buckets: self.paths.remove(&id).unwrap_or_default(),
+            })
+            .collect();
+
+        Ok(SearchExplanation {
+            ranking_rules: self.ranking_rules_ids.unwrap_or_default(),
+            query_graph,
+            candidates: self.initial_universe.map_or(0, |u| u.len()),
+            buckets,
+            documents,
+        })
+    }
+
+    /// Attaches the buckets of a finished ranking rule iteration to the bucket of its parent.
+    fn close_level(&mut self, level: Vec<LoggedBucket>) {
+        match self.levels.last_mut().and_then(|parent| parent.last_mut()) {
+            Some(parent) => parent.buckets = level,
+            None => self.buckets.extend(level),
+        }
+    }
+}
+
+impl<Q: RankingRuleQueryTrait> SearchLogger<Q> for ExplainSearchLogger {
+    fn initial_query(&mut self, query: &Q) {
+        let query: &dyn Any = query;
+        self.initial_query = query.downcast_ref::<QueryGraph>().cloned();
+    }
+
+    fn initial_universe(&mut self, universe: &RoaringBitmap) {
+        self.initial_universe = Some(universe.clone());
+    }
+
+    fn query_for_initial_universe(&mut self, _query: &Q) {}
+
+    fn ranking_rules(&mut self, rr: &[BoxRankingRule<Q>]) {
+        self.ranking_rules_ids = Some(rr.iter().map(|rr| rr.id()).collect());
+    }
+
+    fn start_iteration_ranking_rule(
+        &mut self,
+        _ranking_rule_idx: usize,
+        _ranking_rule: &dyn RankingRule<Q>,
+        query: &Q,
+        _universe: &RoaringBitmap,
+    ) {
+        // the query of a sub-iteration is the one of the bucket it sorts
+        if let Some(bucket) = self.levels.last_mut().and_then(|parent| parent.last_mut()) {
+            let query: &dyn Any = query;
+            bucket.query = query.downcast_ref::<QueryGraph>().cloned();
+        }
+        self.levels.push(Vec::new());
+    }
+
+    fn next_bucket_ranking_rule(
+        &mut self,
+        _ranking_rule_idx: usize,
+        ranking_rule: &dyn RankingRule<Q>,
+        _universe: &RoaringBitmap,
+        candidates: &RoaringBitmap,
+    ) {
+        let Some(level) = self.levels.last_mut() else { return };
+        level.push(LoggedBucket {
+            ranking_rule: ranking_rule.id(),
+            rank: level.len() as u32,
+            size: candidates.len(),
+            query: None,
+            buckets: Vec::new(),
+        });
+
+        // the buckets partition their universe, so a later bucket containing
+        // a document is always nested in the previous one
+        for &id in self.documents.iter().filter(|&&id| candidates.contains(id)) {
+            let path = self
+                .levels
+                .iter()
+                .filter_map(|level| level.last())
+                .map(|bucket| BucketStep {
+                    ranking_rule: bucket.ranking_rule.clone(),
+                    rank: bucket.rank,
+                    size: bucket.size,
+                })
+                .collect();
+            self.paths.insert(id, path);
+        }
+    }
+
+    fn skip_bucket_ranking_rule(
+        &mut self,
+        _ranking_rule_idx: usize,
+        _ranking_rule: &dyn RankingRule<Q>,
+        candidates: &RoaringBitmap,
+    ) {
+        self.skipped |= candidates;
+    }
+
+    fn end_iteration_ranking_rule(
+        &mut self,
+        _ranking_rule_idx: usize,
+        _ranking_rule: &dyn RankingRule<Q>,
+        _universe: &RoaringBitmap,
+    ) {
+        if let Some(level) = self.levels.pop() {
+            self.close_level(level);
+        }
+    }
+
+    fn add_to_results(&mut self, _docids: &[u32]) {}
+
+    fn log_internal_state(&mut self, _rr: &dyn Any) {}
+}
+
+/// How the ranking rules sorted the candidates of a search.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchExplanation {
+    pub ranking_rules: Vec<String>,
+    /// The graph of the query terms, `None` for a placeholder search.
+    pub query_graph: Option<ExplainedQueryGraph>,
+    /// The number of documents sorted by the ranking rules.
+    pub candidates: u64,
+    /// The buckets returned by the first ranking rule, containing the ones
+    /// the next ranking rules split them into.
+    pub buckets: Vec<ExplainedBucket>,
+    pub documents: Vec<ExplainedDocument>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExplainedBucket {
+    pub ranking_rule: String,
+    pub rank: u32,
+    pub size: u64,
+    /// The query terms the next ranking rule sorted this bucket with.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query: Option<Vec<ExplainedTerm>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub buckets: Vec<ExplainedBucket>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BucketStep {
+    pub ranking_rule: String,
+    pub rank: u32,
+    pub size: u64,
+}
+
+/// Where a document landed in the buckets of the ranking rules.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExplainedDocument {
+    pub id: DocumentId,
+    /// Whether the document matches the filter and the query.
+    pub candidate: bool,
+    /// The position of the document in the returned results.
+    pub position: Option<usize>,
+    /// Whether the document was sorted before the requested page.
+    pub skipped: bool,
+    /// The bucket the document was found in for each ranking rule, stopping
+    /// at the ranking rule that didn't need to sort it further.
+    pub buckets: Vec<BucketStep>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExplainedQueryGraph {
+    pub nodes: Vec<ExplainedQueryNode>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExplainedQueryNode {
+    pub id: u16,
+    /// `start`, `end`, or `term`
+    pub kind: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub term: Option<ExplainedTerm>,
+    pub successors: Vec<u16>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExplainedTerm {
+    pub original: String,
+    /// The first and last positions in the query of the words making the term.
+    pub positions: [u16; 2],
+    pub max_typos: u8,
+    pub derivations: TermDerivations,
+}
+
+/// The words and phrases a query term is matched with.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TermDerivations {
+    pub exact: Vec<String>,
+    pub one_typo: Vec<String>,
+    pub two_typos: Vec<String>,
+    pub phrases: Vec<String>,
+    /// The prefix whose documents are retrieved from the prefix databases.
+    pub prefix_db: Option<String>,
+}
+
+fn explain_bucket(ctx: &mut SearchContext, bucket: LoggedBucket) -> Result<ExplainedBucket> {
+    let LoggedBucket { ranking_rule, rank, size, query, buckets } = bucket;
+    let query = match query {
+        Some(graph) => Some(
+            graph
+                .nodes
+                .iter()
+                .filter_map(|(_, node)| match &node.data {
+                    QueryNodeData::Term(term) => Some(term),
+                    _ => None,
+                })
+                .map(|term| explain_term(ctx, term))
+                .collect::<Result<_>>()?,
+        ),
+        None => None,
+    };
+    let buckets =
+        buckets.into_iter().map(|bucket| explain_bucket(ctx, bucket)).collect::<Result<_>>()?;
+    Ok(ExplainedBucket { ranking_rule, rank, size, query, buckets })
+}
+
+fn explain_query_graph(ctx: &mut SearchContext, graph: &QueryGraph) -> Result<ExplainedQueryGraph> {
+    let mut nodes = Vec::new();
+    for (node_id, node) in graph.nodes.iter() {
+        let (kind, term) = match &node.data {
+            QueryNodeData::Term(term) => ("term", Some(explain_term(ctx, term)?)),
+            QueryNodeData::Start => ("start", None),
+            QueryNodeData::End => ("end", None),
+            QueryNodeData::Deleted => continue,
+        };
+        nodes.push(ExplainedQueryNode {
+            id: node_id.into_raw(),
+            kind,
+            term,
+            successors: node.successors.iter().map(|successor| successor.into_raw()).collect(),
+        });
+    }
+    Ok(ExplainedQueryGraph { nodes })
+}
+
+fn explain_term(ctx: &mut SearchContext, term: &LocatedQueryTermSubset) -> Result<ExplainedTerm> {
+    let LocatedQueryTermSubset { term_subset, positions, term_ids: _ } = term;
+
+    let mut exact = term_subset.clone();
+    exact.clear_one_typo_subset();
+    exact.clear_two_typo_subset();
+    let mut one_typo = term_subset.clone();
+    one_typo.clear_zero_typo_subset();
+    one_typo.clear_two_typo_subset();
+    let mut two_typos = term_subset.clone();
+    two_typos.clear_zero_typo_subset();
+    two_typos.clear_one_typo_subset();
+
+    let derivations = TermDerivations {
+        exact: words(ctx, &exact)?,
+        one_typo: words(ctx, &one_typo)?,
+        two_typos: words(ctx, &two_typos)?,
+        phrases: term_subset
+            .all_phrases(ctx)?
+            .into_iter()
+            .map(|phrase| phrase.description(ctx))
+            .collect(),
+        prefix_db: term_subset
+            .use_prefix_db(ctx)
+            .map(|word| ctx.word_interner.get(word.interned()).to_owned()),
+    };
+
+    Ok(ExplainedTerm {
+        original: term_subset.description(ctx),
+        positions: [*positions.start(), *positions.end()],
+        max_typos: term_subset.max_typo_cost(ctx),
+        derivations,
+    })
+}
+
+fn words(ctx: &mut SearchContext, subset: &QueryTermSubset) -> Result<Vec<String>> {
+    Ok(subset
+        .all_single_words_except_prefix_db(ctx)?
+        .into_iter()
+        .map(|word| ctx.word_interner.get(word.interned()).to_owned())
+        .collect())
+}
@@ -1,3 +1,4 @@
+pub mod explain;
 // #[cfg(test)]
 pub mod visual;
 
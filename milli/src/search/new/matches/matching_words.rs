@@ -43,10 +43,23 @@ impl MatchingWords {
             let (matching_words, matching_phrases) = term.all_computed_derivations();
 
             for matching_phrase in matching_phrases {
-                phrases.push(LocatedMatchingPhrase {
-                    value: matching_phrase,
-                    positions: located_term.positions.clone(),
-                });
+                let phrase = ctx.phrase_interner.get(matching_phrase);
+                // the words of a sloppy phrase are not side-by-side, so they are matched one by one.
+                if phrase.slop > 0 {
+                    for word in phrase.words.iter().flatten() {
+                        words.push(LocatedMatchingWords {
+                            value: vec![*word],
+                            positions: located_term.positions.clone(),
+                            is_prefix: false,
+                            original_char_count: ctx.word_interner.get(*word).chars().count(),
+                        });
+                    }
+                } else {
+                    phrases.push(LocatedMatchingPhrase {
+                        value: matching_phrase,
+                        positions: located_term.positions.clone(),
+                    });
+                }
             }
 
             words.push(LocatedMatchingWords {
@@ -263,7 +276,7 @@ pub(crate) mod tests {
         let tokenizer = builder.build();
         let tokens = tokenizer.tokenize("split this world");
         let ExtractedTokens { query_terms, .. } =
-            located_query_terms_from_tokens(&mut ctx, tokens, None).unwrap();
+            located_query_terms_from_tokens(&mut ctx, tokens, None, &[]).unwrap();
         let matching_words = MatchingWords::new(ctx, query_terms);
 
         assert_eq!(
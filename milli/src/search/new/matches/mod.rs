@@ -520,11 +520,14 @@ mod tests {
                 crate::search::new::GeoSortStrategy::default(),
                 0,
                 100,
+                None,
                 Some(10),
                 &mut crate::DefaultSearchLogger,
                 &mut crate::DefaultSearchLogger,
                 TimeBudget::max(),
                 None,
+                None,
+                false,
             )
             .unwrap();
 
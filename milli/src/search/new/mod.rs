@@ -1,5 +1,6 @@
 mod bucket_sort;
 mod db_cache;
+mod decay;
 mod distinct;
 mod geo_sort;
 mod graph_based_ranking_rule;
@@ -21,40 +22,52 @@ mod vector_sort;
 #[cfg(test)]
 mod tests;
 
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 
-use bucket_sort::{bucket_sort, BucketSortOutput};
-use charabia::TokenizerBuilder;
+use bucket_sort::{bucket_sort, BucketSortOutput, PinnedDocuments};
+pub use bucket_sort::{InvalidSearchCursor, RankedCursor, SearchCursor, SearchCursorToken};
+use charabia::{Language, Token, TokenizerBuilder};
 use db_cache::DatabaseCache;
+use decay::Decay;
 use exact_attribute::ExactAttribute;
 use graph_based_ranking_rule::{Exactness, Fid, Position, Proximity, Typo};
 use heed::RoTxn;
 use interner::{DedupInterner, Interner};
+pub use logger::explain::{
+    BucketStep, ExplainSearchLogger, ExplainedBucket, ExplainedDocument, ExplainedQueryGraph,
+    ExplainedQueryNode, ExplainedTerm, SearchExplanation, TermDerivations,
+};
 pub use logger::visual::VisualSearchLogger;
 pub use logger::{DefaultSearchLogger, SearchLogger};
-use query_graph::{QueryGraph, QueryNode};
+pub(crate) use query_graph::QueryGraph;
+use query_graph::QueryNode;
+pub use query_term::suggest_query;
 use query_term::{
-    located_query_terms_from_tokens, ExtractedTokens, LocatedQueryTerm, Phrase, QueryTerm,
-};
-use ranking_rules::{
-    BoxRankingRule, PlaceholderQuery, RankingRule, RankingRuleOutput, RankingRuleQueryTrait,
+    extract_field_scopes, located_query_terms_from_tokens, number_of_typos_allowed,
+    partially_initialized_term_from_word, ExtractedTokens, LocatedQueryTerm, Phrase, QueryTerm,
+    QueryTermSubset,
 };
+pub(crate) use ranking_rules::PlaceholderQuery;
+use ranking_rules::{BoxRankingRule, RankingRule, RankingRuleOutput, RankingRuleQueryTrait};
 use resolve_query_graph::{compute_query_graph_docids, PhraseDocIdsCache};
 use roaring::RoaringBitmap;
 use sort::Sort;
 
+pub(crate) use self::distinct::distinct_single_docid;
 use self::distinct::facet_string_values;
 use self::geo_sort::GeoSort;
 pub use self::geo_sort::Strategy as GeoSortStrategy;
 use self::graph_based_ranking_rule::Words;
 use self::interner::Interned;
 use self::vector_sort::VectorSort;
+use crate::localized_attributes_rules::script_language_allow_list;
 use crate::score_details::{ScoreDetails, ScoringStrategy};
+use crate::search::analyze::AnalyzedQueryTerm;
 use crate::search::new::distinct::apply_distinct_rule;
 use crate::vector::Embedder;
 use crate::{
-    AscDesc, DocumentId, FieldId, Filter, Index, Member, Result, TermsMatchingStrategy, TimeBudget,
-    UserError, Weight,
+    AscDesc, Criterion, DocumentId, FieldId, FieldidsWeightsMap, Filter, Index, Member, Result,
+    TermsMatchingStrategy, TimeBudget, UserError, Weight,
 };
 
 /// A structure used throughout the execution of a search query.
@@ -67,6 +80,12 @@ pub struct SearchContext<'ctx> {
     pub term_interner: Interner<QueryTerm>,
     pub phrase_docids: PhraseDocIdsCache,
     pub restricted_fids: Option<RestrictedFids>,
+    /// The weights of the searchable attributes overridden for this query
+    pub attributes_weights: Option<FieldidsWeightsMap>,
+    /// The ranking rules replacing the ones of the index for this query
+    pub ranking_rules: Option<Vec<Criterion>>,
+    /// Whether the distinct attribute of the index is ignored when the query doesn't set one
+    pub ignore_index_distinct: bool,
 }
 
 impl<'ctx> SearchContext<'ctx> {
@@ -93,6 +112,9 @@ impl<'ctx> SearchContext<'ctx> {
             term_interner: <_>::default(),
             phrase_docids: <_>::default(),
             restricted_fids: None,
+            attributes_weights: None,
+            ranking_rules: None,
+            ignore_index_distinct: false,
         })
     }
 
@@ -152,6 +174,82 @@ impl<'ctx> SearchContext<'ctx> {
 
         Ok(())
     }
+
+    pub fn attributes_weights(&mut self, attributes_weights: &[(String, Weight)]) -> Result<()> {
+        let user_defined_searchable = self.index.user_defined_searchable_fields(self.txn)?;
+        let searchable_fields_weights = self.index.searchable_fields_and_weights(self.txn)?;
+
+        for (field_name, _) in attributes_weights {
+            let is_searchable =
+                searchable_fields_weights.iter().any(|(name, _, _)| name == field_name);
+            // The field is not searchable while the user defined the searchable attributes
+            if !is_searchable && user_defined_searchable.is_some() {
+                let (valid_fields, hidden_fields) = self.index.remove_hidden_fields(
+                    self.txn,
+                    searchable_fields_weights.iter().map(|(name, _, _)| name),
+                )?;
+
+                let field = field_name.to_string();
+                return Err(UserError::InvalidSearchableAttribute {
+                    field,
+                    valid_fields,
+                    hidden_fields,
+                }
+                .into());
+            }
+        }
+
+        let Some(max_weight) = attributes_weights.iter().map(|(_, weight)| *weight).max() else {
+            return Ok(());
+        };
+
+        // The attribute ranking rule prefers the lowest weights, so the attributes given a weight
+        // are ranked first by decreasing weight and the other ones keep their order after them.
+        let mut weights_map = FieldidsWeightsMap::default();
+        for (name, fid, weight) in searchable_fields_weights {
+            let given_weight =
+                attributes_weights.iter().find(|(field_name, _)| *field_name == name);
+            let weight = match given_weight {
+                Some((_, given_weight)) => max_weight - given_weight,
+                None => max_weight.saturating_add(1).saturating_add(weight),
+            };
+            weights_map.insert(fid, weight);
+        }
+        self.attributes_weights = Some(weights_map);
+
+        Ok(())
+    }
+
+    pub fn ranking_rules(&mut self, ranking_rules: Vec<Criterion>) -> Result<()> {
+        // The fields of the custom ranking rules are only guaranteed
+        // to be in the facet databases when they are sortable.
+        let sortable_fields = self.index.sortable_fields(self.txn)?;
+        for field in ranking_rules.iter().filter_map(Criterion::field_name) {
+            if !crate::is_faceted(field, &sortable_fields) {
+                let (valid_fields, hidden_fields) =
+                    self.index.remove_hidden_fields(self.txn, sortable_fields)?;
+
+                return Err(UserError::InvalidRankingRuleAttribute {
+                    field: field.to_string(),
+                    valid_fields,
+                    hidden_fields,
+                }
+                .into());
+            }
+        }
+
+        self.ranking_rules = Some(ranking_rules);
+
+        Ok(())
+    }
+
+    /// The ranking rules of the query, or the ones of the index when they are not overridden.
+    fn criteria(&self) -> Result<Vec<Criterion>> {
+        match &self.ranking_rules {
+            Some(ranking_rules) => Ok(ranking_rules.clone()),
+            None => Ok(self.index.criteria(self.txn)?),
+        }
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, PartialOrd, Ord, Eq)]
@@ -233,11 +331,27 @@ fn resolve_universe(
 fn resolve_negative_words(
     ctx: &mut SearchContext,
     negative_words: &[Word],
+    typo_tolerant: bool,
 ) -> Result<RoaringBitmap> {
+    let nbr_typos = number_of_typos_allowed(ctx)?;
     let mut negative_bitmap = RoaringBitmap::new();
     for &word in negative_words {
-        if let Some(bitmap) = ctx.word_docids(word)? {
-            negative_bitmap |= bitmap;
+        // when typos are allowed, the negated word excludes all its typo derivations
+        // but neither its synonyms, its split words, nor the words it is a prefix or an infix of.
+        let words = if typo_tolerant {
+            let original = ctx.word_interner.get(word.interned()).to_owned();
+            let max_typo = nbr_typos(&original);
+            let term =
+                partially_initialized_term_from_word(ctx, &original, max_typo, false, false)?;
+            let term = ctx.term_interner.push(term);
+            QueryTermSubset::full(term).exact_and_typo_words(ctx)?
+        } else {
+            BTreeSet::from([word])
+        };
+        for word in words {
+            if let Some(bitmap) = ctx.word_docids(word)? {
+                negative_bitmap |= bitmap;
+            }
         }
     }
     Ok(negative_bitmap)
@@ -268,7 +382,7 @@ fn get_ranking_rules_for_placeholder_search<'ctx>(
     let mut sorted_fields = HashSet::new();
     let mut geo_sorted = false;
     let mut ranking_rules: Vec<BoxRankingRule<PlaceholderQuery>> = vec![];
-    let settings_ranking_rules = ctx.index.criteria(ctx.txn)?;
+    let settings_ranking_rules = ctx.criteria()?;
     for rr in settings_ranking_rules {
         match rr {
             // These rules need a query to have an effect; ignore them in placeholder search
@@ -305,6 +419,9 @@ fn get_ranking_rules_for_placeholder_search<'ctx>(
                 sorted_fields.insert(field_name.clone());
                 ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false)?));
             }
+            crate::Criterion::Decay(decay) => {
+                ranking_rules.push(Box::new(Decay::new(ctx.index, ctx.txn, decay)?));
+            }
         }
     }
     Ok(ranking_rules)
@@ -328,7 +445,7 @@ fn get_ranking_rules_for_vector<'ctx>(
     let mut vector = false;
     let mut ranking_rules: Vec<BoxRankingRule<PlaceholderQuery>> = vec![];
 
-    let settings_ranking_rules = ctx.index.criteria(ctx.txn)?;
+    let settings_ranking_rules = ctx.criteria()?;
     for rr in settings_ranking_rules {
         match rr {
             crate::Criterion::Words
@@ -378,6 +495,9 @@ fn get_ranking_rules_for_vector<'ctx>(
                 sorted_fields.insert(field_name.clone());
                 ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false)?));
             }
+            crate::Criterion::Decay(decay) => {
+                ranking_rules.push(Box::new(Decay::new(ctx.index, ctx.txn, decay)?));
+            }
         }
     }
 
@@ -407,7 +527,7 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
     }
 
     let mut ranking_rules: Vec<BoxRankingRule<QueryGraph>> = vec![];
-    let settings_ranking_rules = ctx.index.criteria(ctx.txn)?;
+    let settings_ranking_rules = ctx.criteria()?;
     for rr in settings_ranking_rules {
         // Add Words before any of: typo, proximity, attribute
         match rr {
@@ -488,6 +608,9 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
                 sorted_fields.insert(field_name.clone());
                 ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false)?));
             }
+            crate::Criterion::Decay(decay) => {
+                ranking_rules.push(Box::new(Decay::new(ctx.index, ctx.txn, decay)?));
+            }
         }
     }
     Ok(ranking_rules)
@@ -564,14 +687,16 @@ pub fn filtered_universe(
 #[allow(clippy::too_many_arguments)]
 pub fn execute_vector_search(
     ctx: &mut SearchContext,
+    query: Option<&str>,
     vector: &[f32],
     scoring_strategy: ScoringStrategy,
-    universe: RoaringBitmap,
+    mut universe: RoaringBitmap,
     sort_criteria: &Option<Vec<AscDesc>>,
     distinct: &Option<String>,
     geo_strategy: geo_sort::Strategy,
     from: usize,
     length: usize,
+    search_after: Option<&SearchCursor>,
     embedder_name: &str,
     embedder: &Embedder,
     time_budget: TimeBudget,
@@ -579,13 +704,20 @@ pub fn execute_vector_search(
 ) -> Result<PartialSearchResult> {
     check_sort_criteria(ctx, sort_criteria.as_ref())?;
 
+    let pinned_documents = match query {
+        Some(query) => PinnedDocuments::resolve(ctx, query, distinct.as_deref(), &universe)?,
+        None => PinnedDocuments::default(),
+    };
+    universe -= &pinned_documents.excluded;
+    let page = pinned_documents.page(from, length, search_after)?;
+
     // FIXME: input universe = universe & documents_with_vectors
     // for now if we're computing embeddings for ALL documents, we can assume that this is just universe
     let ranking_rules = get_ranking_rules_for_vector(
         ctx,
         sort_criteria,
         geo_strategy,
-        from + length,
+        page.ranked_before + page.bucket_sort_length,
         vector,
         embedder_name,
         embedder,
@@ -595,30 +727,109 @@ pub fn execute_vector_search(
     let placeholder_search_logger: &mut dyn SearchLogger<PlaceholderQuery> =
         &mut placeholder_search_logger;
 
-    let BucketSortOutput { docids, scores, all_candidates, degraded } = bucket_sort(
+    let bucket_sort_output = bucket_sort(
         ctx,
         ranking_rules,
         &PlaceholderQuery,
         distinct.as_deref(),
         &universe,
-        from,
-        length,
+        page.bucket_sort_from,
+        page.bucket_sort_length,
+        page.search_after.as_ref(),
         scoring_strategy,
         placeholder_search_logger,
         time_budget,
         ranking_score_threshold,
     )?;
 
+    let (BucketSortOutput { docids, scores, all_candidates, cursor: _, degraded }, cursor) =
+        pinned_documents.splice(&page, bucket_sort_output);
+
     Ok(PartialSearchResult {
         candidates: all_candidates,
         document_scores: scores,
         documents_ids: docids,
         located_query_terms: None,
+        cursor,
         degraded,
         used_negative_operator: false,
     })
 }
 
+/// Tokenizes the query with the stop words, separators, dictionary and languages of the index.
+pub(crate) fn tokenize_query<'o>(
+    ctx: &SearchContext,
+    query: &'o str,
+    locales: Option<&Vec<Language>>,
+) -> Result<Vec<Token<'o>>> {
+    let span = tracing::trace_span!(target: "search::tokens", "tokenizer_builder");
+    let entered = span.enter();
+
+    // We make sure that the analyzer is aware of the stop words
+    // this ensures that the query builder is able to properly remove them.
+    let mut tokbuilder = TokenizerBuilder::new();
+    let stop_words = ctx.index.stop_words(ctx.txn)?;
+    if let Some(ref stop_words) = stop_words {
+        tokbuilder.stop_words(stop_words);
+    }
+
+    let separators = ctx.index.allowed_separators(ctx.txn)?;
+    let separators: Option<Vec<_>> =
+        separators.as_ref().map(|x| x.iter().map(String::as_str).collect());
+    if let Some(ref separators) = separators {
+        tokbuilder.separators(separators);
+    }
+
+    let dictionary = ctx.index.dictionary(ctx.txn)?;
+    let dictionary: Option<Vec<_>> =
+        dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
+    if let Some(ref dictionary) = dictionary {
+        tokbuilder.words_dict(dictionary);
+    }
+
+    // the locales given by the user take precedence over the ones detected at indexing time,
+    // an empty list of locales is the same as not giving any.
+    let script_lang_map = match locales {
+        Some(locales) if !locales.is_empty() => script_language_allow_list(locales),
+        _ => ctx.index.script_language(ctx.txn)?,
+    };
+    if !script_lang_map.is_empty() {
+        tokbuilder.allow_list(&script_lang_map);
+    }
+
+    let tokenizer = tokbuilder.build();
+    drop(entered);
+
+    let span = tracing::trace_span!(target: "search::tokens", "tokenize");
+    let _entered = span.enter();
+    Ok(tokenizer.tokenize(query).take(limits::MAX_TOKEN_COUNT).collect())
+}
+
+/// Describes the terms a search for the query is made of, including the n-grams
+/// of consecutive words, along with all their derivations.
+pub fn analyze_query(
+    ctx: &mut SearchContext,
+    query: &str,
+    locales: Option<&Vec<Language>>,
+    words_limit: usize,
+) -> Result<Vec<AnalyzedQueryTerm>> {
+    // the query is preprocessed exactly like in `execute_search`
+    let (query, field_scopes) = extract_field_scopes(ctx, query)?;
+    let tokens = tokenize_query(ctx, &query, locales)?;
+    let ExtractedTokens { query_terms, .. } =
+        located_query_terms_from_tokens(ctx, tokens.into_iter(), Some(words_limit), &field_scopes)?;
+    if query_terms.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // the query graph adds the n-grams after the terms of the query
+    let (_, located_query_terms) = QueryGraph::from_query(ctx, &query_terms)?;
+    located_query_terms
+        .into_iter()
+        .map(|LocatedQueryTerm { value, positions }| value.analyze(ctx, &positions))
+        .collect()
+}
+
 #[allow(clippy::too_many_arguments)]
 #[tracing::instrument(level = "trace", skip_all, target = "search::main")]
 pub fn execute_search(
@@ -633,74 +844,33 @@ pub fn execute_search(
     geo_strategy: geo_sort::Strategy,
     from: usize,
     length: usize,
+    search_after: Option<&SearchCursor>,
     words_limit: Option<usize>,
     placeholder_search_logger: &mut dyn SearchLogger<PlaceholderQuery>,
     query_graph_logger: &mut dyn SearchLogger<QueryGraph>,
     time_budget: TimeBudget,
     ranking_score_threshold: Option<f64>,
+    locales: Option<&Vec<Language>>,
+    typo_tolerant_negation: bool,
 ) -> Result<PartialSearchResult> {
     check_sort_criteria(ctx, sort_criteria.as_ref())?;
 
-    let mut used_negative_operator = false;
     let mut located_query_terms = None;
-    let query_terms = if let Some(query) = query {
-        let span = tracing::trace_span!(target: "search::tokens", "tokenizer_builder");
-        let entered = span.enter();
-
-        // We make sure that the analyzer is aware of the stop words
-        // this ensures that the query builder is able to properly remove them.
-        let mut tokbuilder = TokenizerBuilder::new();
-        let stop_words = ctx.index.stop_words(ctx.txn)?;
-        if let Some(ref stop_words) = stop_words {
-            tokbuilder.stop_words(stop_words);
-        }
-
-        let separators = ctx.index.allowed_separators(ctx.txn)?;
-        let separators: Option<Vec<_>> =
-            separators.as_ref().map(|x| x.iter().map(String::as_str).collect());
-        if let Some(ref separators) = separators {
-            tokbuilder.separators(separators);
-        }
-
-        let dictionary = ctx.index.dictionary(ctx.txn)?;
-        let dictionary: Option<Vec<_>> =
-            dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
-        if let Some(ref dictionary) = dictionary {
-            tokbuilder.words_dict(dictionary);
-        }
-
-        let script_lang_map = ctx.index.script_language(ctx.txn)?;
-        if !script_lang_map.is_empty() {
-            tokbuilder.allow_list(&script_lang_map);
-        }
-
-        let tokenizer = tokbuilder.build();
-        drop(entered);
-
-        let span = tracing::trace_span!(target: "search::tokens", "tokenize");
-        let entered = span.enter();
-        let tokens = tokenizer.tokenize(query);
-        drop(entered);
-
-        let ExtractedTokens { query_terms, negative_words, negative_phrases } =
-            located_query_terms_from_tokens(ctx, tokens, words_limit)?;
-        used_negative_operator = !negative_words.is_empty() || !negative_phrases.is_empty();
-
-        let ignored_documents = resolve_negative_words(ctx, &negative_words)?;
-        let ignored_phrases = resolve_negative_phrases(ctx, &negative_phrases)?;
-
-        universe -= ignored_documents;
-        universe -= ignored_phrases;
+    let (query_terms, used_negative_operator) = resolve_query_terms(
+        ctx,
+        query,
+        words_limit,
+        locales,
+        typo_tolerant_negation,
+        &mut universe,
+    )?;
 
-        if query_terms.is_empty() {
-            // Do a placeholder search instead
-            None
-        } else {
-            Some(query_terms)
-        }
-    } else {
-        None
+    let pinned_documents = match query {
+        Some(query) => PinnedDocuments::resolve(ctx, query, distinct.as_deref(), &universe)?,
+        None => PinnedDocuments::default(),
     };
+    universe -= &pinned_documents.excluded;
+    let page = pinned_documents.page(from, length, search_after)?;
 
     let bucket_sort_output = if let Some(query_terms) = query_terms {
         let (graph, new_located_query_terms) = QueryGraph::from_query(ctx, &query_terms)?;
@@ -722,8 +892,9 @@ pub fn execute_search(
             &graph,
             distinct.as_deref(),
             &universe,
-            from,
-            length,
+            page.bucket_sort_from,
+            page.bucket_sort_length,
+            page.search_after.as_ref(),
             scoring_strategy,
             query_graph_logger,
             time_budget,
@@ -738,8 +909,9 @@ pub fn execute_search(
             &PlaceholderQuery,
             distinct.as_deref(),
             &universe,
-            from,
-            length,
+            page.bucket_sort_from,
+            page.bucket_sort_length,
+            page.search_after.as_ref(),
             scoring_strategy,
             placeholder_search_logger,
             time_budget,
@@ -747,7 +919,8 @@ pub fn execute_search(
         )?
     };
 
-    let BucketSortOutput { docids, scores, mut all_candidates, degraded } = bucket_sort_output;
+    let (BucketSortOutput { docids, scores, mut all_candidates, cursor: _, degraded }, cursor) =
+        pinned_documents.splice(&page, bucket_sort_output);
     let fields_ids_map = ctx.index.fields_ids_map(ctx.txn)?;
 
     // The candidates is the universe unless the exhaustive number of hits
@@ -755,6 +928,7 @@ pub fn execute_search(
     if exhaustive_number_hits {
         let distinct_field = match distinct.as_deref() {
             Some(distinct) => Some(distinct),
+            None if ctx.ignore_index_distinct => None,
             None => ctx.index.distinct_field(ctx.txn)?,
         };
 
@@ -770,11 +944,158 @@ pub fn execute_search(
         document_scores: scores,
         documents_ids: docids,
         located_query_terms,
+        cursor,
         degraded,
         used_negative_operator,
     })
 }
 
+/// Returns the candidates of a search without ranking them, i.e. the documents of the universe
+/// matching the query, the pinned documents and without the hidden ones.
+///
+/// With a ranking score threshold, the first `length` documents are ranked like the search does
+/// to remove the documents scoring below the threshold, within the same time budget.
+#[allow(clippy::too_many_arguments)]
+pub fn execute_search_candidates(
+    ctx: &mut SearchContext,
+    query: Option<&str>,
+    terms_matching_strategy: TermsMatchingStrategy,
+    scoring_strategy: ScoringStrategy,
+    exhaustive_number_hits: bool,
+    mut universe: RoaringBitmap,
+    sort_criteria: &Option<Vec<AscDesc>>,
+    distinct: &Option<String>,
+    geo_strategy: geo_sort::Strategy,
+    length: usize,
+    words_limit: Option<usize>,
+    time_budget: TimeBudget,
+    ranking_score_threshold: Option<f64>,
+    locales: Option<&Vec<Language>>,
+    typo_tolerant_negation: bool,
+) -> Result<RoaringBitmap> {
+    let (query_terms, _) = resolve_query_terms(
+        ctx,
+        query,
+        words_limit,
+        locales,
+        typo_tolerant_negation,
+        &mut universe,
+    )?;
+
+    let pinned_documents = match query {
+        Some(query) => PinnedDocuments::resolve(ctx, query, distinct.as_deref(), &universe)?,
+        None => PinnedDocuments::default(),
+    };
+    universe -= &pinned_documents.excluded;
+
+    if let Some(query_terms) = query_terms {
+        let (graph, _) = QueryGraph::from_query(ctx, &query_terms)?;
+        universe &= resolve_universe(
+            ctx,
+            &universe,
+            &graph,
+            terms_matching_strategy,
+            &mut DefaultSearchLogger,
+        )?;
+
+        if ranking_score_threshold.is_some() {
+            let ranking_rules = get_ranking_rules_for_query_graph_search(
+                ctx,
+                sort_criteria,
+                geo_strategy,
+                terms_matching_strategy,
+            )?;
+            universe = bucket_sort(
+                ctx,
+                ranking_rules,
+                &graph,
+                distinct.as_deref(),
+                &universe,
+                0,
+                length,
+                None,
+                scoring_strategy,
+                &mut DefaultSearchLogger,
+                time_budget,
+                ranking_score_threshold,
+            )?
+            .all_candidates;
+        }
+    } else if ranking_score_threshold.is_some() {
+        let ranking_rules =
+            get_ranking_rules_for_placeholder_search(ctx, sort_criteria, geo_strategy)?;
+        universe = bucket_sort(
+            ctx,
+            ranking_rules,
+            &PlaceholderQuery,
+            distinct.as_deref(),
+            &universe,
+            0,
+            length,
+            None,
+            scoring_strategy,
+            &mut DefaultSearchLogger,
+            time_budget,
+            ranking_score_threshold,
+        )?
+        .all_candidates;
+    }
+    universe.extend(pinned_documents.documents.iter().map(|&(_, docid)| docid));
+
+    if exhaustive_number_hits {
+        let distinct_field = match distinct.as_deref() {
+            Some(distinct) => Some(distinct),
+            None if ctx.ignore_index_distinct => None,
+            None => ctx.index.distinct_field(ctx.txn)?,
+        };
+
+        if let Some(f) = distinct_field {
+            if let Some(distinct_fid) = ctx.index.fields_ids_map(ctx.txn)?.id(f) {
+                universe = apply_distinct_rule(ctx, distinct_fid, &universe)?.remaining;
+            }
+        }
+    }
+
+    Ok(universe)
+}
+
+/// Extracts the terms of the query and removes the documents containing its negated words
+/// and phrases from the universe.
+///
+/// Returns `None` instead of the terms for a placeholder search, and whether the query used
+/// the negative operator.
+fn resolve_query_terms(
+    ctx: &mut SearchContext,
+    query: Option<&str>,
+    words_limit: Option<usize>,
+    locales: Option<&Vec<Language>>,
+    typo_tolerant_negation: bool,
+    universe: &mut RoaringBitmap,
+) -> Result<(Option<Vec<LocatedQueryTerm>>, bool)> {
+    let Some(query) = query else { return Ok((None, false)) };
+
+    // the `field:` prefixes are removed before tokenizing the query
+    let (query, field_scopes) = extract_field_scopes(ctx, query)?;
+    let tokens = tokenize_query(ctx, &query, locales)?;
+
+    let ExtractedTokens { query_terms, negative_words, negative_phrases } =
+        located_query_terms_from_tokens(ctx, tokens.into_iter(), words_limit, &field_scopes)?;
+    let used_negative_operator = !negative_words.is_empty() || !negative_phrases.is_empty();
+
+    let ignored_documents = resolve_negative_words(ctx, &negative_words, typo_tolerant_negation)?;
+    let ignored_phrases = resolve_negative_phrases(ctx, &negative_phrases)?;
+
+    *universe -= ignored_documents;
+    *universe -= ignored_phrases;
+
+    if query_terms.is_empty() {
+        // Do a placeholder search instead
+        Ok((None, used_negative_operator))
+    } else {
+        Ok((Some(query_terms), used_negative_operator))
+    }
+}
+
 fn check_sort_criteria(ctx: &SearchContext, sort_criteria: Option<&Vec<AscDesc>>) -> Result<()> {
     let sort_criteria = if let Some(sort_criteria) = sort_criteria {
         sort_criteria
@@ -788,7 +1109,7 @@ fn check_sort_criteria(ctx: &SearchContext, sort_criteria: Option<&Vec<AscDesc>>
 
     // We check that the sort ranking rule exists and throw an
     // error if we try to use it and that it doesn't.
-    let sort_ranking_rule_missing = !ctx.index.criteria(ctx.txn)?.contains(&crate::Criterion::Sort);
+    let sort_ranking_rule_missing = !ctx.criteria()?.contains(&crate::Criterion::Sort);
     if sort_ranking_rule_missing {
         return Err(UserError::SortRankingRuleMissing.into());
     }
@@ -832,6 +1153,7 @@ pub struct PartialSearchResult {
     pub candidates: RoaringBitmap,
     pub documents_ids: Vec<DocumentId>,
     pub document_scores: Vec<Vec<ScoreDetails>>,
+    pub cursor: Option<SearchCursor>,
 
     pub degraded: bool,
     pub used_negative_operator: bool,
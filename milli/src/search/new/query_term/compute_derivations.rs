@@ -1,5 +1,6 @@
 use std::borrow::Cow;
 use std::collections::BTreeSet;
+use std::iter::once;
 use std::ops::ControlFlow;
 
 use fst::automaton::Str;
@@ -12,7 +13,7 @@ use crate::search::new::interner::{DedupInterner, Interned};
 use crate::search::new::query_term::{Lazy, TwoTypoTerm};
 use crate::search::new::{limits, SearchContext};
 use crate::search::{build_dfa, get_first};
-use crate::{Result, MAX_WORD_LENGTH};
+use crate::{Result, INFIX_NGRAM_LENGTH, MAX_WORD_LENGTH};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum NumberOfTypos {
@@ -70,6 +71,54 @@ fn find_zero_typo_prefix_derivations(
     Ok(())
 }
 
+/// Returns the first n-gram of the given word, or `None` if it is shorter than the indexed n-grams.
+pub fn first_ngram(word: &str) -> Option<&str> {
+    let ngram_end = word
+        .char_indices()
+        .map(|(index, _)| index)
+        .chain(once(word.len()))
+        .nth(INFIX_NGRAM_LENGTH)?;
+    Some(&word[..ngram_end])
+}
+
+/// Finds the words of the infix searchable attributes containing the given word.
+///
+/// The candidates are the words sharing the first n-gram of the given word,
+/// they are then filtered on the whole word.
+fn find_zero_typo_infix_derivations(
+    ctx: &mut SearchContext,
+    word_interned: Interned<String>,
+    is_prefix: bool,
+    mut visit: impl FnMut(Interned<String>) -> Result<ControlFlow<()>>,
+) -> Result<()> {
+    let word = ctx.word_interner.get(word_interned).to_owned();
+    let Some(ngram) = first_ngram(&word) else {
+        // the word is shorter than the indexed n-grams
+        return Ok(());
+    };
+
+    let (index, txn) = (ctx.index, ctx.txn);
+    let iter =
+        index.ngram_word_docids.remap_data_type::<DecodeIgnore>().prefix_iter(txn, &(ngram, ""))?;
+    for result in iter {
+        let ((_ngram, derived_word), ()) = result?;
+        // the words starting with a prefix term are already derived by the prefix search
+        if derived_word == word
+            || (is_prefix && derived_word.starts_with(word.as_str()))
+            || !derived_word.contains(word.as_str())
+        {
+            continue;
+        }
+
+        let derived_word_interned = ctx.word_interner.insert(derived_word.to_owned());
+        let cf = visit(derived_word_interned)?;
+        if cf.is_break() {
+            break;
+        }
+    }
+    Ok(())
+}
+
 fn find_zero_one_typo_derivations(
     ctx: &mut SearchContext,
     word_interned: Interned<String>,
@@ -190,6 +239,7 @@ pub fn partially_initialized_term_from_word(
                 zero_typo: <_>::default(),
                 one_typo: Lazy::Init(<_>::default()),
                 two_typo: Lazy::Init(<_>::default()),
+                restricted_fids: None,
             }
         });
     }
@@ -234,6 +284,17 @@ pub fn partially_initialized_term_from_word(
             },
         )?;
     }
+    let mut infix_of = BTreeSet::new();
+    if !is_ngram {
+        find_zero_typo_infix_derivations(ctx, word_interned, is_prefix, |derived_word| {
+            if infix_of.len() < limits::MAX_INFIX_COUNT {
+                infix_of.insert(derived_word);
+                Ok(ControlFlow::Continue(()))
+            } else {
+                Ok(ControlFlow::Break(()))
+            }
+        })?;
+    }
     let synonyms = ctx.index.synonyms(ctx.txn)?;
     let mut synonym_word_count = 0;
     let synonyms = synonyms
@@ -248,11 +309,17 @@ pub fn partially_initialized_term_from_word(
             }
             synonym_word_count += words.len();
             let words = words.into_iter().map(|w| Some(ctx.word_interner.insert(w))).collect();
-            Some(ctx.phrase_interner.insert(Phrase { words }))
+            Some(ctx.phrase_interner.insert(Phrase { words, slop: 0 }))
         })
         .collect();
-    let zero_typo =
-        ZeroTypoTerm { phrase: None, exact: zero_typo, prefix_of, synonyms, use_prefix_db };
+    let zero_typo = ZeroTypoTerm {
+        phrase: None,
+        exact: zero_typo,
+        prefix_of,
+        infix_of,
+        synonyms,
+        use_prefix_db,
+    };
 
     Ok(QueryTerm {
         original: word_interned,
@@ -262,12 +329,13 @@ pub fn partially_initialized_term_from_word(
         zero_typo,
         one_typo: Lazy::Uninit,
         two_typo: Lazy::Uninit,
+        restricted_fids: None,
     })
 }
 
 fn find_split_words(ctx: &mut SearchContext, word: &str) -> Result<Option<Interned<Phrase>>> {
     if let Some((l, r)) = split_best_frequency(ctx, word)? {
-        Ok(Some(ctx.phrase_interner.insert(Phrase { words: vec![Some(l), Some(r)] })))
+        Ok(Some(ctx.phrase_interner.insert(Phrase { words: vec![Some(l), Some(r)], slop: 0 })))
     } else {
         Ok(None)
     }
@@ -325,7 +393,7 @@ impl Interned<QueryTerm> {
         let split_words = if let Some((ngram_words, split_words)) =
             self_mut.ngram_words.as_ref().zip(split_words.as_ref())
         {
-            let Phrase { words } = ctx.phrase_interner.get(*split_words);
+            let Phrase { words, .. } = ctx.phrase_interner.get(*split_words);
             if ngram_words.iter().ne(words.iter().flatten()) {
                 Some(*split_words)
             } else {
@@ -0,0 +1,130 @@
+use std::ops::Range;
+
+use crate::{FieldId, Result, SearchContext, UserError};
+
+/// A part of the search query restricted to some attributes with the `field:term`
+/// or `field:"phrase"` syntax.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldScope {
+    /// The bytes of the stripped query covered by the scope.
+    pub bytes: Range<usize>,
+    /// The searchable fields the scoped words must appear in.
+    pub fids: Vec<FieldId>,
+}
+
+/// Returns the scope covering the given byte of the stripped query, if any.
+pub fn field_scope_at(scopes: &[FieldScope], byte: usize) -> Option<&FieldScope> {
+    scopes.iter().find(|scope| scope.bytes.contains(&byte))
+}
+
+/// Removes the `field:` prefixes from the query and returns the ranges of the stripped query
+/// that are restricted to these fields.
+///
+/// A prefix is only considered when it starts a word outside of a phrase and names a field of
+/// the index, so that queries like `12:30` are left untouched. Naming a field that exists but
+/// is not searchable is an error.
+pub fn extract_field_scopes(ctx: &SearchContext, query: &str) -> Result<(String, Vec<FieldScope>)> {
+    let fields_ids_map = ctx.index.fields_ids_map(ctx.txn)?;
+    let searchable_fields_weights = ctx.index.searchable_fields_and_weights(ctx.txn)?;
+
+    let mut stripped = String::with_capacity(query.len());
+    let mut scopes = Vec::new();
+    let mut in_phrase = false;
+    let mut word_start = true;
+    let mut offset = 0;
+
+    while let Some(c) = query[offset..].chars().next() {
+        if word_start && !in_phrase {
+            if let Some((field, scoped)) = split_field_prefix(&query[offset..]) {
+                let is_known_field =
+                    fields_ids_map.names().any(|name| is_field_or_nested(name, field));
+                if is_known_field {
+                    let fids: Vec<_> = searchable_fields_weights
+                        .iter()
+                        .filter(|(name, _, _)| is_field_or_nested(name, field))
+                        .map(|(_, fid, _)| *fid)
+                        .collect();
+                    if fids.is_empty() {
+                        let (valid_fields, hidden_fields) = ctx.index.remove_hidden_fields(
+                            ctx.txn,
+                            searchable_fields_weights.iter().map(|(name, _, _)| name),
+                        )?;
+                        return Err(UserError::InvalidFieldScopedSearch {
+                            field: field.to_string(),
+                            valid_fields,
+                            hidden_fields,
+                        }
+                        .into());
+                    }
+
+                    let start = stripped.len();
+                    stripped.push_str(scoped);
+                    scopes.push(FieldScope { bytes: start..stripped.len(), fids });
+                    offset += field.len() + 1 + scoped.len();
+                    word_start = false;
+                    continue;
+                }
+            }
+        }
+
+        if c == '"' {
+            in_phrase = !in_phrase;
+        }
+        word_start = c.is_whitespace();
+        stripped.push(c);
+        offset += c.len_utf8();
+    }
+
+    Ok((stripped, scopes))
+}
+
+/// Splits `field:rest` into the field name and the scoped text, which is either
+/// the following word or the following quoted phrase, quotes included.
+fn split_field_prefix(s: &str) -> Option<(&str, &str)> {
+    let end = s.find(|c: char| c.is_whitespace() || c == '"' || c == ':')?;
+    let (field, rest) = s.split_at(end);
+    let rest = rest.strip_prefix(':')?;
+    // negations cannot be restricted to a field
+    if field.is_empty() || rest.starts_with(|c: char| c.is_whitespace() || c == ':' || c == '-') {
+        return None;
+    }
+
+    let len = match rest.strip_prefix('"') {
+        // an unclosed phrase extends until the end of the query
+        Some(phrase) => phrase.find('"').map_or(rest.len(), |end| end + 2),
+        None => rest.find(char::is_whitespace).unwrap_or(rest.len()),
+    };
+    if len == 0 {
+        return None;
+    }
+
+    Some((field, &rest[..len]))
+}
+
+fn is_field_or_nested(name: &str, field: &str) -> bool {
+    name.strip_prefix(field).map_or(false, |rest| rest.is_empty() || rest.starts_with('.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split_field_prefix;
+
+    #[test]
+    fn split_field_prefixes() {
+        assert_eq!(split_field_prefix("title:rust"), Some(("title", "rust")));
+        assert_eq!(split_field_prefix("title:rust lang"), Some(("title", "rust")));
+        assert_eq!(split_field_prefix("author.name:ferris"), Some(("author.name", "ferris")));
+        assert_eq!(
+            split_field_prefix("title:\"the rust book\" 2nd"),
+            Some(("title", "\"the rust book\""))
+        );
+        assert_eq!(split_field_prefix("title:\"the rust book"), Some(("title", "\"the rust book")));
+        assert_eq!(split_field_prefix("title: rust"), None);
+        assert_eq!(split_field_prefix("title:"), None);
+        assert_eq!(split_field_prefix(":rust"), None);
+        assert_eq!(split_field_prefix("title::rust"), None);
+        assert_eq!(split_field_prefix("title:-rust"), None);
+        assert_eq!(split_field_prefix("rust"), None);
+        assert_eq!(split_field_prefix("\"title:rust\""), None);
+    }
+}
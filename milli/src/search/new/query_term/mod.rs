@@ -1,22 +1,28 @@
 mod compute_derivations;
+mod field_scope;
 mod ntypo_subset;
 mod parse_query;
 mod phrase;
+mod suggestion;
 
 use std::collections::BTreeSet;
 use std::iter::FromIterator;
 use std::ops::RangeInclusive;
 
+pub use compute_derivations::{first_ngram, partially_initialized_term_from_word};
 use either::Either;
+pub use field_scope::{extract_field_scopes, FieldScope};
 pub use ntypo_subset::NTypoTermSubset;
 pub use parse_query::{
     located_query_terms_from_tokens, make_ngram, number_of_typos_allowed, ExtractedTokens,
 };
 pub use phrase::Phrase;
+pub use suggestion::suggest_query;
 
 use super::interner::{DedupInterner, Interned};
 use super::{limits, SearchContext, Word};
-use crate::Result;
+use crate::search::analyze::{AnalyzedQueryTerm, QueryTermDerivations};
+use crate::{FieldId, Result};
 
 /// A set of word derivations attached to a location in the search query.
 #[derive(Clone, PartialEq, Eq, Hash)]
@@ -51,6 +57,8 @@ pub struct QueryTerm {
     one_typo: Lazy<OneTypoTerm>,
     // May not be computed yet
     two_typo: Lazy<TwoTypoTerm>,
+    /// The fields the term must appear in, when restricted with the `field:term` syntax
+    restricted_fids: Option<Vec<FieldId>>,
 }
 
 // SubTerms will be in a dedup interner
@@ -62,6 +70,8 @@ struct ZeroTypoTerm {
     exact: Option<Interned<String>>,
     /// All the words that contain the original word as prefix
     prefix_of: BTreeSet<Interned<String>>,
+    /// All the words of the infix searchable attributes that contain the original word
+    infix_of: BTreeSet<Interned<String>>,
     /// All the synonyms of the original word or phrase
     synonyms: BTreeSet<Interned<Phrase>>,
     /// A prefix in the prefix databases matching the original word
@@ -128,6 +138,10 @@ impl QueryTermSubset {
     pub fn make_mandatory(&mut self) {
         self.mandatory = true;
     }
+    /// The fields the term is restricted to, if any.
+    pub fn restricted_fids(&self, ctx: &SearchContext) -> Option<Vec<FieldId>> {
+        ctx.term_interner.get(self.original).restricted_fids.clone()
+    }
     pub fn exact_term(&self, ctx: &SearchContext) -> Option<ExactTerm> {
         let full_query_term = ctx.term_interner.get(self.original);
         if full_query_term.ngram_words.is_some() {
@@ -212,6 +226,7 @@ impl QueryTermSubset {
                     phrase: _,
                     exact: zero_typo,
                     prefix_of,
+                    infix_of,
                     synonyms: _,
                     use_prefix_db: _,
                 } = &original.zero_typo;
@@ -229,12 +244,14 @@ impl QueryTermSubset {
                         Word::Original(w)
                     }
                 }));
+                result.extend(infix_of.iter().copied().map(Word::Derived));
             }
             NTypoTermSubset::Subset { words, phrases: _ } => {
                 let ZeroTypoTerm {
                     phrase: _,
                     exact: zero_typo,
                     prefix_of,
+                    infix_of,
                     synonyms: _,
                     use_prefix_db: _,
                 } = &original.zero_typo;
@@ -254,6 +271,7 @@ impl QueryTermSubset {
                         Word::Original(w)
                     }
                 }));
+                result.extend(infix_of.intersection(words).copied().map(Word::Derived));
             }
             NTypoTermSubset::Nothing => {}
         }
@@ -290,6 +308,66 @@ impl QueryTermSubset {
 
         Ok(result)
     }
+    /// The words of the subset that are only derived from the original word by the infix search.
+    ///
+    /// These words must only match in the infix searchable attributes, see
+    /// [`SearchContext::get_db_infix_word_docids`].
+    pub fn infix_only_words(&self, ctx: &mut SearchContext) -> Result<BTreeSet<Interned<String>>> {
+        if !self.one_typo_subset.is_empty() || !self.two_typo_subset.is_empty() {
+            self.original.compute_fully_if_needed(ctx)?;
+        }
+
+        let original = ctx.term_interner.get(self.original);
+        let infix_of = &original.zero_typo.infix_of;
+        let mut words: BTreeSet<_> = match &self.zero_typo_subset {
+            NTypoTermSubset::All => infix_of.clone(),
+            NTypoTermSubset::Subset { words, phrases: _ } => {
+                infix_of.intersection(words).copied().collect()
+            }
+            NTypoTermSubset::Nothing => BTreeSet::new(),
+        };
+
+        // the words also derived with typos match in all the searchable attributes
+        if let Lazy::Init(OneTypoTerm { split_words: _, one_typo }) = &original.one_typo {
+            words.retain(|w| !(one_typo.contains(w) && self.one_typo_subset.contains_word(*w)));
+        }
+        if let Lazy::Init(TwoTypoTerm { two_typos }) = &original.two_typo {
+            words.retain(|w| !(two_typos.contains(w) && self.two_typo_subset.contains_word(*w)));
+        }
+
+        Ok(words)
+    }
+    /// The exact word of the subset and the words a typo away from it, without the words
+    /// it is a prefix or an infix of, nor its synonyms and split words.
+    pub fn exact_and_typo_words(&self, ctx: &mut SearchContext) -> Result<BTreeSet<Word>> {
+        if !self.one_typo_subset.is_empty() || !self.two_typo_subset.is_empty() {
+            self.original.compute_fully_if_needed(ctx)?;
+        }
+
+        let original = ctx.term_interner.get(self.original);
+        let mut result = BTreeSet::default();
+        if let Some(exact) = original.zero_typo.exact {
+            if self.zero_typo_subset.contains_word(exact) {
+                result.insert(Word::Original(exact));
+            }
+        }
+        if let Lazy::Init(OneTypoTerm { split_words: _, one_typo }) = &original.one_typo {
+            let one_typo =
+                one_typo.iter().copied().filter(|w| self.one_typo_subset.contains_word(*w));
+            result.extend(one_typo.map(Word::Derived));
+        }
+        if let Lazy::Init(TwoTypoTerm { two_typos }) = &original.two_typo {
+            let two_typos =
+                two_typos.iter().copied().filter(|w| self.two_typo_subset.contains_word(*w));
+            result.extend(two_typos.map(Word::Derived));
+        }
+
+        Ok(result)
+    }
+    /// The original word of the term, from which its derivations are computed.
+    pub fn original_word_interned(&self, ctx: &SearchContext) -> Interned<String> {
+        ctx.term_interner.get(self.original).original
+    }
     pub fn all_phrases(&self, ctx: &mut SearchContext) -> Result<BTreeSet<Interned<Phrase>>> {
         let mut result = BTreeSet::default();
 
@@ -298,8 +376,14 @@ impl QueryTermSubset {
         }
         let original = ctx.term_interner.get_mut(self.original);
 
-        let ZeroTypoTerm { phrase, exact: _, prefix_of: _, synonyms, use_prefix_db: _ } =
-            &original.zero_typo;
+        let ZeroTypoTerm {
+            phrase,
+            exact: _,
+            prefix_of: _,
+            infix_of: _,
+            synonyms,
+            use_prefix_db: _,
+        } = &original.zero_typo;
         result.extend(phrase.iter().copied());
         result.extend(synonyms.iter().copied());
 
@@ -407,10 +491,12 @@ impl QueryTermSubset {
 
 impl ZeroTypoTerm {
     fn is_empty(&self) -> bool {
-        let ZeroTypoTerm { phrase, exact: zero_typo, prefix_of, synonyms, use_prefix_db } = self;
+        let ZeroTypoTerm { phrase, exact: zero_typo, prefix_of, infix_of, synonyms, use_prefix_db } =
+            self;
         phrase.is_none()
             && zero_typo.is_none()
             && prefix_of.is_empty()
+            && infix_of.is_empty()
             && synonyms.is_empty()
             && use_prefix_db.is_none()
     }
@@ -456,6 +542,48 @@ impl Interned<QueryTerm> {
     }
 }
 
+impl Interned<QueryTerm> {
+    /// Describes the term and all its derivations, computing them if needed.
+    pub fn analyze(
+        self,
+        ctx: &mut SearchContext,
+        positions: &RangeInclusive<u16>,
+    ) -> Result<AnalyzedQueryTerm> {
+        self.compute_fully_if_needed(ctx)?;
+        let ctx: &SearchContext = ctx;
+        let term = ctx.term_interner.get(self);
+        let word = |word: &Interned<String>| ctx.word_interner.get(*word).to_owned();
+
+        let ZeroTypoTerm { phrase, exact, prefix_of, infix_of, synonyms, use_prefix_db } =
+            &term.zero_typo;
+        let mut derivations = QueryTermDerivations {
+            exact: exact.as_ref().map(word),
+            prefix_of: prefix_of.iter().map(word).collect(),
+            infix_of: infix_of.iter().map(word).collect(),
+            synonyms: synonyms.iter().map(|synonym| synonym.description(ctx)).collect(),
+            prefix_db: use_prefix_db.as_ref().map(word),
+            ..Default::default()
+        };
+        if let Lazy::Init(OneTypoTerm { split_words, one_typo }) = &term.one_typo {
+            derivations.split_words = split_words.map(|split_words| split_words.description(ctx));
+            derivations.one_typo = one_typo.iter().map(word).collect();
+        }
+        if let Lazy::Init(TwoTypoTerm { two_typos }) = &term.two_typo {
+            derivations.two_typos = two_typos.iter().map(word).collect();
+        }
+
+        Ok(AnalyzedQueryTerm {
+            original: word(&term.original),
+            positions: [*positions.start(), *positions.end()],
+            ngram_words: term.ngram_words.as_ref().map(|words| words.iter().map(word).collect()),
+            phrase: phrase.map(|phrase| phrase.description(ctx)),
+            is_prefix: term.is_prefix,
+            max_typos: term.max_levenshtein_distance,
+            derivations,
+        })
+    }
+}
+
 /// A query term coupled with its position in the user's search query.
 #[derive(Clone)]
 pub struct LocatedQueryTerm {
@@ -489,10 +617,17 @@ impl QueryTerm {
         let mut words = BTreeSet::new();
         let mut phrases = BTreeSet::new();
 
-        let ZeroTypoTerm { phrase, exact: zero_typo, prefix_of, synonyms, use_prefix_db: _ } =
-            &self.zero_typo;
+        let ZeroTypoTerm {
+            phrase,
+            exact: zero_typo,
+            prefix_of,
+            infix_of,
+            synonyms,
+            use_prefix_db: _,
+        } = &self.zero_typo;
         words.extend(zero_typo.iter().copied());
         words.extend(prefix_of.iter().copied());
+        words.extend(infix_of.iter().copied());
         phrases.extend(phrase.iter().copied());
         phrases.extend(synonyms.iter().copied());
 
@@ -1,13 +1,13 @@
 use std::collections::BTreeSet;
 
-use charabia::normalizer::NormalizedTokenIter;
-use charabia::{SeparatorKind, TokenKind};
+use charabia::{SeparatorKind, Token, TokenKind};
 
 use super::compute_derivations::partially_initialized_term_from_word;
+use super::field_scope::{field_scope_at, FieldScope};
 use super::{LocatedQueryTerm, ZeroTypoTerm};
 use crate::search::new::query_term::{Lazy, Phrase, QueryTerm};
 use crate::search::new::Word;
-use crate::{Result, SearchContext, MAX_WORD_LENGTH};
+use crate::{FieldId, Result, SearchContext, UserError, MAX_WORD_LENGTH};
 
 #[derive(Clone)]
 /// Extraction of the content of a query.
@@ -21,11 +21,14 @@ pub struct ExtractedTokens {
 }
 
 /// Convert the tokenised search query into a list of located query terms.
+///
+/// The terms located in one of the `field_scopes` are restricted to its fields.
 #[tracing::instrument(level = "trace", skip_all, target = "search::query")]
 pub fn located_query_terms_from_tokens(
     ctx: &mut SearchContext,
-    query: NormalizedTokenIter,
+    query: impl Iterator<Item = Token<'_>>,
     words_limit: Option<usize>,
+    field_scopes: &[FieldScope],
 ) -> Result<ExtractedTokens> {
     let nbr_typos = number_of_typos_allowed(ctx)?;
 
@@ -73,13 +76,15 @@ pub fn located_query_terms_from_tokens(
                     match token.kind {
                         TokenKind::Word => {
                             let word = token.lemma();
-                            let term = partially_initialized_term_from_word(
+                            let mut term = partially_initialized_term_from_word(
                                 ctx,
                                 word,
                                 nbr_typos(word),
                                 false,
                                 false,
                             )?;
+                            term.restricted_fids = field_scope_at(field_scopes, token.byte_start)
+                                .map(|scope| scope.fids.clone());
                             let located_term = LocatedQueryTerm {
                                 value: ctx.term_interner.push(term),
                                 positions: position..=position,
@@ -90,13 +95,15 @@ pub fn located_query_terms_from_tokens(
                     }
                 } else {
                     let word = token.lemma();
-                    let term = partially_initialized_term_from_word(
+                    let mut term = partially_initialized_term_from_word(
                         ctx,
                         word,
                         nbr_typos(word),
                         true,
                         false,
                     )?;
+                    term.restricted_fids = field_scope_at(field_scopes, token.byte_start)
+                        .map(|scope| scope.fids.clone());
                     let located_term = LocatedQueryTerm {
                         value: ctx.term_interner.push(term),
                         positions: position..=position,
@@ -116,6 +123,7 @@ pub fn located_query_terms_from_tokens(
                     // If we have a hard separator inside a phrase, we immediately start a new phrase
                     let phrase = if separator_kind == SeparatorKind::Hard {
                         if let Some(phrase) = phrase {
+                            let restricted_fids = phrase.restricted_fids.clone();
                             if let Some(located_query_term) = phrase.build(ctx) {
                                 // as we are evaluating a negative operator we put the phrase
                                 // in the negative one *but* we don't reset the negative operator
@@ -126,7 +134,7 @@ pub fn located_query_terms_from_tokens(
                                     query_terms.push(located_query_term);
                                 }
                             }
-                            Some(PhraseBuilder::empty())
+                            Some(PhraseBuilder { restricted_fids, ..PhraseBuilder::empty() })
                         } else {
                             None
                         }
@@ -141,9 +149,20 @@ pub fn located_query_terms_from_tokens(
                     }
 
                     // Consume the closing quote and the phrase
-                    if let Some(phrase) = phrase {
+                    if let Some(mut phrase) = phrase {
                         // Per the check above, quote_count > 0
                         quote_count -= 1;
+
+                        // A closing quote directly followed by `~N` defines the slop of the phrase
+                        let slop = match peekable.peek() {
+                            Some(next) => parse_slop(&token, next)?,
+                            None => None,
+                        };
+                        if let Some(slop) = slop {
+                            phrase.slop = slop;
+                            peekable.next();
+                        }
+
                         if let Some(located_query_term) = phrase.build(ctx) {
                             // we were evaluating a negative operator so we
                             // put the phrase in the negative phrases
@@ -159,7 +178,10 @@ pub fn located_query_terms_from_tokens(
                     // Start new phrase if the token ends with an opening quote
                     if quote_count % 2 == 1 {
                         negative_phrase = negative_next_token;
-                        Some(PhraseBuilder::empty())
+                        // the opening quote is the last byte of the token
+                        let restricted_fids = field_scope_at(field_scopes, token.byte_end - 1)
+                            .map(|scope| scope.fids.clone());
+                        Some(PhraseBuilder { restricted_fids, ..PhraseBuilder::empty() })
                     } else {
                         None
                     }
@@ -230,6 +252,11 @@ pub fn make_ngram(
             return Ok(None);
         }
     }
+    // words restricted to different fields cannot be merged
+    let restricted_fids = ctx.term_interner.get(terms[0].value).restricted_fids.clone();
+    if terms.iter().any(|t| ctx.term_interner.get(t.value).restricted_fids != restricted_fids) {
+        return Ok(None);
+    }
     let mut words_interned = vec![];
     for term in terms {
         if let Some(original_term_word) = term.value.original_single_word(ctx) {
@@ -262,7 +289,7 @@ pub fn make_ngram(
     term.zero_typo.synonyms.extend(
         index_synonyms.get(&words).cloned().unwrap_or_default().into_iter().map(|words| {
             let words = words.into_iter().map(|w| Some(ctx.word_interner.insert(w))).collect();
-            ctx.phrase_interner.insert(Phrase { words })
+            ctx.phrase_interner.insert(Phrase { words, slop: 0 })
         }),
     );
 
@@ -274,6 +301,7 @@ pub fn make_ngram(
         zero_typo: term.zero_typo,
         one_typo: Lazy::Uninit,
         two_typo: Lazy::Uninit,
+        restricted_fids,
     };
 
     let term = LocatedQueryTerm { value: ctx.term_interner.push(term), positions: start..=end };
@@ -281,15 +309,54 @@ pub fn make_ngram(
     Ok(Some(term))
 }
 
+/// Parses the slop of a phrase from the token closing the phrase and the token following it.
+///
+/// Returns an error if the slop is greater than the maximum supported slop.
+fn parse_slop(closing: &charabia::Token, next: &charabia::Token) -> Result<Option<u8>> {
+    if next.kind != TokenKind::Word {
+        return Ok(None);
+    }
+    // the tilde is either part of the separator closing the phrase or of the following word
+    let digits = if closing.lemma().ends_with("\"~") {
+        next.lemma()
+    } else if closing.lemma().ends_with('"') {
+        match next.lemma().strip_prefix('~') {
+            Some(digits) => digits,
+            None => return Ok(None),
+        }
+    } else {
+        return Ok(None);
+    };
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Ok(None);
+    }
+    match digits.parse::<u8>() {
+        Ok(slop) if slop <= super::limits::MAX_PHRASE_SLOP => Ok(Some(slop)),
+        _ => Err(UserError::InvalidPhraseSlop {
+            slop: digits.to_string(),
+            max: super::limits::MAX_PHRASE_SLOP,
+        }
+        .into()),
+    }
+}
+
 struct PhraseBuilder {
     words: Vec<Option<crate::search::new::Interned<String>>>,
     start: u16,
     end: u16,
+    slop: u8,
+    restricted_fids: Option<Vec<FieldId>>,
 }
 
 impl PhraseBuilder {
     fn empty() -> Self {
-        Self { words: Default::default(), start: u16::MAX, end: u16::MAX }
+        Self {
+            words: Default::default(),
+            start: u16::MAX,
+            end: u16::MAX,
+            slop: 0,
+            restricted_fids: None,
+        }
     }
 
     fn is_empty(&self) -> bool {
@@ -317,7 +384,8 @@ impl PhraseBuilder {
         }
         Some(LocatedQueryTerm {
             value: ctx.term_interner.push({
-                let phrase = ctx.phrase_interner.insert(Phrase { words: self.words });
+                let phrase =
+                    ctx.phrase_interner.insert(Phrase { words: self.words, slop: self.slop });
                 let phrase_desc = phrase.description(ctx);
                 QueryTerm {
                     original: ctx.word_interner.insert(phrase_desc),
@@ -328,11 +396,13 @@ impl PhraseBuilder {
                         phrase: Some(phrase),
                         exact: None,
                         prefix_of: BTreeSet::default(),
+                        infix_of: BTreeSet::default(),
                         synonyms: BTreeSet::default(),
                         use_prefix_db: None,
                     },
                     one_typo: Lazy::Uninit,
                     two_typo: Lazy::Uninit,
+                    restricted_fids: self.restricted_fids,
                 }
             }),
             positions: self.start..=self.end,
@@ -369,7 +439,7 @@ mod tests {
         let mut ctx = SearchContext::new(&index, &rtxn)?;
         // panics with `attempt to add with overflow` before <https://github.com/meilisearch/meilisearch/issues/3785>
         let ExtractedTokens { query_terms, .. } =
-            located_query_terms_from_tokens(&mut ctx, tokens, None)?;
+            located_query_terms_from_tokens(&mut ctx, tokens, None, &[])?;
         assert!(query_terms.is_empty());
 
         Ok(())
@@ -5,9 +5,15 @@ use crate::SearchContext;
 
 /// A phrase in the user's search query, consisting of several words
 /// that must appear side-by-side in the search results.
+///
+/// The `slop` is the number of words that are allowed to sit between
+/// two consecutive words of the phrase, e.g. `"quick fox"~2`.
+/// It is checked on each pair of adjacent words independently and not on the
+/// whole phrase: `"a b c"~1` allows one word between `a` and `b` *and* one between `b` and `c`.
 #[derive(Default, Clone, PartialEq, Eq, Hash)]
 pub struct Phrase {
     pub words: Vec<Option<Interned<String>>>,
+    pub slop: u8,
 }
 impl Interned<Phrase> {
     pub fn description(self, ctx: &SearchContext) -> String {
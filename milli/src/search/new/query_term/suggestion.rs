@@ -0,0 +1,131 @@
+use std::cmp::Reverse;
+
+use charabia::{Language, TokenKind};
+
+use super::compute_derivations::partially_initialized_term_from_word;
+use super::parse_query::number_of_typos_allowed;
+use super::{Lazy, OneTypoTerm, QueryTerm, TwoTypoTerm, ZeroTypoTerm};
+use crate::search::new::{tokenize_query, Word};
+use crate::{Result, SearchContext};
+
+/// Builds a corrected version of the query by replacing its unknown words with the words of the
+/// index that are the fewest typos away from them, the most frequent one winning the ties.
+///
+/// The negated words and the `field:` prefixes are left untouched. Returns `None` when no word
+/// of the query could be corrected.
+#[tracing::instrument(level = "trace", skip_all, target = "search::suggestion")]
+pub fn suggest_query(
+    ctx: &mut SearchContext,
+    query: &str,
+    locales: Option<&Vec<Language>>,
+) -> Result<Option<String>> {
+    let tokens = tokenize_query(ctx, query, locales)?;
+    let nbr_typos = number_of_typos_allowed(ctx)?;
+
+    let mut corrections = Vec::new();
+    let mut in_phrase = false;
+    let mut encountered_whitespace = true;
+    let mut negative_next_token = false;
+
+    for (i, token) in tokens.iter().enumerate() {
+        if token.lemma().is_empty() {
+            continue;
+        }
+
+        match token.kind {
+            TokenKind::Word => {
+                let is_negated = negative_next_token;
+                let is_field_prefix = query[token.byte_end..].starts_with(':');
+                negative_next_token = false;
+                if !is_negated && !is_field_prefix {
+                    let word = token.lemma();
+                    // like in the search, only the last token of the query is a prefix
+                    let is_prefix = !in_phrase && i + 1 == tokens.len();
+                    if let Some(correction) = correct_word(ctx, word, nbr_typos(word), is_prefix)? {
+                        corrections.push((token.byte_start..token.byte_end, correction));
+                    }
+                }
+            }
+            TokenKind::StopWord => negative_next_token = false,
+            TokenKind::Separator(_) => {
+                if token.lemma().chars().filter(|&c| c == '"').count() % 2 == 1 {
+                    in_phrase = !in_phrase;
+                }
+                negative_next_token = !in_phrase && token.lemma() == "-" && encountered_whitespace;
+            }
+            TokenKind::Unknown => (),
+        }
+
+        encountered_whitespace =
+            token.lemma().chars().last().filter(|c| c.is_whitespace()).is_some();
+    }
+
+    if corrections.is_empty() {
+        return Ok(None);
+    }
+
+    let mut suggestion = String::with_capacity(query.len());
+    let mut offset = 0;
+    for (bytes, correction) in corrections {
+        suggestion.push_str(&query[offset..bytes.start]);
+        suggestion.push_str(&correction);
+        offset = bytes.end;
+    }
+    suggestion.push_str(&query[offset..]);
+
+    Ok(Some(suggestion))
+}
+
+/// Returns the typo derivation of the word with the fewest typos and the most documents,
+/// or `None` if the word is known to the index.
+fn correct_word(
+    ctx: &mut SearchContext,
+    word: &str,
+    max_typos: u8,
+    is_prefix: bool,
+) -> Result<Option<String>> {
+    let term = partially_initialized_term_from_word(ctx, word, max_typos, is_prefix, false)?;
+    let ZeroTypoTerm { phrase: _, exact, prefix_of, infix_of, synonyms, use_prefix_db } =
+        &term.zero_typo;
+    let is_known = exact.is_some()
+        || use_prefix_db.is_some()
+        || !prefix_of.is_empty()
+        || !infix_of.is_empty()
+        || !synonyms.is_empty();
+    if is_known || max_typos == 0 {
+        return Ok(None);
+    }
+
+    // the correction of an unknown prefix must be a whole word
+    let term = if is_prefix {
+        partially_initialized_term_from_word(ctx, word, max_typos, false, false)?
+    } else {
+        term
+    };
+    let term = ctx.term_interner.push(term);
+    term.compute_fully_if_needed(ctx)?;
+
+    let QueryTerm { one_typo, two_typo, .. } = ctx.term_interner.get(term);
+    let mut derivations = Vec::new();
+    if let Lazy::Init(OneTypoTerm { one_typo, .. }) = one_typo {
+        derivations.extend(one_typo.iter().map(|&derived_word| (1, derived_word)));
+    }
+    if let Lazy::Init(TwoTypoTerm { two_typos }) = two_typo {
+        derivations.extend(two_typos.iter().map(|&derived_word| (2, derived_word)));
+    }
+
+    let mut best = None;
+    for (typos, derived_word) in derivations {
+        let frequency =
+            ctx.word_docids(Word::Original(derived_word))?.map_or(0, |docids| docids.len());
+        if frequency == 0 {
+            continue;
+        }
+        let rank = (typos, Reverse(frequency));
+        if best.as_ref().map_or(true, |(best_rank, _)| rank < *best_rank) {
+            best = Some((rank, derived_word));
+        }
+    }
+
+    Ok(best.map(|(_, derived_word)| ctx.word_interner.get(derived_word).to_owned()))
+}
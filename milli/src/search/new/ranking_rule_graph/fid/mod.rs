@@ -72,7 +72,14 @@ impl RankingRuleGraphTrait for FidGraph {
             all_fields.extend(fields);
         }
 
-        let weights_map = ctx.index.fieldids_weights_map(ctx.txn)?;
+        let index_weights_map;
+        let weights_map = match &ctx.attributes_weights {
+            Some(weights_map) => weights_map,
+            None => {
+                index_weights_map = ctx.index.fieldids_weights_map(ctx.txn)?;
+                &index_weights_map
+            }
+        };
 
         let mut edges = vec![];
         for fid in all_fields.iter().copied() {
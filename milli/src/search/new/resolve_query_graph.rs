@@ -34,8 +34,22 @@ pub fn compute_query_term_subset_docids(
     term: &QueryTermSubset,
 ) -> Result<RoaringBitmap> {
     let mut docids = RoaringBitmap::new();
+    if let Some(fids) = term.restricted_fids(ctx) {
+        for fid in fids {
+            docids |= compute_query_term_subset_docids_within_field_id(ctx, term, fid)?;
+        }
+        return Ok(docids);
+    }
+
+    let original_word = term.original_word_interned(ctx);
+    let infix_words = term.infix_only_words(ctx)?;
     for word in term.all_single_words_except_prefix_db(ctx)? {
-        if let Some(word_docids) = ctx.word_docids(word)? {
+        let word_docids = if infix_words.contains(&word.interned()) {
+            ctx.get_db_infix_word_docids(original_word, word.interned())?
+        } else {
+            ctx.word_docids(word)?
+        };
+        if let Some(word_docids) = word_docids {
             docids |= word_docids;
         }
     }
@@ -58,8 +72,19 @@ pub fn compute_query_term_subset_docids_within_field_id(
     fid: u16,
 ) -> Result<RoaringBitmap> {
     let mut docids = RoaringBitmap::new();
+    if term.restricted_fids(ctx).map_or(false, |fids| !fids.contains(&fid)) {
+        return Ok(docids);
+    }
+
+    let original_word = term.original_word_interned(ctx);
+    let infix_words = term.infix_only_words(ctx)?;
     for word in term.all_single_words_except_prefix_db(ctx)? {
-        if let Some(word_fid_docids) = ctx.get_db_word_fid_docids(word.interned(), fid)? {
+        if let Some(mut word_fid_docids) = ctx.get_db_word_fid_docids(word.interned(), fid)? {
+            if infix_words.contains(&word.interned()) {
+                word_fid_docids &= ctx
+                    .get_db_infix_word_docids(original_word, word.interned())?
+                    .unwrap_or_default();
+            }
             docids |= word_fid_docids;
         }
     }
@@ -91,10 +116,17 @@ pub fn compute_query_term_subset_docids_within_position(
     position: u16,
 ) -> Result<RoaringBitmap> {
     let mut docids = RoaringBitmap::new();
+    let original_word = term.original_word_interned(ctx);
+    let infix_words = term.infix_only_words(ctx)?;
     for word in term.all_single_words_except_prefix_db(ctx)? {
-        if let Some(word_position_docids) =
+        if let Some(mut word_position_docids) =
             ctx.get_db_word_position_docids(word.interned(), position)?
         {
+            if infix_words.contains(&word.interned()) {
+                word_position_docids &= ctx
+                    .get_db_infix_word_docids(original_word, word.interned())?
+                    .unwrap_or_default();
+            }
             docids |= word_position_docids;
         }
     }
@@ -181,7 +213,7 @@ pub fn compute_phrase_docids(
     ctx: &mut SearchContext,
     phrase: Interned<Phrase>,
 ) -> Result<RoaringBitmap> {
-    let Phrase { words } = ctx.phrase_interner.get(phrase).clone();
+    let Phrase { words, slop } = ctx.phrase_interner.get(phrase).clone();
 
     if words.is_empty() {
         return Ok(RoaringBitmap::new());
@@ -195,7 +227,9 @@ pub fn compute_phrase_docids(
         }
     }
 
-    let winsize = words.len().min(3);
+    // The pairs of words further apart than the greatest stored proximity can't be checked,
+    // a sloppy phrase is thus only resolved on its consecutive words.
+    let winsize = if slop == 0 { words.len().min(3) } else { words.len().min(2) };
 
     for win in words.windows(winsize) {
         // Get all the documents with the matching distance for each word pairs.
@@ -211,7 +245,7 @@ pub fn compute_phrase_docids(
                 .enumerate()
                 .filter_map(|(index, word)| word.as_ref().map(|word| (index, word)))
             {
-                if dist == 0 {
+                if dist == 0 && slop == 0 {
                     match ctx.get_db_word_pair_proximity_docids(s1, s2, 1)? {
                         Some(m) => bitmaps.push(m),
                         // If there are no documents for this pair, there will be no
@@ -220,10 +254,8 @@ pub fn compute_phrase_docids(
                     }
                 } else {
                     let mut bitmap = RoaringBitmap::new();
-                    for dist in 0..=dist {
-                        if let Some(m) =
-                            ctx.get_db_word_pair_proximity_docids(s1, s2, dist as u8 + 1)?
-                        {
+                    for proximity in 1..=dist as u8 + 1 + slop {
+                        if let Some(m) = ctx.get_db_word_pair_proximity_docids(s1, s2, proximity)? {
                             bitmap |= m;
                         }
                     }
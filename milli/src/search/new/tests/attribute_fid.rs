@@ -157,3 +157,54 @@ fn test_attribute_fid_ngrams() {
     let document_ids_scores: Vec<_> = documents_ids.iter().zip(document_scores).collect();
     insta::assert_snapshot!(format!("{document_ids_scores:#?}"));
 }
+
+#[test]
+fn test_attribute_fid_query_weights() {
+    let index = TempIndex::new();
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["title".to_owned(), "description".to_owned()]);
+            s.set_criteria(vec![Criterion::Attribute]);
+        })
+        .unwrap();
+    index
+        .add_documents(documents!([
+            { "id": 0, "title": "the fox", "description": "" },
+            { "id": 1, "title": "", "description": "the fox" },
+        ]))
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("fox");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    assert_eq!(documents_ids, vec![0, 1]);
+
+    // the description is given a weight and is ranked before the title
+    let weights = vec![("description".to_owned(), 0)];
+    let mut s = Search::new(&txn, &index);
+    s.query("fox");
+    s.attributes_weights(&weights);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    assert_eq!(documents_ids, vec![1, 0]);
+
+    // the highest weight comes first
+    let weights = vec![("title".to_owned(), 1), ("description".to_owned(), 2)];
+    let mut s = Search::new(&txn, &index);
+    s.query("fox");
+    s.attributes_weights(&weights);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    assert_eq!(documents_ids, vec![1, 0]);
+
+    let weights = vec![("plot".to_owned(), 1)];
+    let mut s = Search::new(&txn, &index);
+    s.query("fox");
+    s.attributes_weights(&weights);
+    let error = s.execute().unwrap_err();
+    assert!(matches!(
+        error,
+        crate::Error::UserError(crate::UserError::InvalidSearchableAttribute { .. })
+    ));
+}
@@ -0,0 +1,132 @@
+/*!
+This module tests the decay ranking rule:
+
+1. documents are ranked from the closest value to the origin to the furthest one, on both sides of the origin
+2. documents with multiple values are ranked by their closest value
+3. documents without a numeric value for the field are returned last, with a score of 0
+4. it can be combined with the relevancy rules to favor recent documents without burying the relevant old ones
+5. the documents with the same score are returned in the same bucket, to be ordered by the next ranking rules
+*/
+
+use big_s::S;
+use meili_snap::insta;
+
+use crate::index::tests::TempIndex;
+use crate::score_details::{self, ScoreDetails, ScoringStrategy};
+use crate::{Criterion, Search, SearchResult, TermsMatchingStrategy};
+
+fn decay_details(rule: &str, value: Option<f64>, score: f64) -> Vec<ScoreDetails> {
+    vec![ScoreDetails::Decay(score_details::Decay { rule: S(rule), redacted: false, value, score })]
+}
+
+#[test]
+fn decay_around_number() {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_criteria(vec![Criterion::Decay("price:gauss(100, 10)".parse().unwrap())]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "price": 100 },
+            { "id": 1, "price": 90 },
+            { "id": 2, "price": 130 },
+            { "id": 3, "price": [60, 104] },
+            { "id": 4, "price": "cheap" },
+            { "id": 5 },
+            { "id": 6, "price": 110 },
+            { "id": 7, "price": 100 },
+        ]))
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    let SearchResult { documents_ids, document_scores, .. } = s.execute().unwrap();
+
+    // 90 and 110 are as far from the origin, 1 and 6 are thus in the same bucket
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 7, 3, 1, 6, 2, 4, 5]");
+
+    let rule = "price:gauss(100, 10)";
+    assert_eq!(document_scores[0], decay_details(rule, Some(100.), 1.));
+    assert_eq!(
+        document_scores[2],
+        decay_details(rule, Some(104.), 0.5f64.powf((4f64 / 10.).powi(2)))
+    );
+    assert_eq!(document_scores[3], decay_details(rule, Some(110.), 0.5));
+    assert_eq!(document_scores[4], decay_details(rule, Some(110.), 0.5));
+    assert_eq!(document_scores[6], decay_details(rule, None, 0.));
+    assert_eq!(document_scores[7], decay_details(rule, None, 0.));
+}
+
+#[test]
+fn decay_after_relevancy() {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec![S("title")]);
+            s.set_criteria(vec![
+                Criterion::Words,
+                Criterion::Decay("published_at:exp(now, 7d)".parse().unwrap()),
+            ]);
+        })
+        .unwrap();
+
+    let now = time::OffsetDateTime::now_utc().unix_timestamp();
+    let day = 24 * 60 * 60;
+    index
+        .add_documents(documents!([
+            { "id": 0, "title": "the quick brown fox", "published_at": now - 400 * day },
+            { "id": 1, "title": "the quick fox", "published_at": now - day },
+            { "id": 2, "title": "the quick brown fox", "published_at": now - 2 * day },
+            { "id": 3, "title": "the fox", "published_at": now - 30 * day },
+            { "id": 4, "title": "the quick fox", "published_at": now - 10 * day },
+        ]))
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.query("the quick brown fox");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+
+    // the old but fully matching document is still ranked before the fresh partially matching ones
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2, 0, 1, 4, 3]");
+}
+
+#[test]
+fn decay_equal_scores_in_same_bucket() {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_criteria(vec![
+                Criterion::Decay("price:linear(100, 10, 5)".parse().unwrap()),
+                Criterion::Asc(S("rank")),
+            ]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "price": 100, "rank": 2 },
+            { "id": 1, "price": 103, "rank": 0 },
+            { "id": 2, "price": 96, "rank": 1 },
+            { "id": 3, "price": 110, "rank": 0 },
+        ]))
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let s = Search::new(&txn, &index);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+
+    // all the prices within the offset have a score of 1 and are ordered by rank
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1, 2, 0, 3]");
+}
@@ -0,0 +1,136 @@
+/*!
+This module tests the candidates computed for the disjunctive facets:
+
+1. the clauses of the filter applied on a facet are ignored for this facet only
+2. the other clauses of the filter and the query still restrict the candidates
+3. the facets without any clause in the filter keep the candidates of the search
+4. the negated words of the query are removed from the candidates of every facet
+5. the documents ranked below the ranking score threshold are removed from the candidates
+*/
+
+use big_s::S;
+use maplit::hashset;
+
+use crate::index::tests::TempIndex;
+use crate::{Filter, Search, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec![S("title")]);
+            s.set_filterable_fields(hashset! { S("color"), S("size") });
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "title": "red shirt", "color": "red", "size": "S" },
+            { "id": 1, "title": "blue shirt", "color": "blue", "size": "M" },
+            { "id": 2, "title": "red dress", "color": "red", "size": "M" },
+            { "id": 3, "title": "green shirt", "color": "green", "size": "S" },
+            { "id": 4, "title": "blue shirt", "color": "blue", "size": "S" },
+            { "id": 5, "title": "red hat", "color": "red", "size": "L" },
+        ]))
+        .unwrap();
+    index
+}
+
+fn disjunctive_candidates(
+    index: &TempIndex,
+    filter: Filter,
+    facets: &[String],
+) -> (Vec<u32>, Vec<(String, Vec<u32>)>) {
+    disjunctive_candidates_with_query(index, "shirt", filter, facets)
+}
+
+fn disjunctive_candidates_with_query(
+    index: &TempIndex,
+    query: &str,
+    filter: Filter,
+    facets: &[String],
+) -> (Vec<u32>, Vec<(String, Vec<u32>)>) {
+    let txn = index.read_txn().unwrap();
+    let mut search = Search::new(&txn, index);
+    search.query(query);
+    search.filter(filter);
+    let candidates = search.execute().unwrap().candidates;
+
+    let facets_candidates = search.disjunctive_facets_candidates(facets, &candidates).unwrap();
+    (
+        candidates.into_iter().collect(),
+        facets_candidates
+            .into_iter()
+            .map(|(facet, candidates)| (facet, candidates.into_iter().collect()))
+            .collect(),
+    )
+}
+
+#[test]
+fn ignore_the_clauses_on_the_facet() {
+    let index = create_index();
+
+    let filter = Filter::from_str("color = red AND size = S").unwrap().unwrap();
+    let (candidates, facets) = disjunctive_candidates(&index, filter, &[S("color"), S("size")]);
+    assert_eq!(candidates, vec![0]);
+    assert_eq!(facets, vec![(S("color"), vec![0, 3, 4]), (S("size"), vec![0])]);
+}
+
+#[test]
+fn keep_the_other_clauses() {
+    let index = create_index();
+
+    let filter = vec![
+        either::Either::Left(vec!["color = red", "color = blue"]),
+        either::Either::Right("size = S"),
+    ];
+    let filter = Filter::from_array(filter).unwrap().unwrap();
+    let (candidates, facets) = disjunctive_candidates(&index, filter, &[S("color")]);
+    assert_eq!(candidates, vec![0, 4]);
+    assert_eq!(facets, vec![(S("color"), vec![0, 3, 4])]);
+}
+
+#[test]
+fn facet_without_clause() {
+    let index = create_index();
+
+    let filter = Filter::from_str("size = M").unwrap().unwrap();
+    let (candidates, facets) = disjunctive_candidates(&index, filter, &[S("color")]);
+    assert_eq!(candidates, vec![1]);
+    assert_eq!(facets, vec![(S("color"), vec![1])]);
+}
+
+#[test]
+fn negated_words() {
+    let index = create_index();
+
+    let filter = Filter::from_str("color = red AND size = S").unwrap().unwrap();
+    let (candidates, facets) =
+        disjunctive_candidates_with_query(&index, "shirt -green", filter, &[S("color")]);
+    assert_eq!(candidates, vec![0]);
+    assert_eq!(facets, vec![(S("color"), vec![0, 4])]);
+}
+
+#[test]
+fn ranking_score_threshold() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut search = Search::new(&txn, &index);
+    search.query("red shirt");
+    search.terms_matching_strategy(TermsMatchingStrategy::Last);
+    search.filter(Filter::from_str("color = red AND size = M").unwrap().unwrap());
+    let candidates = search.execute().unwrap().candidates;
+    assert_eq!(candidates.iter().collect::<Vec<_>>(), vec![2]);
+    let facets = search.disjunctive_facets_candidates(&[S("size")], &candidates).unwrap();
+    assert_eq!(facets[&S("size")].iter().collect::<Vec<_>>(), vec![0, 2, 5]);
+
+    // only the documents containing both words are ranked above the threshold
+    search.ranking_score_threshold(0.9);
+    let candidates = search.execute().unwrap().candidates;
+    assert!(candidates.is_empty());
+    let facets = search.disjunctive_facets_candidates(&[S("size")], &candidates).unwrap();
+    assert_eq!(facets[&S("size")].iter().collect::<Vec<_>>(), vec![0]);
+}
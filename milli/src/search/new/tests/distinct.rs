@@ -632,3 +632,48 @@ fn test_distinct_typo() {
     ]
     "###);
 }
+
+#[test]
+fn test_distinct_collapsed_documents() {
+    let index = create_index();
+    index
+        .update_settings(|s| {
+            s.set_criteria(vec![Criterion::Sort]);
+        })
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.sort_criteria(vec![AscDesc::Desc(Member::Field(S("rank1")))]);
+    s.limit(4);
+
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[14, 26, 4, 7]");
+
+    // the collapsed documents are ranked like the results
+    let collapsed = s.collapsed_documents(&documents_ids, 2).unwrap();
+    let collapsed: Vec<_> = collapsed.iter().map(|c| (c.count, &c.documents_ids)).collect();
+    insta::assert_snapshot!(format!("{collapsed:?}"), @"[(5, [13, 12]), (0, []), (2, [3, 2]), (2, [6, 5])]");
+
+    // only the number of collapsed documents is computed
+    let collapsed = s.collapsed_documents(&documents_ids, 0).unwrap();
+    let collapsed: Vec<_> = collapsed.iter().map(|c| (c.count, &c.documents_ids)).collect();
+    insta::assert_snapshot!(format!("{collapsed:?}"), @"[(5, []), (0, []), (2, []), (2, [])]");
+
+    // only the collapsed documents matching the query are returned
+    let mut s = Search::new(&txn, &index);
+    s.query("jumps");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.sort_criteria(vec![AscDesc::Desc(Member::Field(S("rank1")))]);
+    s.limit(1);
+
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[14]");
+    let collapsed = s.collapsed_documents(&documents_ids, 10).unwrap();
+    let collapsed: Vec<_> = collapsed.iter().map(|c| (c.count, &c.documents_ids)).collect();
+    insta::assert_snapshot!(format!("{collapsed:?}"), @"[(4, [13, 12, 11, 9])]");
+    let collapsed = s.collapsed_documents(&documents_ids, 0).unwrap();
+    let collapsed: Vec<_> = collapsed.iter().map(|c| (c.count, &c.documents_ids)).collect();
+    insta::assert_snapshot!(format!("{collapsed:?}"), @"[(4, [])]");
+}
@@ -0,0 +1,112 @@
+/*!
+This module tests the explanation of a search:
+
+1. the buckets of each ranking rule are nested in the bucket they split
+2. the requested documents are located in the buckets and the results
+3. the query graph describes the derivations of the terms
+*/
+
+use crate::index::tests::TempIndex;
+use crate::search::new::BucketStep;
+use crate::{Criterion, Search, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_criteria(vec![Criterion::Words, Criterion::Typo]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "a jumping horse" },
+            { "id": 1, "text": "a jumping house" },
+            { "id": 2, "text": "a jumping dog" },
+            { "id": 3, "text": "a sleeping cat" },
+        ]))
+        .unwrap();
+    index
+}
+
+fn step(ranking_rule: &str, rank: u32, size: u64) -> BucketStep {
+    BucketStep { ranking_rule: ranking_rule.to_owned(), rank, size }
+}
+
+#[test]
+fn explain_buckets() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.query("jumping horse");
+    let (result, explanation) = s.explain(&[1, 2, 3]).unwrap();
+    assert_eq!(result.documents_ids, vec![0, 1, 2]);
+
+    assert_eq!(explanation.ranking_rules, vec!["words", "typo"]);
+    assert_eq!(explanation.candidates, 3);
+    let sizes: Vec<_> = explanation.buckets.iter().map(|bucket| bucket.size).collect();
+    assert_eq!(sizes, vec![2, 1]);
+    // only the first bucket needs to be sorted by the typo rule
+    assert!(explanation.buckets[0].buckets.iter().all(|bucket| bucket.ranking_rule == "typo"));
+    assert!(explanation.buckets[1].buckets.is_empty());
+
+    let [one, two, three] = &explanation.documents[..] else { panic!() };
+    assert_eq!(one.position, Some(1));
+    assert_eq!(one.buckets, vec![step("words", 0, 2), step("typo", 1, 1)]);
+    assert_eq!(two.position, Some(2));
+    assert_eq!(two.buckets, vec![step("words", 1, 1)]);
+    assert!(!three.candidate);
+    assert_eq!(three.position, None);
+    assert!(three.buckets.is_empty());
+}
+
+#[test]
+fn explain_pagination() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.query("jumping horse");
+    s.offset(1);
+    s.limit(1);
+    let (result, explanation) = s.explain(&[0, 1, 2]).unwrap();
+    assert_eq!(result.documents_ids, vec![1]);
+
+    let skipped: Vec<_> = explanation.documents.iter().map(|document| document.skipped).collect();
+    assert_eq!(skipped, vec![true, false, false]);
+    let positions: Vec<_> =
+        explanation.documents.iter().map(|document| document.position).collect();
+    assert_eq!(positions, vec![None, Some(0), None]);
+    // the search stops before sorting the last bucket
+    assert!(explanation.documents[2].buckets.is_empty());
+}
+
+#[test]
+fn explain_query_graph() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("jumping horse");
+    let (_, explanation) = s.explain(&[]).unwrap();
+
+    let graph = explanation.query_graph.unwrap();
+    let terms: Vec<_> = graph.nodes.iter().filter_map(|node| node.term.as_ref()).collect();
+    let horse = terms.iter().find(|term| term.original == "horse").unwrap();
+    assert_eq!(horse.positions, [1, 1]);
+    assert_eq!(horse.max_typos, 1);
+    assert!(horse.derivations.exact.contains(&"horse".to_owned()));
+    assert_eq!(horse.derivations.one_typo, vec!["house".to_owned()]);
+
+    // a placeholder search has no query graph
+    let s = Search::new(&txn, &index);
+    let (_, explanation) = s.explain(&[]).unwrap();
+    assert!(explanation.query_graph.is_none());
+    assert!(explanation.documents.is_empty());
+}
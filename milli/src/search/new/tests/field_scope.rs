@@ -0,0 +1,97 @@
+/*!
+This module tests the `field:term` syntax of the search query:
+- a scoped word or phrase only matches the documents containing it in the given field
+- scoped and unscoped terms can be mixed in the same query
+- an unknown field name is searched as regular words
+- a field that exists but is not searchable returns an error
+*/
+
+use crate::index::tests::TempIndex;
+use crate::{Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["title".to_owned(), "author".to_owned()]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            {
+                "id": 0,
+                "title": "rust in action",
+                "author": "tim mcnamara",
+                "description": "systems programming",
+            },
+            {
+                "id": 1,
+                "title": "programming rust",
+                "author": "jim blandy",
+                "description": "fast and safe",
+            },
+            {
+                "id": 2,
+                "title": "the crab book",
+                "author": "the rust team",
+                "description": "the official book",
+            },
+            {
+                "id": 3,
+                "title": "rust programming for everyone",
+                "author": "jim doe",
+                "description": "a gentle introduction",
+            },
+        ]))
+        .unwrap();
+    index
+}
+
+fn search(index: &TempIndex, query: &str) -> crate::Result<Vec<u32>> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.query(query);
+    let SearchResult { mut documents_ids, .. } = s.execute()?;
+    documents_ids.sort_unstable();
+    Ok(documents_ids)
+}
+
+#[test]
+fn test_scoped_word() {
+    let index = create_index();
+    insta::assert_snapshot!(format!("{:?}", search(&index, "rust").unwrap()), @"[0, 1, 2, 3]");
+    insta::assert_snapshot!(format!("{:?}", search(&index, "title:rust").unwrap()), @"[0, 1, 3]");
+    insta::assert_snapshot!(format!("{:?}", search(&index, "author:rust").unwrap()), @"[2]");
+}
+
+#[test]
+fn test_scoped_phrase() {
+    let index = create_index();
+    insta::assert_snapshot!(format!("{:?}", search(&index, "title:\"programming rust\"").unwrap()), @"[1]");
+    insta::assert_snapshot!(format!("{:?}", search(&index, "author:\"programming rust\"").unwrap()), @"[]");
+}
+
+#[test]
+fn test_mixed_scoped_and_unscoped_terms() {
+    let index = create_index();
+    insta::assert_snapshot!(format!("{:?}", search(&index, "title:rust author:jim").unwrap()), @"[1, 3]");
+    insta::assert_snapshot!(format!("{:?}", search(&index, "title:rust blandy").unwrap()), @"[1]");
+    insta::assert_snapshot!(format!("{:?}", search(&index, "title:rust title:programming jim").unwrap()), @"[1, 3]");
+}
+
+#[test]
+fn test_unknown_field_is_searched_as_words() {
+    let index = create_index();
+    insta::assert_snapshot!(format!("{:?}", search(&index, "the:crab").unwrap()), @"[2]");
+}
+
+#[test]
+fn test_unsearchable_field() {
+    let index = create_index();
+    let error = search(&index, "description:book").unwrap_err();
+    insta::assert_snapshot!(error, @"Attribute `description` used in the search query is not searchable. Available searchable attributes are: `author, title`.");
+}
@@ -171,6 +171,36 @@ fn test_geo_sort_around_the_edge_of_the_flat_earth() {
     insta::assert_snapshot!(format!("{scores:#?}"));
 }
 
+#[test]
+fn test_geo_sort_with_several_points() {
+    let index = create_index();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "_geo": [{ "lat": 10, "lng": 0 }, { "lat": 1, "lng": 0 }] },
+            { "id": 1, "_geo": [{ "lat": 2, "lng": 0 }] },
+            { "id": 2, "_geo": [{ "lat": 0, "lng": 3 }, { "lat": "0", "lng": "-0.5" }] },
+            { "id": 3, "_geo": [{ "lat": 5, "lng": 5 }, { "lat": 4, "lng": 0 }] },
+            { "id": 4, "_geo": { "lat": 50, "lng": 50 } },
+            { "id": 5 },
+        ]))
+        .unwrap();
+
+    let rtxn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&rtxn, &index);
+    s.scoring_strategy(crate::score_details::ScoringStrategy::Detailed);
+
+    // the documents are sorted by their nearest point
+    s.sort_criteria(vec![AscDesc::Asc(Member::Geo([0., 0.]))]);
+    let (ids, _scores) = execute_iterative_and_rtree_returns_the_same(&rtxn, &index, &mut s);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[2, 0, 1, 3, 4, 5]");
+
+    s.sort_criteria(vec![AscDesc::Desc(Member::Geo([0., 0.]))]);
+    let (ids, _scores) = execute_iterative_and_rtree_returns_the_same(&rtxn, &index, &mut s);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[4, 3, 1, 0, 2, 5]");
+}
+
 #[test]
 fn geo_sort_mixed_with_words() {
     let index = create_index();
@@ -0,0 +1,147 @@
+/*!
+This module tests the infix search on the attributes set as infix searchable:
+- a word of these attributes can be found from any part of it of at least three characters
+- the words of the other attributes are not derived from their parts
+- a word found from one of its parts only matches in the infix searchable attributes
+- a negated word doesn't exclude the words it is an infix of, even when typos are allowed
+- resetting the setting or deleting the documents removes their n-grams
+*/
+
+use std::collections::HashSet;
+
+use crate::index::tests::TempIndex;
+use crate::{Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["sku".to_owned(), "name".to_owned()]);
+            s.set_infix_searchable_attributes(HashSet::from(["sku".to_owned()]));
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            {
+                "id": 0,
+                "sku": "AB-12345",
+                "name": "blue widget",
+            },
+            {
+                "id": 1,
+                "sku": "CD-99123",
+                "name": "red widget",
+            },
+            {
+                "id": 2,
+                "sku": "EF-77777",
+                "name": "green 612345 gadget",
+            },
+            {
+                "id": 3,
+                "sku": "GH-12346",
+                "name": "thing",
+            },
+        ]))
+        .unwrap();
+    index
+}
+
+fn search(index: &TempIndex, query: &str) -> Vec<u32> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.query(query);
+    let SearchResult { mut documents_ids, .. } = s.execute().unwrap();
+    documents_ids.sort_unstable();
+    documents_ids
+}
+
+#[test]
+fn test_infix_word() {
+    let index = create_index();
+    insta::assert_snapshot!(format!("{:?}", search(&index, "2345")), @"[0]");
+    insta::assert_snapshot!(format!("{:?}", search(&index, "912")), @"[1]");
+    insta::assert_snapshot!(format!("{:?}", search(&index, "123")), @"[0, 1, 3]");
+    insta::assert_snapshot!(format!("{:?}", search(&index, "2345 blue")), @"[0]");
+}
+
+#[test]
+fn test_infix_too_short() {
+    let index = create_index();
+    insta::assert_snapshot!(format!("{:?}", search(&index, "99 widget")), @"[]");
+}
+
+#[test]
+fn test_not_infix_searchable_attribute() {
+    let index = create_index();
+    insta::assert_snapshot!(format!("{:?}", search(&index, "1234 gadget")), @"[]");
+}
+
+#[test]
+fn test_infix_word_in_other_attribute() {
+    let index = create_index();
+
+    index
+        .add_documents(documents!([
+            {
+                "id": 4,
+                "sku": "IJ-00000",
+                "name": "12345 widget",
+            },
+        ]))
+        .unwrap();
+
+    // `12345` contains `2345`, but only appears in the `name` of 4
+    insta::assert_snapshot!(format!("{:?}", search(&index, "2345")), @"[0]");
+    insta::assert_snapshot!(format!("{:?}", search(&index, "12345")), @"[0, 4]");
+}
+
+#[test]
+fn test_typo_tolerant_negation() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let search = |query: &str| {
+        let mut s = Search::new(&txn, &index);
+        s.terms_matching_strategy(TermsMatchingStrategy::All);
+        s.typo_tolerant_negation(true);
+        s.query(query);
+        let SearchResult { mut documents_ids, .. } = s.execute().unwrap();
+        documents_ids.sort_unstable();
+        documents_ids
+    };
+
+    // `234` is an infix of `12345` and `12346`, but no document contains it
+    insta::assert_snapshot!(format!("{:?}", search("-234")), @"[0, 1, 2, 3]");
+    // `12346` is a typo away from `12345`
+    insta::assert_snapshot!(format!("{:?}", search("-12345")), @"[1, 2]");
+}
+
+#[test]
+fn test_reset_infix_searchable_attributes() {
+    let index = create_index();
+
+    index
+        .update_settings(|s| {
+            s.reset_infix_searchable_attributes();
+        })
+        .unwrap();
+
+    insta::assert_snapshot!(format!("{:?}", search(&index, "2345")), @"[]");
+    let txn = index.read_txn().unwrap();
+    assert!(index.ngram_word_docids.is_empty(&txn).unwrap());
+}
+
+#[test]
+fn test_delete_documents() {
+    let index = create_index();
+
+    index.delete_documents(vec!["0".to_owned()]);
+
+    insta::assert_snapshot!(format!("{:?}", search(&index, "2345")), @"[]");
+    insta::assert_snapshot!(format!("{:?}", search(&index, "912")), @"[1]");
+}
@@ -1,17 +1,27 @@
 pub mod attribute_fid;
 pub mod attribute_position;
 pub mod cutoff;
+pub mod decay;
+pub mod disjunctive_facets;
 pub mod distinct;
 pub mod exactness;
+pub mod explain;
+pub mod field_scope;
 pub mod geo_sort;
+pub mod infix;
 pub mod integration;
 #[cfg(feature = "all-tokenizations")]
 pub mod language;
 pub mod ngram_split_words;
+pub mod phrase_slop;
+pub mod pinned;
 pub mod proximity;
 pub mod proximity_typo;
+pub mod ranking_rules;
+pub mod search_after;
 pub mod sort;
 pub mod stop_words;
+pub mod suggestion;
 pub mod typo;
 pub mod typo_proximity;
 pub mod words_tms;
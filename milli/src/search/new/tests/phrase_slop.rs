@@ -0,0 +1,91 @@
+/*!
+This module tests the following properties about the slop of phrases:
+- a phrase without slop only matches words that are side-by-side
+- a phrase with a slop of N matches words separated by at most N other words
+- the slop is checked between each pair of adjacent words, not across the whole phrase
+- a slop greater than the maximum proximity stored in the database is rejected
+- a tilde separated from the closing quote is not a slop
+*/
+
+use crate::index::tests::TempIndex;
+use crate::{Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            {
+                "id": 0,
+                "text": "the quick brown fox",
+            },
+            {
+                "id": 1,
+                "text": "the quick red brown fox",
+            },
+            {
+                "id": 2,
+                "text": "quick fox",
+            },
+            {
+                "id": 3,
+                "text": "the quick old red brown fox",
+            },
+        ]))
+        .unwrap();
+    index
+}
+
+fn search_phrase(index: &TempIndex, query: &str) -> Vec<u32> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.query(query);
+    let SearchResult { mut documents_ids, .. } = s.execute().unwrap();
+    documents_ids.sort_unstable();
+    documents_ids
+}
+
+#[test]
+fn test_phrase_without_slop() {
+    let index = create_index();
+    insta::assert_snapshot!(format!("{:?}", search_phrase(&index, "\"quick fox\"")), @"[2]");
+    insta::assert_snapshot!(format!("{:?}", search_phrase(&index, "\"quick fox\"~0")), @"[2]");
+}
+
+#[test]
+fn test_phrase_with_slop() {
+    let index = create_index();
+    insta::assert_snapshot!(format!("{:?}", search_phrase(&index, "\"quick fox\"~1")), @"[0, 2]");
+    insta::assert_snapshot!(format!("{:?}", search_phrase(&index, "\"quick fox\"~2")), @"[0, 1, 2]");
+}
+
+#[test]
+fn test_phrase_slop_is_checked_per_pair_of_words() {
+    let index = create_index();
+    // in 3, there is one word between `quick` and `red` and another between `red` and `fox`
+    insta::assert_snapshot!(format!("{:?}", search_phrase(&index, "\"quick red fox\"~1")), @"[1, 3]");
+}
+
+#[test]
+fn test_phrase_slop_too_large() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("\"quick fox\"~10");
+    let error = s.execute().unwrap_err();
+    insta::assert_snapshot!(error, @"Phrase slop `~10` is too large. The slop of a phrase can be at most `2`.");
+}
+
+#[test]
+fn test_detached_tilde_is_not_a_slop() {
+    let index = create_index();
+    insta::assert_snapshot!(format!("{:?}", search_phrase(&index, "\"quick fox\" ~2")), @"[]");
+}
@@ -0,0 +1,200 @@
+/*!
+This module tests the pinned results rules:
+
+1. the pinned documents are placed at their positions for the queries matching the rule
+2. the hidden documents are removed from the results
+3. the pinned documents respect the filters and the pagination
+4. the other queries are not affected
+5. the distinct attribute applies to the pinned documents and to their siblings
+6. the pinned documents positioned after the ranked ones are placed right after them
+*/
+
+use big_s::S;
+use maplit::hashset;
+
+use crate::index::tests::TempIndex;
+use crate::pinned_results::{PinnedDocument, PinnedResultsRule, QueryMatching};
+use crate::score_details::{self, ScoreDetails, ScoringStrategy};
+use crate::{Filter, Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec![S("title")]);
+            s.set_filterable_fields(hashset! { S("id") });
+            s.set_pinned_results(vec![PinnedResultsRule {
+                query: S("summer sale"),
+                matching: QueryMatching::Normalized,
+                pinned: vec![
+                    PinnedDocument { id: S("7"), position: 0 },
+                    PinnedDocument { id: S("3"), position: 1 },
+                    PinnedDocument { id: S("unknown"), position: 2 },
+                ],
+                hidden: vec![S("1")],
+            }]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "title": "summer sale shirt" },
+            { "id": 1, "title": "summer sale hat" },
+            { "id": 2, "title": "summer dress" },
+            { "id": 3, "title": "winter coat" },
+            { "id": 4, "title": "summer sale shoes" },
+            { "id": 5, "title": "sale" },
+            { "id": 6, "title": "autumn scarf" },
+            { "id": 7, "title": "christmas sweater" },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn pinned_results() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    s.query("Summer  Sale");
+    let SearchResult { documents_ids, document_scores, candidates, .. } = s.execute().unwrap();
+    assert_eq!(documents_ids, vec![7, 3, 0, 4, 2]);
+    assert_eq!(candidates.len(), 5);
+    assert_eq!(
+        document_scores[1],
+        vec![ScoreDetails::Pinned(score_details::Pinned { position: 1 })]
+    );
+
+    // the query doesn't match the rule
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.query("summer sales");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    assert!(!documents_ids.contains(&7));
+    assert!(documents_ids.contains(&1));
+}
+
+#[test]
+fn pinned_results_pagination() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let page = |offset, limit| {
+        let mut s = Search::new(&txn, &index);
+        s.terms_matching_strategy(TermsMatchingStrategy::Last);
+        s.query("summer sale");
+        s.offset(offset);
+        s.limit(limit);
+        s.execute().unwrap().documents_ids
+    };
+
+    assert_eq!(page(0, 1), vec![7]);
+    assert_eq!(page(1, 2), vec![3, 0]);
+    assert_eq!(page(2, 2), vec![0, 4]);
+    assert_eq!(page(3, 10), vec![4, 2]);
+    assert_eq!(page(5, 10), Vec::<u32>::new());
+}
+
+#[test]
+fn pinned_results_respect_filters() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.query("summer sale");
+    s.filter(Filter::from_str("id != 7").unwrap().unwrap());
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    // 3 keeps its position
+    assert_eq!(documents_ids, vec![0, 3, 4, 2]);
+}
+
+#[test]
+fn pinned_results_distinct() {
+    let index = create_index();
+    index.update_settings(|s| s.set_distinct_field(S("group"))).unwrap();
+    index
+        .add_documents(documents!([
+            { "id": 3, "title": "winter coat", "group": "knitwear" },
+            { "id": 7, "title": "christmas sweater", "group": "knitwear" },
+            { "id": 8, "title": "summer sale sweater", "group": "knitwear" },
+        ]))
+        .unwrap();
+    let txn = index.read_txn().unwrap();
+
+    // 3 shares the distinct value of 7, which is pinned before it, and so does 8
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.query("summer sale");
+    let SearchResult { documents_ids, candidates, .. } = s.execute().unwrap();
+    assert_eq!(documents_ids, vec![7, 0, 4, 2]);
+    assert_eq!(candidates.len(), 4);
+
+    // once 7 is filtered out, 3 is the pinned document of its distinct value
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.query("summer sale");
+    s.filter(Filter::from_str("id != 7").unwrap().unwrap());
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    // 3 keeps its position
+    assert_eq!(documents_ids, vec![0, 3, 4, 2]);
+}
+
+#[test]
+fn pinned_results_positions() {
+    let index = create_index();
+    index
+        .update_settings(|s| {
+            s.set_pinned_results(vec![PinnedResultsRule {
+                query: S("summer sale"),
+                matching: QueryMatching::Normalized,
+                pinned: vec![
+                    PinnedDocument { id: S("3"), position: 10 },
+                    PinnedDocument { id: S("6"), position: 2 },
+                    // the position is taken by 6, so 5 is shifted after it
+                    PinnedDocument { id: S("5"), position: 2 },
+                    PinnedDocument { id: S("7"), position: 0 },
+                ],
+                hidden: vec![S("1")],
+            }]);
+        })
+        .unwrap();
+    let txn = index.read_txn().unwrap();
+
+    let search = |offset, limit, filter: Option<&str>| {
+        let mut s = Search::new(&txn, &index);
+        s.terms_matching_strategy(TermsMatchingStrategy::Last);
+        s.query("summer sale");
+        s.offset(offset);
+        s.limit(limit);
+        if let Some(filter) = filter {
+            s.filter(Filter::from_str(filter).unwrap().unwrap());
+        }
+        s.execute().unwrap()
+    };
+
+    // 3 is placed right after the last ranked document
+    let SearchResult { documents_ids, candidates, .. } = search(0, 20, None);
+    assert_eq!(documents_ids, vec![7, 0, 6, 5, 4, 2, 3]);
+    assert_eq!(candidates.len(), 7);
+
+    let page = |offset, limit| search(offset, limit, None).documents_ids;
+    assert_eq!(page(0, 2), vec![7, 0]);
+    assert_eq!(page(2, 2), vec![6, 5]);
+    assert_eq!(page(3, 3), vec![5, 4, 2]);
+    assert_eq!(page(5, 1), vec![2]);
+    assert_eq!(page(6, 1), vec![3]);
+    assert_eq!(page(6, 10), vec![3]);
+    assert_eq!(page(7, 10), Vec::<u32>::new());
+
+    // the other pinned documents keep their positions when one of them is filtered out
+    let filtered = |offset, limit, filter| search(offset, limit, Some(filter)).documents_ids;
+    assert_eq!(filtered(0, 20, "id != 6"), vec![7, 0, 4, 5, 2, 3]);
+    assert_eq!(filtered(0, 20, "id != 0"), vec![7, 4, 6, 5, 2, 3]);
+    assert_eq!(filtered(4, 20, "id != 4"), vec![2, 3]);
+}
@@ -0,0 +1,83 @@
+/*!
+This module tests the ranking rules given at search time:
+
+1. they replace the ranking rules of the index for the search only
+2. the `sort` parameter can be used when they contain the `sort` ranking rule
+3. an error is returned if the fields of their custom ranking rules are not sortable
+*/
+
+use big_s::S;
+use maplit::hashset;
+use meili_snap::insta;
+
+use crate::index::tests::TempIndex;
+use crate::{AscDesc, Criterion, Member, Search, SearchResult};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_sortable_fields(hashset! { S("rank") });
+            s.set_criteria(vec![Criterion::Asc(S("rank"))]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "rank": 2 },
+            { "id": 1, "rank": 0 },
+            { "id": 2, "rank": 1 },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn test_ranking_rules_override() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let s = Search::new(&txn, &index);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    assert_eq!(documents_ids, vec![1, 2, 0]);
+
+    let mut s = Search::new(&txn, &index);
+    s.ranking_rules(vec![Criterion::Desc(S("rank"))]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    assert_eq!(documents_ids, vec![0, 2, 1]);
+
+    // the ranking rules of the index are left untouched
+    assert_eq!(index.criteria(&txn).unwrap(), vec![Criterion::Asc(S("rank"))]);
+}
+
+#[test]
+fn test_ranking_rules_override_sort() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let sort = vec![AscDesc::Desc(Member::Field(S("rank")))];
+
+    let mut s = Search::new(&txn, &index);
+    s.sort_criteria(sort.clone());
+    let error = s.execute().unwrap_err();
+    assert!(matches!(error, crate::Error::UserError(crate::UserError::SortRankingRuleMissing)));
+
+    let mut s = Search::new(&txn, &index);
+    s.ranking_rules(vec![Criterion::Sort]);
+    s.sort_criteria(sort);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    assert_eq!(documents_ids, vec![0, 2, 1]);
+}
+
+#[test]
+fn test_ranking_rules_override_unsortable_field() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.ranking_rules(vec![Criterion::Words, Criterion::Asc(S("id"))]);
+    let error = s.execute().unwrap_err();
+    insta::assert_snapshot!(error.to_string(), @"Attribute `id` is not sortable and thus, cannot be used in the ranking rules of a search. Available sortable attributes are: `rank`.");
+}
@@ -0,0 +1,246 @@
+/*!
+This module tests the resumption of a search after a cursor:
+
+1. the pages of results fetched by resuming the search after the cursor of the previous page
+are the same as the results of a single search, whatever the scoring strategy of each page
+2. the distinct attribute and the pinned documents are taken into account
+3. the cursors are exchanged as opaque tokens, only valid as long as the index doesn't change
+and for searches with the same parameters
+4. the cursors that can't locate a document among the results are rejected
+*/
+
+use big_s::S;
+use heed::RoTxn;
+use maplit::hashset;
+
+use crate::index::tests::TempIndex;
+use crate::pinned_results::{PinnedDocument, PinnedResultsRule, QueryMatching};
+use crate::score_details::ScoringStrategy;
+use crate::{
+    AscDesc, Criterion, Error, Index, Member, Search, SearchCursor, SearchCursorToken,
+    SearchResult, TermsMatchingStrategy, UserError,
+};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec![S("text")]);
+            s.set_sortable_fields(hashset! { S("rank") });
+            s.set_filterable_fields(hashset! { S("brand") });
+            s.set_criteria(vec![
+                Criterion::Words,
+                Criterion::Typo,
+                Criterion::Sort,
+                Criterion::Exactness,
+            ]);
+            s.set_pinned_results(vec![PinnedResultsRule {
+                query: S("quick fox"),
+                matching: QueryMatching::Normalized,
+                pinned: vec![
+                    PinnedDocument { id: S("13"), position: 0 },
+                    PinnedDocument { id: S("4"), position: 5 },
+                    // more than the number of matching documents
+                    PinnedDocument { id: S("1"), position: 100 },
+                ],
+                hidden: vec![],
+            }]);
+        })
+        .unwrap();
+
+    let texts = ["the quick brown fox", "the quikc brown dog", "quick fox jumps", "a lazy fox"];
+    let documents = (0..40).map(|id| {
+        let document = serde_json::json!({
+            "id": id,
+            "text": texts[id % texts.len()],
+            "rank": id % 3,
+            "brand": id % 7,
+        });
+        document.as_object().unwrap().clone()
+    });
+    index.add_documents(crate::documents::documents_batch_reader_from_objects(documents)).unwrap();
+    index
+}
+
+/// Returns the results of the search fetched `limit` by `limit`, each page resuming the search
+/// after the cursor of the previous one and alternating the scoring strategies.
+fn paginate(index: &Index, txn: &RoTxn, limit: usize, configure: impl Fn(&mut Search)) -> Vec<u32> {
+    let mut documents_ids = vec![];
+    let mut cursor: Option<SearchCursor> = None;
+    for page in 0.. {
+        let mut s = Search::new(txn, index);
+        configure(&mut s);
+        s.limit(limit);
+        s.scoring_strategy(if page % 2 == 0 {
+            ScoringStrategy::Skip
+        } else {
+            ScoringStrategy::Detailed
+        });
+        if let Some(cursor) = cursor.take() {
+            s.search_after(cursor);
+        }
+        let SearchResult { documents_ids: page_ids, cursor: next_cursor, .. } =
+            s.execute().unwrap();
+        documents_ids.extend_from_slice(&page_ids);
+        if page_ids.len() < limit {
+            break;
+        }
+        // the cursors are given to the users as tokens
+        let token = SearchCursorToken::new(next_cursor.unwrap(), index, txn, b"").unwrap();
+        let token: SearchCursorToken = token.to_string().parse().unwrap();
+        cursor = Some(token.into_cursor(index, txn, b"").unwrap());
+    }
+    documents_ids
+}
+
+fn check_pagination(index: &Index, txn: &RoTxn, configure: impl Fn(&mut Search)) {
+    let mut s = Search::new(txn, index);
+    configure(&mut s);
+    s.limit(100);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    assert!(!documents_ids.is_empty());
+
+    for limit in [1, 2, 3, 7, 100] {
+        assert_eq!(paginate(index, txn, limit, &configure), documents_ids, "limit: {limit}");
+    }
+}
+
+#[test]
+fn search_after_placeholder() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    check_pagination(&index, &txn, |_| ());
+    check_pagination(&index, &txn, |s| {
+        s.sort_criteria(vec![AscDesc::Desc(Member::Field(S("rank")))]);
+    });
+}
+
+#[test]
+fn search_after_query() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    check_pagination(&index, &txn, |s| {
+        s.query("quick brown fox");
+        s.terms_matching_strategy(TermsMatchingStrategy::Last);
+        s.sort_criteria(vec![AscDesc::Asc(Member::Field(S("rank")))]);
+    });
+}
+
+#[test]
+fn search_after_distinct() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    check_pagination(&index, &txn, |s| {
+        s.distinct(S("brand"));
+    });
+    check_pagination(&index, &txn, |s| {
+        s.query("the brown fox");
+        s.terms_matching_strategy(TermsMatchingStrategy::Last);
+        s.sort_criteria(vec![AscDesc::Asc(Member::Field(S("rank")))]);
+        s.distinct(S("brand"));
+    });
+}
+
+#[test]
+fn search_after_no_ranking_rules() {
+    let index = create_index();
+    index
+        .update_settings(|s| {
+            s.set_criteria(vec![]);
+        })
+        .unwrap();
+    let txn = index.read_txn().unwrap();
+
+    check_pagination(&index, &txn, |_| ());
+    check_pagination(&index, &txn, |s| {
+        s.distinct(S("brand"));
+    });
+}
+
+#[test]
+fn search_after_pinned_documents() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    check_pagination(&index, &txn, |s| {
+        s.query("quick fox");
+        s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    });
+
+    let mut s = Search::new(&txn, &index);
+    s.query("quick fox");
+    s.limit(1);
+    let SearchResult { documents_ids, cursor, .. } = s.execute().unwrap();
+    assert_eq!(documents_ids, vec![13]);
+    assert_eq!(cursor, Some(SearchCursor { position: 0, ranked_count: 0, ranked: None }));
+}
+
+#[test]
+fn search_cursor_token() {
+    let index = create_index();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("fox");
+    s.limit(3);
+    let SearchResult { cursor, .. } = s.execute().unwrap();
+    let cursor = cursor.unwrap();
+    let token = SearchCursorToken::new(cursor.clone(), &index, &txn, b"fox").unwrap().to_string();
+    // the token can be given in a url
+    assert!(token.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+
+    let parsed: SearchCursorToken = token.parse().unwrap();
+    assert_eq!(parsed.to_string(), token);
+    assert_eq!(parsed.clone().into_cursor(&index, &txn, b"fox").unwrap(), cursor);
+
+    // the token is rejected by a search with other parameters
+    let error = parsed.clone().into_cursor(&index, &txn, b"dog").unwrap_err();
+    assert!(matches!(error, Error::UserError(UserError::SearchCursorMismatch)), "{error}");
+    drop(txn);
+
+    for token in ["", "p0", "42.0.3.1", "AAAA", "not a cursor", &token[1..]] {
+        assert!(token.parse::<SearchCursorToken>().is_err(), "{token}");
+    }
+
+    // the cursor is stale once the index is updated
+    index.add_documents(documents!([{ "id": 100, "text": "a fox" }])).unwrap();
+    let txn = index.read_txn().unwrap();
+    let error = parsed.into_cursor(&index, &txn, b"fox").unwrap_err();
+    assert!(matches!(error, Error::UserError(UserError::StaleSearchCursor)), "{error}");
+}
+
+#[test]
+fn invalid_search_cursor() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    // there are only three pinned documents to place before the ranked ones counted by the cursor
+    let cursors = [
+        SearchCursor { position: 10, ranked_count: 0, ranked: None },
+        SearchCursor { position: 10, ranked_count: 20, ranked: None },
+        SearchCursor { position: usize::MAX, ranked_count: 0, ranked: None },
+    ];
+    for cursor in cursors {
+        let mut s = Search::new(&txn, &index);
+        s.query("quick fox");
+        s.search_after(cursor.clone());
+        let error = s.execute().unwrap_err();
+        assert!(
+            matches!(error, Error::UserError(UserError::InvalidSearchCursor)),
+            "{cursor:?}: {error}"
+        );
+    }
+
+    // the offset can't move the page past the last position
+    let mut s = Search::new(&txn, &index);
+    s.query("quick fox");
+    s.offset(usize::MAX);
+    s.search_after(SearchCursor { position: 10, ranked_count: 8, ranked: None });
+    let error = s.execute().unwrap_err();
+    assert!(matches!(error, Error::UserError(UserError::InvalidSearchCursor)), "{error}");
+}
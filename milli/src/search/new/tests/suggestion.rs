@@ -0,0 +1,71 @@
+/*!
+This module tests the "did you mean" query suggestions:
+
+1. the unknown words are replaced by their typo derivation with the fewest typos
+2. the ties are broken by choosing the word contained in the most documents
+3. the known words, the prefixes of known words, and the words too short to have typos are kept
+4. the negated words are not corrected, the words of a phrase are
+5. the rest of the query is left untouched
+*/
+
+use crate::index::tests::TempIndex;
+use crate::{suggest_query, SearchContext};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "the summer dress" },
+            { "id": 1, "text": "summer shoes" },
+            { "id": 2, "text": "a warm sweater" },
+            { "id": 3, "text": "summer hat" },
+            { "id": 4, "text": "simmer sauce" },
+        ]))
+        .unwrap();
+    index
+}
+
+fn suggest(index: &TempIndex, query: &str) -> Option<String> {
+    let txn = index.read_txn().unwrap();
+    let mut ctx = SearchContext::new(index, &txn).unwrap();
+    suggest_query(&mut ctx, query, None).unwrap()
+}
+
+#[test]
+fn suggest_known_words() {
+    let index = create_index();
+
+    assert_eq!(suggest(&index, "sammer dress"), Some("summer dress".to_owned()));
+    assert_eq!(suggest(&index, "summer drezs"), Some("summer dress".to_owned()));
+    assert_eq!(suggest(&index, "SAMMER  Dress"), Some("summer  Dress".to_owned()));
+    assert_eq!(suggest(&index, "warm swaeter"), Some("warm sweater".to_owned()));
+}
+
+#[test]
+fn no_suggestion_for_known_words() {
+    let index = create_index();
+
+    assert_eq!(suggest(&index, "summer dress"), None);
+    // the last word is a prefix
+    assert_eq!(suggest(&index, "summer dre"), None);
+    // too short to contain a typo
+    assert_eq!(suggest(&index, "summer hst"), None);
+    // too far from any word of the index
+    assert_eq!(suggest(&index, "umbrella"), None);
+}
+
+#[test]
+fn suggestion_keeps_the_query_syntax() {
+    let index = create_index();
+
+    assert_eq!(suggest(&index, "sammer -swaeter"), Some("summer -swaeter".to_owned()));
+    assert_eq!(suggest(&index, "\"sammer dress\" hat"), Some("\"summer dress\" hat".to_owned()));
+}
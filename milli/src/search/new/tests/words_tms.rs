@@ -10,9 +10,12 @@ account by the proximity ranking rule.
 5. Unclosed double quotes still make a phrase
 6. The `all` term matching strategy does not remove any term from the query
 7. The search is capable of returning no results if no documents match the query
+8. The `frequency` term matching strategy starts removing terms from the query
+starting from the most frequent ones, and removes the terms that match no document first
 */
 
 use crate::index::tests::TempIndex;
+use crate::score_details::{ScoreDetails, ScoringStrategy, Words};
 use crate::search::new::tests::collect_field_values;
 use crate::{Criterion, Search, SearchResult, TermsMatchingStrategy};
 
@@ -458,3 +461,93 @@ fn test_words_tms_all() {
     let texts = collect_field_values(&index, &txn, "text", &documents_ids);
     insta::assert_debug_snapshot!(texts, @"[]");
 }
+
+fn create_frequency_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_criteria(vec![Criterion::Words]);
+        })
+        .unwrap();
+
+    // "the" appears in 5 documents, "extended" in 4 and "rings" in 3
+    index
+        .add_documents(documents!([
+            {
+                "id": 0,
+                "text": "the rings extended",
+            },
+            {
+                "id": 1,
+                "text": "the rings",
+            },
+            {
+                "id": 2,
+                "text": "the extended",
+            },
+            {
+                "id": 3,
+                "text": "rings extended",
+            },
+            {
+                "id": 4,
+                "text": "the",
+            },
+            {
+                "id": 5,
+                "text": "the cut",
+            },
+            {
+                "id": 6,
+                "text": "extended edition",
+            },
+        ]))
+        .unwrap();
+    index
+}
+
+fn words_score(matching_words: u32, max_matching_words: u32) -> Vec<ScoreDetails> {
+    vec![ScoreDetails::Words(Words { matching_words, max_matching_words })]
+}
+
+#[test]
+fn test_words_tms_frequency() {
+    let index = create_frequency_index();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("the rings extended");
+    s.terms_matching_strategy(TermsMatchingStrategy::Frequency);
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    let SearchResult { documents_ids, document_scores, .. } = s.execute().unwrap();
+
+    // "the" is removed first, then "extended", but "rings" is always kept
+    assert_eq!(documents_ids, vec![0, 3, 1]);
+    assert_eq!(document_scores, vec![words_score(3, 3), words_score(2, 3), words_score(1, 3)]);
+
+    // whereas the `last` strategy keeps the first word of the query
+    let mut s = Search::new(&txn, &index);
+    s.query("the rings extended");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    assert_eq!(documents_ids, vec![0, 1, 2, 4, 5]);
+}
+
+#[test]
+fn test_words_tms_frequency_unknown_word() {
+    let index = create_frequency_index();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("extravagant the rings extended");
+    s.terms_matching_strategy(TermsMatchingStrategy::Frequency);
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    let SearchResult { documents_ids, document_scores, .. } = s.execute().unwrap();
+
+    // a word matching no document is removed before the most frequent ones
+    assert_eq!(documents_ids, vec![0, 3, 1]);
+    assert_eq!(document_scores, vec![words_score(3, 4), words_score(2, 4), words_score(1, 4)]);
+}
@@ -139,6 +139,7 @@ impl<'a> Similar<'a> {
             candidates,
             documents_ids,
             document_scores,
+            cursor: None,
             degraded: false,
             used_negative_operator: false,
         })
@@ -93,6 +93,7 @@ Create a snapshot test of the given database.
     - `word_position_docids`
     - `field_id_word_count_docids`
     - `word_prefix_position_docids`
+    - `ngram_word_docids`
     - `facet_id_f64_docids`
     - `facet_id_string_docids`
     - `documents_ids`
@@ -229,6 +230,11 @@ pub fn snap_word_fid_docids(index: &Index) -> String {
         &format!("{word:<16} {fid:<3} {}", display_bitmap(&b))
     })
 }
+pub fn snap_ngram_word_docids(index: &Index) -> String {
+    make_db_snap_from_iter!(index, ngram_word_docids, |((ngram, word), b)| {
+        &format!("{ngram:<4} {word:<16} {}", display_bitmap(&b))
+    })
+}
 pub fn snap_field_id_word_count_docids(index: &Index) -> String {
     make_db_snap_from_iter!(index, field_id_word_count_docids, |((field_id, word_count), b)| {
         &format!("{field_id:<3} {word_count:<6} {}", display_bitmap(&b))
@@ -452,6 +458,9 @@ macro_rules! full_snap_of_db {
     ($index:ident, word_fid_docids) => {{
         $crate::snapshot_tests::snap_word_fid_docids(&$index)
     }};
+    ($index:ident, ngram_word_docids) => {{
+        $crate::snapshot_tests::snap_ngram_word_docids(&$index)
+    }};
     ($index:ident, field_id_word_count_docids) => {{
         $crate::snapshot_tests::snap_field_id_word_count_docids(&$index)
     }};
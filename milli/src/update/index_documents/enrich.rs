@@ -237,27 +237,31 @@ pub fn validate_geo_from_json(id: &DocumentId, bytes: &[u8]) -> Result<StdResult
     let debug_id = || {
         serde_json::from_slice(id.value().as_bytes()).unwrap_or_else(|_| Value::from(id.debug()))
     };
-    match serde_json::from_slice(bytes).map_err(InternalError::SerdeJson)? {
+    let validate_point = |value: Value| match value {
         Value::Object(mut object) => match (object.remove("lat"), object.remove("lng")) {
             (Some(lat), Some(lng)) => {
                 match (extract_finite_float_from_value(lat), extract_finite_float_from_value(lng)) {
-                    (Ok(_), Ok(_)) if !object.is_empty() => Ok(Err(UnexpectedExtraFields {
-                        document_id: debug_id(),
-                        value: object.into(),
-                    })),
-                    (Ok(_), Ok(_)) => Ok(Ok(())),
-                    (Err(value), Ok(_)) => Ok(Err(BadLatitude { document_id: debug_id(), value })),
-                    (Ok(_), Err(value)) => Ok(Err(BadLongitude { document_id: debug_id(), value })),
+                    (Ok(_), Ok(_)) if !object.is_empty() => {
+                        Err(UnexpectedExtraFields { document_id: debug_id(), value: object.into() })
+                    }
+                    (Ok(_), Ok(_)) => Ok(()),
+                    (Err(value), Ok(_)) => Err(BadLatitude { document_id: debug_id(), value }),
+                    (Ok(_), Err(value)) => Err(BadLongitude { document_id: debug_id(), value }),
                     (Err(lat), Err(lng)) => {
-                        Ok(Err(BadLatitudeAndLongitude { document_id: debug_id(), lat, lng }))
+                        Err(BadLatitudeAndLongitude { document_id: debug_id(), lat, lng })
                     }
                 }
             }
-            (None, Some(_)) => Ok(Err(MissingLatitude { document_id: debug_id() })),
-            (Some(_), None) => Ok(Err(MissingLongitude { document_id: debug_id() })),
-            (None, None) => Ok(Err(MissingLatitudeAndLongitude { document_id: debug_id() })),
+            (None, Some(_)) => Err(MissingLatitude { document_id: debug_id() }),
+            (Some(_), None) => Err(MissingLongitude { document_id: debug_id() }),
+            (None, None) => Err(MissingLatitudeAndLongitude { document_id: debug_id() }),
         },
+        value => Err(NotAnObject { document_id: debug_id(), value }),
+    };
+    match serde_json::from_slice(bytes).map_err(InternalError::SerdeJson)? {
+        // a document can have several geo points, it is matched if any of them is
+        Value::Array(points) => Ok(points.into_iter().try_for_each(validate_point)),
         Value::Null => Ok(Ok(())),
-        value => Ok(Err(NotAnObject { document_id: debug_id(), value })),
+        value => Ok(validate_point(value)),
     }
 }
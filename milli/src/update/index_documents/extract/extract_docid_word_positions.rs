@@ -68,6 +68,23 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
         None,
     );
     let del_tokenizer = del_builder.build();
+    let old_allow_lists = settings_diff.old.localized_searchable_fields_ids.allow_lists();
+    let mut del_localized_builders: Vec<_> = old_allow_lists
+        .iter()
+        .map(|(field_ids, allow_list)| {
+            let builder = tokenizer_builder(
+                old_stop_words,
+                old_separators.as_deref(),
+                old_dictionary.as_deref(),
+                Some(allow_list),
+            );
+            (field_ids.as_slice(), builder)
+        })
+        .collect();
+    let del_localized_tokenizers: Vec<_> = del_localized_builders
+        .iter_mut()
+        .map(|(field_ids, builder)| (*field_ids, builder.build()))
+        .collect();
 
     let new_stop_words = settings_diff.new.stop_words.as_ref();
     let new_separators: Option<Vec<_>> = settings_diff
@@ -84,6 +101,23 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
         None,
     );
     let add_tokenizer = add_builder.build();
+    let new_allow_lists = settings_diff.new.localized_searchable_fields_ids.allow_lists();
+    let mut add_localized_builders: Vec<_> = new_allow_lists
+        .iter()
+        .map(|(field_ids, allow_list)| {
+            let builder = tokenizer_builder(
+                new_stop_words,
+                new_separators.as_deref(),
+                new_dictionary.as_deref(),
+                Some(allow_list),
+            );
+            (field_ids.as_slice(), builder)
+        })
+        .collect();
+    let add_localized_tokenizers: Vec<_> = add_localized_builders
+        .iter_mut()
+        .map(|(field_ids, builder)| (*field_ids, builder.build()))
+        .collect();
 
     // iterate over documents.
     let mut cursor = obkv_documents.into_cursor()?;
@@ -113,6 +147,7 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
                     &obkv,
                     &settings_diff.old,
                     &del_tokenizer,
+                    &del_localized_tokenizers,
                     max_positions_per_attributes,
                     DelAdd::Deletion,
                     &mut del_buffers,
@@ -124,6 +159,7 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
                     &obkv,
                     &settings_diff.new,
                     &add_tokenizer,
+                    &add_localized_tokenizers,
                     max_positions_per_attributes,
                     DelAdd::Addition,
                     &mut add_buffers,
@@ -225,12 +261,17 @@ fn tokenizer_builder<'a>(
     tokenizer_builder
 }
 
+/// The tokenizers dedicated to the localized fields,
+/// each one is restricted to the locales of the fields it is associated with.
+type LocalizedTokenizers<'t> = [(&'t [FieldId], Tokenizer<'t>)];
+
 /// Extract words mapped with their positions of a document,
 /// ensuring no Language detection mistakes was made.
 fn lang_safe_tokens_from_document<'a>(
     obkv: &KvReader<FieldId>,
     settings: &InnerIndexSettings,
     tokenizer: &Tokenizer,
+    localized_tokenizers: &LocalizedTokenizers,
     max_positions_per_attributes: u32,
     del_add: DelAdd,
     buffers: &'a mut Buffers,
@@ -241,6 +282,7 @@ fn lang_safe_tokens_from_document<'a>(
         obkv,
         &settings.searchable_fields_ids,
         tokenizer,
+        localized_tokenizers,
         max_positions_per_attributes,
         del_add,
         buffers,
@@ -281,6 +323,7 @@ fn lang_safe_tokens_from_document<'a>(
                 obkv,
                 &settings.searchable_fields_ids,
                 &tokenizer,
+                localized_tokenizers,
                 max_positions_per_attributes,
                 del_add,
                 buffers,
@@ -298,6 +341,7 @@ fn tokens_from_document<'a>(
     obkv: &KvReader<FieldId>,
     searchable_fields: &[FieldId],
     tokenizer: &Tokenizer,
+    localized_tokenizers: &LocalizedTokenizers,
     max_positions_per_attributes: u32,
     del_add: DelAdd,
     buffers: &'a mut Buffers,
@@ -321,6 +365,12 @@ fn tokens_from_document<'a>(
                 // convert json into a unique string.
                 buffers.field_buffer.clear();
                 if let Some(field) = json_to_string(&value, &mut buffers.field_buffer) {
+                    // use the tokenizer restricted to the locales of the field, if any.
+                    let tokenizer = localized_tokenizers
+                        .iter()
+                        .find(|(field_ids, _)| field_ids.contains(&field_id))
+                        .map_or(tokenizer, |(_, tokenizer)| tokenizer);
+
                     // create an iterator of token with their positions.
                     let tokens = process_tokens(tokenizer.tokenize(field))
                         .take_while(|(p, _)| (*p as u32) < max_positions_per_attributes);
@@ -393,7 +443,7 @@ fn json_to_string<'a>(value: &'a Value, buffer: &'a mut String) -> Option<&'a st
 /// take an iterator on tokens and compute their relative position depending on separator kinds
 /// if it's an `Hard` separator we add an additional relative proximity of 8 between words,
 /// else we keep the standard proximity of 1 between words.
-fn process_tokens<'a>(
+pub(crate) fn process_tokens<'a>(
     tokens: impl Iterator<Item = Token<'a>>,
 ) -> impl Iterator<Item = (usize, Token<'a>)> {
     tokens
@@ -475,6 +475,11 @@ fn extract_facet_values(value: &Value, geo_field: bool) -> FilterableValues {
                         )
                     }
                 }
+                // dates are also faceted as their timestamp so that they can be filtered and sorted
+                // chronologically, while their string still works for equality and the facet distribution.
+                if let Some(timestamp) = crate::date_to_timestamp(original) {
+                    output_numbers.push(timestamp);
+                }
                 let normalized = crate::normalize_facet(original);
                 output_strings.push((normalized, original.clone()));
             }
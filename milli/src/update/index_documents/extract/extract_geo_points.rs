@@ -1,7 +1,6 @@
 use std::fs::File;
 use std::io::{self, BufReader};
 
-use concat_arrays::concat_arrays;
 use serde_json::Value;
 
 use super::helpers::{create_writer, writer_into_reader, GrenadParameters};
@@ -14,6 +13,7 @@ use crate::{FieldId, InternalError, Result};
 /// Extracts the geographical coordinates contained in each document under the `_geo` field.
 ///
 /// Returns the generated grenad reader containing the docid as key associated to the (latitude, longitude)
+/// of each of its points
 #[tracing::instrument(level = "trace", skip_all, target = "indexing::extract")]
 pub fn extract_geo_points<R: io::Read + io::Seek>(
     obkv_documents: grenad::Reader<R>,
@@ -40,23 +40,17 @@ pub fn extract_geo_points<R: io::Read + io::Seek>(
         };
 
         // extract old version
-        let del_lat_lng =
-            extract_lat_lng(&obkv, &settings_diff.old, DelAdd::Deletion, document_id)?;
+        let del_points = extract_lat_lng(&obkv, &settings_diff.old, DelAdd::Deletion, document_id)?;
         // extract new version
-        let add_lat_lng =
-            extract_lat_lng(&obkv, &settings_diff.new, DelAdd::Addition, document_id)?;
+        let add_points = extract_lat_lng(&obkv, &settings_diff.new, DelAdd::Addition, document_id)?;
 
-        if del_lat_lng != add_lat_lng {
+        if del_points != add_points {
             let mut obkv = KvWriterDelAdd::memory();
-            if let Some([lat, lng]) = del_lat_lng {
-                #[allow(clippy::drop_non_drop)]
-                let bytes: [u8; 16] = concat_arrays![lat.to_ne_bytes(), lng.to_ne_bytes()];
-                obkv.insert(DelAdd::Deletion, bytes)?;
+            if !del_points.is_empty() {
+                obkv.insert(DelAdd::Deletion, points_to_bytes(&del_points))?;
             }
-            if let Some([lat, lng]) = add_lat_lng {
-                #[allow(clippy::drop_non_drop)]
-                let bytes: [u8; 16] = concat_arrays![lat.to_ne_bytes(), lng.to_ne_bytes()];
-                obkv.insert(DelAdd::Addition, bytes)?;
+            if !add_points.is_empty() {
+                obkv.insert(DelAdd::Addition, points_to_bytes(&add_points))?;
             }
             let bytes = obkv.into_inner()?;
             writer.insert(docid_bytes, bytes)?;
@@ -66,13 +60,13 @@ pub fn extract_geo_points<R: io::Read + io::Seek>(
     writer_into_reader(writer)
 }
 
-/// Extract the finite floats lat and lng from two bytes slices.
+/// Extract the finite floats lat and lng of each point of the document.
 fn extract_lat_lng(
     document: &obkv::KvReader<FieldId>,
     settings: &InnerIndexSettings,
     deladd: DelAdd,
     document_id: impl Fn() -> Value,
-) -> Result<Option<[f64; 2]>> {
+) -> Result<Vec<[f64; 2]>> {
     match settings.geo_fields_ids {
         Some((lat_fid, lng_fid)) => {
             let lat = document.get(lat_fid).map(KvReaderDelAdd::new).and_then(|r| r.get(deladd));
@@ -85,19 +79,45 @@ fn extract_lat_lng(
                 (None, Some(_)) => {
                     return Err(GeoError::MissingLongitude { document_id: document_id() }.into())
                 }
-                (None, None) => return Ok(None),
+                (None, None) => return Ok(Vec::new()),
             };
-            let lat = extract_finite_float_from_value(
-                serde_json::from_slice(lat).map_err(InternalError::SerdeJson)?,
-            )
-            .map_err(|lat| GeoError::BadLatitude { document_id: document_id(), value: lat })?;
+            // when the `_geo` field is an array of points, the flattened `_geo.lat`
+            // and `_geo.lng` fields are arrays with one value per point, in the same order.
+            let lats =
+                flattened_values(serde_json::from_slice(lat).map_err(InternalError::SerdeJson)?);
+            let lngs =
+                flattened_values(serde_json::from_slice(lng).map_err(InternalError::SerdeJson)?);
+            if lats.len() < lngs.len() {
+                return Err(GeoError::MissingLatitude { document_id: document_id() }.into());
+            } else if lats.len() > lngs.len() {
+                return Err(GeoError::MissingLongitude { document_id: document_id() }.into());
+            }
 
-            let lng = extract_finite_float_from_value(
-                serde_json::from_slice(lng).map_err(InternalError::SerdeJson)?,
-            )
-            .map_err(|lng| GeoError::BadLongitude { document_id: document_id(), value: lng })?;
-            Ok(Some([lat, lng]))
+            lats.into_iter()
+                .zip(lngs)
+                .map(|(lat, lng)| {
+                    let lat = extract_finite_float_from_value(lat).map_err(|lat| {
+                        GeoError::BadLatitude { document_id: document_id(), value: lat }
+                    })?;
+                    let lng = extract_finite_float_from_value(lng).map_err(|lng| {
+                        GeoError::BadLongitude { document_id: document_id(), value: lng }
+                    })?;
+                    Ok([lat, lng])
+                })
+                .collect()
         }
-        None => Ok(None),
+        None => Ok(Vec::new()),
+    }
+}
+
+fn flattened_values(value: Value) -> Vec<Value> {
+    match value {
+        Value::Array(values) => values,
+        value => vec![value],
     }
 }
+
+/// Concatenates the latitude and longitude of each point.
+fn points_to_bytes(points: &[[f64; 2]]) -> Vec<u8> {
+    points.iter().flatten().flat_map(|coord| coord.to_ne_bytes()).collect()
+}
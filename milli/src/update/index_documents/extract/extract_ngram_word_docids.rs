@@ -0,0 +1,154 @@
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::iter::once;
+
+use obkv::KvReaderU16;
+
+use super::helpers::{
+    create_sorter, merge_deladd_cbo_roaring_bitmaps, sorter_into_reader, try_split_array_at,
+    GrenadParameters,
+};
+use crate::error::SerializationError;
+use crate::index::db_name::DOCID_WORD_POSITIONS;
+use crate::update::del_add::{DelAdd, KvReaderDelAdd, KvWriterDelAdd};
+use crate::update::settings::InnerIndexSettingsDiff;
+use crate::update::MergeFn;
+use crate::{DocumentId, Result, INFIX_NGRAM_LENGTH};
+
+/// Extracts the character n-grams of the words of the infix searchable attributes
+/// and the documents ids where these words appear.
+///
+/// Returns a grenad reader with the list of extracted n-gram and word pairs
+/// and documents ids from the given chunk of docid word positions.
+#[tracing::instrument(level = "trace", skip_all, target = "indexing::extract")]
+pub fn extract_ngram_word_docids<R: io::Read + io::Seek>(
+    docid_word_positions: grenad::Reader<R>,
+    indexer: GrenadParameters,
+    settings_diff: &InnerIndexSettingsDiff,
+) -> Result<grenad::Reader<BufReader<File>>> {
+    let max_memory = indexer.max_memory_by_thread();
+    let old_infix_fids = &settings_diff.old.infix_searchable_attributes;
+    let new_infix_fids = &settings_diff.new.infix_searchable_attributes;
+
+    let mut ngram_word_docids_sorter = create_sorter(
+        grenad::SortAlgorithm::Unstable,
+        merge_deladd_cbo_roaring_bitmaps,
+        indexer.chunk_compression_type,
+        indexer.chunk_compression_level,
+        indexer.max_nb_chunks,
+        max_memory,
+    );
+
+    // skip the extraction if no attribute is or was infix searchable.
+    if old_infix_fids.is_empty() && new_infix_fids.is_empty() {
+        return sorter_into_reader(ngram_word_docids_sorter, indexer);
+    }
+
+    let mut del_keys = BTreeSet::new();
+    let mut add_keys = BTreeSet::new();
+    let mut current_document_id = None;
+    let mut cursor = docid_word_positions.into_cursor()?;
+    while let Some((key, value)) = cursor.move_on_next()? {
+        let (document_id_bytes, fid_bytes) = try_split_array_at(key)
+            .ok_or(SerializationError::Decoding { db_name: Some(DOCID_WORD_POSITIONS) })?;
+        let (fid_bytes, _) = try_split_array_at(fid_bytes)
+            .ok_or(SerializationError::Decoding { db_name: Some(DOCID_WORD_POSITIONS) })?;
+        let document_id = u32::from_be_bytes(document_id_bytes);
+        let fid = u16::from_be_bytes(fid_bytes);
+
+        // a word can appear in several fields of a document,
+        // so the n-grams are only written once all the fields of the document are read.
+        if let Some(previous_document_id) = current_document_id {
+            if previous_document_id != document_id {
+                ngrams_into_sorter(
+                    previous_document_id,
+                    &del_keys,
+                    &add_keys,
+                    &mut ngram_word_docids_sorter,
+                )?;
+                del_keys.clear();
+                add_keys.clear();
+            }
+        }
+        current_document_id = Some(document_id);
+
+        let del_add_reader = KvReaderDelAdd::new(value);
+        // extract all unique n-grams to remove.
+        if old_infix_fids.contains(&fid) {
+            if let Some(deletion) = del_add_reader.get(DelAdd::Deletion) {
+                for (_pos, word) in KvReaderU16::new(deletion).iter() {
+                    insert_word_ngrams(word, &mut del_keys)?;
+                }
+            }
+        }
+
+        // extract all unique additional n-grams.
+        if new_infix_fids.contains(&fid) {
+            if let Some(addition) = del_add_reader.get(DelAdd::Addition) {
+                for (_pos, word) in KvReaderU16::new(addition).iter() {
+                    insert_word_ngrams(word, &mut add_keys)?;
+                }
+            }
+        }
+    }
+
+    if let Some(document_id) = current_document_id {
+        ngrams_into_sorter(document_id, &del_keys, &add_keys, &mut ngram_word_docids_sorter)?;
+    }
+
+    sorter_into_reader(ngram_word_docids_sorter, indexer)
+}
+
+/// Inserts an `ngram\0word` key for every n-gram of the word.
+///
+/// Words shorter than the n-grams are ignored.
+fn insert_word_ngrams(word: &[u8], keys: &mut BTreeSet<Vec<u8>>) -> Result<()> {
+    let word = std::str::from_utf8(word)
+        .map_err(|_| SerializationError::Decoding { db_name: Some(DOCID_WORD_POSITIONS) })?;
+    let boundaries: Vec<_> =
+        word.char_indices().map(|(index, _)| index).chain(once(word.len())).collect();
+
+    for window in boundaries.windows(INFIX_NGRAM_LENGTH + 1) {
+        let ngram = &word[window[0]..window[INFIX_NGRAM_LENGTH]];
+        let mut key = Vec::with_capacity(ngram.len() + 1 + word.len());
+        key.extend_from_slice(ngram.as_bytes());
+        key.push(0);
+        key.extend_from_slice(word.as_bytes());
+        keys.insert(key);
+    }
+
+    Ok(())
+}
+
+fn ngrams_into_sorter(
+    document_id: DocumentId,
+    del_keys: &BTreeSet<Vec<u8>>,
+    add_keys: &BTreeSet<Vec<u8>>,
+    ngram_word_docids_sorter: &mut grenad::Sorter<MergeFn>,
+) -> Result<()> {
+    use itertools::merge_join_by;
+    use itertools::EitherOrBoth::{Both, Left, Right};
+
+    let mut buffer = Vec::new();
+    for eob in merge_join_by(del_keys.iter(), add_keys.iter(), |d, a| d.cmp(a)) {
+        buffer.clear();
+        let mut value_writer = KvWriterDelAdd::new(&mut buffer);
+        let key = match eob {
+            Left(key) => {
+                value_writer.insert(DelAdd::Deletion, document_id.to_ne_bytes()).unwrap();
+                key
+            }
+            Right(key) => {
+                value_writer.insert(DelAdd::Addition, document_id.to_ne_bytes()).unwrap();
+                key
+            }
+            // the n-gram is kept for this document, nothing to change.
+            Both(_, _) => continue,
+        };
+
+        ngram_word_docids_sorter.insert(key, value_writer.into_inner().unwrap())?;
+    }
+
+    Ok(())
+}
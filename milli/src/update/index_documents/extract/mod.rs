@@ -4,6 +4,7 @@ mod extract_facet_string_docids;
 mod extract_fid_docid_facet_values;
 mod extract_fid_word_count_docids;
 mod extract_geo_points;
+mod extract_ngram_word_docids;
 mod extract_vector_points;
 mod extract_word_docids;
 mod extract_word_pair_proximity_docids;
@@ -17,11 +18,13 @@ use crossbeam_channel::Sender;
 use rayon::prelude::*;
 
 use self::extract_docid_word_positions::extract_docid_word_positions;
+pub(crate) use self::extract_docid_word_positions::process_tokens;
 use self::extract_facet_number_docids::extract_facet_number_docids;
 use self::extract_facet_string_docids::extract_facet_string_docids;
 use self::extract_fid_docid_facet_values::{extract_fid_docid_facet_values, ExtractedFacetValues};
 use self::extract_fid_word_count_docids::extract_fid_word_count_docids;
 use self::extract_geo_points::extract_geo_points;
+use self::extract_ngram_word_docids::extract_ngram_word_docids;
 use self::extract_vector_points::{
     extract_embeddings, extract_vector_points, ExtractedVectorPoints,
 };
@@ -126,6 +129,15 @@ pub(crate) fn data_from_obkv_documents(
                             TypedChunk::WordPositionDocids,
                         );
 
+                        run_extraction_task::<_, _, grenad::Reader<BufReader<File>>>(
+                            docid_word_positions_chunk.clone(),
+                            indexer,
+                            settings_diff.clone(),
+                            lmdb_writer_sx.clone(),
+                            extract_ngram_word_docids,
+                            TypedChunk::NgramWordDocids,
+                        );
+
                         run_extraction_task::<
                             _,
                             _,
@@ -24,6 +24,7 @@ use typed_chunk::{write_typed_chunk_into_index, ChunkAccumulator, TypedChunk};
 
 use self::enrich::enrich_documents_batch;
 pub use self::enrich::{extract_finite_float_from_value, DocumentId};
+pub(crate) use self::extract::process_tokens;
 pub use self::helpers::{
     as_cloneable_grenad, create_sorter, create_writer, fst_stream_into_hashset,
     fst_stream_into_vec, merge_cbo_roaring_bitmaps, merge_deladd_cbo_roaring_bitmaps,
@@ -1180,6 +1181,26 @@ mod tests {
             &error.to_string(),
             r#"Could not parse longitude in the document with the id: `0`. Was expecting a finite number but instead got `"hello"`."#
         );
+
+        let error = index
+            .add_documents(documents!([
+              { "id": 0, "_geo": [{ "lat": 12, "lng": 42 }, { "lat": 13 }] }
+            ]))
+            .unwrap_err();
+        assert_eq!(
+            &error.to_string(),
+            r#"Could not find longitude in the document with the id: `0`. Was expecting a `_geo.lng` field."#
+        );
+
+        let error = index
+            .add_documents(documents!([
+              { "id": 0, "_geo": [{ "lat": 12, "lng": 42 }, [13, 43]] }
+            ]))
+            .unwrap_err();
+        assert_eq!(
+            &error.to_string(),
+            r#"The `_geo` field in the document with the id: `0` is not an object. Was expecting an object with the `_geo.lat` and `_geo.lng` fields, or an array of such objects, but instead got `[13,43]`."#
+        );
     }
 
     #[test]
@@ -78,6 +78,7 @@ pub(crate) enum TypedChunk {
         word_fid_docids_reader: grenad::Reader<BufReader<File>>,
     },
     WordPositionDocids(grenad::Reader<BufReader<File>>),
+    NgramWordDocids(grenad::Reader<BufReader<File>>),
     WordPairProximityDocids(grenad::Reader<BufReader<File>>),
     FieldIdFacetStringDocids((grenad::Reader<BufReader<File>>, grenad::Reader<BufReader<File>>)),
     FieldIdFacetNumberDocids(grenad::Reader<BufReader<File>>),
@@ -107,6 +108,7 @@ impl TypedChunk {
             | (FieldIdWordCountDocids(_), FieldIdWordCountDocids(_))
             | (WordDocids { .. }, WordDocids { .. })
             | (WordPositionDocids(_), WordPositionDocids(_))
+            | (NgramWordDocids(_), NgramWordDocids(_))
             | (WordPairProximityDocids(_), WordPairProximityDocids(_))
             | (FieldIdFacetStringDocids(_), FieldIdFacetStringDocids(_))
             | (FieldIdFacetNumberDocids(_), FieldIdFacetNumberDocids(_))
@@ -350,6 +352,29 @@ pub(crate) fn write_typed_chunk_into_index(
             )?;
             is_merged_database = true;
         }
+        TypedChunk::NgramWordDocids(_) => {
+            let span = tracing::trace_span!(target: "indexing::write_db", "ngram_word_docids");
+            let _entered = span.enter();
+
+            let mut builder = MergerBuilder::new(merge_deladd_cbo_roaring_bitmaps as MergeFn);
+            for typed_chunk in typed_chunks {
+                let TypedChunk::NgramWordDocids(chunk) = typed_chunk else {
+                    unreachable!();
+                };
+
+                builder.push(chunk.into_cursor()?);
+            }
+            let merger = builder.build();
+
+            write_entries_into_database(
+                merger,
+                &index.ngram_word_docids,
+                wtxn,
+                deladd_serialize_add_side,
+                merge_deladd_cbo_roaring_bitmaps_into_cbo_roaring_bitmap,
+            )?;
+            is_merged_database = true;
+        }
         TypedChunk::FieldIdFacetNumberDocids(_) => {
             let span =
                 tracing::trace_span!(target: "indexing::write_db","field_id_facet_number_docids");
@@ -605,13 +630,15 @@ pub(crate) fn write_typed_chunk_into_index(
 
                 let deladd_obkv = KvReaderDelAdd::new(value);
                 if let Some(value) = deladd_obkv.get(DelAdd::Deletion) {
-                    let geopoint = extract_geo_point(value, docid);
-                    rtree.remove(&geopoint);
+                    for geopoint in extract_geo_points(value, docid) {
+                        rtree.remove(&geopoint);
+                    }
                     geo_faceted_docids.remove(docid);
                 }
                 if let Some(value) = deladd_obkv.get(DelAdd::Addition) {
-                    let geopoint = extract_geo_point(value, docid);
-                    rtree.insert(geopoint);
+                    for geopoint in extract_geo_points(value, docid) {
+                        rtree.insert(geopoint);
+                    }
                     geo_faceted_docids.insert(docid);
                 }
             }
@@ -807,13 +834,15 @@ pub(crate) fn write_typed_chunk_into_index(
     Ok((RoaringBitmap::new(), is_merged_database))
 }
 
-/// Converts the latitude and longitude back to an xyz GeoPoint.
-fn extract_geo_point(value: &[u8], docid: DocumentId) -> GeoPoint {
-    let (lat, tail) = helpers::try_split_array_at::<u8, 8>(value).unwrap();
-    let (lng, _) = helpers::try_split_array_at::<u8, 8>(tail).unwrap();
-    let point = [f64::from_ne_bytes(lat), f64::from_ne_bytes(lng)];
-    let xyz_point = lat_lng_to_xyz(&point);
-    GeoPoint::new(xyz_point, (docid, point))
+/// Converts the latitudes and longitudes of a document back to xyz GeoPoints.
+fn extract_geo_points(value: &[u8], docid: DocumentId) -> impl Iterator<Item = GeoPoint> + '_ {
+    value.chunks_exact(16).map(move |bytes| {
+        let (lat, tail) = helpers::try_split_array_at::<u8, 8>(bytes).unwrap();
+        let (lng, _) = helpers::try_split_array_at::<u8, 8>(tail).unwrap();
+        let point = [f64::from_ne_bytes(lat), f64::from_ne_bytes(lng)];
+        let xyz_point = lat_lng_to_xyz(&point);
+        GeoPoint::new(xyz_point, (docid, point))
+    })
 }
 
 fn merge_word_docids_reader_into_fst(
@@ -18,7 +18,9 @@ use crate::error::UserError;
 use crate::index::{
     IndexEmbeddingConfig, DEFAULT_MIN_WORD_LEN_ONE_TYPO, DEFAULT_MIN_WORD_LEN_TWO_TYPOS,
 };
+use crate::localized_attributes_rules::{LocalizedAttributesRule, LocalizedFieldIds};
 use crate::order_by_map::OrderByMap;
+use crate::pinned_results::PinnedResultsRule;
 use crate::proximity::ProximityPrecision;
 use crate::update::index_documents::IndexDocumentsMethod;
 use crate::update::{IndexDocuments, UpdateIndexingStep};
@@ -159,6 +161,10 @@ pub struct Settings<'a, 't, 'i> {
     proximity_precision: Setting<ProximityPrecision>,
     embedder_settings: Setting<BTreeMap<String, Setting<EmbeddingSettings>>>,
     search_cutoff: Setting<u64>,
+    localized_attributes_rules: Setting<Vec<LocalizedAttributesRule>>,
+    /// Attributes whose words can be found from any part of them.
+    infix_searchable_attributes: Setting<HashSet<String>>,
+    pinned_results: Setting<Vec<PinnedResultsRule>>,
 }
 
 impl<'a, 't, 'i> Settings<'a, 't, 'i> {
@@ -193,6 +199,9 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             proximity_precision: Setting::NotSet,
             embedder_settings: Setting::NotSet,
             search_cutoff: Setting::NotSet,
+            localized_attributes_rules: Setting::NotSet,
+            infix_searchable_attributes: Setting::NotSet,
+            pinned_results: Setting::NotSet,
             indexer_config,
         }
     }
@@ -391,6 +400,30 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.search_cutoff = Setting::Reset;
     }
 
+    pub fn set_localized_attributes_rules(&mut self, value: Vec<LocalizedAttributesRule>) {
+        self.localized_attributes_rules = Setting::Set(value);
+    }
+
+    pub fn reset_localized_attributes_rules(&mut self) {
+        self.localized_attributes_rules = Setting::Reset;
+    }
+
+    pub fn set_infix_searchable_attributes(&mut self, attrs: HashSet<String>) {
+        self.infix_searchable_attributes = Setting::Set(attrs);
+    }
+
+    pub fn reset_infix_searchable_attributes(&mut self) {
+        self.infix_searchable_attributes = Setting::Reset;
+    }
+
+    pub fn set_pinned_results(&mut self, rules: Vec<PinnedResultsRule>) {
+        self.pinned_results = Setting::Set(rules);
+    }
+
+    pub fn reset_pinned_results(&mut self) {
+        self.pinned_results = Setting::Reset;
+    }
+
     #[tracing::instrument(
         level = "trace"
         skip(self, progress_callback, should_abort, settings_diff),
@@ -1118,6 +1151,61 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         Ok(changed)
     }
 
+    fn update_localized_attributes_rules(&mut self) -> Result<bool> {
+        let changed = match &self.localized_attributes_rules {
+            Setting::Set(new) => {
+                let old = self.index.localized_attributes_rules(self.wtxn)?;
+                if old.as_ref() == Some(new) {
+                    false
+                } else {
+                    self.index.put_localized_attributes_rules(self.wtxn, new.clone())?;
+                    true
+                }
+            }
+            Setting::Reset => self.index.delete_localized_attributes_rules(self.wtxn)?,
+            Setting::NotSet => false,
+        };
+
+        Ok(changed)
+    }
+
+    fn update_infix_searchable_attributes(&mut self) -> Result<bool> {
+        match self.infix_searchable_attributes {
+            Setting::Set(ref attrs) => {
+                let old_attrs = self.index.infix_searchable_attributes(self.wtxn)?;
+                let old_attrs = old_attrs.into_iter().map(String::from).collect::<HashSet<_>>();
+
+                if attrs != &old_attrs {
+                    let attrs = attrs.iter().map(String::as_str).collect::<Vec<_>>();
+                    self.index.put_infix_searchable_attributes(self.wtxn, &attrs)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_infix_searchable_attributes(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
+    fn update_pinned_results(&mut self) -> Result<bool> {
+        let changed = match &self.pinned_results {
+            Setting::Set(new) => {
+                let old = self.index.pinned_results(self.wtxn)?;
+                if &old == new {
+                    false
+                } else {
+                    self.index.put_pinned_results(self.wtxn, new)?;
+                    true
+                }
+            }
+            Setting::Reset => self.index.delete_pinned_results(self.wtxn)?,
+            Setting::NotSet => false,
+        };
+
+        Ok(changed)
+    }
+
     pub fn execute<FP, FA>(mut self, progress_callback: FP, should_abort: FA) -> Result<()>
     where
         FP: Fn(UpdateIndexingStep) + Sync,
@@ -1139,6 +1227,7 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.update_sort_facet_values_by()?;
         self.update_pagination_max_total_hits()?;
         self.update_search_cutoff()?;
+        self.update_pinned_results()?;
 
         // could trigger re-indexing
         self.update_filterable()?;
@@ -1151,6 +1240,8 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.update_searchable()?;
         self.update_exact_attributes()?;
         self.update_proximity_precision()?;
+        self.update_localized_attributes_rules()?;
+        self.update_infix_searchable_attributes()?;
 
         let embedding_config_updates = self.update_embedding_configs()?;
 
@@ -1189,7 +1280,8 @@ pub struct InnerIndexSettingsDiff {
     pub(crate) only_additional_fields: Option<HashSet<String>>,
 
     // Cache the check to see if all the stop_words, allowed_separators, dictionary,
-    // exact_attributes, proximity_precision are different.
+    // exact_attributes, proximity_precision, localized_searchable_fields_ids,
+    // infix_searchable_attributes are different.
     pub(crate) cache_reindex_searchable_without_user_defined: bool,
     // Cache the check to see if the user_defined_searchables are different.
     pub(crate) cache_user_defined_searchables: bool,
@@ -1229,6 +1321,10 @@ impl InnerIndexSettingsDiff {
                 || old_settings.allowed_separators != new_settings.allowed_separators
                 || old_settings.dictionary != new_settings.dictionary
                 || old_settings.proximity_precision != new_settings.proximity_precision
+                || old_settings.localized_searchable_fields_ids
+                    != new_settings.localized_searchable_fields_ids
+                || old_settings.infix_searchable_attributes
+                    != new_settings.infix_searchable_attributes
         };
 
         let cache_exact_attributes = old_settings.exact_attributes != new_settings.exact_attributes;
@@ -1341,6 +1437,9 @@ pub(crate) struct InnerIndexSettings {
     pub geo_fields_ids: Option<(FieldId, FieldId)>,
     pub non_searchable_fields_ids: Vec<FieldId>,
     pub non_faceted_fields_ids: Vec<FieldId>,
+    pub localized_attributes_rules: Option<Vec<LocalizedAttributesRule>>,
+    pub localized_searchable_fields_ids: LocalizedFieldIds,
+    pub infix_searchable_attributes: HashSet<FieldId>,
 }
 
 impl InnerIndexSettings {
@@ -1357,6 +1456,7 @@ impl InnerIndexSettings {
         let mut searchable_fields_ids = index.searchable_fields_ids(rtxn)?;
         let mut faceted_fields_ids = index.faceted_fields_ids(rtxn)?;
         let exact_attributes = index.exact_attributes_ids(rtxn)?;
+        let infix_searchable_attributes = index.infix_searchable_attributes_ids(rtxn)?;
         let proximity_precision = index.proximity_precision(rtxn)?.unwrap_or_default();
         let embedding_configs = embedders(index.embedding_configs(rtxn)?)?;
         let existing_fields: HashSet<_> = index
@@ -1387,6 +1487,13 @@ impl InnerIndexSettings {
         searchable_fields_ids.retain(|id| !vectors_fids.contains(id));
         faceted_fields_ids.retain(|id| !vectors_fids.contains(id));
 
+        let localized_attributes_rules = index.localized_attributes_rules(rtxn)?;
+        let localized_searchable_fields_ids = LocalizedFieldIds::new(
+            &localized_attributes_rules,
+            &fields_ids_map,
+            searchable_fields_ids.iter().cloned(),
+        );
+
         Ok(Self {
             stop_words,
             allowed_separators,
@@ -1403,6 +1510,9 @@ impl InnerIndexSettings {
             geo_fields_ids,
             non_searchable_fields_ids: vectors_fids.clone(),
             non_faceted_fields_ids: vectors_fids.clone(),
+            localized_attributes_rules,
+            localized_searchable_fields_ids,
+            infix_searchable_attributes,
         })
     }
 
@@ -1439,6 +1549,18 @@ impl InnerIndexSettings {
         }
         let searchable_fields_ids = index.searchable_fields_ids(wtxn)?;
         self.searchable_fields_ids = searchable_fields_ids;
+        self.localized_searchable_fields_ids = LocalizedFieldIds::new(
+            &self.localized_attributes_rules,
+            &self.fields_ids_map,
+            self.searchable_fields_ids.iter().cloned(),
+        );
+        let infix_searchable_attributes = index.infix_searchable_attributes(wtxn)?;
+        self.infix_searchable_attributes = self
+            .fields_ids_map
+            .iter()
+            .filter(|(_fid, field)| crate::is_faceted(field, &infix_searchable_attributes))
+            .map(|(fid, _field)| fid)
+            .collect();
 
         Ok(())
     }
@@ -2356,6 +2478,65 @@ mod tests {
         assert_eq!(result.documents_ids.len(), 2);
     }
 
+    #[cfg(feature = "default")]
+    #[test]
+    fn set_and_reset_localized_attributes_rules() {
+        use charabia::{Language, Script};
+
+        let index = TempIndex::new();
+
+        // Kanji-only texts are detected as chinese when nothing else is known about them.
+        index
+            .add_documents(documents!([
+                { "id": 0, "title_ja": "東京都庁" },
+                { "id": 1, "title_zh": "東京都庁" },
+            ]))
+            .unwrap();
+
+        let key_jpn = (Script::Cj, Language::Jpn);
+        let key_cmn = (Script::Cj, Language::Cmn);
+
+        let rtxn = index.read_txn().unwrap();
+        let jpn_docs = index.script_language_documents_ids(&rtxn, &key_jpn).unwrap();
+        assert_eq!(jpn_docs.unwrap_or_default(), RoaringBitmap::new());
+        let cmn_docs = index.script_language_documents_ids(&rtxn, &key_cmn).unwrap().unwrap();
+        assert_eq!(cmn_docs, [0, 1].iter().collect());
+        drop(rtxn);
+
+        // Constrain the japanese attributes to the japanese language.
+        index
+            .update_settings(|settings| {
+                settings.set_localized_attributes_rules(vec![LocalizedAttributesRule::new(
+                    vec![S("*_ja")],
+                    vec![Language::Jpn],
+                )]);
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let rules = index.localized_attributes_rules(&rtxn).unwrap().unwrap();
+        assert_eq!(rules, vec![LocalizedAttributesRule::new(vec![S("*_ja")], vec![Language::Jpn])]);
+        // only the document whose text is in a japanese attribute is now tokenized as japanese
+        let jpn_docs = index.script_language_documents_ids(&rtxn, &key_jpn).unwrap().unwrap();
+        assert_eq!(jpn_docs, [0].iter().collect());
+        let cmn_docs = index.script_language_documents_ids(&rtxn, &key_cmn).unwrap().unwrap();
+        assert_eq!(cmn_docs, [1].iter().collect());
+        drop(rtxn);
+
+        index
+            .update_settings(|settings| {
+                settings.reset_localized_attributes_rules();
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.localized_attributes_rules(&rtxn).unwrap(), None);
+        let jpn_docs = index.script_language_documents_ids(&rtxn, &key_jpn).unwrap();
+        assert_eq!(jpn_docs.unwrap_or_default(), RoaringBitmap::new());
+        let cmn_docs = index.script_language_documents_ids(&rtxn, &key_cmn).unwrap().unwrap();
+        assert_eq!(cmn_docs, [0, 1].iter().collect());
+    }
+
     #[test]
     fn setting_searchable_recomputes_other_settings() {
         let index = TempIndex::new();
@@ -2626,6 +2807,9 @@ mod tests {
                     proximity_precision,
                     embedder_settings,
                     search_cutoff,
+                    localized_attributes_rules,
+                    infix_searchable_attributes,
+                    pinned_results,
                 } = settings;
                 assert!(matches!(searchable_fields, Setting::NotSet));
                 assert!(matches!(displayed_fields, Setting::NotSet));
@@ -2650,6 +2834,9 @@ mod tests {
                 assert!(matches!(proximity_precision, Setting::NotSet));
                 assert!(matches!(embedder_settings, Setting::NotSet));
                 assert!(matches!(search_cutoff, Setting::NotSet));
+                assert!(matches!(localized_attributes_rules, Setting::NotSet));
+                assert!(matches!(infix_searchable_attributes, Setting::NotSet));
+                assert!(matches!(pinned_results, Setting::NotSet));
             })
             .unwrap();
     }
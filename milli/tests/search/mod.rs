@@ -149,7 +149,7 @@ pub fn expected_order(
                     new_groups
                         .extend(group.linear_group_by_key(|d| d.asc_desc_rank).map(Vec::from));
                 }
-                Criterion::Asc(_) | Criterion::Desc(_) | Criterion::Sort => {
+                Criterion::Asc(_) | Criterion::Desc(_) | Criterion::Decay(_) | Criterion::Sort => {
                     new_groups.push(group.clone())
                 }
             }